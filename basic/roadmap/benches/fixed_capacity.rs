@@ -0,0 +1,159 @@
+//! Compares the fixed-capacity collections with `Vec`.
+//!
+//! std only, so there is no statistics crate: each case runs a few
+//! times and the fastest run is reported, which is the least disturbed by
+//! other work on the machine. Run with `cargo bench --bench fixed_capacity`.
+
+use std::collections::VecDeque;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use roadmap::collections::{ArrayVec, BitSet, RingBuffer};
+
+const ROUNDS: usize = 5;
+const OPS: usize = 1_000_000;
+
+fn fastest(mut run: impl FnMut()) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn report(group: &str, cases: &[(&str, Duration)]) {
+    println!("{group}");
+    for (name, time) in cases {
+        let per_op = time.as_nanos() as f64 / OPS as f64;
+        println!("  {name:<28} {per_op:>7.2} ns/op");
+    }
+    println!();
+}
+
+/// Fill to 64 and empty again, as a small stack would be used.
+fn stack() {
+    const CAP: usize = 64;
+    let array_vec = fastest(|| {
+        for _ in 0..OPS / CAP {
+            let mut stack: ArrayVec<u64, CAP> = ArrayVec::new();
+            for i in 0..CAP as u64 {
+                let _ = stack.try_push(black_box(i));
+            }
+            while let Some(value) = stack.pop() {
+                black_box(value);
+            }
+        }
+    });
+    let vec_fresh = fastest(|| {
+        for _ in 0..OPS / CAP {
+            let mut stack = Vec::new();
+            for i in 0..CAP as u64 {
+                stack.push(black_box(i));
+            }
+            while let Some(value) = stack.pop() {
+                black_box(value);
+            }
+        }
+    });
+    let vec_reserved = fastest(|| {
+        for _ in 0..OPS / CAP {
+            let mut stack = Vec::with_capacity(CAP);
+            for i in 0..CAP as u64 {
+                stack.push(black_box(i));
+            }
+            while let Some(value) = stack.pop() {
+                black_box(value);
+            }
+        }
+    });
+    report(
+        "push + pop, 64 elements",
+        &[
+            ("ArrayVec<u64, 64>", array_vec),
+            ("Vec::new", vec_fresh),
+            ("Vec::with_capacity(64)", vec_reserved),
+        ],
+    );
+}
+
+/// A sliding window of the last 64 values.
+fn queue() {
+    const CAP: usize = 64;
+    let ring = fastest(|| {
+        let mut window: RingBuffer<u64, CAP> = RingBuffer::new();
+        for i in 0..OPS as u64 {
+            black_box(window.push_overwrite(black_box(i)));
+        }
+    });
+    let deque = fastest(|| {
+        let mut window = VecDeque::with_capacity(CAP);
+        for i in 0..OPS as u64 {
+            if window.len() == CAP {
+                black_box(window.pop_front());
+            }
+            window.push_back(black_box(i));
+        }
+    });
+    let vec = fastest(|| {
+        let mut window = Vec::with_capacity(CAP);
+        for i in 0..OPS as u64 {
+            if window.len() == CAP {
+                // What a Vec has to do without wrapping indexes
+                black_box(window.remove(0));
+            }
+            window.push(black_box(i));
+        }
+    });
+    report(
+        "sliding window of 64",
+        &[
+            ("RingBuffer<u64, 64>", ring),
+            ("VecDeque", deque),
+            ("Vec with remove(0)", vec),
+        ],
+    );
+}
+
+/// Random inserts and lookups in a universe of 1024.
+fn membership() {
+    const BITS: usize = 1024;
+    let indexes: Vec<usize> = (0..OPS)
+        .map(|i| i.wrapping_mul(2_654_435_761) % BITS)
+        .collect();
+    let bitset = fastest(|| {
+        let mut set: BitSet<{ BITS / 64 }> = BitSet::new();
+        for pair in indexes.chunks_exact(2) {
+            let _ = set.insert(pair[0]);
+            black_box(set.contains(pair[1]));
+        }
+        black_box(set.len());
+    });
+    let vec_bool = fastest(|| {
+        let mut set = vec![false; BITS];
+        for pair in indexes.chunks_exact(2) {
+            if let Some(slot) = set.get_mut(pair[0]) {
+                *slot = true;
+            }
+            black_box(set.get(pair[1]).copied().unwrap_or(false));
+        }
+        black_box(set.iter().filter(|&&b| b).count());
+    });
+    report(
+        "insert + contains, 1024 values",
+        &[("BitSet<16>", bitset), ("Vec<bool>", vec_bool)],
+    );
+    println!(
+        "  memory: BitSet<16> {} bytes inline, Vec<bool> {} bytes on the heap",
+        size_of::<BitSet<{ BITS / 64 }>>(),
+        BITS
+    );
+}
+
+fn main() {
+    stack();
+    queue();
+    membership();
+}
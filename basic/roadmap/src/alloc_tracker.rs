@@ -0,0 +1,200 @@
+//! A counting global allocator, used to measure what the stack and heap
+//! lessons claim: that `String::from` allocates, that `clone` copies the
+//! heap data and that a move does not.
+//!
+//! Counters are kept per thread, so measurements are not disturbed by
+//! other threads (such as tests running in parallel). Memory freed on a
+//! different thread than it was allocated on is counted where it is freed.
+//!
+//! A binary opts in by installing the allocator:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: roadmap::alloc_tracker::CountingAllocator =
+//!     roadmap::alloc_tracker::CountingAllocator;
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Wraps [`System`] and counts every allocation made through it.
+pub struct CountingAllocator;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: usize,
+    deallocations: usize,
+    reallocations: usize,
+    bytes_allocated: usize,
+    bytes_freed: usize,
+    current: usize,
+    peak: usize,
+}
+
+thread_local! {
+    // Const-initialised and without a destructor, so it is safe to touch
+    // from inside the allocator, even while the thread is shutting down.
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            deallocations: 0,
+            reallocations: 0,
+            bytes_allocated: 0,
+            bytes_freed: 0,
+            current: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn update(f: impl FnOnce(&mut Counters)) {
+    let _ = COUNTERS.try_with(|cell| {
+        let mut counters = cell.get();
+        f(&mut counters);
+        counters.peak = counters.peak.max(counters.current);
+        cell.set(counters);
+    });
+}
+
+fn record_alloc(size: usize) {
+    update(|c| {
+        c.allocations += 1;
+        c.bytes_allocated += size;
+        c.current += size;
+    });
+}
+
+fn record_dealloc(size: usize) {
+    update(|c| {
+        c.deallocations += 1;
+        c.bytes_freed += size;
+        c.current = c.current.saturating_sub(size);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    // A reallocation is not a new allocation, but its size change still
+    // counts towards bytes allocated or freed.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            let old_size = layout.size();
+            update(|c| {
+                c.reallocations += 1;
+                if new_size >= old_size {
+                    c.bytes_allocated += new_size - old_size;
+                    c.current += new_size - old_size;
+                } else {
+                    c.bytes_freed += old_size - new_size;
+                    c.current = c.current.saturating_sub(old_size - new_size);
+                }
+            });
+        }
+        new_ptr
+    }
+}
+
+/// Whether a [`CountingAllocator`] is installed as the global allocator
+/// of this process. Without one every measurement is all zeros.
+pub fn is_installed() -> bool {
+    // Make sure at least one allocation has gone through the allocator.
+    drop(Box::new(0u8));
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// What one measured closure did on the heap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub deallocations: usize,
+    pub reallocations: usize,
+    pub bytes_allocated: usize,
+    pub bytes_freed: usize,
+    /// Highest number of live bytes above what was live before the call.
+    pub peak_bytes: usize,
+}
+
+impl AllocStats {
+    /// Bytes allocated but not freed during the call.
+    pub fn bytes_retained(&self) -> isize {
+        self.bytes_allocated as isize - self.bytes_freed as isize
+    }
+}
+
+/// Runs `f` and reports the heap activity it caused on this thread.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    let mut before = COUNTERS.with(Cell::get);
+    // Restart peak tracking from the current level.
+    before.peak = before.current;
+    COUNTERS.with(|cell| cell.set(before));
+
+    let result = f();
+
+    let after = COUNTERS.with(Cell::get);
+    let stats = AllocStats {
+        allocations: after.allocations - before.allocations,
+        deallocations: after.deallocations - before.deallocations,
+        reallocations: after.reallocations - before.reallocations,
+        bytes_allocated: after.bytes_allocated - before.bytes_allocated,
+        bytes_freed: after.bytes_freed - before.bytes_freed,
+        peak_bytes: after.peak.saturating_sub(before.current),
+    };
+    (result, stats)
+}
+
+/// Formats measurements as an aligned table, one row per section.
+pub fn summary_table(rows: &[(&str, AllocStats)]) -> String {
+    let width = rows
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max(7);
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:<width$}  {:>6}  {:>6}  {:>8}  {:>11}  {:>11}  {:>10}",
+        "section", "allocs", "frees", "reallocs", "bytes alloc", "bytes freed", "peak bytes"
+    );
+    for (name, s) in rows {
+        let _ = writeln!(
+            out,
+            "{:<width$}  {:>6}  {:>6}  {:>8}  {:>11}  {:>11}  {:>10}",
+            name,
+            s.allocations,
+            s.deallocations,
+            s.reallocations,
+            s.bytes_allocated,
+            s.bytes_freed,
+            s.peak_bytes
+        );
+    }
+    out
+}
@@ -0,0 +1,89 @@
+//! Prints a month in the layout of the Unix `cal` command, or converts a
+//! date between its ISO 8601 forms.
+//!
+//! usage: cal [-m] [[MONTH] YEAR]
+//!        cal --date DATE
+
+use std::env;
+use std::process::ExitCode;
+
+use roadmap::calendar::{self, Date, Month, WeekStart};
+
+const USAGE: &str = "usage: cal [-m] [[MONTH] YEAR] | cal --date DATE";
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1).collect()) {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("cal: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<String, String> {
+    let mut start = WeekStart::Sunday;
+    let mut numbers = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-m" => start = WeekStart::Monday,
+            "--date" => {
+                let text = args.next().ok_or(USAGE)?;
+                let date: Date = text.parse().map_err(|e| format!("`{text}`: {e}"))?;
+                return Ok(describe(date));
+            }
+            _ => numbers.push(
+                arg.parse::<i32>()
+                    .map_err(|_| format!("invalid number `{arg}`"))?,
+            ),
+        }
+    }
+
+    let today = Date::today_utc();
+    let (month, year) = match numbers[..] {
+        [] => (today.month(), today.year()),
+        [year] => return full_year(year, start),
+        [month, year] => (parse_month(month)?, year),
+        _ => return Err(USAGE.to_string()),
+    };
+    check_year(year)?;
+    Ok(calendar::month_calendar(year, month, start))
+}
+
+fn full_year(year: i32, start: WeekStart) -> Result<String, String> {
+    check_year(year)?;
+    let months: Vec<String> = Month::ALL
+        .iter()
+        .map(|&month| calendar::month_calendar(year, month, start))
+        .collect();
+    Ok(months.join("\n"))
+}
+
+fn parse_month(number: i32) -> Result<Month, String> {
+    u8::try_from(number)
+        .ok()
+        .and_then(Month::from_number)
+        .ok_or(format!("month {number} is not in 1..=12"))
+}
+
+fn check_year(year: i32) -> Result<(), String> {
+    if (Date::MIN.year()..=Date::MAX.year()).contains(&year) {
+        Ok(())
+    } else {
+        Err(format!("year {year} is outside -9999..=9999"))
+    }
+}
+
+fn describe(date: Date) -> String {
+    format!(
+        "{date} {}\nweek date: {}\nordinal:   {}\n{} days from 1970-01-01\n",
+        date.weekday(),
+        date.to_iso_week_string(),
+        date.to_ordinal_string(),
+        date.days_since_epoch()
+    )
+}
@@ -0,0 +1,186 @@
+//! Records terminal input as `WebEvent`s and replays recorded sessions.
+//!
+//! usage: events record <log> | events replay <log> [--realtime]
+//!
+//! `record` reads keystrokes, pastes and mouse clicks until Ctrl-D (or
+//! the end of piped input), echoing how each event was handled, then
+//! writes the session log. `replay` feeds a log through the same handlers.
+
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::process::ExitCode;
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+use roadmap::events::terminal::{Decoder, RawMode};
+use roadmap::events::{Bus, EventKind, EventLog, Propagation, Recorder, WebEvent};
+
+const USAGE: &str = "usage: events record <log> | events replay <log> [--realtime]";
+
+/// Ends a recording; never dispatched or recorded.
+const CTRL_D: char = '\x04';
+const BACKSPACE: char = '\x7f';
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args[..] {
+        ["record", path] => record(path),
+        ["replay", path] => replay(path, false),
+        ["replay", path, "--realtime"] => replay(path, true),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("events: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// A one-line editor driven entirely by bus handlers.
+#[derive(Default)]
+struct Editor {
+    line: String,
+    lines: Vec<String>,
+}
+
+fn install_handlers(bus: &mut Bus, editor: &Rc<RefCell<Editor>>) {
+    bus.on(EventKind::PageLoad, 0, |_| {
+        println!("page loaded");
+        Propagation::Continue
+    });
+
+    // Control keys are handled here and never reach the text handler
+    let state = Rc::clone(editor);
+    bus.on(EventKind::KeyPress, 10, move |event| {
+        let WebEvent::KeyPress(c) = event else {
+            return Propagation::Continue;
+        };
+        let mut editor = state.borrow_mut();
+        match *c {
+            '\r' | '\n' => {
+                let line = std::mem::take(&mut editor.line);
+                println!("enter: {line:?}");
+                editor.lines.push(line);
+            }
+            BACKSPACE | '\x08' => {
+                let removed = editor.line.pop();
+                println!("backspace: removed {removed:?}");
+            }
+            c if c.is_control() => println!("ignored control key {:?}", c),
+            _ => return Propagation::Continue,
+        }
+        Propagation::Stop
+    });
+
+    let state = Rc::clone(editor);
+    bus.on(EventKind::KeyPress, 0, move |event| {
+        if let WebEvent::KeyPress(c) = event {
+            state.borrow_mut().line.push(*c);
+            println!("key {c:?}");
+        }
+        Propagation::Continue
+    });
+
+    let state = Rc::clone(editor);
+    bus.on(EventKind::Paste, 0, move |event| {
+        if let WebEvent::Paste(text) = event {
+            // Pasted newlines would split the line, so flatten them
+            let flat = text.replace(['\r', '\n'], " ");
+            println!("paste: {} chars", flat.chars().count());
+            state.borrow_mut().line.push_str(&flat);
+        }
+        Propagation::Continue
+    });
+
+    bus.on(EventKind::Click, 0, |event| {
+        if let WebEvent::Click { x, y } = event {
+            println!("click at column {x}, row {y}");
+        }
+        Propagation::Continue
+    });
+
+    let state = Rc::clone(editor);
+    bus.on(EventKind::PageUnload, 0, move |_| {
+        let editor = state.borrow();
+        println!("page unloaded, {} line(s) entered", editor.lines.len());
+        for line in &editor.lines {
+            println!("  {line}");
+        }
+        if !editor.line.is_empty() {
+            println!("  {} (not entered)", editor.line);
+        }
+        Propagation::Continue
+    });
+}
+
+fn record(path: &str) -> Result<(), String> {
+    let editor = Rc::new(RefCell::new(Editor::default()));
+    let mut bus = Bus::new();
+    install_handlers(&mut bus, &editor);
+    let mut recorder = Recorder::new();
+    let mut emit = |event: WebEvent| {
+        bus.dispatch(&event);
+        recorder.record(event);
+    };
+
+    let raw = RawMode::enable();
+    if raw.is_some() {
+        println!("recording, press Ctrl-D to stop");
+    }
+    emit(WebEvent::PageLoad);
+
+    let mut decoder = Decoder::new();
+    let mut stdin = io::stdin().lock();
+    let mut buffer = [0u8; 256];
+    'input: loop {
+        let read = stdin.read(&mut buffer).map_err(|e| e.to_string())?;
+        let events = if read == 0 {
+            decoder.finish()
+        } else {
+            decoder.feed(&buffer[..read])
+        };
+        for event in events {
+            if event == WebEvent::KeyPress(CTRL_D) {
+                break 'input;
+            }
+            emit(event);
+        }
+        if read == 0 {
+            break;
+        }
+    }
+    drop(raw);
+
+    emit(WebEvent::PageUnload);
+    let log = recorder.into_log();
+    fs::write(path, log.to_string()).map_err(|e| format!("{path}: {e}"))?;
+    println!("wrote {} events to {path}", log.len());
+    Ok(())
+}
+
+fn replay(path: &str, realtime: bool) -> Result<(), String> {
+    let input = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let log: EventLog = input.parse().map_err(|e| format!("{path}: {e}"))?;
+
+    let editor = Rc::new(RefCell::new(Editor::default()));
+    let mut bus = Bus::new();
+    install_handlers(&mut bus, &editor);
+    if !realtime {
+        bus.replay(&log);
+        return Ok(());
+    }
+
+    let mut previous = Duration::ZERO;
+    for (at, event) in log.entries() {
+        thread::sleep(at.saturating_sub(previous));
+        previous = *at;
+        bus.dispatch(event);
+    }
+    Ok(())
+}
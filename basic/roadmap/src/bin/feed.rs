@@ -0,0 +1,89 @@
+//! Prints a page of a feed file, newest first.
+//!
+//! usage: feed <file> [--author NAME] [--page N] [--per-page N] [--width N] [--oldest-first]
+//!
+//! The file format is described in `roadmap::feed::parse`.
+
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+
+use roadmap::feed::{self, Feed};
+
+const USAGE: &str =
+    "usage: feed <file> [--author NAME] [--page N] [--per-page N] [--width N] [--oldest-first]";
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("feed: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut path = None;
+    let mut author = None;
+    let mut page = 1;
+    let mut per_page = 10;
+    let mut width = 80;
+    let mut oldest_first = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--author" => author = Some(args.next().ok_or(USAGE)?),
+            "--page" => page = number(args.next())?,
+            "--per-page" => per_page = number(args.next())?,
+            "--width" => width = number(args.next())?,
+            "--oldest-first" => oldest_first = true,
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let path = path.ok_or(USAGE)?;
+
+    let input = fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+    let mut feed: Feed = input.parse().map_err(|e| format!("{path}: {e}"))?;
+    if oldest_first {
+        feed.sort_oldest_first();
+    } else {
+        feed.sort_newest_first();
+    }
+    let items = match &author {
+        Some(author) => feed.by_author(author),
+        None => feed.iter().collect(),
+    };
+    if items.is_empty() {
+        println!("no items");
+        return Ok(());
+    }
+
+    let page = feed::paginate(&items, page, per_page).ok_or(format!(
+        "no page {page} with {} items at {per_page} per page",
+        items.len()
+    ))?;
+    // The timestamp and two spaces come before the summary
+    let summary_width = width.saturating_sub(18);
+    for item in page.items {
+        println!(
+            "{}  {}",
+            item.timestamp(),
+            item.summarize_within(summary_width)
+        );
+    }
+    println!(
+        "page {} of {} ({} items)",
+        page.number, page.page_count, page.total
+    );
+    Ok(())
+}
+
+fn number(value: Option<String>) -> Result<usize, String> {
+    let value = value.ok_or(USAGE)?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid number `{value}`"))
+}
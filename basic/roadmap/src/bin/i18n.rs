@@ -0,0 +1,85 @@
+//! Checks the message catalogs against the `tr!` ids the lessons use and
+//! prints missing ids (with where they are used), unused ids and
+//! translations with arguments English lacks, per language.
+//!
+//! Exits with status 1 if anything is reported.
+//!
+//! usage: i18n [--lang CODE]
+
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+
+use roadmap::i18n::check::{self, Problem};
+use roadmap::i18n::{self, LANGUAGES};
+
+const USAGE: &str = "usage: i18n [--lang CODE]";
+
+fn main() -> ExitCode {
+    let mut only = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--lang" => match args.next() {
+                Some(lang) if i18n::catalog(&lang).is_some() => only = Some(lang),
+                Some(lang) => {
+                    eprintln!(
+                        "i18n: no catalog for `{lang}` (have {})",
+                        LANGUAGES.join(", ")
+                    );
+                    return ExitCode::FAILURE;
+                }
+                None => {
+                    eprintln!("{USAGE}");
+                    return ExitCode::FAILURE;
+                }
+            },
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let used = match check::lesson_uses(root) {
+        Ok(used) => used,
+        Err(err) => {
+            eprintln!("i18n: cannot read lessons: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let lang_of = |problem: &Problem| match problem {
+        Problem::Missing { lang, .. }
+        | Problem::Unused { lang, .. }
+        | Problem::ExtraArgument { lang, .. } => *lang,
+    };
+    let problems: Vec<Problem> = check::check(&used)
+        .into_iter()
+        .filter(|problem| only.as_deref().is_none_or(|only| lang_of(problem) == only))
+        .collect();
+    for problem in &problems {
+        println!("{problem}");
+    }
+
+    for lang in LANGUAGES {
+        if only.as_deref().is_some_and(|only| only != lang) {
+            continue;
+        }
+        let count = problems
+            .iter()
+            .filter(|problem| lang_of(problem) == lang)
+            .count();
+        let size = i18n::catalog(lang).map_or(0, |catalog| catalog.len());
+        eprintln!("i18n: {lang}: {size} messages, {count} problems");
+    }
+    if problems.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,44 @@
+//! Prints size, alignment, field offsets, padding and niche use for the
+//! types defined in the lessons, comparing default and `#[repr(C)]` layout.
+//!
+//! usage: layout [TYPE...] [--niches]
+
+use std::env;
+use std::process::ExitCode;
+
+use roadmap::layout;
+
+fn main() -> ExitCode {
+    let mut names = Vec::new();
+    let mut niches_only = false;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--niches" => niches_only = true,
+            "-h" | "--help" => {
+                println!("usage: layout [TYPE...] [--niches]");
+                return ExitCode::SUCCESS;
+            }
+            _ => names.push(arg),
+        }
+    }
+
+    if !niches_only {
+        let reports: Vec<_> = layout::catalog()
+            .into_iter()
+            .filter(|r| names.is_empty() || names.iter().any(|n| n == r.name))
+            .collect();
+        if reports.is_empty() {
+            eprintln!("layout: no lesson type named {}", names.join(", "));
+            return ExitCode::FAILURE;
+        }
+        for report in &reports {
+            println!("{report}");
+        }
+    }
+
+    if niches_only || names.is_empty() {
+        println!("Option<T> niches in the standard library");
+        print!("{}", layout::niche_table(&layout::niche_examples()));
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,95 @@
+//! Runs Conway's Game of Life in the terminal.
+//!
+//! usage: life [PATTERN | FILE] [--size WxH] [--generations N] [--delay MS]
+//!
+//! PATTERN is one of the built-in patterns (glider, blinker, toad,
+//! r-pentomino); a FILE holds a board of `#` and `.` lines. The pattern is
+//! centered on a board that wraps at the edges. On a terminal each
+//! generation redraws the screen; otherwise every generation is printed
+//! one after the other.
+
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use roadmap::grid::Life;
+use roadmap::grid::life::PATTERNS;
+
+const USAGE: &str = "usage: life [PATTERN | FILE] [--size WxH] [--generations N] [--delay MS]";
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("life: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut source = String::from("r-pentomino");
+    let (mut width, mut height): (usize, usize) = (40, 20);
+    let mut generations = 100;
+    let mut delay = 100;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                let size = args.next().ok_or(USAGE)?;
+                (width, height) = size
+                    .split_once('x')
+                    .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+                    .ok_or(format!("invalid size `{size}`"))?;
+            }
+            "--generations" => generations = number(args.next())?,
+            "--delay" => delay = number(args.next())?,
+            _ if !arg.starts_with("--") => source = arg,
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let text = match PATTERNS.iter().find(|(name, _)| *name == source) {
+        Some((_, text)) => text.to_string(),
+        None => fs::read_to_string(&source).map_err(|e| format!("{source}: {e}"))?,
+    };
+    let pattern: Life = text.parse().map_err(|e| format!("{source}: {e}"))?;
+    let (x, y) = (
+        width.saturating_sub(pattern.cells().width()) / 2,
+        height.saturating_sub(pattern.cells().height()) / 2,
+    );
+    let mut life = Life::with_pattern(width, height, &pattern, x, y).map_err(|e| e.to_string())?;
+
+    let terminal = io::stdout().is_terminal();
+    let mut out = io::stdout().lock();
+    for _ in 0..=generations {
+        if terminal {
+            // Cursor home and clear, then draw over the previous frame
+            write!(out, "\x1b[H\x1b[2J").map_err(|e| e.to_string())?;
+        }
+        writeln!(
+            out,
+            "generation {}, population {}\n{life}\n",
+            life.generation(),
+            life.population()
+        )
+        .map_err(|e| e.to_string())?;
+        out.flush().map_err(|e| e.to_string())?;
+        if terminal {
+            thread::sleep(Duration::from_millis(delay));
+        }
+        life.step();
+    }
+    Ok(())
+}
+
+fn number<T: std::str::FromStr>(value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or(USAGE)?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid number `{value}`"))
+}
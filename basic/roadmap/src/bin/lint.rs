@@ -0,0 +1,85 @@
+//! Checks lesson files against the conventions in
+//! `.kiro/steering/structure.md` and prints `file:line` diagnostics.
+//!
+//! With no files, checks every lesson under `src/`. Exits with status 1 if
+//! anything is reported.
+//!
+//! usage: lint [--registered] [FILE...]
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use roadmap::lessons::LESSONS;
+use roadmap::lint;
+
+const USAGE: &str = "usage: lint [--registered] [FILE...]";
+
+fn main() -> ExitCode {
+    let mut files = Vec::new();
+    let mut registered_only = false;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--registered" => registered_only = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ if arg.starts_with('-') => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+            _ => files.push(PathBuf::from(arg)),
+        }
+    }
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let registered: Vec<&str> = LESSONS.iter().map(|lesson| lesson.id).collect();
+    if files.is_empty() {
+        files = match lint::lesson_files(root) {
+            Ok(files) => files,
+            Err(err) => {
+                eprintln!("lint: cannot list lessons: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+    }
+    if registered_only {
+        files.retain(|path| {
+            lint::lesson_id(path).is_some_and(|id| registered.contains(&id.as_str()))
+        });
+    }
+
+    let mut problems = 0;
+    let mut failed_files = 0;
+    for path in &files {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(err) => {
+                eprintln!("lint: cannot read {}: {err}", path.display());
+                return ExitCode::FAILURE;
+            }
+        };
+        // Paths relative to the crate read better and match editors'
+        // `file:line` links
+        let shown = path.strip_prefix(root).unwrap_or(path);
+        let diagnostics = lint::check_file(shown, &source, &registered);
+        for diagnostic in &diagnostics {
+            println!("{diagnostic}");
+        }
+        problems += diagnostics.len();
+        failed_files += usize::from(!diagnostics.is_empty());
+    }
+
+    if problems == 0 {
+        eprintln!("lint: {} files ok", files.len());
+        ExitCode::SUCCESS
+    } else {
+        eprintln!(
+            "lint: {problems} problems in {failed_files} of {} files",
+            files.len()
+        );
+        ExitCode::FAILURE
+    }
+}
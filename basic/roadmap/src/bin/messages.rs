@@ -0,0 +1,125 @@
+//! Runs `Message` commands interactively or from a script.
+//!
+//! usage: messages [SCRIPT | -]
+//!
+//! Without arguments this is a REPL; `state` prints the current state and
+//! `help` lists the commands. With a script (or `-` for stdin) every line
+//! is parsed first, so a typo stops the run before anything happens.
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::process::ExitCode;
+
+use roadmap::commands::{self, Interpreter, Statement};
+
+const USAGE: &str = "usage: messages [SCRIPT | -]";
+
+const HELP: &str = "\
+commands:
+  move X Y          move the cursor
+  write \"TEXT\"      append to the buffer
+  color R G B       change the color (0-255 each)
+  undo, redo        walk the history
+  quit              stop
+  state, help       show the state or this help";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.as_slice() {
+        [] => repl(),
+        [path] => batch(path),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("messages: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Executes a statement and describes what happened.
+fn execute(interpreter: &mut Interpreter, statement: Statement) -> String {
+    match statement {
+        Statement::Undo => match interpreter.undo() {
+            Some(message) => format!("undid `{message}`"),
+            None => "nothing to undo".to_string(),
+        },
+        Statement::Redo => match interpreter.redo() {
+            Some(message) => format!("redid `{message}`"),
+            None => "nothing to redo".to_string(),
+        },
+        Statement::Message(message) => {
+            interpreter.apply(message);
+            interpreter.state().to_string()
+        }
+    }
+}
+
+fn repl() -> Result<(), String> {
+    let mut interpreter = Interpreter::new();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut number = 0;
+    println!("type `help` for the commands");
+    while !interpreter.state().quit {
+        print!("> ");
+        io::stdout().flush().map_err(|e| e.to_string())?;
+        let Some(line) = lines.next() else {
+            println!();
+            break;
+        };
+        let line = line.map_err(|e| e.to_string())?;
+        number += 1;
+        match line.trim() {
+            "help" => println!("{HELP}"),
+            "state" => println!("{}", interpreter.state()),
+            _ => match commands::parse_line(&line, number) {
+                Ok(Some(statement)) => println!("{}", execute(&mut interpreter, statement)),
+                Ok(None) => {}
+                Err(err) => {
+                    println!("{}", err.caret(&line));
+                    println!("error: {}", err.message);
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+fn batch(path: &str) -> Result<(), String> {
+    let source = if path == "-" {
+        let mut source = String::new();
+        io::stdin()
+            .read_to_string(&mut source)
+            .map_err(|e| e.to_string())?;
+        source
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?
+    };
+
+    let statements = match commands::parse_script(&source) {
+        Ok(statements) => statements,
+        Err(errors) => {
+            let lines: Vec<&str> = source.lines().collect();
+            for err in &errors {
+                eprintln!("{path}:{}:{}: {}", err.line, err.column, err.message);
+                eprintln!("{}", err.caret(lines[err.line - 1]));
+            }
+            return Err(format!("{} error(s), nothing was run", errors.len()));
+        }
+    };
+
+    let mut interpreter = Interpreter::new();
+    for statement in statements {
+        if interpreter.state().quit {
+            break;
+        }
+        let line = statement.to_string();
+        println!("{line:<24} {}", execute(&mut interpreter, statement));
+    }
+    println!("final: {}", interpreter.state());
+    Ok(())
+}
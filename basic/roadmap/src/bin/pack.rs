@@ -0,0 +1,95 @@
+//! Packs rectangle sizes read from a file into fixed-size bins.
+//!
+//! usage: pack <file> --bin WxH [--heuristic shelf|guillotine|maxrects] [--rotate]
+
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+
+use roadmap::packing::{self, Heuristic, PackOptions};
+
+const USAGE: &str =
+    "usage: pack <file> --bin WxH [--heuristic shelf|guillotine|maxrects] [--rotate]";
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("pack: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut path = None;
+    let mut bin = None;
+    let mut options = PackOptions::default();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bin" => {
+                let size = args.next().ok_or(USAGE)?;
+                bin = Some(packing::parse_size(&size).ok_or(format!("invalid bin size `{size}`"))?);
+            }
+            "--heuristic" => {
+                let name = args.next().ok_or(USAGE)?;
+                options.heuristic = name.parse::<Heuristic>().map_err(|e| e.to_string())?;
+            }
+            "--rotate" => options.allow_rotation = true,
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let (path, bin) = path.zip(bin).ok_or(USAGE)?;
+
+    let input = fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+    let items = packing::parse_sizes(&input).map_err(|e| format!("{path}: {e}"))?;
+    let result = packing::pack(&items, &bin, &options);
+
+    println!(
+        "{} items into {}x{} bins, {}, rotation {}",
+        items.len(),
+        bin.width(),
+        bin.height(),
+        options.heuristic,
+        if options.allow_rotation { "on" } else { "off" }
+    );
+    println!();
+    println!("{:>5}  {:>11}  {:>4}  position", "item", "size", "bin");
+
+    let mut placements = result.placements.clone();
+    placements.sort_by_key(|p| (p.bin, p.item));
+    for p in &placements {
+        let item = &items[p.item];
+        println!(
+            "{:>5}  {:>11}  {:>4}  {}{}",
+            p.item,
+            format!("{}x{}", item.width(), item.height()),
+            p.bin,
+            p.rect.origin(),
+            if p.rotated { " rotated" } else { "" }
+        );
+    }
+    for &index in &result.unplaced {
+        let item = &items[index];
+        println!(
+            "{:>5}  {:>11}     -  does not fit",
+            index,
+            format!("{}x{}", item.width(), item.height())
+        );
+    }
+
+    println!();
+    for b in 0..result.bin_count {
+        println!("bin {b}: {:.1}% used", result.bin_utilization(b) * 100.0);
+    }
+    println!(
+        "total: {} bins, {:.1}% used, {} unplaced",
+        result.bin_count,
+        result.utilization() * 100.0,
+        result.unplaced.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,260 @@
+//! A vending machine session, interactive or from a script.
+//!
+//! usage: vending [SCRIPT] [--year YEAR] [--transcript FILE]
+//!        vending change CENTS [--coins 1,5,10,25]
+//!
+//! Session commands: `insert dime 1999`, `insert quarter new york 2001`,
+//! `select A1`, `refund`, `status`, `collection`, `help`, `quit`. The year
+//! on a coin defaults to the current one. `change` prints the fewest coins
+//! for an amount in any coin system.
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::process::ExitCode;
+
+use roadmap::calendar::Date;
+use roadmap::vending::change::CoinSystem;
+use roadmap::vending::{Cents, Coin, Denomination, Machine, UsState};
+
+const USAGE: &str = "usage: vending [SCRIPT] [--year YEAR] [--transcript FILE]
+       vending change CENTS [--coins 1,5,10,25]";
+
+const HELP: &str = "\
+insert penny|nickel|dime [YEAR]     insert a coin
+insert quarter STATE [YEAR]         insert a state quarter
+select SLOT                         buy a product
+refund                              return the credit
+status                              credit, products and coin tubes
+collection                          state quarters seen so far
+quit                                end the session";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("change") => change(&args[1..]),
+        _ => session(&args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("vending: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn change(args: &[String]) -> Result<(), String> {
+    let (amount, coins) = match args {
+        [amount] => (amount, CoinSystem::us()),
+        [amount, flag, list] if flag == "--coins" => {
+            let coins: Vec<u32> = list
+                .split(',')
+                .map(|c| c.trim().parse().map_err(|_| format!("invalid coin `{c}`")))
+                .collect::<Result<_, _>>()?;
+            (amount, CoinSystem::new(&coins).map_err(|e| e.to_string())?)
+        }
+        _ => return Err(USAGE.to_string()),
+    };
+    let amount: u32 = amount
+        .parse()
+        .map_err(|_| format!("invalid amount `{amount}`"))?;
+
+    println!(
+        "coins {:?}, {}",
+        coins.denominations(),
+        if coins.is_canonical() {
+            "canonical: greedy is optimal"
+        } else {
+            "not canonical: using dynamic programming"
+        }
+    );
+    match coins.greedy(amount) {
+        Some(greedy) => println!("greedy:  {greedy} ({} coins)", greedy.coin_count()),
+        None => println!("greedy:  stuck"),
+    }
+    match coins.make_change(amount) {
+        Some(best) => println!("optimal: {best} ({} coins)", best.coin_count()),
+        None => println!("optimal: {amount} cannot be paid with these coins"),
+    }
+    Ok(())
+}
+
+/// Prints session output and keeps a copy for `--transcript`.
+struct Transcript {
+    text: String,
+}
+
+impl Transcript {
+    fn line(&mut self, line: impl AsRef<str>) {
+        let line = line.as_ref();
+        println!("{line}");
+        self.text.push_str(line);
+        self.text.push('\n');
+    }
+}
+
+fn session(args: &[String]) -> Result<(), String> {
+    let mut script = None;
+    let mut transcript_path = None;
+    let mut year = current_year();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => {
+                let value = args.next().ok_or(USAGE)?;
+                year = value
+                    .parse()
+                    .map_err(|_| format!("invalid year `{value}`"))?;
+            }
+            "--transcript" => transcript_path = Some(args.next().ok_or(USAGE)?),
+            _ if script.is_none() && !arg.starts_with("--") => script = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let input: Box<dyn BufRead> = match script {
+        Some(path) => Box::new(BufReader::new(
+            fs::File::open(path).map_err(|e| format!("{path}: {e}"))?,
+        )),
+        None => Box::new(io::stdin().lock()),
+    };
+    let interactive = script.is_none() && io::stdin().is_terminal();
+
+    let mut machine = stocked_machine(year);
+    let mut transcript = Transcript {
+        text: String::new(),
+    };
+    transcript.line(format!("vending machine, {year}; type `help` for commands"));
+    for line in input.lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        let command = line.trim();
+        if command.is_empty() || command.starts_with('#') {
+            continue;
+        }
+        if interactive {
+            // The terminal already shows what was typed
+            transcript.text.push_str(&format!("> {command}\n"));
+        } else {
+            transcript.line(format!("> {command}"));
+        }
+        if command == "quit" {
+            break;
+        }
+        for reply in run_command(&mut machine, command, year) {
+            transcript.line(format!("  {reply}"));
+        }
+        if interactive {
+            print!("> ");
+            io::stdout().flush().map_err(|e| e.to_string())?;
+        }
+    }
+
+    let credit = machine.credit();
+    if credit > 0 {
+        transcript.line(format!("returning {} left in the machine", Cents(credit)));
+        let payout = machine.refund();
+        transcript.line(format!("  {payout}"));
+    }
+    if let Some(path) = transcript_path {
+        fs::write(path, &transcript.text).map_err(|e| format!("{path}: {e}"))?;
+    }
+    Ok(())
+}
+
+fn stocked_machine(year: u16) -> Machine {
+    let mut machine = Machine::new(year);
+    machine.add_product("A1", "Water", 100, 5);
+    machine.add_product("A2", "Chips", 65, 3);
+    machine.add_product("B1", "Candy", 85, 4);
+    machine.add_product("B2", "Gum", 20, 10);
+    for denomination in [
+        Denomination::Nickel,
+        Denomination::Dime,
+        Denomination::Quarter,
+    ] {
+        machine.load(denomination, 10);
+    }
+    machine
+}
+
+/// Runs one session command and returns the lines to print.
+fn run_command(machine: &mut Machine, command: &str, this_year: u16) -> Vec<String> {
+    let words: Vec<&str> = command.split_whitespace().collect();
+    match words[..] {
+        ["help"] => HELP.lines().map(String::from).collect(),
+        ["insert", denomination, ref rest @ ..] => {
+            // A trailing number is the year on the coin
+            let (rest, year) = match rest.split_last() {
+                Some((last, init)) if last.parse::<u16>().is_ok() => {
+                    (init, last.parse().expect("checked"))
+                }
+                _ => (rest, this_year),
+            };
+            let coin = match (denomination, rest) {
+                ("penny", []) => Coin::Penny,
+                ("nickel", []) => Coin::Nickel,
+                ("dime", []) => Coin::Dime,
+                ("quarter", []) => return vec!["which state is on the quarter?".to_string()],
+                ("quarter", state) => match state.join(" ").parse::<UsState>() {
+                    Ok(state) => Coin::Quarter(state),
+                    Err(err) => return vec![err.to_string()],
+                },
+                _ => return vec![format!("cannot insert `{}`", words[1..].join(" "))],
+            };
+            match machine.insert(coin, year) {
+                Ok(credit) => vec![format!(
+                    "{coin} ({year}) accepted, credit {}",
+                    Cents(credit)
+                )],
+                Err(err) => vec![format!("{coin} ({year}) rejected: {err}")],
+            }
+        }
+        ["select", slot] => match machine.select(slot) {
+            Ok(vend) => vec![
+                format!("vended {}", vend.product),
+                format!("change: {} ({})", vend.change, Cents(vend.change.total())),
+            ],
+            Err(err) => vec![err.to_string()],
+        },
+        ["refund"] => {
+            let payout = machine.refund();
+            vec![format!("refunded {} ({})", payout, Cents(payout.total()))]
+        }
+        ["status"] => {
+            let mut lines = vec![format!("credit {}", Cents(machine.credit()))];
+            for product in machine.products() {
+                lines.push(format!(
+                    "{:<3} {:<6} {:>6}  {} left",
+                    product.slot,
+                    product.name,
+                    Cents(product.price).to_string(),
+                    product.stock
+                ));
+            }
+            let tubes: Vec<String> = machine
+                .tubes()
+                .iter()
+                .map(|(denomination, count)| format!("{denomination} {count}"))
+                .collect();
+            lines.push(format!("tubes: {}", tubes.join(", ")));
+            lines
+        }
+        ["collection"] => {
+            let collection = machine.collection();
+            let mut lines: Vec<String> = collection.to_string().lines().map(String::from).collect();
+            lines.push(format!(
+                "{} quarters, {} of 50 states",
+                collection.total(),
+                50 - collection.missing().len()
+            ));
+            lines
+        }
+        _ => vec![format!("unknown command `{command}`, try `help`")],
+    }
+}
+
+/// The current year in UTC.
+fn current_year() -> u16 {
+    Date::today_utc().year() as u16
+}
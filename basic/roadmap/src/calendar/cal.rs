@@ -0,0 +1,111 @@
+//! A month grid in the layout of the Unix `cal` command.
+
+use super::{Date, Month};
+
+/// The first column of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeekStart {
+    /// The `cal` default.
+    #[default]
+    Sunday,
+    /// As in ISO 8601, `cal -m`.
+    Monday,
+}
+
+impl WeekStart {
+    fn header(self) -> &'static str {
+        match self {
+            WeekStart::Sunday => "Su Mo Tu We Th Fr Sa",
+            WeekStart::Monday => "Mo Tu We Th Fr Sa Su",
+        }
+    }
+}
+
+/// Width of a week row: seven two-character days and six separators.
+const WIDTH: usize = 20;
+
+/// The month as a title line, a weekday header and one line per week,
+/// each ending in `\n` with trailing spaces trimmed:
+///
+/// ```text
+///    February 2024
+/// Su Mo Tu We Th Fr Sa
+///              1  2  3
+///  4  5  6  7  8  9 10
+/// ```
+///
+/// Panics if `year` is outside the range of [`Date`].
+pub fn month_calendar(year: i32, month: Month, start: WeekStart) -> String {
+    let first = Date::from_parts(year, month, 1).expect("year in range");
+    let title = format!("{month} {year}");
+    let mut out = format!(
+        "{:indent$}{title}\n{}\n",
+        "",
+        start.header(),
+        indent = WIDTH.saturating_sub(title.len()) / 2
+    );
+
+    let offset = usize::from(match start {
+        WeekStart::Sunday => first.weekday().days_from_sunday(),
+        WeekStart::Monday => first.weekday().number() - 1,
+    });
+    let mut line = "   ".repeat(offset);
+    for day in 1..=month.days_in(year) {
+        line.push_str(&format!("{day:>2} "));
+        if (offset + usize::from(day)) % 7 == 0 {
+            out.push_str(line.trim_end());
+            out.push('\n');
+            line.clear();
+        }
+    }
+    if !line.is_empty() {
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn february_2024_from_sunday() {
+        let expected = "   February 2024
+Su Mo Tu We Th Fr Sa
+             1  2  3
+ 4  5  6  7  8  9 10
+11 12 13 14 15 16 17
+18 19 20 21 22 23 24
+25 26 27 28 29
+";
+        assert_eq!(
+            month_calendar(2024, Month::February, WeekStart::Sunday),
+            expected
+        );
+    }
+
+    #[test]
+    fn october_2026_from_monday() {
+        let expected = "    October 2026
+Mo Tu We Th Fr Sa Su
+          1  2  3  4
+ 5  6  7  8  9 10 11
+12 13 14 15 16 17 18
+19 20 21 22 23 24 25
+26 27 28 29 30 31
+";
+        assert_eq!(
+            month_calendar(2026, Month::October, WeekStart::Monday),
+            expected
+        );
+    }
+
+    #[test]
+    fn month_ending_on_the_last_column_has_no_blank_line() {
+        // February 2015 starts on a Sunday and fills exactly four rows
+        let grid = month_calendar(2015, Month::February, WeekStart::Sunday);
+        assert_eq!(grid.lines().count(), 6);
+        assert!(grid.ends_with("22 23 24 25 26 27 28\n"));
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt;
+use std::ops::Sub;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{DateError, Month, Weekday, days_in_year, is_leap_year};
+
+/// Days from 0000-03-01 to 1970-01-01.
+const EPOCH_SHIFT: i64 = 719_468;
+/// Days in 400 Gregorian years, after which the calendar repeats.
+const DAYS_PER_ERA: i64 = 146_097;
+
+/// A day in the proleptic Gregorian calendar between [`Date::MIN`] and
+/// [`Date::MAX`], the range ISO 8601 covers with four-digit years.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Date {
+    year: i32,
+    month: Month,
+    day: u8,
+}
+
+impl Date {
+    pub const MIN: Date = Date {
+        year: -9999,
+        month: Month::January,
+        day: 1,
+    };
+    pub const MAX: Date = Date {
+        year: 9999,
+        month: Month::December,
+        day: 31,
+    };
+    /// 1970-01-01, day 0 of [`Date::days_since_epoch`].
+    pub const EPOCH: Date = Date {
+        year: 1970,
+        month: Month::January,
+        day: 1,
+    };
+
+    pub fn new(year: i32, month: u8, day: u8) -> Result<Self, DateError> {
+        let month = Month::from_number(month).ok_or(DateError::InvalidMonth(month))?;
+        Self::from_parts(year, month, day)
+    }
+
+    pub fn from_parts(year: i32, month: Month, day: u8) -> Result<Self, DateError> {
+        if !(Self::MIN.year..=Self::MAX.year).contains(&year) {
+            return Err(DateError::YearOutOfRange(year.into()));
+        }
+        if day == 0 || day > month.days_in(year) {
+            return Err(DateError::InvalidDay { year, month, day });
+        }
+        Ok(Self { year, month, day })
+    }
+
+    /// The `ordinal`th day of `year`, counting January 1 as 1.
+    pub fn from_ordinal(year: i32, ordinal: u16) -> Result<Self, DateError> {
+        let start = Self::from_parts(year, Month::January, 1)?;
+        if ordinal == 0 || ordinal > days_in_year(year) {
+            return Err(DateError::InvalidOrdinal { year, ordinal });
+        }
+        Ok(start.add_days(i64::from(ordinal) - 1))
+    }
+
+    /// Today in UTC, from the system clock.
+    pub fn today_utc() -> Self {
+        let secs = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_secs() as i64,
+            Err(before) => -(before.duration().as_secs() as i64) - 1,
+        };
+        Self::EPOCH.add_days(secs.div_euclid(86_400))
+    }
+
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    pub fn month(self) -> Month {
+        self.month
+    }
+
+    pub fn day(self) -> u8 {
+        self.day
+    }
+
+    pub fn is_leap_year(self) -> bool {
+        is_leap_year(self.year)
+    }
+
+    /// Day of the year, 1 for January 1.
+    pub fn ordinal(self) -> u16 {
+        let before: u16 = Month::ALL[..self.month as usize - 1]
+            .iter()
+            .map(|m| u16::from(m.days_in(self.year)))
+            .sum();
+        before + u16::from(self.day)
+    }
+
+    pub fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday
+        let number = (self.days_since_epoch() + 3).rem_euclid(7) + 1;
+        Weekday::from_number(number as u8).expect("in 1..=7")
+    }
+
+    /// Days from 1970-01-01, negative before it.
+    ///
+    /// Counts from a year starting in March so that the leap day falls at
+    /// the end, as in Howard Hinnant's `days_from_civil`.
+    pub fn days_since_epoch(self) -> i64 {
+        let month = i64::from(self.month.number());
+        let year = i64::from(self.year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let march_month = (month + 9) % 12;
+        let day_of_year = (153 * march_month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * DAYS_PER_ERA + day_of_era - EPOCH_SHIFT
+    }
+
+    /// The inverse of [`Date::days_since_epoch`], `None` outside the range.
+    pub fn from_days_since_epoch(days: i64) -> Option<Self> {
+        if !(Self::MIN.days_since_epoch()..=Self::MAX.days_since_epoch()).contains(&days) {
+            return None;
+        }
+        let days = days + EPOCH_SHIFT;
+        let era = days.div_euclid(DAYS_PER_ERA);
+        let day_of_era = days - era * DAYS_PER_ERA;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let march_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * march_month + 2) / 5 + 1;
+        let month = if march_month < 10 {
+            march_month + 3
+        } else {
+            march_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Some(Self {
+            year: year as i32,
+            month: Month::from_number(month as u8).expect("in 1..=12"),
+            day: day as u8,
+        })
+    }
+
+    pub fn checked_add_days(self, days: i64) -> Option<Self> {
+        Self::from_days_since_epoch(self.days_since_epoch().checked_add(days)?)
+    }
+
+    /// Panics past [`Date::MIN`] or [`Date::MAX`]; see
+    /// [`Date::checked_add_days`].
+    pub fn add_days(self, days: i64) -> Self {
+        self.checked_add_days(days).expect("date out of range")
+    }
+
+    pub fn checked_sub_days(self, days: i64) -> Option<Self> {
+        self.checked_add_days(days.checked_neg()?)
+    }
+
+    /// Panics past [`Date::MIN`] or [`Date::MAX`].
+    pub fn sub_days(self, days: i64) -> Self {
+        self.checked_sub_days(days).expect("date out of range")
+    }
+
+    /// Moves by whole months, clamping the day to the length of the target
+    /// month: January 31 plus one month is February 28 (or 29).
+    pub fn checked_add_months(self, months: i64) -> Option<Self> {
+        let index = i64::from(self.year) * 12 + i64::from(self.month.number()) - 1;
+        let index = index.checked_add(months)?;
+        let year = i32::try_from(index.div_euclid(12)).ok()?;
+        let month = Month::from_number(index.rem_euclid(12) as u8 + 1)?;
+        let day = self.day.min(month.days_in(year));
+        Self::from_parts(year, month, day).ok()
+    }
+
+    /// Panics past [`Date::MIN`] or [`Date::MAX`]; see
+    /// [`Date::checked_add_months`].
+    pub fn add_months(self, months: i64) -> Self {
+        self.checked_add_months(months).expect("date out of range")
+    }
+
+    /// Whole years, clamping February 29 to February 28 in common years.
+    pub fn checked_add_years(self, years: i64) -> Option<Self> {
+        self.checked_add_months(years.checked_mul(12)?)
+    }
+
+    /// Days from `self` to `other`, negative if `other` is earlier.
+    pub fn days_until(self, other: Date) -> i64 {
+        other.days_since_epoch() - self.days_since_epoch()
+    }
+
+    pub fn first_of_month(self) -> Self {
+        Self { day: 1, ..self }
+    }
+
+    pub fn last_of_month(self) -> Self {
+        Self {
+            day: self.month.days_in(self.year),
+            ..self
+        }
+    }
+}
+
+/// `later - earlier` is the number of days between them.
+impl Sub for Date {
+    type Output = i64;
+
+    fn sub(self, other: Date) -> i64 {
+        other.days_until(self)
+    }
+}
+
+impl Default for Date {
+    fn default() -> Self {
+        Self::EPOCH
+    }
+}
+
+/// ISO 8601 calendar date, `2024-02-29`; years before 1 get a sign,
+/// `-0044-03-15`.
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = if self.year < 0 {
+            format!(
+                "{:05}-{:02}-{:02}",
+                self.year,
+                self.month.number(),
+                self.day
+            )
+        } else {
+            format!(
+                "{:04}-{:02}-{:02}",
+                self.year,
+                self.month.number(),
+                self.day
+            )
+        };
+        f.pad(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn validates_fields() {
+        assert_eq!(
+            Date::new(2023, 2, 29),
+            Err(DateError::InvalidDay {
+                year: 2023,
+                month: Month::February,
+                day: 29
+            })
+        );
+        assert!(Date::new(2024, 2, 29).is_ok());
+        assert_eq!(Date::new(2024, 13, 1), Err(DateError::InvalidMonth(13)));
+        assert_eq!(
+            Date::new(2024, 4, 0).unwrap_err().to_string(),
+            "April 2024 has 30 days, not 0"
+        );
+        assert_eq!(
+            Date::new(10_000, 1, 1),
+            Err(DateError::YearOutOfRange(10_000))
+        );
+    }
+
+    #[test]
+    fn month_arithmetic_clamps() {
+        assert_eq!(date(2024, 1, 31).add_months(1), date(2024, 2, 29));
+        assert_eq!(date(2023, 1, 31).add_months(1), date(2023, 2, 28));
+        assert_eq!(date(2024, 3, 31).add_months(-1), date(2024, 2, 29));
+        assert_eq!(date(2024, 12, 15).add_months(1), date(2025, 1, 15));
+        assert_eq!(date(2024, 1, 15).add_months(-13), date(2022, 12, 15));
+        assert_eq!(
+            date(2024, 2, 29).checked_add_years(1),
+            Some(date(2025, 2, 28))
+        );
+        assert_eq!(Date::MAX.checked_add_months(1), None);
+        assert_eq!(Date::MIN.checked_add_months(-1), None);
+    }
+
+    #[test]
+    fn day_arithmetic() {
+        assert_eq!(date(2024, 2, 28).add_days(1), date(2024, 2, 29));
+        assert_eq!(date(2024, 3, 1).sub_days(1), date(2024, 2, 29));
+        assert_eq!(date(1999, 12, 31).add_days(1), date(2000, 1, 1));
+        assert_eq!(date(2024, 12, 25) - date(2024, 1, 1), 359);
+        assert_eq!(date(2000, 1, 1).days_until(date(1970, 1, 1)), -10_957);
+        assert_eq!(Date::MAX.checked_add_days(1), None);
+        assert_eq!(Date::MIN.checked_sub_days(1), None);
+        assert_eq!(date(2024, 1, 1).checked_add_days(i64::MAX), None);
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!(date(2024, 2, 9).to_string(), "2024-02-09");
+        assert_eq!(date(33, 4, 3).to_string(), "0033-04-03");
+        assert_eq!(date(-44, 3, 15).to_string(), "-0044-03-15");
+        assert_eq!(date(2024, 2, 9).ordinal(), 40);
+        assert_eq!(date(2024, 12, 31).ordinal(), 366);
+    }
+}
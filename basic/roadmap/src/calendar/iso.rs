@@ -0,0 +1,219 @@
+//! ISO 8601 week dates and text forms.
+//!
+//! Week 1 of an ISO year is the week (Monday to Sunday) containing the
+//! year's first Thursday, so the ISO year of a date near January 1 can
+//! differ from its calendar year: 2005-01-01 is `2004-W53-6`.
+
+use std::str::FromStr;
+
+use super::{Date, DateError, Month, Weekday};
+
+/// 52 or 53. A year has 53 weeks when it starts on a Thursday, or on a
+/// Wednesday in a leap year; either way it ends on a Thursday.
+pub fn weeks_in_iso_year(year: i32) -> u8 {
+    let jan1 = |year| Date::from_parts(year, Month::January, 1).map(Date::weekday);
+    let dec31 = |year| Date::from_parts(year, Month::December, 31).map(Date::weekday);
+    if jan1(year) == Ok(Weekday::Thursday) || dec31(year) == Ok(Weekday::Thursday) {
+        53
+    } else {
+        52
+    }
+}
+
+impl Date {
+    /// The ISO year and week number, `1..=53`.
+    pub fn iso_week(self) -> (i32, u8) {
+        let ordinal = i32::from(self.ordinal());
+        let weekday = i32::from(self.weekday().number());
+        // The Thursday of this week decides which year the week belongs to
+        let week = (ordinal - weekday + 10) / 7;
+        if week < 1 {
+            let year = self.year() - 1;
+            (year, weeks_in_iso_year(year))
+        } else if week > i32::from(weeks_in_iso_year(self.year())) {
+            (self.year() + 1, 1)
+        } else {
+            (self.year(), week as u8)
+        }
+    }
+
+    pub fn from_iso_week(year: i32, week: u8, weekday: Weekday) -> Result<Self, DateError> {
+        if week == 0 || week > weeks_in_iso_year(year) {
+            return Err(DateError::InvalidWeek { year, week });
+        }
+        // January 4 is always in week 1
+        let jan4 = Date::from_parts(year, Month::January, 4)?;
+        let monday = jan4.checked_sub_days(i64::from(jan4.weekday().number()) - 1);
+        monday
+            .and_then(|m| {
+                m.checked_add_days(7 * (i64::from(week) - 1) + i64::from(weekday.number()) - 1)
+            })
+            .ok_or(DateError::YearOutOfRange(year.into()))
+    }
+
+    /// `2004-W53-6`.
+    pub fn to_iso_week_string(self) -> String {
+        let (year, week) = self.iso_week();
+        format!("{}-W{week:02}-{}", year_text(year), self.weekday().number())
+    }
+
+    /// `2024-060`.
+    pub fn to_ordinal_string(self) -> String {
+        format!("{}-{:03}", year_text(self.year()), self.ordinal())
+    }
+}
+
+fn year_text(year: i32) -> String {
+    if year < 0 {
+        format!("{year:05}")
+    } else {
+        format!("{year:04}")
+    }
+}
+
+/// Accepts the calendar (`2024-02-29`, `20240229`), ordinal (`2024-060`,
+/// `2024060`) and week (`2024-W09-4`, `2024W094`) forms, with an optional
+/// `+` or `-` before the four-digit year.
+impl FromStr for Date {
+    type Err = DateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, unsigned) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if !unsigned.is_ascii() {
+            return Err(DateError::Parse("unexpected character"));
+        }
+        let (year, rest) = unsigned
+            .split_at_checked(4)
+            .ok_or(DateError::Parse("expected a four-digit year"))?;
+        let year = digits(year).ok_or(DateError::Parse("expected a four-digit year"))? as i32;
+        let year = if negative { -year } else { year };
+
+        let (extended, rest) = match rest.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, rest),
+        };
+        if let Some(week) = rest.strip_prefix('W') {
+            let (week, day) = match (extended, week.len()) {
+                (true, 4) if week.as_bytes()[2] == b'-' => (&week[..2], &week[3..]),
+                (false, 3) => (&week[..2], &week[2..]),
+                _ => return Err(DateError::Parse("expected a week date like 2024-W09-4")),
+            };
+            let week = digits(week).ok_or(DateError::Parse("week is not a number"))?;
+            let day = digits(day).ok_or(DateError::Parse("weekday is not a number"))?;
+            let weekday = Weekday::from_number(day as u8)
+                .ok_or(DateError::Parse("weekday is not in 1..=7"))?;
+            return Date::from_iso_week(year, week as u8, weekday);
+        }
+        match (extended, rest.len()) {
+            (_, 3) => {
+                let ordinal =
+                    digits(rest).ok_or(DateError::Parse("day of year is not a number"))?;
+                Date::from_ordinal(year, ordinal as u16)
+            }
+            (true, 5) if rest.as_bytes()[2] == b'-' => calendar_date(year, &rest[..2], &rest[3..]),
+            (false, 4) => calendar_date(year, &rest[..2], &rest[2..]),
+            (_, 0) => Err(DateError::Parse("missing month and day")),
+            _ => Err(DateError::Parse(
+                "expected YYYY-MM-DD, YYYY-DDD or YYYY-Www-D",
+            )),
+        }
+    }
+}
+
+fn calendar_date(year: i32, month: &str, day: &str) -> Result<Date, DateError> {
+    let month = digits(month).ok_or(DateError::Parse("month is not a number"))?;
+    let day = digits(day).ok_or(DateError::Parse("day is not a number"))?;
+    Date::new(year, month as u8, day as u8)
+}
+
+/// Only ASCII digits; `str::parse` would also accept a sign.
+fn digits(text: &str) -> Option<u32> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_every_form() {
+        let leap_day = date(2024, 2, 29);
+        for text in [
+            "2024-02-29",
+            "20240229",
+            "2024-060",
+            "2024060",
+            "2024-W09-4",
+            "2024W094",
+            "+2024-02-29",
+        ] {
+            assert_eq!(text.parse(), Ok(leap_day), "{text}");
+        }
+        assert_eq!("-0044-03-15".parse(), Ok(date(-44, 3, 15)));
+        assert_eq!("0000-12-31".parse(), Ok(date(0, 12, 31)));
+    }
+
+    #[test]
+    fn rejects_malformed_text() {
+        for text in [
+            "",
+            "2024",
+            "2024-",
+            "24-02-29",
+            "2024-2-29",
+            "2024-02-29T00:00",
+            "2024/02/29",
+            "2024-02-+9",
+            "2024-W9-4",
+            "2024-W09-8",
+            "２０２４-02-29",
+            "2024-0229",
+        ] {
+            assert!(
+                matches!(text.parse::<Date>(), Err(DateError::Parse(_))),
+                "{text:?} parsed"
+            );
+        }
+        assert_eq!(
+            "2023-02-29".parse::<Date>(),
+            Err(DateError::InvalidDay {
+                year: 2023,
+                month: Month::February,
+                day: 29
+            })
+        );
+        assert_eq!(
+            "2023-366".parse::<Date>(),
+            Err(DateError::InvalidOrdinal {
+                year: 2023,
+                ordinal: 366
+            })
+        );
+        assert_eq!(
+            "2024-W53-1".parse::<Date>(),
+            Err(DateError::InvalidWeek {
+                year: 2024,
+                week: 53
+            })
+        );
+    }
+
+    #[test]
+    fn week_and_ordinal_strings() {
+        assert_eq!(date(2005, 1, 1).to_iso_week_string(), "2004-W53-6");
+        assert_eq!(date(2008, 12, 29).to_iso_week_string(), "2009-W01-1");
+        assert_eq!(date(2024, 2, 29).to_ordinal_string(), "2024-060");
+        assert_eq!(date(-1, 1, 1).to_ordinal_string(), "-0001-001");
+    }
+}
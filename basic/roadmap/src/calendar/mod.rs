@@ -0,0 +1,212 @@
+//! Dates in the proleptic Gregorian calendar, std only.
+//!
+//! Grown from `demonstrate_months_example` in
+//! `syntax_and_semantics/arrays.rs`, which indexes a `[&str; 12]` of month
+//! names. "Proleptic" means the Gregorian leap-year rule is applied to
+//! every year, including those before the calendar was adopted in 1582,
+//! as ISO 8601 does. Year 0 exists and is the year before 1.
+
+use std::fmt;
+
+mod cal;
+mod date;
+mod iso;
+
+pub use cal::{WeekStart, month_calendar};
+pub use date::Date;
+pub use iso::weeks_in_iso_year;
+
+/// The month names from the arrays lesson.
+pub const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Divisible by 4, except centuries, except every fourth century.
+pub fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+pub fn days_in_year(year: i32) -> u16 {
+    if is_leap_year(year) { 366 } else { 365 }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Month {
+    January = 1,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December,
+}
+
+impl Month {
+    pub const ALL: [Month; 12] = [
+        Month::January,
+        Month::February,
+        Month::March,
+        Month::April,
+        Month::May,
+        Month::June,
+        Month::July,
+        Month::August,
+        Month::September,
+        Month::October,
+        Month::November,
+        Month::December,
+    ];
+
+    /// `None` outside `1..=12`.
+    pub fn from_number(number: u8) -> Option<Self> {
+        Self::ALL.get(usize::from(number).checked_sub(1)?).copied()
+    }
+
+    /// 1 for January through 12 for December.
+    pub fn number(self) -> u8 {
+        self as u8
+    }
+
+    pub fn name(self) -> &'static str {
+        MONTH_NAMES[self as usize - 1]
+    }
+
+    pub fn days_in(self, year: i32) -> u8 {
+        match self {
+            Month::February if is_leap_year(year) => 29,
+            Month::February => 28,
+            Month::April | Month::June | Month::September | Month::November => 30,
+            _ => 31,
+        }
+    }
+}
+
+impl fmt::Display for Month {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// `None` if `month` is not in `1..=12`.
+pub fn days_in_month(year: i32, month: u8) -> Option<u8> {
+    Month::from_number(month).map(|m| m.days_in(year))
+}
+
+/// Days of the week, numbered from Monday as in ISO 8601.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Weekday {
+    Monday = 1,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// 1 for Monday through 7 for Sunday.
+    pub fn number(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_number(number: u8) -> Option<Self> {
+        Self::ALL.get(usize::from(number).checked_sub(1)?).copied()
+    }
+
+    /// Days since the previous Sunday, 0 for Sunday itself.
+    pub fn days_from_sunday(self) -> u8 {
+        self.number() % 7
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Monday",
+            Weekday::Tuesday => "Tuesday",
+            Weekday::Wednesday => "Wednesday",
+            Weekday::Thursday => "Thursday",
+            Weekday::Friday => "Friday",
+            Weekday::Saturday => "Saturday",
+            Weekday::Sunday => "Sunday",
+        }
+    }
+}
+
+impl fmt::Display for Weekday {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    /// Outside `Date::MIN..=Date::MAX`.
+    YearOutOfRange(i64),
+    InvalidMonth(u8),
+    InvalidDay {
+        year: i32,
+        month: Month,
+        day: u8,
+    },
+    InvalidOrdinal {
+        year: i32,
+        ordinal: u16,
+    },
+    InvalidWeek {
+        year: i32,
+        week: u8,
+    },
+    /// Text that is not an ISO 8601 date, with the reason.
+    Parse(&'static str),
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateError::YearOutOfRange(year) => {
+                write!(f, "year {year} is outside -9999..=9999")
+            }
+            DateError::InvalidMonth(month) => write!(f, "month {month} is not in 1..=12"),
+            DateError::InvalidDay { year, month, day } => write!(
+                f,
+                "{month} {year} has {} days, not {day}",
+                month.days_in(*year)
+            ),
+            DateError::InvalidOrdinal { year, ordinal } => {
+                write!(f, "{year} has {} days, not {ordinal}", days_in_year(*year))
+            }
+            DateError::InvalidWeek { year, week } => {
+                write!(f, "ISO year {year} has no week {week}")
+            }
+            DateError::Parse(reason) => write!(f, "invalid ISO 8601 date: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for DateError {}
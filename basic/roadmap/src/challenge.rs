@@ -0,0 +1,306 @@
+//! "Predict the output" challenges inside lessons.
+//!
+//! Lessons print computed values with [`predict!`](crate::predict) where
+//! they would use `println!`, with the same format string and arguments:
+//!
+//! ```ignore
+//! predict!("   7 / 3 = {} (not 2.333...)", quotient);
+//! predict!("   u8: {} + {} = {}", a, b; a + b);
+//! ```
+//!
+//! The value after `;` (or the only argument) is the one to guess. Run
+//! normally, `predict!` prints exactly what `println!` would. Inside
+//! [`run`], it first prints the line with `?` in place of the value, asks
+//! for a guess, then prints the real line and scores the guess. Integers
+//! must match exactly; floats within a relative tolerance.
+
+use std::cell::RefCell;
+use std::fmt;
+
+/// Prints like `println!`, marking the last argument as a value to guess
+/// when the lesson runs as a challenge. See [`challenge`](crate::challenge).
+#[macro_export]
+macro_rules! predict {
+    ($fmt:literal $(, $arg:expr)* ; $value:expr) => {
+        match &$value {
+            value => $crate::challenge::checkpoint(
+                $crate::challenge::Questionable::expected(value),
+                || format!($fmt $(, $arg)*, $crate::challenge::Hidden),
+                format_args!($fmt $(, $arg)*, value),
+            ),
+        }
+    };
+    ($fmt:literal, $value:expr $(,)?) => {
+        $crate::predict!($fmt; $value)
+    };
+}
+
+/// The value a guess is checked against.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Integer(i128),
+    Float(f64),
+    /// Compared as displayed, e.g. `true` or `'a'`'s `a`.
+    Text(String),
+}
+
+impl Expected {
+    /// Whether `guess` is right. `tolerance` is relative, with values
+    /// below 1 treated as 1 so that guesses near zero are not held to an
+    /// impossible standard.
+    pub fn accepts(&self, guess: &str, tolerance: f64) -> bool {
+        let guess = guess.trim();
+        match self {
+            Expected::Integer(n) => guess.replace('_', "").parse::<i128>() == Ok(*n),
+            Expected::Float(x) => match guess.replace('_', "").parse::<f64>() {
+                Ok(g) if x.is_nan() => g.is_nan(),
+                Ok(g) if x.is_infinite() => g == *x,
+                Ok(g) => (g - x).abs() <= tolerance * x.abs().max(1.0),
+                Err(_) => false,
+            },
+            Expected::Text(text) => guess == text,
+        }
+    }
+}
+
+/// Values `predict!` can ask about.
+pub trait Questionable {
+    fn expected(&self) -> Expected;
+}
+
+macro_rules! questionable_integers {
+    ($($t:ty),*) => {$(
+        impl Questionable for $t {
+            fn expected(&self) -> Expected {
+                Expected::Integer(i128::from(*self))
+            }
+        }
+    )*};
+}
+
+questionable_integers!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl Questionable for usize {
+    fn expected(&self) -> Expected {
+        // usize is at most 64 bits on every supported target
+        Expected::Integer(*self as i128)
+    }
+}
+
+impl Questionable for isize {
+    fn expected(&self) -> Expected {
+        Expected::Integer(*self as i128)
+    }
+}
+
+impl Questionable for u128 {
+    fn expected(&self) -> Expected {
+        i128::try_from(*self).map_or_else(|_| Expected::Text(self.to_string()), Expected::Integer)
+    }
+}
+
+impl Questionable for f32 {
+    fn expected(&self) -> Expected {
+        Expected::Float(f64::from(*self))
+    }
+}
+
+impl Questionable for f64 {
+    fn expected(&self) -> Expected {
+        Expected::Float(*self)
+    }
+}
+
+macro_rules! questionable_text {
+    ($($t:ty),*) => {$(
+        impl Questionable for $t {
+            fn expected(&self) -> Expected {
+                Expected::Text(self.to_string())
+            }
+        }
+    )*};
+}
+
+questionable_text!(bool, char, str, String);
+
+impl<T: Questionable + ?Sized> Questionable for &T {
+    fn expected(&self) -> Expected {
+        (**self).expected()
+    }
+}
+
+/// Stands in for the value in the prompt; formats as `?`.
+pub struct Hidden;
+
+impl fmt::Display for Hidden {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("?")
+    }
+}
+
+impl fmt::Debug for Hidden {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("?")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Score {
+    pub asked: usize,
+    pub correct: usize,
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {} correct", self.correct, self.asked)
+    }
+}
+
+/// Takes the prompt line and returns the learner's guess, or `None` when
+/// there is no more input; the rest of the lesson then runs without
+/// asking.
+pub type Respond = Box<dyn FnMut(&str) -> Option<String>>;
+
+struct Session {
+    respond: Respond,
+    tolerance: f64,
+    score: Score,
+    finished: bool,
+}
+
+thread_local! {
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+/// Runs `lesson` as a challenge on this thread and returns the score.
+pub fn run(lesson: fn(), tolerance: f64, respond: Respond) -> Score {
+    /// Ends the session even if the lesson panics.
+    struct Active;
+
+    impl Drop for Active {
+        fn drop(&mut self) {
+            SESSION.with_borrow_mut(Option::take);
+        }
+    }
+
+    SESSION.with_borrow_mut(|session| {
+        *session = Some(Session {
+            respond,
+            tolerance,
+            score: Score::default(),
+            finished: false,
+        })
+    });
+    let active = Active;
+    lesson();
+    let score = SESSION.with_borrow(|session| session.as_ref().map(|s| s.score));
+    drop(active);
+    score.unwrap_or_default()
+}
+
+/// What [`predict!`](crate::predict) expands to: prints `line`, asking
+/// about `expected` first if a challenge is running.
+pub fn checkpoint(expected: Expected, prompt: impl FnOnce() -> String, line: fmt::Arguments<'_>) {
+    SESSION.with_borrow_mut(|session| {
+        let Some(session) = session.as_mut().filter(|s| !s.finished) else {
+            println!("{line}");
+            return;
+        };
+        let Some(guess) = (session.respond)(&prompt()) else {
+            session.finished = true;
+            println!("{line}");
+            return;
+        };
+        session.score.asked += 1;
+        println!("{line}");
+        if expected.accepts(&guess, session.tolerance) {
+            session.score.correct += 1;
+            println!("   ✓ correct");
+        } else if guess.trim().is_empty() {
+            println!("   ✗ skipped");
+        } else {
+            println!("   ✗ you said {}", guess.trim());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() {
+        let quotient = 7 / 3;
+        predict!("   7 / 3 = {} (not 2.333...)", quotient);
+        let (a, b) = (100u8, 50u8);
+        predict!("   u8: {} + {} = {}", a, b; a + b);
+        predict!("   7.0 / 3.0 = {:.3}", 7.0 / 3.0);
+        predict!("   {{braces}} {}", 'x');
+    }
+
+    #[test]
+    fn prompts_hide_only_the_value_and_guesses_are_scored() {
+        let prompts = std::rc::Rc::new(RefCell::new(Vec::new()));
+        let seen = prompts.clone();
+        let mut guesses = vec!["2", "150", "2.33", "y"].into_iter();
+        let score = run(
+            sample,
+            0.01,
+            Box::new(move |prompt| {
+                seen.borrow_mut().push(prompt.to_string());
+                guesses.next().map(String::from)
+            }),
+        );
+        assert_eq!(
+            *prompts.borrow(),
+            [
+                "   7 / 3 = ? (not 2.333...)",
+                "   u8: 100 + 50 = ?",
+                "   7.0 / 3.0 = ?",
+                "   {braces} ?",
+            ]
+        );
+        assert_eq!(
+            score,
+            Score {
+                asked: 4,
+                correct: 3
+            }
+        );
+
+        // Outside `run` nothing is asked
+        sample();
+    }
+
+    #[test]
+    fn running_out_of_input_stops_asking() {
+        let mut guesses = vec!["2"].into_iter();
+        let score = run(
+            sample,
+            0.01,
+            Box::new(move |_| guesses.next().map(String::from)),
+        );
+        assert_eq!(
+            score,
+            Score {
+                asked: 1,
+                correct: 1
+            }
+        );
+    }
+
+    #[test]
+    fn integers_exact_floats_within_tolerance() {
+        assert!(Expected::Integer(-2).accepts(" -2 ", 0.0));
+        assert!(Expected::Integer(1_000_000).accepts("1_000_000", 0.0));
+        assert!(!Expected::Integer(2).accepts("2.0", 0.5));
+        let third = Expected::Float(7.0 / 3.0);
+        assert!(third.accepts("2.33", 0.01));
+        assert!(!third.accepts("2.3", 0.01));
+        // Near zero the tolerance is absolute
+        assert!(Expected::Float(1e-9).accepts("0", 0.01));
+        assert!(Expected::Float(f64::NAN).accepts("NaN", 0.01));
+        assert!(Expected::Float(f64::INFINITY).accepts("inf", 0.01));
+        assert!(Expected::Text("true".into()).accepts("true", 0.0));
+        assert_eq!(u128::MAX.expected(), Expected::Text(u128::MAX.to_string()));
+    }
+}
@@ -0,0 +1,321 @@
+use std::fmt;
+use std::mem::MaybeUninit;
+
+use super::CapacityError;
+
+/// A vector with a fixed capacity of `N`, stored inline (on the stack
+/// when the `ArrayVec` itself is).
+///
+/// Only the first `len` slots are initialized. Nothing here panics: adding
+/// to a full vector hands the value back in a [`CapacityError`], and
+/// every index-based method returns an `Option` or `Result`. There is no
+/// `Index` impl, so `v[i]` cannot be written; use [`ArrayVec::get`].
+pub struct ArrayVec<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+/// Why [`ArrayVec::try_insert`] rejected a value, with the value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InsertError<T> {
+    Full(T),
+    /// `index` was past the end; inserting at `len` is allowed.
+    OutOfBounds {
+        index: usize,
+        len: usize,
+        value: T,
+    },
+}
+
+impl<T> InsertError<T> {
+    pub fn into_value(self) -> T {
+        match self {
+            InsertError::Full(value) | InsertError::OutOfBounds { value, .. } => value,
+        }
+    }
+}
+
+impl<T> fmt::Display for InsertError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertError::Full(_) => write!(f, "collection is full"),
+            InsertError::OutOfBounds { index, len, .. } => {
+                write!(f, "insertion index {index} is past the length {len}")
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for InsertError<T> {}
+
+impl<T, const N: usize> ArrayVec<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            items: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub const fn remaining_capacity(&self) -> usize {
+        N - self.len
+    }
+
+    /// Appends `value`, or gives it back if the vector is full.
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        let Some(slot) = self.items.get_mut(self.len) else {
+            return Err(CapacityError(value));
+        };
+        slot.write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.len = self.len.checked_sub(1)?;
+        // SAFETY: the slot at the old `len - 1` was initialized, and
+        // lowering `len` first means it is never read again.
+        Some(unsafe { self.items[self.len].assume_init_read() })
+    }
+
+    /// Inserts at `index`, shifting later elements right.
+    pub fn try_insert(&mut self, index: usize, value: T) -> Result<(), InsertError<T>> {
+        if index > self.len {
+            return Err(InsertError::OutOfBounds {
+                index,
+                len: self.len,
+                value,
+            });
+        }
+        if self.is_full() {
+            return Err(InsertError::Full(value));
+        }
+        // Rotating moves the uninitialized slot at `len` down to `index`;
+        // no value is dropped or duplicated.
+        self.items[index..=self.len].rotate_right(1);
+        self.items[index].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the element at `index`, shifting later elements left.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        self.items[index..self.len].rotate_left(1);
+        self.pop()
+    }
+
+    /// Removes the element at `index` by moving the last one into its
+    /// place. O(1), but does not keep the order.
+    pub fn swap_remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        self.items.swap(index, self.len - 1);
+        self.pop()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.as_mut_slice().get_mut(index)
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` slots are initialized, and
+        // `MaybeUninit<T>` has the same layout as `T`.
+        unsafe { std::slice::from_raw_parts(self.items.as_ptr().cast::<T>(), self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: as in `as_slice`, and `&mut self` makes the borrow unique.
+        unsafe { std::slice::from_raw_parts_mut(self.items.as_mut_ptr().cast::<T>(), self.len) }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Drops elements from the end until at most `len` remain.
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Appends clones of `values`, all or nothing.
+    pub fn try_extend_from_slice(&mut self, values: &[T]) -> Result<(), CapacityError<()>>
+    where
+        T: Clone,
+    {
+        if values.len() > self.remaining_capacity() {
+            return Err(CapacityError(()));
+        }
+        for value in values {
+            // Cannot fail after the check above
+            let _ = self.try_push(value.clone());
+        }
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayVec<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for ArrayVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayVec<T, N> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::new();
+        for value in self {
+            let _ = clone.try_push(value.clone());
+        }
+        clone
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for ArrayVec<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArrayVec<T, N> {}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut ArrayVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Collects the first `N` items, or fails if the slice is longer.
+impl<T: Clone, const N: usize> TryFrom<&[T]> for ArrayVec<T, N> {
+    type Error = CapacityError<()>;
+
+    fn try_from(values: &[T]) -> Result<Self, Self::Error> {
+        let mut vec = Self::new();
+        vec.try_extend_from_slice(values)?;
+        Ok(vec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn push_until_full_then_get_the_value_back() {
+        let mut vec: ArrayVec<String, 2> = ArrayVec::new();
+        assert!(vec.try_push("a".into()).is_ok());
+        assert!(vec.try_push("b".into()).is_ok());
+        let err = vec.try_push("c".into()).unwrap_err();
+        assert_eq!(err.into_inner(), "c");
+        assert_eq!(vec.as_slice(), ["a", "b"]);
+        assert_eq!(vec.get(2), None);
+        assert_eq!(vec.pop().as_deref(), Some("b"));
+        assert_eq!(vec.remaining_capacity(), 1);
+    }
+
+    #[test]
+    fn insert_and_remove_keep_order() {
+        let mut vec: ArrayVec<i32, 5> = ArrayVec::try_from(&[1, 2, 4][..]).unwrap();
+        vec.try_insert(2, 3).unwrap();
+        vec.try_insert(0, 0).unwrap();
+        assert_eq!(vec.as_slice(), [0, 1, 2, 3, 4]);
+        assert_eq!(vec.try_insert(5, 9), Err(InsertError::Full(9)));
+        assert_eq!(vec.remove(1), Some(1));
+        assert_eq!(
+            vec.try_insert(9, 9),
+            Err(InsertError::OutOfBounds {
+                index: 9,
+                len: 4,
+                value: 9
+            })
+        );
+        assert_eq!(vec.swap_remove(0), Some(0));
+        assert_eq!(vec.as_slice(), [4, 2, 3]);
+        assert_eq!(vec.remove(3), None);
+        assert!(ArrayVec::<i32, 2>::try_from(&[1, 2, 3][..]).is_err());
+    }
+
+    #[test]
+    fn zero_capacity_is_always_full() {
+        let mut vec: ArrayVec<u8, 0> = ArrayVec::new();
+        assert!(vec.is_full());
+        assert_eq!(vec.try_push(1), Err(CapacityError(1)));
+        assert_eq!(vec.pop(), None);
+    }
+
+    #[test]
+    fn every_element_is_dropped_exactly_once() {
+        let counter = Rc::new(());
+        {
+            let mut vec: ArrayVec<Rc<()>, 8> = ArrayVec::new();
+            for _ in 0..6 {
+                vec.try_push(Rc::clone(&counter)).unwrap();
+            }
+            vec.try_insert(3, Rc::clone(&counter)).unwrap();
+            drop(vec.remove(0));
+            drop(vec.swap_remove(1));
+            let copy = vec.clone();
+            assert_eq!(Rc::strong_count(&counter), 1 + 5 + 5);
+            drop(copy);
+            vec.truncate(2);
+            assert_eq!(Rc::strong_count(&counter), 3);
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,242 @@
+use std::fmt;
+use std::iter::FusedIterator;
+
+/// A set of small integers in `0..64 * WORDS`, one bit each, stored
+/// inline.
+///
+/// Out-of-range indexes never panic: [`BitSet::insert`] and
+/// [`BitSet::toggle`] return an [`IndexError`], and lookups report
+/// `false`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSet<const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexError {
+    pub index: usize,
+    pub capacity: usize,
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index {} is outside a set of capacity {}",
+            self.index, self.capacity
+        )
+    }
+}
+
+impl std::error::Error for IndexError {}
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    pub const CAPACITY: usize = 64 * WORDS;
+
+    pub const fn new() -> Self {
+        Self { words: [0; WORDS] }
+    }
+
+    /// Every index in `indexes`, or the first one that does not fit.
+    pub fn from_indexes(indexes: impl IntoIterator<Item = usize>) -> Result<Self, IndexError> {
+        let mut set = Self::new();
+        for index in indexes {
+            set.insert(index)?;
+        }
+        Ok(set)
+    }
+
+    /// The word and the mask of `index`'s bit, or an error if it does not
+    /// fit.
+    fn locate(index: usize) -> Result<(usize, u64), IndexError> {
+        if index >= Self::CAPACITY {
+            return Err(IndexError {
+                index,
+                capacity: Self::CAPACITY,
+            });
+        }
+        Ok((index / 64, 1 << (index % 64)))
+    }
+
+    /// Adds `index`; `Ok(true)` if it was not already present.
+    pub fn insert(&mut self, index: usize) -> Result<bool, IndexError> {
+        let (word, mask) = Self::locate(index)?;
+        let added = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        Ok(added)
+    }
+
+    /// Removes `index`; `true` if it was present. An out-of-range index is
+    /// never present.
+    pub fn remove(&mut self, index: usize) -> bool {
+        let Ok((word, mask)) = Self::locate(index) else {
+            return false;
+        };
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        present
+    }
+
+    /// Flips `index`; `Ok(true)` if it is now present.
+    pub fn toggle(&mut self, index: usize) -> Result<bool, IndexError> {
+        let (word, mask) = Self::locate(index)?;
+        self.words[word] ^= mask;
+        Ok(self.words[word] & mask != 0)
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        Self::locate(index).is_ok_and(|(word, mask)| self.words[word] & mask != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; WORDS];
+    }
+
+    fn combine(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        let mut words = self.words;
+        for (word, other) in words.iter_mut().zip(other.words) {
+            *word = op(*word, other);
+        }
+        Self { words }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a ^ b)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Indexes in ascending order.
+    pub fn iter(&self) -> Iter<'_, WORDS> {
+        Iter {
+            set: self,
+            word: 0,
+            bits: self.words.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> fmt::Debug for BitSet<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// `{1, 5, 64}`
+impl<const WORDS: usize> fmt::Display for BitSet<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indexes: Vec<String> = self.iter().map(|i| i.to_string()).collect();
+        write!(f, "{{{}}}", indexes.join(", "))
+    }
+}
+
+pub struct Iter<'a, const WORDS: usize> {
+    set: &'a BitSet<WORDS>,
+    word: usize,
+    /// Bits of `set.words[word]` not yet returned.
+    bits: u64,
+}
+
+impl<const WORDS: usize> Iterator for Iter<'_, WORDS> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.bits == 0 {
+            self.word += 1;
+            self.bits = *self.set.words.get(self.word)?;
+        }
+        let bit = self.bits.trailing_zeros() as usize;
+        // Clear the lowest set bit
+        self.bits &= self.bits - 1;
+        Some(self.word * 64 + bit)
+    }
+}
+
+impl<const WORDS: usize> FusedIterator for Iter<'_, WORDS> {}
+
+impl<'a, const WORDS: usize> IntoIterator for &'a BitSet<WORDS> {
+    type Item = usize;
+    type IntoIter = Iter<'a, WORDS>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove_toggle() {
+        let mut set: BitSet<2> = BitSet::new();
+        assert_eq!(set.insert(3), Ok(true));
+        assert_eq!(set.insert(3), Ok(false));
+        assert_eq!(set.insert(127), Ok(true));
+        assert_eq!(
+            set.insert(128),
+            Err(IndexError {
+                index: 128,
+                capacity: 128
+            })
+        );
+        assert!(!set.contains(128));
+        assert!(!set.remove(usize::MAX));
+        assert_eq!(set.toggle(64), Ok(true));
+        assert_eq!(set.toggle(64), Ok(false));
+        assert!(set.remove(3));
+        assert_eq!(set.to_string(), "{127}");
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn set_operations_match_a_model() {
+        let a: BitSet<3> = BitSet::from_indexes((0..192).filter(|i| i % 3 == 0)).unwrap();
+        let b: BitSet<3> = BitSet::from_indexes((0..192).filter(|i| i % 5 == 0)).unwrap();
+        let model = |f: &dyn Fn(bool, bool) -> bool| -> Vec<usize> {
+            (0..192).filter(|i| f(i % 3 == 0, i % 5 == 0)).collect()
+        };
+        assert!(a.union(&b).iter().eq(model(&|x, y| x || y)));
+        assert!(a.intersection(&b).iter().eq(model(&|x, y| x && y)));
+        assert!(a.difference(&b).iter().eq(model(&|x, y| x && !y)));
+        assert!(a.symmetric_difference(&b).iter().eq(model(&|x, y| x != y)));
+        assert!(a.intersection(&b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+    }
+
+    #[test]
+    fn empty_and_zero_sized_sets() {
+        let empty: BitSet<0> = BitSet::new();
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(BitSet::<0>::new().insert(0).unwrap_err().capacity, 0);
+        assert_eq!(BitSet::<4>::new().to_string(), "{}");
+    }
+}
@@ -0,0 +1,336 @@
+use std::fmt;
+use std::iter::FusedIterator;
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+/// A singly linked list used as a stack: `push` and `pop` work at the head.
+///
+/// Every node is a `Box`, so the list owns all of its elements. Dropping,
+/// cloning, comparing and formatting all walk the list in a loop rather
+/// than recursing, so a list of a million elements is as safe to drop as
+/// one of three.
+pub struct List<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> List<T> {
+    pub const fn new() -> Self {
+        Self { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn push(&mut self, value: T) {
+        let next = self.head.take();
+        self.head = Some(Box::new(Node { value, next }));
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.head.take().map(|node| {
+            let node = *node;
+            self.head = node.next;
+            self.len -= 1;
+            node.value
+        })
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_deref().map(|node| &node.value)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_deref_mut().map(|node| &mut node.value)
+    }
+
+    /// Reverses the list in place by relinking nodes; nothing is moved or
+    /// reallocated.
+    pub fn reverse(&mut self) {
+        let mut reversed: Link<T> = None;
+        let mut current = self.head.take();
+        while let Some(mut node) = current {
+            current = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    pub fn clear(&mut self) {
+        let mut current = self.head.take();
+        while let Some(mut node) = current {
+            // Detach the rest before `node` is dropped, so dropping it
+            // never recurses into the next node.
+            current = node.next.take();
+        }
+        self.len = 0;
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+            remaining: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref_mut(),
+            remaining: self.len,
+        }
+    }
+}
+
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+/// Collects in iteration order: the first item becomes the head, so
+/// `list.iter()` yields items in the order they were collected.
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        for value in iter {
+            list.push(value);
+        }
+        list.reverse();
+        list
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Formats like the lesson's cons list: `1 -> 2 -> 3 -> Nil`.
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for value in self.iter() {
+            write!(f, "{value} -> ")?;
+        }
+        f.write_str("Nil")
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            self.remaining -= 1;
+            &node.value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> FusedIterator for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.take().map(|node| {
+            self.next = node.next.as_deref_mut();
+            self.remaining -= 1;
+            &mut node.value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+pub struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_and_pop_are_last_in_first_out() {
+        let mut list = List::new();
+        assert_eq!(list.pop(), None);
+
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.peek(), Some(&3));
+
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+        list.push(4);
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+    }
+
+    #[test]
+    fn peek_mut_changes_the_head() {
+        let mut list: List<i32> = [1, 2].into_iter().collect();
+        if let Some(head) = list.peek_mut() {
+            *head *= 10;
+        }
+        assert_eq!(list.peek(), Some(&10));
+    }
+
+    #[test]
+    fn collect_keeps_iteration_order() {
+        let list: List<i32> = (1..=4).collect();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert_eq!(list.iter().len(), 4);
+    }
+
+    #[test]
+    fn iterates_by_reference_mutable_reference_and_value() {
+        let mut list: List<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+
+        let borrowed: Vec<&str> = (&list).into_iter().map(String::as_str).collect();
+        assert_eq!(borrowed, ["a", "b", "c"]);
+
+        for value in &mut list {
+            value.push('!');
+        }
+
+        let owned: Vec<String> = list.into_iter().collect();
+        assert_eq!(owned, ["a!", "b!", "c!"]);
+    }
+
+    #[test]
+    fn reverse_relinks_nodes() {
+        let mut list: List<i32> = (1..=5).collect();
+        list.reverse();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [5, 4, 3, 2, 1]);
+        assert_eq!(list.len(), 5);
+
+        let mut empty: List<i32> = List::new();
+        empty.reverse();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn display_matches_the_cons_list_lesson() {
+        let list: List<i32> = (1..=3).collect();
+        assert_eq!(list.to_string(), "1 -> 2 -> 3 -> Nil");
+        assert_eq!(List::<i32>::new().to_string(), "Nil");
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn clone_and_eq_compare_elements() {
+        let list: List<i32> = (1..=3).collect();
+        let copy = list.clone();
+        assert_eq!(list, copy);
+        assert_ne!(list, (1..=2).collect());
+    }
+
+    #[test]
+    fn dropping_a_million_elements_does_not_overflow_the_stack() {
+        let list: List<u32> = (0..1_000_000).collect();
+        assert_eq!(list.len(), 1_000_000);
+        let copy = list.clone();
+        assert_eq!(list, copy);
+        drop(list);
+        drop(copy);
+    }
+}
@@ -0,0 +1,43 @@
+//! Collections built up from the lesson examples.
+//!
+//! `List` is the `enum List { Cons(i32, Box<List>), Nil }` from
+//! `ownership_system/box_stack_heap.rs` grown into a usable generic stack,
+//! and `PersistentList` is its `Rc`-based immutable sibling.
+//!
+//! `ArrayVec`, `RingBuffer` and `BitSet` are fixed-capacity alternatives
+//! to indexing a plain array, as in the bounds-checking section of
+//! `syntax_and_semantics/arrays.rs`: they never allocate and never panic,
+//! returning `Option` or `Result` where an array index would panic.
+
+use std::fmt;
+
+mod array_vec;
+mod bitset;
+mod list;
+mod persistent;
+mod ring_buffer;
+
+pub use array_vec::{ArrayVec, InsertError};
+pub use bitset::{BitSet, IndexError};
+pub use list::{IntoIter, Iter, IterMut, List};
+pub use persistent::{PersistentIter, PersistentList};
+pub use ring_buffer::RingBuffer;
+
+/// A fixed-capacity collection was full; holds the value that did not
+/// fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T>(pub T);
+
+impl<T> CapacityError<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("collection is full")
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for CapacityError<T> {}
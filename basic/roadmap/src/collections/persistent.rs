@@ -0,0 +1,247 @@
+use std::fmt;
+use std::iter::FusedIterator;
+use std::rc::Rc;
+
+type Link<T> = Option<Rc<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+/// An immutable singly linked list with structural sharing.
+///
+/// `prepend` and `tail` return new lists that share every existing node
+/// with the original through `Rc`, so both are O(1) and no element is ever
+/// copied. Cloning a list only bumps a reference count.
+pub struct PersistentList<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> PersistentList<T> {
+    pub const fn new() -> Self {
+        Self { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// A new list with `value` in front of `self`'s nodes.
+    pub fn prepend(&self, value: T) -> Self {
+        Self {
+            head: Some(Rc::new(Node {
+                value,
+                next: self.head.clone(),
+            })),
+            len: self.len + 1,
+        }
+    }
+
+    /// The list without its first element, sharing the remaining nodes.
+    /// The tail of an empty list is empty.
+    pub fn tail(&self) -> Self {
+        match &self.head {
+            Some(node) => Self {
+                head: node.next.clone(),
+                len: self.len - 1,
+            },
+            None => Self::new(),
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_deref().map(|node| &node.value)
+    }
+
+    /// Whether the two lists start at the very same node, i.e. one was
+    /// obtained from the other without adding anything.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    pub fn iter(&self) -> PersistentIter<'_, T> {
+        PersistentIter {
+            next: self.head.as_deref(),
+            remaining: self.len,
+        }
+    }
+}
+
+impl<T: Clone> PersistentList<T> {
+    /// A reversed copy. Nothing can be shared, since every `next` link
+    /// changes.
+    pub fn reverse(&self) -> Self {
+        self.iter()
+            .fold(Self::new(), |list, value| list.prepend(value.clone()))
+    }
+}
+
+impl<T> Drop for PersistentList<T> {
+    fn drop(&mut self) {
+        // Free nodes only while this list is their sole owner. The first
+        // node that is still shared stops the walk: its other owners keep
+        // it, and everything after it, alive.
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => current = node.next.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+impl<T> Clone for PersistentList<T> {
+    fn clone(&self) -> Self {
+        Self {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for PersistentList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq> PartialEq for PersistentList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && (self.ptr_eq(other) || self.iter().eq(other.iter()))
+    }
+}
+
+impl<T: Eq> Eq for PersistentList<T> {}
+
+/// Collects in iteration order, like `List`.
+impl<T> FromIterator<T> for PersistentList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values: Vec<T> = iter.into_iter().collect();
+        values
+            .into_iter()
+            .rev()
+            .fold(Self::new(), |list, value| list.prepend(value))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PersistentList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for PersistentList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for value in self.iter() {
+            write!(f, "{value} -> ")?;
+        }
+        f.write_str("Nil")
+    }
+}
+
+pub struct PersistentIter<'a, T> {
+    next: Option<&'a Node<T>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for PersistentIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            self.remaining -= 1;
+            &node.value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for PersistentIter<'_, T> {}
+impl<T> FusedIterator for PersistentIter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a PersistentList<T> {
+    type Item = &'a T;
+    type IntoIter = PersistentIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepend_and_tail_leave_the_original_untouched() {
+        let base: PersistentList<i32> = (1..=3).collect();
+        let longer = base.prepend(0);
+        let shorter = base.tail();
+
+        assert_eq!(base.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(longer.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert_eq!(shorter.iter().copied().collect::<Vec<_>>(), [2, 3]);
+        assert_eq!((base.len(), longer.len(), shorter.len()), (3, 4, 2));
+        assert_eq!(longer.head(), Some(&0));
+    }
+
+    #[test]
+    fn derived_lists_share_nodes() {
+        let base: PersistentList<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        let longer = base.prepend("z".to_string());
+
+        assert!(longer.tail().ptr_eq(&base));
+        assert!(base.clone().ptr_eq(&base));
+        assert!(!longer.ptr_eq(&base));
+    }
+
+    #[test]
+    fn tail_of_empty_is_empty() {
+        let empty: PersistentList<i32> = PersistentList::new();
+        assert!(empty.tail().is_empty());
+        assert_eq!(empty.head(), None);
+    }
+
+    #[test]
+    fn dropping_one_version_keeps_shared_nodes_alive() {
+        let base: PersistentList<i32> = (1..=3).collect();
+        let longer = base.prepend(0);
+        drop(base);
+        assert_eq!(longer.to_string(), "0 -> 1 -> 2 -> 3 -> Nil");
+    }
+
+    #[test]
+    fn reverse_returns_a_new_list() {
+        let list: PersistentList<i32> = (1..=3).collect();
+        assert_eq!(
+            list.reverse().iter().copied().collect::<Vec<_>>(),
+            [3, 2, 1]
+        );
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn dropping_a_million_elements_does_not_overflow_the_stack() {
+        let list: PersistentList<u32> = (0..1_000_000).collect();
+        let shared = list.tail().tail();
+        drop(list);
+        assert_eq!(shared.len(), 999_998);
+        assert_eq!(shared.head(), Some(&2));
+        drop(shared);
+    }
+}
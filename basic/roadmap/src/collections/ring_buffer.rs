@@ -0,0 +1,230 @@
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::CapacityError;
+
+/// A first-in, first-out queue of at most `N` elements, stored inline.
+///
+/// `N` must be a power of two; other capacities fail to compile. The
+/// buffer keeps two counters, `read` and `write`, that only ever go up
+/// with `wrapping_add`, the intentional wrapping that
+/// `syntax_and_semantics/overflow_methods.rs` recommends for circular
+/// buffers. The length is `write.wrapping_sub(read)` and a counter's slot
+/// is `counter % N`; because `N` divides `usize::MAX + 1`, both stay
+/// correct when a counter wraps past `usize::MAX`.
+pub struct RingBuffer<T, const N: usize> {
+    slots: [Option<T>; N],
+    read: usize,
+    write: usize,
+}
+
+impl<T, const N: usize> RingBuffer<T, N> {
+    const POWER_OF_TWO: () = assert!(
+        N.is_power_of_two(),
+        "RingBuffer capacity must be a power of two"
+    );
+
+    pub const fn new() -> Self {
+        Self::starting_at(0)
+    }
+
+    /// A buffer whose counters start at `start`; lets tests reach the
+    /// wrap-around without pushing `usize::MAX` elements.
+    const fn starting_at(start: usize) -> Self {
+        let () = Self::POWER_OF_TWO;
+        Self {
+            slots: [const { None }; N],
+            read: start,
+            write: start,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.write.wrapping_sub(self.read)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    fn slot(counter: usize) -> usize {
+        counter % N
+    }
+
+    /// Adds `value` at the back, or gives it back if the buffer is full.
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError(value));
+        }
+        self.slots[Self::slot(self.write)] = Some(value);
+        self.write = self.write.wrapping_add(1);
+        Ok(())
+    }
+
+    /// Adds `value` at the back, evicting and returning the oldest element
+    /// if the buffer is full.
+    pub fn push_overwrite(&mut self, value: T) -> Option<T> {
+        let evicted = if self.is_full() { self.pop() } else { None };
+        // Cannot fail: there is room now
+        let _ = self.try_push(value);
+        evicted
+    }
+
+    /// Removes the oldest element.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let value = self.slots[Self::slot(self.read)].take();
+        self.read = self.read.wrapping_add(1);
+        value
+    }
+
+    /// The `index`th oldest element.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        self.slots[Self::slot(self.read.wrapping_add(index))].as_ref()
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+        self.slots[Self::slot(self.read.wrapping_add(index))].as_mut()
+    }
+
+    /// The oldest element, next to be popped.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// The newest element.
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    /// Oldest to newest.
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter {
+            buffer: self,
+            index: 0,
+        }
+    }
+}
+
+impl<T, const N: usize> Default for RingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for RingBuffer<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct Iter<'a, T, const N: usize> {
+    buffer: &'a RingBuffer<T, N>,
+    index: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.buffer.get(self.index)?;
+        self.index += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.buffer.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for Iter<'_, T, N> {}
+impl<T, const N: usize> FusedIterator for Iter<'_, T, N> {}
+
+impl<'a, T, const N: usize> IntoIterator for &'a RingBuffer<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_in_first_out() {
+        let mut ring: RingBuffer<i32, 4> = RingBuffer::new();
+        for value in 1..=4 {
+            ring.try_push(value).unwrap();
+        }
+        assert_eq!(ring.try_push(5), Err(CapacityError(5)));
+        assert_eq!(ring.pop(), Some(1));
+        ring.try_push(5).unwrap();
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 5]);
+        assert_eq!((ring.front(), ring.back()), (Some(&2), Some(&5)));
+        assert_eq!(ring.get(4), None);
+    }
+
+    #[test]
+    fn overwrite_evicts_the_oldest() {
+        let mut ring: RingBuffer<char, 2> = RingBuffer::new();
+        assert_eq!(ring.push_overwrite('a'), None);
+        assert_eq!(ring.push_overwrite('b'), None);
+        assert_eq!(ring.push_overwrite('c'), Some('a'));
+        assert_eq!(format!("{ring:?}"), "['b', 'c']");
+    }
+
+    #[test]
+    fn counters_wrap_past_usize_max() {
+        let mut ring: RingBuffer<usize, 8> = RingBuffer::starting_at(usize::MAX - 5);
+        let mut model = std::collections::VecDeque::new();
+        for step in 0..100 {
+            if step % 3 == 2 {
+                assert_eq!(ring.pop(), model.pop_front());
+            } else {
+                let evicted = ring.push_overwrite(step);
+                if model.len() == 8 {
+                    assert_eq!(evicted, model.pop_front());
+                }
+                model.push_back(step);
+            }
+            assert_eq!(ring.len(), model.len());
+            assert!(ring.iter().eq(model.iter()));
+        }
+        // The counters really did wrap
+        assert!(ring.write < 100);
+    }
+
+    #[test]
+    fn empty_buffer() {
+        let mut ring: RingBuffer<u8, 1> = RingBuffer::default();
+        assert_eq!((ring.pop(), ring.front(), ring.back()), (None, None, None));
+        ring.try_push(7).unwrap();
+        assert!(ring.is_full());
+        ring.clear();
+        assert!(ring.is_empty());
+    }
+}
@@ -0,0 +1,277 @@
+//! Color models and conversions.
+//!
+//! Grown from the `Color` enum in `syntax_and_semantics/destructuring.rs`,
+//! which only carries numbers. [`Rgb`] is 8-bit sRGB and is the hub every
+//! other model converts through.
+//!
+//! Rounding: conversions out of `Rgb` are done in `f64` and not rounded.
+//! Conversions into `Rgb` scale each channel to `0..=255` and round to the
+//! nearest integer, ties away from zero, so `Rgb -> model -> Rgb` is exact.
+//! The percent-style `Display` forms (`hsl(210, 50%, 40%)`) round to whole
+//! degrees and percent and are for people, not for round-tripping.
+
+use std::fmt;
+use std::str::FromStr;
+
+mod models;
+mod wcag;
+
+pub use models::{Cmyk, Hsl, Hsv};
+pub use wcag::WcagLevel;
+
+/// An 8-bit sRGB color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+    pub const RED: Self = Self::new(255, 0, 0);
+    pub const GREEN: Self = Self::new(0, 255, 0);
+    pub const BLUE: Self = Self::new(0, 0, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Channels scaled to `0.0..=1.0`.
+    pub fn to_unit(self) -> [f64; 3] {
+        [self.r, self.g, self.b].map(|c| f64::from(c) / 255.0)
+    }
+
+    /// The inverse of [`Rgb::to_unit`]; inputs are clamped to `0.0..=1.0`
+    /// and NaN counts as 0.
+    pub fn from_unit(r: f64, g: f64, b: f64) -> Self {
+        Self::new(to_channel(r), to_channel(g), to_channel(b))
+    }
+
+    /// Lowercase `#rrggbb`, the same as `Display`.
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// Linear interpolation between `self` (`t = 0`) and `other` (`t = 1`)
+    /// on the stored sRGB values, like CSS `color-mix(in srgb, ...)`.
+    /// `t` is clamped to `0.0..=1.0`.
+    pub fn mix(self, other: Self, t: f64) -> Self {
+        let t = unit(t);
+        let [a, b] = [self.to_unit(), other.to_unit()];
+        Self::from_unit(
+            a[0] + (b[0] - a[0]) * t,
+            a[1] + (b[1] - a[1]) * t,
+            a[2] + (b[2] - a[2]) * t,
+        )
+    }
+
+    /// Like [`Rgb::mix`] but interpolates in linear light, which keeps the
+    /// midpoint of two saturated colors from looking muddy.
+    pub fn mix_linear(self, other: Self, t: f64) -> Self {
+        let t = unit(t);
+        let [a, b] = [self.to_unit(), other.to_unit()].map(|c| c.map(wcag::linearize));
+        let channel = |i: usize| wcag::delinearize(a[i] + (b[i] - a[i]) * t);
+        Self::from_unit(channel(0), channel(1), channel(2))
+    }
+
+    /// Draws `self` with opacity `alpha` over `background`.
+    pub fn over(self, background: Self, alpha: f64) -> Self {
+        background.mix(self, alpha)
+    }
+
+    /// The 24-bit ANSI escape that sets the terminal foreground color.
+    pub fn ansi_foreground(self) -> String {
+        format!("\x1b[38;2;{};{};{}m", self.r, self.g, self.b)
+    }
+
+    /// The 24-bit ANSI escape that sets the terminal background color.
+    pub fn ansi_background(self) -> String {
+        format!("\x1b[48;2;{};{};{}m", self.r, self.g, self.b)
+    }
+}
+
+/// NaN becomes 0, everything else is clamped to `0.0..=1.0`.
+fn unit(x: f64) -> f64 {
+    if x.is_nan() { 0.0 } else { x.clamp(0.0, 1.0) }
+}
+
+fn to_channel(x: f64) -> u8 {
+    // In range after clamping, so the cast cannot saturate
+    (unit(x) * 255.0).round() as u8
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.to_hex())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorError {
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid hex color: {}", self.reason)
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+impl FromStr for Rgb {
+    type Err = ParseColorError;
+
+    /// Accepts `#rgb` or `#rrggbb` in either case; the `#` is optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |reason| ParseColorError { reason };
+        let digits = s.strip_prefix('#').unwrap_or(s);
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(err("expected hex digits"));
+        }
+        let nibble = |i: usize| u8::from_str_radix(&digits[i..=i], 16).expect("checked above");
+        match digits.len() {
+            // `#abc` is shorthand for `#aabbcc`
+            3 => Ok(Self::new(nibble(0) * 17, nibble(1) * 17, nibble(2) * 17)),
+            6 => {
+                let byte = |i: usize| nibble(i) << 4 | nibble(i + 1);
+                Ok(Self::new(byte(0), byte(2), byte(4)))
+            }
+            _ => Err(err("expected 3 or 6 digits")),
+        }
+    }
+}
+
+impl From<(u8, u8, u8)> for Rgb {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Self::new(r, g, b)
+    }
+}
+
+/// A color in any of the supported models, like the lesson's `Color` enum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    Rgb(Rgb),
+    Hsv(Hsv),
+    Hsl(Hsl),
+    Cmyk(Cmyk),
+}
+
+impl Color {
+    pub fn to_rgb(self) -> Rgb {
+        match self {
+            Color::Rgb(rgb) => rgb,
+            Color::Hsv(hsv) => hsv.into(),
+            Color::Hsl(hsl) => hsl.into(),
+            Color::Cmyk(cmyk) => cmyk.into(),
+        }
+    }
+
+    /// WCAG 2 contrast ratio, from 1 (none) to 21 (black on white).
+    pub fn contrast_ratio(self, other: Color) -> f64 {
+        self.to_rgb().contrast_ratio(other.to_rgb())
+    }
+}
+
+impl From<Color> for Rgb {
+    fn from(color: Color) -> Self {
+        color.to_rgb()
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Rgb(c) => c.fmt(f),
+            Color::Hsv(c) => c.fmt(f),
+            Color::Hsl(c) => c.fmt(f),
+            Color::Cmyk(c) => c.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every 5th value per channel, plus the 255 edge: 140k colors.
+    fn sample() -> impl Iterator<Item = Rgb> {
+        let steps = || (0..=255u8).step_by(5).chain([254, 255]);
+        steps()
+            .flat_map(move |r| steps().flat_map(move |g| steps().map(move |b| Rgb::new(r, g, b))))
+    }
+
+    #[test]
+    fn hex_parsing_and_formatting() {
+        assert_eq!("#7a1128".parse(), Ok(Rgb::new(122, 17, 40)));
+        assert_eq!("7A1128".parse(), Ok(Rgb::new(122, 17, 40)));
+        assert_eq!("#fA0".parse(), Ok(Rgb::new(255, 170, 0)));
+        assert_eq!(Rgb::new(122, 17, 40).to_string(), "#7a1128");
+        assert_eq!(format!("{:>9}", Rgb::BLACK), "  #000000");
+        for bad in [
+            "", "#", "#12", "#1234", "#12345g", "##123456", "#+12345", "#1234567",
+        ] {
+            assert!(bad.parse::<Rgb>().is_err(), "{bad}");
+        }
+        for rgb in sample() {
+            assert_eq!(rgb.to_hex().parse(), Ok(rgb));
+        }
+    }
+
+    #[test]
+    fn exact_round_trips() {
+        for rgb in sample() {
+            assert_eq!(Rgb::from(Hsv::from(rgb)), rgb, "hsv {rgb}");
+            assert_eq!(Rgb::from(Hsl::from(rgb)), rgb, "hsl {rgb}");
+            assert_eq!(Rgb::from(Cmyk::from(rgb)), rgb, "cmyk {rgb}");
+        }
+    }
+
+    #[test]
+    fn percent_display_error_is_bounded() {
+        // Half a percent of lightness or saturation is 1.3 steps of 255 each,
+        // and half a degree of hue moves a channel by up to 255 / 120
+        const BOUND: u8 = 5;
+        let mut worst = 0;
+        for rgb in sample() {
+            let hsl = Hsl::from(rgb);
+            let hsl = Hsl::new(
+                hsl.h.round(),
+                (hsl.s * 100.0).round() / 100.0,
+                (hsl.l * 100.0).round() / 100.0,
+            );
+            let back = Rgb::from(hsl);
+            for (a, b) in [(rgb.r, back.r), (rgb.g, back.g), (rgb.b, back.b)] {
+                worst = worst.max(a.abs_diff(b));
+            }
+        }
+        assert!(worst <= BOUND, "worst channel error {worst}");
+    }
+
+    #[test]
+    fn mixing() {
+        assert_eq!(Rgb::BLACK.mix(Rgb::WHITE, 0.5), Rgb::new(128, 128, 128));
+        assert_eq!(Rgb::RED.mix(Rgb::BLUE, 0.0), Rgb::RED);
+        assert_eq!(Rgb::RED.mix(Rgb::BLUE, 1.0), Rgb::BLUE);
+        assert_eq!(Rgb::RED.mix(Rgb::BLUE, 7.0), Rgb::BLUE);
+        assert_eq!(Rgb::RED.mix(Rgb::BLUE, f64::NAN), Rgb::RED);
+        // Linear light keeps the midpoint brighter
+        assert_eq!(Rgb::RED.mix_linear(Rgb::GREEN, 0.5), Rgb::new(188, 188, 0));
+        assert_eq!(Rgb::WHITE.over(Rgb::BLACK, 0.25), Rgb::new(64, 64, 64));
+    }
+
+    #[test]
+    fn color_enum_converts_each_model() {
+        let expected = Rgb::new(122, 17, 40);
+        for color in [
+            Color::Rgb(expected),
+            Color::Hsv(expected.into()),
+            Color::Hsl(expected.into()),
+            Color::Cmyk(expected.into()),
+        ] {
+            assert_eq!(color.to_rgb(), expected, "{color}");
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+
+use super::{Rgb, unit};
+
+/// Hue in degrees and the shared min/max/chroma of the unit channels.
+fn hue_and_extremes(rgb: Rgb) -> (f64, f64, f64) {
+    let [r, g, b] = rgb.to_unit();
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+    let hue = if chroma == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / chroma + 2.0)
+    } else {
+        60.0 * ((r - g) / chroma + 4.0)
+    };
+    (hue, min, max)
+}
+
+/// Places `chroma` on the hue wheel and lifts every channel by `m`.
+fn from_hue_chroma(hue: f64, chroma: f64, m: f64) -> Rgb {
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    Rgb::from_unit(r + m, g + m, b + m)
+}
+
+/// Wraps any finite hue into `0.0..360.0`; NaN and infinities become 0.
+fn wrap_hue(h: f64) -> f64 {
+    if h.is_finite() {
+        // rem_euclid can round up to exactly 360.0 for tiny negative input
+        let h = h.rem_euclid(360.0);
+        if h >= 360.0 { 0.0 } else { h }
+    } else {
+        0.0
+    }
+}
+
+/// Hue, saturation and value, with `h` in degrees and `s`, `v` in `0..=1`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Hsv {
+    pub h: f64,
+    pub s: f64,
+    pub v: f64,
+}
+
+impl Hsv {
+    /// The hue wraps around; `s` and `v` are clamped and NaN counts as 0.
+    pub fn new(h: f64, s: f64, v: f64) -> Self {
+        Self {
+            h: wrap_hue(h),
+            s: unit(s),
+            v: unit(v),
+        }
+    }
+}
+
+impl From<Rgb> for Hsv {
+    fn from(rgb: Rgb) -> Self {
+        let (h, min, max) = hue_and_extremes(rgb);
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+        Self { h, s, v: max }
+    }
+}
+
+impl From<Hsv> for Rgb {
+    fn from(hsv: Hsv) -> Self {
+        let Hsv { h, s, v } = Hsv::new(hsv.h, hsv.s, hsv.v);
+        let chroma = v * s;
+        from_hue_chroma(h, chroma, v - chroma)
+    }
+}
+
+impl fmt::Display for Hsv {
+    /// `hsv(210, 50%, 40%)`, rounded to whole degrees and percent.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = format!(
+            "hsv({}, {}%, {}%)",
+            degrees(self.h),
+            percent(self.s),
+            percent(self.v)
+        );
+        f.pad(&text)
+    }
+}
+
+/// Hue, saturation and lightness, with `h` in degrees and `s`, `l` in `0..=1`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+impl Hsl {
+    /// The hue wraps around; `s` and `l` are clamped and NaN counts as 0.
+    pub fn new(h: f64, s: f64, l: f64) -> Self {
+        Self {
+            h: wrap_hue(h),
+            s: unit(s),
+            l: unit(l),
+        }
+    }
+}
+
+impl From<Rgb> for Hsl {
+    fn from(rgb: Rgb) -> Self {
+        let (h, min, max) = hue_and_extremes(rgb);
+        let l = (max + min) / 2.0;
+        let s = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        Self { h, s, l }
+    }
+}
+
+impl From<Hsl> for Rgb {
+    fn from(hsl: Hsl) -> Self {
+        let Hsl { h, s, l } = Hsl::new(hsl.h, hsl.s, hsl.l);
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        from_hue_chroma(h, chroma, l - chroma / 2.0)
+    }
+}
+
+impl fmt::Display for Hsl {
+    /// `hsl(210, 50%, 40%)`, rounded to whole degrees and percent.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = format!(
+            "hsl({}, {}%, {}%)",
+            degrees(self.h),
+            percent(self.s),
+            percent(self.l)
+        );
+        f.pad(&text)
+    }
+}
+
+/// Subtractive cyan, magenta, yellow and key (black), each in `0..=1`.
+///
+/// This is the naive device-independent formula; it knows nothing about
+/// ink or paper profiles.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Cmyk {
+    pub c: f64,
+    pub m: f64,
+    pub y: f64,
+    pub k: f64,
+}
+
+impl Cmyk {
+    /// Components are clamped and NaN counts as 0.
+    pub fn new(c: f64, m: f64, y: f64, k: f64) -> Self {
+        Self {
+            c: unit(c),
+            m: unit(m),
+            y: unit(y),
+            k: unit(k),
+        }
+    }
+}
+
+impl From<Rgb> for Cmyk {
+    fn from(rgb: Rgb) -> Self {
+        let [r, g, b] = rgb.to_unit();
+        let max = r.max(g).max(b);
+        if max == 0.0 {
+            // Pure black: all key, the other inks do not matter
+            return Self::new(0.0, 0.0, 0.0, 1.0);
+        }
+        Self {
+            c: (max - r) / max,
+            m: (max - g) / max,
+            y: (max - b) / max,
+            k: 1.0 - max,
+        }
+    }
+}
+
+impl From<Cmyk> for Rgb {
+    fn from(cmyk: Cmyk) -> Self {
+        let Cmyk { c, m, y, k } = Cmyk::new(cmyk.c, cmyk.m, cmyk.y, cmyk.k);
+        Rgb::from_unit(
+            (1.0 - c) * (1.0 - k),
+            (1.0 - m) * (1.0 - k),
+            (1.0 - y) * (1.0 - k),
+        )
+    }
+}
+
+impl fmt::Display for Cmyk {
+    /// `cmyk(0%, 86%, 67%, 52%)`, rounded to whole percent.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = format!(
+            "cmyk({}%, {}%, {}%, {}%)",
+            percent(self.c),
+            percent(self.m),
+            percent(self.y),
+            percent(self.k)
+        );
+        f.pad(&text)
+    }
+}
+
+fn degrees(h: f64) -> u32 {
+    // 359.6 rounds to 360, which is the same hue as 0
+    wrap_hue(h).round() as u32 % 360
+}
+
+fn percent(x: f64) -> u32 {
+    (unit(x) * 100.0).round() as u32
+}
@@ -0,0 +1,117 @@
+//! Relative luminance and contrast ratio as defined by WCAG 2.
+
+use super::Rgb;
+
+/// sRGB transfer function, unit channel to linear light.
+///
+/// WCAG 2 quotes 0.03928 as the threshold, a leftover from an early sRGB
+/// draft; 0.04045 is the standard value and no 8-bit channel falls between.
+pub(super) fn linearize(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Inverse of [`linearize`].
+pub(super) fn delinearize(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// How a contrast ratio rates against the WCAG 2 text thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WcagLevel {
+    /// Below 3:1.
+    Fail,
+    /// At least 3:1, enough for large text only.
+    AaLarge,
+    /// At least 4.5:1.
+    Aa,
+    /// At least 7:1.
+    Aaa,
+}
+
+impl WcagLevel {
+    pub fn for_ratio(ratio: f64) -> Self {
+        if ratio >= 7.0 {
+            WcagLevel::Aaa
+        } else if ratio >= 4.5 {
+            WcagLevel::Aa
+        } else if ratio >= 3.0 {
+            WcagLevel::AaLarge
+        } else {
+            WcagLevel::Fail
+        }
+    }
+}
+
+impl Rgb {
+    /// Perceived brightness from 0 (black) to 1 (white).
+    pub fn relative_luminance(self) -> f64 {
+        let [r, g, b] = self.to_unit().map(linearize);
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// From 1 (no contrast) to 21 (black on white); symmetric in its
+    /// arguments.
+    pub fn contrast_ratio(self, other: Rgb) -> f64 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    pub fn wcag_level(self, background: Rgb) -> WcagLevel {
+        WcagLevel::for_ratio(self.contrast_ratio(background))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contrast_extremes() {
+        assert_eq!(Rgb::BLACK.contrast_ratio(Rgb::WHITE), 21.0);
+        assert_eq!(Rgb::WHITE.contrast_ratio(Rgb::BLACK), 21.0);
+        assert_eq!(Rgb::RED.contrast_ratio(Rgb::RED), 1.0);
+    }
+
+    #[test]
+    fn known_ratios() {
+        // Values from the WebAIM contrast checker
+        let cases = [
+            ("#777777", "#ffffff", 4.48),
+            ("#767676", "#ffffff", 4.54),
+            ("#0000ff", "#ffffff", 8.59),
+            ("#ff0000", "#ffffff", 4.0),
+        ];
+        for (fg, bg, expected) in cases {
+            let fg: Rgb = fg.parse().unwrap();
+            let ratio = fg.contrast_ratio(bg.parse().unwrap());
+            assert!((ratio - expected).abs() < 0.01, "{fg}: {ratio}");
+        }
+        // #767676 is the lightest grey that passes AA on white
+        assert_eq!(
+            Rgb::new(0x77, 0x77, 0x77).wcag_level(Rgb::WHITE),
+            WcagLevel::AaLarge
+        );
+        assert_eq!(
+            Rgb::new(0x76, 0x76, 0x76).wcag_level(Rgb::WHITE),
+            WcagLevel::Aa
+        );
+        assert_eq!(Rgb::BLACK.wcag_level(Rgb::WHITE), WcagLevel::Aaa);
+    }
+
+    #[test]
+    fn transfer_function_round_trips() {
+        for c in 0..=255u8 {
+            let unit = f64::from(c) / 255.0;
+            assert!((delinearize(linearize(unit)) - unit).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+
+use super::{Message, Statement};
+use crate::color::Rgb;
+use crate::geometry::Point;
+
+/// What the messages act on.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct State {
+    pub cursor: Point<i32>,
+    pub buffer: String,
+    pub color: Rgb,
+    pub quit: bool,
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cursor {}, color {}, buffer {:?}",
+            self.cursor, self.color, self.buffer
+        )
+    }
+}
+
+/// How to take an applied message back.
+#[derive(Debug, Clone)]
+enum Inverse {
+    MoveTo(Point<i32>),
+    Truncate(usize),
+    Recolor(Rgb),
+}
+
+/// Applies messages to a [`State`] with undo and redo.
+///
+/// History stores the inverse of each change rather than whole snapshots,
+/// so undoing a `write` does not keep a copy of the buffer around. `quit`
+/// is final and is not part of the history.
+#[derive(Debug, Default)]
+pub struct Interpreter {
+    state: State,
+    undo: Vec<(Message, Inverse)>,
+    redo: Vec<Message>,
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Applies a message and clears the redo history.
+    pub fn apply(&mut self, message: Message) {
+        self.redo.clear();
+        self.apply_keeping_redo(message);
+    }
+
+    fn apply_keeping_redo(&mut self, message: Message) {
+        let state = &mut self.state;
+        let inverse = match &message {
+            Message::Quit => {
+                state.quit = true;
+                return;
+            }
+            Message::Move { x, y } => {
+                let from = state.cursor;
+                state.cursor = Point::new(*x, *y);
+                Inverse::MoveTo(from)
+            }
+            Message::Write(text) => {
+                let len = state.buffer.len();
+                state.buffer.push_str(text);
+                Inverse::Truncate(len)
+            }
+            &Message::ChangeColor(r, g, b) => {
+                let from = state.color;
+                // Parsing checks the range; out-of-range values built in
+                // code saturate instead of wrapping
+                let channel = |c: i32| c.clamp(0, 255) as u8;
+                state.color = Rgb::new(channel(r), channel(g), channel(b));
+                Inverse::Recolor(from)
+            }
+        };
+        self.undo.push((message, inverse));
+    }
+
+    /// Reverts the latest change and returns the message that made it.
+    pub fn undo(&mut self) -> Option<&Message> {
+        let (message, inverse) = self.undo.pop()?;
+        match inverse {
+            Inverse::MoveTo(point) => self.state.cursor = point,
+            Inverse::Truncate(len) => self.state.buffer.truncate(len),
+            Inverse::Recolor(color) => self.state.color = color,
+        }
+        self.redo.push(message);
+        self.redo.last()
+    }
+
+    /// Re-applies the latest undone message and returns it.
+    pub fn redo(&mut self) -> Option<&Message> {
+        let message = self.redo.pop()?;
+        self.apply_keeping_redo(message);
+        self.undo.last().map(|(message, _)| message)
+    }
+
+    /// Executes one statement. `false` for an `undo` or `redo` with nothing
+    /// to do.
+    pub fn execute(&mut self, statement: Statement) -> bool {
+        match statement {
+            Statement::Message(message) => {
+                self.apply(message);
+                true
+            }
+            Statement::Undo => self.undo().is_some(),
+            Statement::Redo => self.redo().is_some(),
+        }
+    }
+
+    /// Executes statements until one of them quits, and returns how many
+    /// were executed.
+    pub fn run(&mut self, statements: impl IntoIterator<Item = Statement>) -> usize {
+        let mut count = 0;
+        for statement in statements {
+            if self.state.quit {
+                break;
+            }
+            self.execute(statement);
+            count += 1;
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::parse_script;
+
+    fn run(script: &str) -> Interpreter {
+        let mut interpreter = Interpreter::new();
+        interpreter.run(parse_script(script).unwrap());
+        interpreter
+    }
+
+    #[test]
+    fn applies_messages() {
+        let interpreter = run("move 3 4\nwrite \"hi\"\nwrite \" there\"\ncolor 255 0 0");
+        let state = interpreter.state();
+        assert_eq!(state.cursor, Point::new(3, 4));
+        assert_eq!(state.buffer, "hi there");
+        assert_eq!(state.color, Rgb::RED);
+        assert!(!state.quit);
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_history() {
+        let mut interpreter = run("write \"a\"\nmove 1 1\nwrite \"b\"\ncolor 0 0 255");
+        assert_eq!(interpreter.undo(), Some(&Message::ChangeColor(0, 0, 255)));
+        assert_eq!(interpreter.undo(), Some(&Message::Write("b".into())));
+        assert_eq!(interpreter.state().buffer, "a");
+        assert_eq!(interpreter.state().color, Rgb::BLACK);
+        assert_eq!(interpreter.redo(), Some(&Message::Write("b".into())));
+        assert_eq!(interpreter.state().buffer, "ab");
+
+        // A new message forgets what could have been redone
+        interpreter.apply(Message::Move { x: 9, y: 9 });
+        assert!(!interpreter.can_redo());
+        assert!(interpreter.redo().is_none());
+
+        while interpreter.undo().is_some() {}
+        assert_eq!(*interpreter.state(), State::default());
+    }
+
+    #[test]
+    fn undo_and_redo_inside_scripts() {
+        let interpreter = run("write \"one\"\nwrite \"two\"\nundo\nundo\nredo\nundo\nundo");
+        assert_eq!(interpreter.state().buffer, "");
+        assert!(!interpreter.can_undo());
+        assert!(interpreter.can_redo());
+    }
+
+    #[test]
+    fn quit_stops_a_run() {
+        let mut interpreter = Interpreter::new();
+        let executed = interpreter.run(parse_script("write \"a\"\nquit\nwrite \"b\"").unwrap());
+        assert_eq!(executed, 2);
+        assert!(interpreter.state().quit);
+        assert_eq!(interpreter.state().buffer, "a");
+        // quit is not undoable, so only the write comes back out
+        assert_eq!(interpreter.undo(), Some(&Message::Write("a".into())));
+        assert!(interpreter.state().quit);
+    }
+}
@@ -0,0 +1,67 @@
+//! A small command runtime for the `Message` enum from
+//! `constructs/enums.rs` and `syntax_and_semantics/patterns_and_matching.rs`.
+//!
+//! Messages are written one per line:
+//!
+//! ```text
+//! # comments and blank lines are ignored
+//! move 3 4
+//! write "hello\n"
+//! color 255 0 0
+//! undo
+//! redo
+//! quit
+//! ```
+//!
+//! [`parse_script`] turns text into [`Statement`]s and an [`Interpreter`]
+//! applies them to a [`State`], keeping undo and redo history.
+
+use std::fmt;
+
+mod interpreter;
+mod parse;
+
+pub use interpreter::{Interpreter, State};
+pub use parse::{ParseError, parse_line, parse_script};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Quit,
+    Move {
+        x: i32,
+        y: i32,
+    },
+    Write(String),
+    /// Red, green and blue, each checked to be in `0..=255` when parsed.
+    ChangeColor(i32, i32, i32),
+}
+
+/// One line of a script: a message or a history command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement {
+    Message(Message),
+    Undo,
+    Redo,
+}
+
+/// Writes the message back in the text syntax, so it parses again.
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Message::Quit => f.write_str("quit"),
+            Message::Move { x, y } => write!(f, "move {x} {y}"),
+            Message::Write(text) => write!(f, "write {}", parse::quote(text)),
+            Message::ChangeColor(r, g, b) => write!(f, "color {r} {g} {b}"),
+        }
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Statement::Message(message) => message.fmt(f),
+            Statement::Undo => f.write_str("undo"),
+            Statement::Redo => f.write_str("redo"),
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Message, Statement};
+
+/// A parse failure with a 1-based line and character column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// The offending source line with a caret under the error column.
+    pub fn caret(&self, source_line: &str) -> String {
+        format!("{source_line}\n{:>width$}", "^", width = self.column)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, PartialEq)]
+enum TokenKind {
+    Word(String),
+    Str(String),
+}
+
+#[derive(Debug)]
+struct Token {
+    column: usize,
+    kind: TokenKind,
+}
+
+/// Errors before the line number is known: `(column, message)`.
+type LineError = (usize, String);
+
+fn tokenize(line: &str) -> Result<Vec<Token>, LineError> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().enumerate().peekable();
+    while let Some(&(i, c)) = chars.peek() {
+        let column = i + 1;
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '#' {
+            break;
+        } else if c == '"' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                let Some((i, c)) = chars.next() else {
+                    return Err((column, "unterminated string".to_string()));
+                };
+                match c {
+                    '"' => break,
+                    '\\' => match chars.next() {
+                        Some((_, '"')) => text.push('"'),
+                        Some((_, '\\')) => text.push('\\'),
+                        Some((_, 'n')) => text.push('\n'),
+                        Some((_, 't')) => text.push('\t'),
+                        Some((_, other)) => {
+                            return Err((i + 1, format!("unknown escape `\\{other}`")));
+                        }
+                        None => return Err((column, "unterminated string".to_string())),
+                    },
+                    c => text.push(c),
+                }
+            }
+            if chars
+                .peek()
+                .is_some_and(|&(_, c)| !c.is_whitespace() && c != '#')
+            {
+                return Err((column, "expected a space after the string".to_string()));
+            }
+            tokens.push(Token {
+                column,
+                kind: TokenKind::Str(text),
+            });
+        } else {
+            let mut word = String::new();
+            while let Some(&(_, c)) = chars.peek() {
+                if c.is_whitespace() || c == '"' || c == '#' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token {
+                column,
+                kind: TokenKind::Word(word),
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+/// Escapes `text` so that the tokenizer reads it back unchanged.
+pub(super) fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Walks the arguments of one command, remembering where the line ends
+/// so that a missing argument can point just past it.
+struct Args<'a> {
+    tokens: std::slice::Iter<'a, Token>,
+    end_column: usize,
+}
+
+impl Args<'_> {
+    fn next(&mut self, what: &str) -> Result<&Token, LineError> {
+        self.tokens
+            .next()
+            .ok_or_else(|| (self.end_column, format!("missing {what}")))
+    }
+
+    fn int(&mut self, what: &str) -> Result<i32, LineError> {
+        let token = self.next(what)?;
+        match &token.kind {
+            TokenKind::Word(word) => word.parse().map_err(|_| {
+                (
+                    token.column,
+                    format!("expected an integer {what}, found `{word}`"),
+                )
+            }),
+            TokenKind::Str(_) => Err((
+                token.column,
+                format!("expected an integer {what}, found a string"),
+            )),
+        }
+    }
+
+    fn channel(&mut self, what: &str) -> Result<i32, LineError> {
+        let column = self.tokens.as_slice().first().map(|t| t.column);
+        let value = self.int(what)?;
+        if !(0..=255).contains(&value) {
+            let column = column.unwrap_or(self.end_column);
+            return Err((column, format!("{what} must be in 0..=255, found {value}")));
+        }
+        Ok(value)
+    }
+
+    fn string(&mut self, what: &str) -> Result<String, LineError> {
+        let token = self.next(what)?;
+        match &token.kind {
+            TokenKind::Str(text) => Ok(text.clone()),
+            TokenKind::Word(word) => Err((
+                token.column,
+                format!("expected a quoted {what}, found `{word}`"),
+            )),
+        }
+    }
+
+    fn finish(mut self) -> Result<(), LineError> {
+        match self.tokens.next() {
+            Some(token) => Err((token.column, "unexpected extra argument".to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_tokens(line: &str) -> Result<Option<Statement>, LineError> {
+    let tokens = tokenize(line)?;
+    let Some((command, rest)) = tokens.split_first() else {
+        return Ok(None);
+    };
+    let TokenKind::Word(name) = &command.kind else {
+        return Err((command.column, "expected a command".to_string()));
+    };
+    let mut args = Args {
+        tokens: rest.iter(),
+        end_column: line.trim_end().chars().count() + 1,
+    };
+    let statement = match name.as_str() {
+        "quit" => Statement::Message(Message::Quit),
+        "move" => Statement::Message(Message::Move {
+            x: args.int("x")?,
+            y: args.int("y")?,
+        }),
+        "write" => Statement::Message(Message::Write(args.string("text")?)),
+        "color" | "change_color" => Statement::Message(Message::ChangeColor(
+            args.channel("red")?,
+            args.channel("green")?,
+            args.channel("blue")?,
+        )),
+        "undo" => Statement::Undo,
+        "redo" => Statement::Redo,
+        other => return Err((command.column, format!("unknown command `{other}`"))),
+    };
+    args.finish()?;
+    Ok(Some(statement))
+}
+
+/// Parses one line; `None` for blank lines and comments. `number` is the
+/// 1-based line number used in errors.
+pub fn parse_line(line: &str, number: usize) -> Result<Option<Statement>, ParseError> {
+    parse_tokens(line).map_err(|(column, message)| ParseError {
+        line: number,
+        column,
+        message,
+    })
+}
+
+/// Parses a whole script, collecting every error rather than stopping at
+/// the first, so a batch run can report them all before doing anything.
+pub fn parse_script(source: &str) -> Result<Vec<Statement>, Vec<ParseError>> {
+    let mut statements = Vec::new();
+    let mut errors = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        match parse_line(line, i + 1) {
+            Ok(Some(statement)) => statements.push(statement),
+            Ok(None) => {}
+            Err(err) => errors.push(err),
+        }
+    }
+    if errors.is_empty() {
+        Ok(statements)
+    } else {
+        Err(errors)
+    }
+}
+
+impl FromStr for Message {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_line(s, 1)? {
+            Some(Statement::Message(message)) => Ok(message),
+            _ => Err(ParseError {
+                line: 1,
+                column: 1,
+                message: "expected a message".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(line: &str) -> (usize, String) {
+        let err = parse_line(line, 1).unwrap_err();
+        (err.column, err.message)
+    }
+
+    #[test]
+    fn parses_every_message() {
+        assert_eq!("quit".parse(), Ok(Message::Quit));
+        assert_eq!(
+            "  move -3 4  # comment".parse(),
+            Ok(Message::Move { x: -3, y: 4 })
+        );
+        assert_eq!(
+            r#"write "say \"hi\"\n""#.parse(),
+            Ok(Message::Write("say \"hi\"\n".into()))
+        );
+        assert_eq!(
+            "write \"#not a comment\"".parse(),
+            Ok(Message::Write("#not a comment".into()))
+        );
+        assert_eq!(
+            "color 255 0 10".parse(),
+            Ok(Message::ChangeColor(255, 0, 10))
+        );
+        assert_eq!(
+            "change_color 1 2 3".parse(),
+            Ok(Message::ChangeColor(1, 2, 3))
+        );
+        assert_eq!(parse_line("undo", 1), Ok(Some(Statement::Undo)));
+        assert_eq!(parse_line("   # nothing", 1), Ok(None));
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let messages = [
+            Message::Quit,
+            Message::Move { x: -1, y: 2 },
+            Message::Write("tab\there \"quoted\" back\\slash\n".into()),
+            Message::ChangeColor(0, 128, 255),
+        ];
+        for message in messages {
+            assert_eq!(message.to_string().parse(), Ok(message));
+        }
+    }
+
+    #[test]
+    fn errors_point_at_the_column() {
+        assert_eq!(error("jump 1 2"), (1, "unknown command `jump`".into()));
+        assert_eq!(
+            error("move 1 two"),
+            (8, "expected an integer y, found `two`".into())
+        );
+        assert_eq!(error("move 1"), (7, "missing y".into()));
+        assert_eq!(error("move 1   "), (7, "missing y".into()));
+        assert_eq!(
+            error("move 1 2 3"),
+            (10, "unexpected extra argument".into())
+        );
+        assert_eq!(
+            error("write hi"),
+            (7, "expected a quoted text, found `hi`".into())
+        );
+        assert_eq!(error("write \"open"), (7, "unterminated string".into()));
+        assert_eq!(error("write \"a\\qb\""), (9, "unknown escape `\\q`".into()));
+        assert_eq!(
+            error("write \"a\"b"),
+            (7, "expected a space after the string".into())
+        );
+        assert_eq!(
+            error("color 0 256 0"),
+            (9, "green must be in 0..=255, found 256".into())
+        );
+        assert_eq!(error("\"quit\""), (1, "expected a command".into()));
+        // Columns count characters, not bytes
+        assert_eq!(
+            error("write \"é\" x"),
+            (11, "unexpected extra argument".into())
+        );
+    }
+
+    #[test]
+    fn scripts_report_every_error() {
+        let script = "move 1 2\nfly\n\nwrite \"ok\"\ncolor 1 2\n";
+        let errors = parse_script(script).unwrap_err();
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [2, 5]);
+        assert_eq!(errors[1].to_string(), "line 5, column 10: missing blue");
+        assert_eq!(errors[1].caret("color 1 2"), "color 1 2\n         ^");
+    }
+}
@@ -1,4 +1,6 @@
-fn main() {
+use crate::{commands, net};
+
+pub fn main() {
     println!("=== Enums in Rust ===\n");
 
     // 1. Basic enum definition
@@ -90,7 +92,25 @@ fn demonstrate_enum_with_data() {
         IpAddr::V6(addr) => println!("   Loopback: IPv6 address {}", addr),
     }
 
-    println!("\n   Enum variants become constructor functions\n");
+    println!("\n   Enum variants become constructor functions");
+
+    // The String variants accept any text; crate::net keeps the same two
+    // variants but holds fixed-size, validated addresses instead
+    println!("\n   V4(String) also accepts \"not an address\". With crate::net:");
+    let inputs = [
+        "127.0.0.1",
+        "2001:0DB8:0:0:0:0:0:1",
+        "::ffff:192.0.2.1",
+        "not an address",
+    ];
+    for input in inputs {
+        match input.parse::<net::IpAddr>() {
+            Ok(net::IpAddr::V4(addr)) => println!("   {:<22} -> IPv4 {}", input, addr),
+            Ok(net::IpAddr::V6(addr)) => println!("   {:<22} -> IPv6 {}", input, addr),
+            Err(err) => println!("   {:<22} -> {}", input, err),
+        }
+    }
+    println!();
 }
 
 // 3. Different data types in variants
@@ -145,7 +165,9 @@ fn demonstrate_different_data_types() {
             Message::Quit => println!("   Quit message"),
             Message::Move { x, y } => println!("   Move to ({}, {})", x, y),
             Message::Write(text) => println!("   Write: {}", text),
-            Message::ChangeColor(r, g, b) => println!("   Change color to RGB({}, {}, {})", r, g, b),
+            Message::ChangeColor(r, g, b) => {
+                println!("   Change color to RGB({}, {}, {})", r, g, b)
+            }
         }
     }
 
@@ -192,7 +214,15 @@ fn demonstrate_enum_methods() {
 
     let m = Message::Write(String::from("hello"));
     m.call();
-    println!("   Description: {}\n", m.describe());
+    println!("   Description: {}", m.describe());
+
+    // crate::commands has the same enum with a parser and an interpreter,
+    // so messages can come from text instead of being built in code
+    match "move 3 4".parse::<commands::Message>() {
+        Ok(parsed) => println!("   Parsed \"move 3 4\" into {:?}", parsed),
+        Err(err) => println!("   Parse error: {}", err),
+    }
+    println!();
 }
 
 // 5. The Option enum
@@ -291,4 +321,4 @@ fn demonstrate_working_with_option() {
     }
 
     println!();
-}
\ No newline at end of file
+}
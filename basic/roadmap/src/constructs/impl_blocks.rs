@@ -1,4 +1,6 @@
-fn main() {
+use crate::geometry::{Point, Rectangle};
+
+pub fn main() {
     println!("=== impl Keyword in Rust ===\n");
 
     // 1. Basic methods with &self
@@ -31,33 +33,13 @@ fn demonstrate_basic_methods() {
     println!("1. Basic Methods with &self");
     println!("   Methods that borrow the instance immutably\n");
 
-    #[derive(Debug)]
-    struct Rectangle {
-        width: u32,
-        height: u32,
-    }
-
-    impl Rectangle {
-        // Method that borrows self immutably
-        fn area(&self) -> u32 {
-            self.width * self.height
-        }
-
-        fn perimeter(&self) -> u32 {
-            2 * (self.width + self.height)
-        }
-
-        fn is_square(&self) -> bool {
-            self.width == self.height
-        }
-    }
-
-    let rect = Rectangle {
-        width: 30,
-        height: 50,
-    };
+    // From src/geometry/rectangle.rs:
+    //   fn area(&self) -> T
+    //   fn perimeter(&self) -> T
+    //   fn is_square(&self) -> bool
+    let rect: Rectangle<u32> = Rectangle::new(30, 50);
 
-    println!("   Rectangle: {:?}", rect);
+    println!("   Rectangle: {}", rect);
     println!("   Area: {}", rect.area());
     println!("   Perimeter: {}", rect.perimeter());
     println!("   Is square? {}", rect.is_square());
@@ -69,45 +51,24 @@ fn demonstrate_mutable_methods() {
     println!("2. Mutable Methods with &mut self");
     println!("   Methods that borrow the instance mutably\n");
 
-    #[derive(Debug)]
-    struct Rectangle {
-        width: u32,
-        height: u32,
-    }
+    // From src/geometry/rectangle.rs:
+    //   fn set_width(&mut self, width: T)
+    //   fn scale(&mut self, factor: T)
+    let mut rect: Rectangle<u32> = Rectangle::new(10, 20);
 
-    impl Rectangle {
-        fn area(&self) -> u32 {
-            self.width * self.height
-        }
-
-        // Method that modifies self
-        fn set_width(&mut self, width: u32) {
-            self.width = width;
-        }
-
-        #[allow(dead_code)]
-        fn set_height(&mut self, height: u32) {
-            self.height = height;
-        }
-
-        fn scale(&mut self, factor: u32) {
-            self.width *= factor;
-            self.height *= factor;
-        }
-    }
-
-    let mut rect = Rectangle {
-        width: 10,
-        height: 20,
-    };
-
-    println!("   Original: {:?}, area: {}", rect, rect.area());
+    println!("   Original: {}, area: {}", rect, rect.area());
 
     rect.set_width(15);
-    println!("   After set_width(15): {:?}, area: {}", rect, rect.area());
+    println!("   After set_width(15): {}, area: {}", rect, rect.area());
 
     rect.scale(2);
-    println!("   After scale(2): {:?}, area: {}", rect, rect.area());
+    println!("   After scale(2): {}, area: {}", rect, rect.area());
+
+    // scale panics on overflow, just like `*`; checked_scale returns None
+    println!(
+        "   checked_scale(u32::MAX): {:?}",
+        rect.checked_scale(u32::MAX)
+    );
     println!();
 }
 
@@ -116,50 +77,23 @@ fn demonstrate_methods_with_parameters() {
     println!("3. Methods with Multiple Parameters");
     println!("   Methods can take additional parameters after self\n");
 
-    #[derive(Debug)]
-    struct Rectangle {
-        width: u32,
-        height: u32,
-    }
-
-    impl Rectangle {
-        #[allow(dead_code)]
-        fn area(&self) -> u32 {
-            self.width * self.height
-        }
-
-        // Method with another parameter
-        fn can_hold(&self, other: &Rectangle) -> bool {
-            self.width > other.width && self.height > other.height
-        }
-
-        // Method with multiple parameters
-        #[allow(dead_code)]
-        fn resize(&mut self, new_width: u32, new_height: u32) {
-            self.width = new_width;
-            self.height = new_height;
-        }
-    }
-
-    let rect1 = Rectangle {
-        width: 30,
-        height: 50,
-    };
-    let rect2 = Rectangle {
-        width: 10,
-        height: 40,
-    };
-    let rect3 = Rectangle {
-        width: 60,
-        height: 45,
-    };
+    // From src/geometry/rectangle.rs:
+    //   fn can_hold(&self, other: &Rectangle<T>) -> bool
+    //   fn resize(&mut self, width: T, height: T)
+    let rect1: Rectangle<u32> = Rectangle::new(30, 50);
+    let rect2 = Rectangle::new(10, 40);
+    let mut rect3 = Rectangle::new(60, 45);
 
-    println!("   rect1: {:?}", rect1);
-    println!("   rect2: {:?}", rect2);
-    println!("   rect3: {:?}", rect3);
+    println!("   rect1: {}", rect1);
+    println!("   rect2: {}", rect2);
+    println!("   rect3: {}", rect3);
     println!();
     println!("   Can rect1 hold rect2? {}", rect1.can_hold(&rect2));
     println!("   Can rect1 hold rect3? {}", rect1.can_hold(&rect3));
+
+    rect3.resize(20, 20);
+    println!("   After rect3.resize(20, 20): {}", rect3);
+    println!("   Can rect1 hold rect3? {}", rect1.can_hold(&rect3));
     println!();
 }
 
@@ -168,44 +102,23 @@ fn demonstrate_associated_functions() {
     println!("4. Associated Functions");
     println!("   Functions without self, often used as constructors\n");
 
-    #[allow(dead_code)]
-    #[derive(Debug)]
-    struct Rectangle {
-        width: u32,
-        height: u32,
-    }
-
-    impl Rectangle {
-        // Associated function - constructor
-        fn new(width: u32, height: u32) -> Self {
-            Self { width, height }
-        }
-
-        // Associated function - specialized constructor
-        fn square(size: u32) -> Self {
-            Self {
-                width: size,
-                height: size,
-            }
-        }
-
-        // Associated function - factory method
-        fn default() -> Self {
-            Self {
-                width: 1,
-                height: 1,
-            }
-        }
-    }
+    // From src/geometry/rectangle.rs:
+    //   fn new(width: T, height: T) -> Self
+    //   fn square(size: T) -> Self
+    //   fn at(origin: Point<T>, width: T, height: T) -> Self
 
     // Call with :: syntax
-    let rect1 = Rectangle::new(30, 50);
-    let rect2 = Rectangle::square(25);
-    let rect3 = Rectangle::default();
+    let rect1: Rectangle<u32> = Rectangle::new(30, 50);
+    let rect2: Rectangle<u32> = Rectangle::square(25);
+    let rect3 = Rectangle::at(Point::new(5, 5), 10, 20);
 
-    println!("   Rectangle::new(30, 50): {:?}", rect1);
-    println!("   Rectangle::square(25): {:?}", rect2);
-    println!("   Rectangle::default(): {:?}", rect3);
+    println!("   Rectangle::new(30, 50): {}", rect1);
+    println!("   Rectangle::square(25): {}", rect2);
+    println!("   Rectangle::at(Point::new(5, 5), 10, 20): {}", rect3);
+
+    // Fallible constructors return a Result instead of panicking
+    let bad: Result<Rectangle<i32>, _> = Rectangle::try_at(Point::new(0, 0), -1, 5);
+    println!("   Rectangle::try_at(.., -1, 5): {:?}", bad);
     println!();
 }
 
@@ -214,25 +127,7 @@ fn demonstrate_method_syntax_sugar() {
     println!("5. Method Calls as Syntactic Sugar");
     println!("   Method calls are sugar for function calls\n");
 
-    struct Rectangle {
-        width: u32,
-        height: u32,
-    }
-
-    impl Rectangle {
-        fn area(&self) -> u32 {
-            self.width * self.height
-        }
-
-        fn set_width(&mut self, width: u32) {
-            self.width = width;
-        }
-    }
-
-    let mut r = Rectangle {
-        width: 10,
-        height: 20,
-    };
+    let mut r: Rectangle<u32> = Rectangle::new(10, 20);
 
     // Method syntax
     let area1 = r.area();
@@ -250,7 +145,7 @@ fn demonstrate_method_syntax_sugar() {
     println!("   Function syntax: Rectangle::set_width(&mut r, 20)");
     Rectangle::set_width(&mut r, 20);
 
-    println!("   Final width: {}", r.width);
+    println!("   Final width: {}", r.width());
     println!();
 }
 
@@ -355,66 +250,68 @@ fn demonstrate_consuming_methods() {
     println!("8. Methods that Consume self");
     println!("   Methods can take ownership of self\n");
 
+    // From src/geometry/rectangle.rs:
+    //   fn max(self, other: Rectangle<T>) -> Rectangle<T>
+    //   fn into_square(self) -> Rectangle<T>
+    // Rectangle<u32> is Copy, so to show a real move we use a wrapper that
+    // owns a label and is not Copy.
     #[derive(Debug)]
-    struct Rectangle {
-        width: u32,
-        height: u32,
+    struct Labeled {
+        label: String,
+        rect: Rectangle<u32>,
     }
 
-    impl Rectangle {
-        #[allow(dead_code)]
-        fn area(&self) -> u32 {
-            self.width * self.height
-        }
-
+    impl Labeled {
         // Method that consumes self and returns a new value
-        fn max(self, other: Rectangle) -> Rectangle {
-            Rectangle {
-                width: self.width.max(other.width),
-                height: self.height.max(other.height),
+        fn max(self, other: Labeled) -> Labeled {
+            Labeled {
+                label: format!("max({}, {})", self.label, other.label),
+                rect: self.rect.max(other.rect),
             }
         }
 
         // Method that transforms self into something else
-        fn into_square(self) -> Rectangle {
-            let size = self.width.max(self.height);
-            Rectangle {
-                width: size,
-                height: size,
+        fn into_square(self) -> Labeled {
+            Labeled {
+                label: format!("square({})", self.label),
+                rect: self.rect.into_square(),
             }
         }
     }
 
-    let rect1 = Rectangle {
-        width: 30,
-        height: 50,
+    let rect1 = Labeled {
+        label: String::from("rect1"),
+        rect: Rectangle::new(30, 50),
     };
-    let rect2 = Rectangle {
-        width: 40,
-        height: 20,
+    let rect2 = Labeled {
+        label: String::from("rect2"),
+        rect: Rectangle::new(40, 20),
     };
 
-    println!("   rect1: {:?}", rect1);
-    println!("   rect2: {:?}", rect2);
+    println!("   {}: {}", rect1.label, rect1.rect);
+    println!("   {}: {}", rect2.label, rect2.rect);
 
     // This consumes both rectangles
     let max_rect = rect1.max(rect2);
-    println!("   max_rect: {:?}", max_rect);
+    println!("   {}: {}", max_rect.label, max_rect.rect);
 
     // rect1 and rect2 are no longer usable here
     // println!("{:?}", rect1); // Error: value borrowed after move
 
-    let rect3 = Rectangle {
-        width: 30,
-        height: 50,
+    let rect3 = Labeled {
+        label: String::from("rect3"),
+        rect: Rectangle::new(30, 50),
     };
-    println!("\n   rect3: {:?}", rect3);
+    println!("\n   {}: {}", rect3.label, rect3.rect);
 
     let square = rect3.into_square();
-    println!("   Transformed into square: {:?}", square);
+    println!(
+        "   Transformed into square: {}: {}",
+        square.label, square.rect
+    );
 
     // rect3 is no longer usable
     // println!("{:?}", rect3); // Error: value borrowed after move
 
     println!("\n   Consuming methods are useful for transformations\n");
-}
\ No newline at end of file
+}
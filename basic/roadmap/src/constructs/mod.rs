@@ -0,0 +1,13 @@
+// Lessons deliberately show verbose and non-idiomatic forms next to the
+// idiomatic ones, so clippy's style lints are off here, as is the lint
+// against `unwrap_or` on a literal `Some` or `None`, which `enums.rs` uses
+// to show what `unwrap_or` does. Correctness, suspicious and perf lints
+// still apply.
+#![allow(clippy::style, clippy::unnecessary_literal_unwrap)]
+
+pub mod enums;
+pub mod impl_blocks;
+pub mod rectangle_packing;
+pub mod structs;
+pub mod summary_feed;
+pub mod traits;
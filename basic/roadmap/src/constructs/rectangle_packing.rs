@@ -0,0 +1,163 @@
+use crate::geometry::{Point, Rectangle};
+use crate::packing::{self, Heuristic, PackOptions, QuadTree};
+
+pub fn main() {
+    println!("=== Capstone: Packing Rectangles ===\n");
+
+    // 1. From can_hold to can_fit
+    demonstrate_can_hold_vs_can_fit();
+
+    // 2. Borrowing the input while packing
+    demonstrate_packing_borrows_input();
+
+    // 3. Comparing heuristics
+    demonstrate_heuristics();
+
+    // 4. A quadtree owns its values
+    demonstrate_quadtree_ownership();
+
+    // 5. Query results borrow the tree
+    demonstrate_query_borrows();
+}
+
+// 1. From can_hold to can_fit
+fn demonstrate_can_hold_vs_can_fit() {
+    println!("1. From can_hold to can_fit");
+    println!("   can_hold is strict; packing needs exact fits to count\n");
+
+    let bin: Rectangle<u32> = Rectangle::new(30, 50);
+    let exact = Rectangle::new(30, 50);
+
+    println!("   bin: {}, item: {}", bin, exact);
+    println!("   bin.can_hold(&item) = {}", bin.can_hold(&exact));
+    println!("   bin.can_fit(&item)  = {}", bin.can_fit(&exact));
+
+    // Both methods take &self and &Rectangle, so nothing is moved
+    println!("   bin is still usable: {}", bin);
+    println!();
+}
+
+// 2. Borrowing the input while packing
+fn demonstrate_packing_borrows_input() {
+    println!("2. Borrowing the Input While Packing");
+    println!("   pack takes &[Rectangle] and returns indexes, not references\n");
+
+    let items: Vec<Rectangle<u32>> = vec![
+        Rectangle::new(60, 40),
+        Rectangle::new(40, 40),
+        Rectangle::new(60, 60),
+        Rectangle::new(40, 60),
+    ];
+    let bin = Rectangle::new(100, 100);
+
+    // pack borrows `items` immutably for the duration of the call
+    let result = packing::pack(&items, &bin, &PackOptions::default());
+
+    // Placements store the item's index, so `result` does not borrow
+    // `items` and both can be used (or dropped) independently
+    for placement in &result.placements {
+        println!(
+            "   item {} ({}) -> bin {} at {}",
+            placement.item,
+            items[placement.item],
+            placement.bin,
+            placement.rect.origin()
+        );
+    }
+    println!("   bins used: {}", result.bin_count);
+
+    drop(items);
+    println!(
+        "   items dropped, result still has {} placements",
+        result.placements.len()
+    );
+    println!();
+}
+
+// 3. Comparing heuristics
+fn demonstrate_heuristics() {
+    println!("3. Comparing Heuristics");
+    println!("   The same items, three ways of tracking free space\n");
+
+    let items: Vec<Rectangle<u32>> = [(50, 30), (30, 50), (50, 30), (30, 50), (20, 20), (40, 20)]
+        .iter()
+        .map(|&(w, h)| Rectangle::new(w, h))
+        .collect();
+    let bin = Rectangle::new(100, 80);
+
+    for heuristic in Heuristic::ALL {
+        for allow_rotation in [false, true] {
+            let options = PackOptions {
+                heuristic,
+                allow_rotation,
+            };
+            let result = packing::pack(&items, &bin, &options);
+            println!(
+                "   {:<10} rotation {:<3}: {} bins, {:.1}% used",
+                heuristic.to_string(),
+                if allow_rotation { "on" } else { "off" },
+                result.bin_count,
+                result.utilization() * 100.0
+            );
+        }
+    }
+    println!();
+}
+
+// 4. A quadtree owns its values
+fn demonstrate_quadtree_ownership() {
+    println!("4. A Quadtree Owns Its Values");
+    println!("   insert moves the value in, and hands it back on failure\n");
+
+    let mut tree = QuadTree::new(Rectangle::new(100, 100));
+
+    let label = String::from("north-west");
+    tree.insert(Rectangle::new(10, 10), label).unwrap();
+    // println!("{}", label); // Error: label was moved into the tree
+
+    let outside = String::from("too far east");
+    match tree.insert(Rectangle::at(Point::new(95, 0), 10, 10), outside) {
+        Ok(()) => println!("   inserted"),
+        // Ownership comes back through the Err variant
+        Err(returned) => println!("   rejected, got back: {:?}", returned),
+    }
+
+    println!("   tree holds {} item(s)", tree.len());
+    println!();
+}
+
+// 5. Query results borrow the tree
+fn demonstrate_query_borrows() {
+    println!("5. Query Results Borrow the Tree");
+    println!("   query returns references that live as long as the borrow\n");
+
+    let mut tree = QuadTree::new(Rectangle::new(64, 64));
+    for (i, (x, y)) in [(0, 0), (8, 8), (40, 40), (50, 10), (30, 30)]
+        .iter()
+        .enumerate()
+    {
+        let name = format!("rect{}", i);
+        tree.insert(Rectangle::at(Point::new(*x, *y), 12, 12), name)
+            .unwrap();
+    }
+
+    let region = Rectangle::at(Point::new(5, 5), 30, 30);
+    let hits = tree.query(&region);
+    println!("   Items overlapping {}:", region);
+    for (rect, name) in &hits {
+        println!("   - {} at {}", name, rect);
+    }
+
+    // tree.insert(Rectangle::new(1, 1), String::new()); // Error: `tree` is
+    // still borrowed by `hits`, which is used below
+    println!("   {} hits", hits.len());
+
+    // Once `hits` is no longer used the mutable borrow is allowed again
+    tree.insert(Rectangle::new(1, 1), String::from("late"))
+        .unwrap();
+    println!(
+        "   Point (1, 1) is in {} item(s)",
+        tree.query_point(Point::new(1, 1)).len()
+    );
+    println!();
+}
@@ -1,4 +1,6 @@
-fn main() {
+use crate::geometry::Rectangle;
+
+pub fn main() {
     println!("=== Structs in Rust ===\n");
 
     // 1. Defining and instantiating structs
@@ -152,7 +154,7 @@ fn demonstrate_struct_update_syntax() {
     // Without update syntax (verbose)
     let user2_verbose = User {
         active: user1.active,
-        username: String::from("user2name"), // Different
+        username: String::from("user2name"),        // Different
         email: String::from("another@example.com"), // Different
         sign_in_count: user1.sign_in_count,
     };
@@ -186,7 +188,10 @@ fn demonstrate_tuple_structs() {
 
     println!("   Color and Point are different types!");
     println!("   Black color: ({}, {}, {})", black.0, black.1, black.2);
-    println!("   Origin point: ({}, {}, {})", origin.0, origin.1, origin.2);
+    println!(
+        "   Origin point: ({}, {}, {})",
+        origin.0, origin.1, origin.2
+    );
 
     // Destructuring tuple structs
     let Color(r, g, b) = black;
@@ -232,59 +237,24 @@ fn demonstrate_struct_methods() {
     println!("7. Struct Methods");
     println!("   Define methods using impl blocks\n");
 
-    #[derive(Debug)]
-    struct Rectangle {
-        width: u32,
-        height: u32,
-    }
-
-    impl Rectangle {
-        // Method (takes &self)
-        fn area(&self) -> u32 {
-            self.width * self.height
-        }
-
-        // Method that takes mutable reference
-        fn double(&mut self) {
-            self.width *= 2;
-            self.height *= 2;
-        }
-
-        // Method that checks a condition
-        fn can_hold(&self, other: &Rectangle) -> bool {
-            self.width > other.width && self.height > other.height
-        }
-
-        // Associated function (no self) - like a constructor
-        fn square(size: u32) -> Self {
-            Self {
-                width: size,
-                height: size,
-            }
-        }
-    }
-
-    let mut rect1 = Rectangle {
-        width: 30,
-        height: 50,
-    };
-
-    let rect2 = Rectangle {
-        width: 10,
-        height: 40,
-    };
-
+    // Rectangle lives in src/geometry/rectangle.rs. Its impl block defines:
+    //   fn area(&self) -> T                        (method, borrows self)
+    //   fn scale(&mut self, factor: T)             (method, borrows self mutably)
+    //   fn can_hold(&self, other: &Rectangle<T>) -> bool
+    //   fn square(size: T) -> Self                 (associated function)
+    let mut rect1: Rectangle<u32> = Rectangle::new(30, 50);
+    let rect2 = Rectangle::new(10, 40);
     let rect3 = Rectangle::square(25);
 
-    println!("   rect1: {:?}", rect1);
+    println!("   rect1: {}", rect1);
     println!("   rect1 area: {}", rect1.area());
     println!("   Can rect1 hold rect2? {}", rect1.can_hold(&rect2));
     println!("   Can rect1 hold rect3? {}", rect1.can_hold(&rect3));
 
-    rect1.double();
-    println!("\n   After doubling rect1: {:?}", rect1);
+    rect1.scale(2);
+    println!("\n   After doubling rect1: {}", rect1);
     println!("   rect1 area: {}", rect1.area());
 
-    println!("\n   Square (associated function): {:?}", rect3);
+    println!("\n   Square (associated function): {}", rect3);
     println!();
-}
\ No newline at end of file
+}
@@ -0,0 +1,186 @@
+use crate::feed::{self, Feed, NewsArticle, Summary, Timestamp, Tweet};
+
+const SAMPLE: &str = "\
+# kind time | fields... | content
+article 2024-06-12T20:15 | Iceburgh | Pittsburgh, PA, USA | Penguins win the Stanley Cup! | The Pittsburgh Penguins are the best hockey team.
+tweet 2024-06-12T20:20 | horse_ebooks | of course, as you probably already know, people
+reply 2024-06-12T20:31 | ferris | the borrow checker approves of this cup
+retweet 2024-06-12T21:02 | iceburgh | of course, as you probably already know, people
+article 2024-06-13T08:00 | Ferris | Rustacean Station | Traits let one feed hold every kind of post | Box<dyn Summary> erases the concrete type.
+tweet 2024-06-13T09:45 | horse_ebooks | everything happens so much
+";
+
+pub fn main() {
+    println!("=== Capstone: A Feed of Summaries ===\n");
+
+    // 1. One trait, many types
+    demonstrate_trait_objects();
+
+    // 2. Default methods
+    demonstrate_default_methods();
+
+    // 3. Sorting through the trait
+    demonstrate_sorting();
+
+    // 4. Filtering borrows the feed
+    demonstrate_filtering();
+
+    // 5. Pages are slices
+    demonstrate_pagination();
+
+    // 6. Loading a feed from text
+    demonstrate_loading();
+}
+
+// 1. One trait, many types
+fn demonstrate_trait_objects() {
+    println!("1. One Trait, Many Types");
+    println!("   impl Summary returns one type; Box<dyn Summary> holds any\n");
+
+    let mut feed = Feed::new();
+    feed.push(NewsArticle {
+        headline: String::from("Penguins win the Stanley Cup!"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from("The Pittsburgh Penguins are the best hockey team."),
+        published: "2024-06-12T20:15".parse().unwrap(),
+    });
+    feed.push(Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+        posted: "2024-06-12T20:20".parse().unwrap(),
+    });
+
+    // Each call goes through the vtable of the item's concrete type
+    for item in feed.iter() {
+        println!("   {}", item.summarize());
+    }
+    println!(
+        "   size_of::<Box<dyn Summary>>() = {} (data pointer + vtable pointer)",
+        std::mem::size_of::<Box<dyn Summary>>()
+    );
+    println!();
+}
+
+// 2. Default methods
+fn demonstrate_default_methods() {
+    println!("2. Default Methods");
+    println!("   Implement author(), timestamp() and summarize_author(); the rest comes free\n");
+
+    struct Postcard {
+        from: String,
+    }
+
+    impl Summary for Postcard {
+        fn author(&self) -> &str {
+            &self.from
+        }
+
+        fn timestamp(&self) -> Timestamp {
+            Timestamp::default()
+        }
+
+        fn summarize_author(&self) -> String {
+            format!("{} (by post)", self.from)
+        }
+    }
+
+    let card = Postcard {
+        from: String::from("Grandma"),
+    };
+    println!("   summarize()           = {}", card.summarize());
+    println!("   summarize_within(20)  = {}", card.summarize_within(20));
+    println!("   is_by(\"grandma\")      = {}", card.is_by("grandma"));
+
+    // Tweet overrides summarize but still inherits the other defaults
+    let tweet = Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+        posted: Timestamp::default(),
+    };
+    println!("   tweet, within 30      = {}", tweet.summarize_within(30));
+    println!();
+}
+
+// 3. Sorting through the trait
+fn demonstrate_sorting() {
+    println!("3. Sorting Through the Trait");
+    println!("   sort_by_key only needs timestamp(), whatever the item is\n");
+
+    let mut feed: Feed = SAMPLE.parse().unwrap();
+    feed.sort_newest_first();
+    for item in feed.iter().take(3) {
+        println!("   {}  {}", item.timestamp(), item.summarize_within(50));
+    }
+    println!("   ... {} more", feed.len() - 3);
+    println!();
+}
+
+// 4. Filtering borrows the feed
+fn demonstrate_filtering() {
+    println!("4. Filtering Borrows the Feed");
+    println!("   by_author returns Vec<&dyn Summary>, not copies\n");
+
+    let feed: Feed = SAMPLE.parse().unwrap();
+    let by_horse = feed.by_author("@HORSE_EBOOKS");
+    for item in &by_horse {
+        println!("   {}", item.summarize());
+    }
+
+    // feed.push(...); // Error: `feed` is borrowed by `by_horse` below
+    println!("   {} of {} items", by_horse.len(), feed.len());
+    println!();
+}
+
+// 5. Pages are slices
+fn demonstrate_pagination() {
+    println!("5. Pages Are Slices");
+    println!("   paginate works for any &[T], including the filtered view\n");
+
+    let feed: Feed = SAMPLE.parse().unwrap();
+    let mut number = 1;
+    while let Some(page) = feed.page(number, 4) {
+        println!("   page {} of {}:", page.number, page.page_count);
+        for item in page.items {
+            println!("   - {}", item.summarize_within(40));
+        }
+        number += 1;
+    }
+
+    let by_horse = feed.by_author("horse_ebooks");
+    let page = feed::paginate(&by_horse, 1, 1).unwrap();
+    println!(
+        "   filtered page {} of {}: {}",
+        page.number,
+        page.page_count,
+        page.items[0].summarize()
+    );
+    println!(
+        "   feed.page(3, 4) = {:?}",
+        feed.page(3, 4).map(|p| p.number)
+    );
+    println!();
+}
+
+// 6. Loading a feed from text
+fn demonstrate_loading() {
+    println!("6. Loading a Feed From Text");
+    println!("   FromStr builds boxed items; errors name the line\n");
+
+    let feed: Result<Feed, _> = SAMPLE.parse();
+    println!(
+        "   SAMPLE parsed into {} items",
+        feed.map_or(0, |f| f.len())
+    );
+
+    let broken = "tweet 2024-06-12T20:20 | ferris | ok\nstatus 2024-06-12T20:21 | ferris | hi";
+    match broken.parse::<Feed>() {
+        Ok(_) => println!("   parsed"),
+        Err(err) => println!("   Error: {}", err),
+    }
+    println!();
+}
@@ -1,6 +1,6 @@
 use std::fmt::{Debug, Display};
 
-fn main() {
+pub fn main() {
     println!("=== Traits in Rust ===\n");
 
     // 1. Defining and implementing traits
@@ -189,7 +189,11 @@ fn demonstrate_trait_bounds() {
 
     // When you need same type for multiple parameters
     fn notify_same<T: Summary>(item1: &T, item2: &T) {
-        println!("   Same type: {} and {}", item1.summarize(), item2.summarize());
+        println!(
+            "   Same type: {} and {}",
+            item1.summarize(),
+            item2.summarize()
+        );
     }
 
     let article1 = Article {
@@ -301,7 +305,10 @@ fn demonstrate_where_clauses() {
     };
     let number = 42;
 
-    println!("   Without where: {}", some_function_ugly(&article, &number));
+    println!(
+        "   Without where: {}",
+        some_function_ugly(&article, &number)
+    );
     println!("   With where: {}", some_function_clean(&article, &number));
     println!();
 }
@@ -339,6 +346,7 @@ fn demonstrate_returning_traits() {
     println!("   Returned: {}", tweet.summarize());
 
     println!("\n   Note: impl Trait can only return a single concrete type");
+    // constructs::summary_feed keeps a Vec<Box<dyn Summary>> of mixed items
     println!("   For multiple types, use trait objects (Box<dyn Trait>)\n");
 }
 
@@ -360,6 +368,8 @@ fn demonstrate_conditional_implementations() {
     }
 
     // Only available when T implements Display + PartialOrd
+    // (crate::ordered::Pair grows this into Ord-only and PartialOrd-only
+    // method sets, with intervals built on the same bounds)
     impl<T: Display + PartialOrd> Pair<T> {
         fn cmp_display(&self) {
             if self.x >= self.y {
@@ -380,4 +390,4 @@ fn demonstrate_conditional_implementations() {
     let s = num.to_string(); // Works because i32 implements Display
     println!("   42.to_string() = \"{}\"", s);
     println!();
-}
\ No newline at end of file
+}
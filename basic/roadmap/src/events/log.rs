@@ -0,0 +1,322 @@
+//! Recorded event logs.
+//!
+//! One event per line: milliseconds since the session started, the event
+//! name and its data. Text is quoted with `\"`, `\\`, `\n`, `\r`, `\t` and
+//! `\u{..}` escapes so every line stays printable.
+//!
+//! ```text
+//! # roadmap event log
+//! 0 page_load
+//! 412 key_press "h"
+//! 2310 paste "two\nlines"
+//! 4071 click 20 80
+//! 5002 page_unload
+//! ```
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use super::{EventKind, WebEvent};
+
+const HEADER: &str = "# roadmap event log";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EventLog {
+    entries: Vec<(Duration, WebEvent)>,
+}
+
+impl EventLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Offsets are expected to be non-decreasing; parsing enforces it.
+    pub fn push(&mut self, at: Duration, event: WebEvent) {
+        self.entries.push((at, event));
+    }
+
+    pub fn entries(&self) -> &[(Duration, WebEvent)] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = &WebEvent> {
+        self.entries.iter().map(|(_, event)| event)
+    }
+}
+
+/// Timestamps events relative to when the recorder was created.
+#[derive(Debug)]
+pub struct Recorder {
+    start: Instant,
+    log: EventLog,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            log: EventLog::new(),
+        }
+    }
+
+    pub fn record(&mut self, event: WebEvent) {
+        // Millisecond precision, which is all the log format keeps
+        let millis = self.start.elapsed().as_millis() as u64;
+        self.log.push(Duration::from_millis(millis), event);
+    }
+
+    pub fn log(&self) -> &EventLog {
+        &self.log
+    }
+
+    pub fn into_log(self) -> EventLog {
+        self.log
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn write_quoted(out: &mut String, text: &str) -> fmt::Result {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{{{:x}}}", c as u32)?,
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    Ok(())
+}
+
+/// Parses a quoted string at the start of `input` and returns it with the
+/// rest of the line.
+fn read_quoted(input: &str) -> Result<(String, &str), String> {
+    let mut chars = input
+        .strip_prefix('"')
+        .ok_or("expected a quoted string")?
+        .char_indices();
+    let body = &input[1..];
+    let mut text = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((text, &body[i + 1..])),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('"') => text.push('"'),
+                Some('\\') => text.push('\\'),
+                Some('n') => text.push('\n'),
+                Some('r') => text.push('\r'),
+                Some('t') => text.push('\t'),
+                Some('u') => {
+                    let rest = &body[i + 2..];
+                    let end = rest.find('}').ok_or("unterminated `\\u{`")?;
+                    let hex = rest[..end].strip_prefix('{').ok_or("expected `\\u{`")?;
+                    let c = u32::from_str_radix(hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| format!("invalid escape `\\u{{{hex}}}`"))?;
+                    text.push(c);
+                    // Skip past the closing brace
+                    for _ in 0..=end {
+                        chars.next();
+                    }
+                }
+                Some(other) => return Err(format!("unknown escape `\\{other}`")),
+                None => break,
+            },
+            c => text.push(c),
+        }
+    }
+    Err("unterminated string".to_string())
+}
+
+fn parse_event(line: &str) -> Result<WebEvent, String> {
+    let (name, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let kind = EventKind::ALL
+        .into_iter()
+        .find(|kind| kind.name() == name)
+        .ok_or_else(|| format!("unknown event `{name}`"))?;
+    let rest = rest.trim_start();
+    let event = match kind {
+        EventKind::PageLoad | EventKind::PageUnload => {
+            if !rest.is_empty() {
+                return Err(format!("`{name}` takes no arguments"));
+            }
+            if kind == EventKind::PageLoad {
+                WebEvent::PageLoad
+            } else {
+                WebEvent::PageUnload
+            }
+        }
+        EventKind::KeyPress | EventKind::Paste => {
+            let (text, tail) = read_quoted(rest)?;
+            if !tail.trim().is_empty() {
+                return Err(format!("unexpected `{}` after the string", tail.trim()));
+            }
+            if kind == EventKind::Paste {
+                WebEvent::Paste(text)
+            } else {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => WebEvent::KeyPress(c),
+                    _ => return Err("`key_press` takes exactly one character".to_string()),
+                }
+            }
+        }
+        EventKind::Click => {
+            let coords: Vec<&str> = rest.split_whitespace().collect();
+            let [x, y] = coords[..] else {
+                return Err("`click` takes two coordinates".to_string());
+            };
+            let parse = |s: &str| {
+                s.parse::<i64>()
+                    .map_err(|_| format!("invalid coordinate `{s}`"))
+            };
+            WebEvent::Click {
+                x: parse(x)?,
+                y: parse(y)?,
+            }
+        }
+    };
+    Ok(event)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLogError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseLogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseLogError {}
+
+impl FromStr for EventLog {
+    type Err = ParseLogError;
+
+    /// Blank lines and lines starting with `#` are skipped.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut log = EventLog::new();
+        for (number, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let err = |message: String| ParseLogError {
+                line: number + 1,
+                message,
+            };
+            let (millis, event) = line
+                .split_once(' ')
+                .ok_or_else(|| err(format!("expected `MILLIS EVENT`, found `{line}`")))?;
+            let millis: u64 = millis
+                .parse()
+                .map_err(|_| err(format!("invalid timestamp `{millis}`")))?;
+            let at = Duration::from_millis(millis);
+            if log.entries.last().is_some_and(|(last, _)| at < *last) {
+                return Err(err("timestamps go backwards".to_string()));
+            }
+            log.push(at, parse_event(event.trim()).map_err(err)?);
+        }
+        Ok(log)
+    }
+}
+
+impl fmt::Display for EventLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        let mut line = String::new();
+        for (at, event) in &self.entries {
+            line.clear();
+            write!(line, "{} {}", at.as_millis(), event.kind())?;
+            match event {
+                WebEvent::PageLoad | WebEvent::PageUnload => {}
+                WebEvent::KeyPress(c) => {
+                    line.push(' ');
+                    write_quoted(&mut line, c.encode_utf8(&mut [0; 4]))?;
+                }
+                WebEvent::Paste(text) => {
+                    line.push(' ');
+                    write_quoted(&mut line, text)?;
+                }
+                WebEvent::Click { x, y } => write!(line, " {x} {y}")?,
+            }
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_every_event() {
+        let mut log = EventLog::new();
+        let events = [
+            WebEvent::PageLoad,
+            WebEvent::KeyPress('h'),
+            WebEvent::KeyPress('"'),
+            WebEvent::KeyPress('\x1b'),
+            WebEvent::KeyPress('é'),
+            WebEvent::Paste("two\nlines \"quoted\" \\ tab\there \u{7f}".to_string()),
+            WebEvent::Paste(String::new()),
+            WebEvent::Click { x: -3, y: 80 },
+            WebEvent::PageUnload,
+        ];
+        for (i, event) in events.into_iter().enumerate() {
+            log.push(Duration::from_millis(i as u64 * 100), event);
+        }
+        let text = log.to_string();
+        assert!(text.contains("100 key_press \"h\"\n"));
+        assert!(text.contains("300 key_press \"\\u{1b}\"\n"));
+        assert_eq!(text.parse::<EventLog>(), Ok(log));
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let cases = [
+            ("0 page_load\n5 jump", 2, "unknown event `jump`"),
+            ("x page_load", 1, "invalid timestamp `x`"),
+            (
+                "0 key_press \"ab\"",
+                1,
+                "`key_press` takes exactly one character",
+            ),
+            ("0 paste \"open", 1, "unterminated string"),
+            ("0 paste \"\\q\"", 1, "unknown escape `\\q`"),
+            ("0 click 1", 1, "`click` takes two coordinates"),
+            (
+                "# header\n\n9 page_load\n3 page_unload",
+                4,
+                "timestamps go backwards",
+            ),
+        ];
+        for (input, line, message) in cases {
+            let err = input.parse::<EventLog>().unwrap_err();
+            assert_eq!((err.line, err.message.as_str()), (line, message), "{input}");
+        }
+    }
+}
@@ -0,0 +1,340 @@
+//! An event bus for the `WebEvent` enum from
+//! `syntax_and_semantics/destructuring.rs`.
+//!
+//! Handlers subscribe to one [`EventKind`] (or to every event) with a
+//! priority. [`Bus::dispatch`] calls them from the highest priority down,
+//! in registration order among equals, until one returns
+//! [`Propagation::Stop`]. Events can also be queued and dispatched later,
+//! or replayed from an [`EventLog`] recorded by a terminal session.
+
+use std::collections::VecDeque;
+use std::fmt;
+
+mod log;
+pub mod terminal;
+
+pub use log::{EventLog, ParseLogError, Recorder};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebEvent {
+    PageLoad,
+    PageUnload,
+    KeyPress(char),
+    Paste(String),
+    Click { x: i64, y: i64 },
+}
+
+impl WebEvent {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            WebEvent::PageLoad => EventKind::PageLoad,
+            WebEvent::PageUnload => EventKind::PageUnload,
+            WebEvent::KeyPress(_) => EventKind::KeyPress,
+            WebEvent::Paste(_) => EventKind::Paste,
+            WebEvent::Click { .. } => EventKind::Click,
+        }
+    }
+}
+
+/// The variant of a [`WebEvent`] without its data, used to subscribe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    PageLoad,
+    PageUnload,
+    KeyPress,
+    Paste,
+    Click,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 5] = [
+        EventKind::PageLoad,
+        EventKind::PageUnload,
+        EventKind::KeyPress,
+        EventKind::Paste,
+        EventKind::Click,
+    ];
+
+    /// The name used in event logs.
+    pub fn name(self) -> &'static str {
+        match self {
+            EventKind::PageLoad => "page_load",
+            EventKind::PageUnload => "page_unload",
+            EventKind::KeyPress => "key_press",
+            EventKind::Paste => "paste",
+            EventKind::Click => "click",
+        }
+    }
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// What a handler wants to happen after it has seen an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Propagation {
+    Continue,
+    /// Lower-priority handlers do not see this event.
+    Stop,
+}
+
+/// Returned by [`Bus::on`], to unsubscribe with [`Bus::off`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandlerId(u64);
+
+/// The outcome of dispatching one event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Dispatch {
+    /// How many handlers were called.
+    pub handled: usize,
+    /// Whether a handler stopped propagation.
+    pub stopped: bool,
+}
+
+type Handler = Box<dyn FnMut(&WebEvent) -> Propagation>;
+
+struct Registered {
+    id: HandlerId,
+    /// `None` for handlers that see every event.
+    kind: Option<EventKind>,
+    priority: i32,
+    handler: Handler,
+}
+
+#[derive(Default)]
+pub struct Bus {
+    /// Sorted by descending priority, then registration order.
+    handlers: Vec<Registered>,
+    queue: VecDeque<WebEvent>,
+    next_id: u64,
+}
+
+impl Bus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `handler` for every event of `kind`. Higher priorities run first.
+    pub fn on<F>(&mut self, kind: EventKind, priority: i32, handler: F) -> HandlerId
+    where
+        F: FnMut(&WebEvent) -> Propagation + 'static,
+    {
+        self.register(Some(kind), priority, Box::new(handler))
+    }
+
+    /// Calls `handler` for every event, interleaved with the per-kind
+    /// handlers by priority.
+    pub fn on_any<F>(&mut self, priority: i32, handler: F) -> HandlerId
+    where
+        F: FnMut(&WebEvent) -> Propagation + 'static,
+    {
+        self.register(None, priority, Box::new(handler))
+    }
+
+    fn register(&mut self, kind: Option<EventKind>, priority: i32, handler: Handler) -> HandlerId {
+        let id = HandlerId(self.next_id);
+        self.next_id += 1;
+        // After every handler of the same priority, so ties keep their order
+        let at = self.handlers.partition_point(|h| h.priority >= priority);
+        self.handlers.insert(
+            at,
+            Registered {
+                id,
+                kind,
+                priority,
+                handler,
+            },
+        );
+        id
+    }
+
+    /// Removes a handler; `false` if it was already gone.
+    pub fn off(&mut self, id: HandlerId) -> bool {
+        let before = self.handlers.len();
+        self.handlers.retain(|h| h.id != id);
+        self.handlers.len() != before
+    }
+
+    pub fn handler_count(&self) -> usize {
+        self.handlers.len()
+    }
+
+    pub fn dispatch(&mut self, event: &WebEvent) -> Dispatch {
+        let kind = event.kind();
+        let mut outcome = Dispatch::default();
+        for registered in &mut self.handlers {
+            if registered.kind.is_some_and(|k| k != kind) {
+                continue;
+            }
+            outcome.handled += 1;
+            if (registered.handler)(event) == Propagation::Stop {
+                outcome.stopped = true;
+                break;
+            }
+        }
+        outcome
+    }
+
+    /// Queues an event for a later [`Bus::drain`].
+    pub fn enqueue(&mut self, event: WebEvent) {
+        self.queue.push_back(event);
+    }
+
+    pub fn queued(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Dispatches queued events in the order they were queued and returns
+    /// how many there were.
+    pub fn drain(&mut self) -> usize {
+        let mut count = 0;
+        while let Some(event) = self.queue.pop_front() {
+            self.dispatch(&event);
+            count += 1;
+        }
+        count
+    }
+
+    /// Dispatches every event of `log` in order, ignoring the recorded
+    /// timing so that a replay always gives the same result.
+    pub fn replay(&mut self, log: &EventLog) -> usize {
+        for (_, event) in log.entries() {
+            self.dispatch(event);
+        }
+        log.len()
+    }
+}
+
+impl fmt::Debug for Bus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bus")
+            .field("handlers", &self.handlers.len())
+            .field("queued", &self.queue.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+
+    /// A bus whose handlers append `name` to a shared trace.
+    fn traced() -> (Bus, Rc<RefCell<Vec<String>>>) {
+        (Bus::new(), Rc::new(RefCell::new(Vec::new())))
+    }
+
+    fn push(
+        trace: &Rc<RefCell<Vec<String>>>,
+        name: &str,
+        then: Propagation,
+    ) -> impl FnMut(&WebEvent) -> Propagation + 'static {
+        let trace = Rc::clone(trace);
+        let name = name.to_string();
+        move |_| {
+            trace.borrow_mut().push(name.clone());
+            then
+        }
+    }
+
+    #[test]
+    fn handlers_run_by_priority_then_registration() {
+        let (mut bus, trace) = traced();
+        bus.on(
+            EventKind::KeyPress,
+            0,
+            push(&trace, "low", Propagation::Continue),
+        );
+        bus.on(
+            EventKind::KeyPress,
+            10,
+            push(&trace, "high", Propagation::Continue),
+        );
+        bus.on(
+            EventKind::KeyPress,
+            0,
+            push(&trace, "low again", Propagation::Continue),
+        );
+        bus.on_any(5, push(&trace, "any", Propagation::Continue));
+        bus.on(
+            EventKind::Click,
+            100,
+            push(&trace, "click", Propagation::Continue),
+        );
+
+        let outcome = bus.dispatch(&WebEvent::KeyPress('a'));
+        assert_eq!(*trace.borrow(), ["high", "any", "low", "low again"]);
+        assert_eq!(
+            outcome,
+            Dispatch {
+                handled: 4,
+                stopped: false
+            }
+        );
+    }
+
+    #[test]
+    fn stop_hides_the_event_from_lower_priorities() {
+        let (mut bus, trace) = traced();
+        bus.on(
+            EventKind::Paste,
+            1,
+            push(&trace, "filter", Propagation::Stop),
+        );
+        bus.on(
+            EventKind::Paste,
+            0,
+            push(&trace, "editor", Propagation::Continue),
+        );
+
+        let outcome = bus.dispatch(&WebEvent::Paste("secret".into()));
+        assert_eq!(*trace.borrow(), ["filter"]);
+        assert_eq!(
+            outcome,
+            Dispatch {
+                handled: 1,
+                stopped: true
+            }
+        );
+    }
+
+    #[test]
+    fn unsubscribing() {
+        let (mut bus, trace) = traced();
+        let id = bus.on(
+            EventKind::PageLoad,
+            0,
+            push(&trace, "once", Propagation::Continue),
+        );
+        assert!(bus.off(id));
+        assert!(!bus.off(id));
+        assert_eq!(bus.dispatch(&WebEvent::PageLoad).handled, 0);
+        assert!(trace.borrow().is_empty());
+    }
+
+    #[test]
+    fn queued_events_dispatch_in_order() {
+        let seen = Rc::new(RefCell::new(String::new()));
+        let mut bus = Bus::new();
+        let sink = Rc::clone(&seen);
+        bus.on(EventKind::KeyPress, 0, move |event| {
+            if let WebEvent::KeyPress(c) = event {
+                sink.borrow_mut().push(*c);
+            }
+            Propagation::Continue
+        });
+        for c in "queue".chars() {
+            bus.enqueue(WebEvent::KeyPress(c));
+        }
+        assert_eq!(bus.queued(), 5);
+        assert!(seen.borrow().is_empty());
+        assert_eq!(bus.drain(), 5);
+        assert_eq!(*seen.borrow(), "queue");
+        assert_eq!(bus.queued(), 0);
+    }
+}
@@ -0,0 +1,243 @@
+//! Turns raw terminal input into [`WebEvent`]s.
+//!
+//! Typed characters become `KeyPress`, bracketed paste (`ESC [200~ ...
+//! ESC [201~`) becomes `Paste`, and SGR mouse reports (`ESC [<0;x;yM`)
+//! become `Click` with 1-based cell coordinates. Other escape sequences,
+//! such as arrow keys, are skipped.
+
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use super::WebEvent;
+
+const ESC: u8 = 0x1b;
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Incremental decoder; bytes may arrive split at any point.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    pending: Vec<u8>,
+    /// Inside a bracketed paste, collecting until `PASTE_END`.
+    pasting: bool,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decodes as much of `bytes` (plus anything held back from earlier
+    /// calls) as forms complete events.
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<WebEvent> {
+        self.pending.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        let mut at = 0;
+        while at < self.pending.len() {
+            match step(&mut self.pasting, &self.pending[at..]) {
+                Step::Event(used, event) => {
+                    events.push(event);
+                    at += used;
+                }
+                Step::Skip(used) => at += used,
+                Step::Incomplete => break,
+            }
+        }
+        self.pending.drain(..at);
+        events
+    }
+
+    /// Flushes input that was held back waiting for more bytes, e.g. a
+    /// lone `ESC` key press at the end of the stream.
+    pub fn finish(&mut self) -> Vec<WebEvent> {
+        let pending = std::mem::take(&mut self.pending);
+        if self.pasting {
+            self.pasting = false;
+            return vec![WebEvent::Paste(
+                String::from_utf8_lossy(&pending).into_owned(),
+            )];
+        }
+        String::from_utf8_lossy(&pending)
+            .chars()
+            .map(WebEvent::KeyPress)
+            .collect()
+    }
+}
+
+fn step(pasting: &mut bool, input: &[u8]) -> Step {
+    if *pasting {
+        return match find(input, PASTE_END) {
+            Some(end) => {
+                *pasting = false;
+                let text = String::from_utf8_lossy(&input[..end]).into_owned();
+                Step::Event(end + PASTE_END.len(), WebEvent::Paste(text))
+            }
+            None => Step::Incomplete,
+        };
+    }
+    match input {
+        [ESC] => Step::Incomplete,
+        [ESC, b'[', rest @ ..] => csi(pasting, rest),
+        [ESC, ..] => Step::Event(1, WebEvent::KeyPress('\x1b')),
+        _ => decode_char(input),
+    }
+}
+
+/// A control sequence; `rest` follows the `ESC [`.
+fn csi(pasting: &mut bool, rest: &[u8]) -> Step {
+    // Parameters and intermediates, then one final byte in 0x40..=0x7e
+    let Some(end) = rest.iter().position(|b| (0x40..=0x7e).contains(b)) else {
+        return Step::Incomplete;
+    };
+    let used = 2 + end + 1;
+    let sequence = &rest[..=end];
+    if sequence == &PASTE_START[2..] {
+        *pasting = true;
+        return Step::Skip(used);
+    }
+    if let [b'<', params @ .., b'M'] = sequence
+        && let Some(click) = parse_mouse(params)
+    {
+        return Step::Event(used, click);
+    }
+    Step::Skip(used)
+}
+
+enum Step {
+    Event(usize, WebEvent),
+    Skip(usize),
+    Incomplete,
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn decode_char(input: &[u8]) -> Step {
+    let width = match input[0] {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Step::Event(1, WebEvent::KeyPress(char::REPLACEMENT_CHARACTER)),
+    };
+    if input.len() < width {
+        return Step::Incomplete;
+    }
+    match std::str::from_utf8(&input[..width]) {
+        Ok(s) => Step::Event(
+            width,
+            WebEvent::KeyPress(s.chars().next().expect("one char")),
+        ),
+        Err(_) => Step::Event(1, WebEvent::KeyPress(char::REPLACEMENT_CHARACTER)),
+    }
+}
+
+/// `button;x;y` of an SGR mouse press. Only a plain left click counts.
+fn parse_mouse(params: &[u8]) -> Option<WebEvent> {
+    let params = std::str::from_utf8(params).ok()?;
+    let mut fields = params.split(';').map(|f| f.parse::<i64>().ok());
+    let (button, x, y) = (fields.next()??, fields.next()??, fields.next()??);
+    (button == 0 && fields.next().is_none()).then_some(WebEvent::Click { x, y })
+}
+
+/// Puts the terminal into a character-at-a-time mode with bracketed paste
+/// and mouse reporting, and restores it on drop.
+///
+/// Uses `stty` on the controlling terminal, so it does nothing (and
+/// [`RawMode::enable`] returns `None`) when stdin is not a terminal.
+pub struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    pub fn enable() -> Option<Self> {
+        if !io::stdin().is_terminal() {
+            return None;
+        }
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+        // Bracketed paste, button press reporting, SGR coordinates
+        print!("\x1b[?2004h\x1b[?1000h\x1b[?1006h");
+        io::stdout().flush().ok()?;
+        Some(Self {
+            saved: saved.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        print!("\x1b[?1006l\x1b[?1000l\x1b[?2004l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(text: &str) -> Vec<WebEvent> {
+        text.chars().map(WebEvent::KeyPress).collect()
+    }
+
+    #[test]
+    fn plain_and_multibyte_keys() {
+        let mut decoder = Decoder::new();
+        assert_eq!(decoder.feed("hé!".as_bytes()), keys("hé!"));
+    }
+
+    #[test]
+    fn input_split_anywhere_decodes_the_same() {
+        let input = "a\u{1b}[200~pasted\ntext\u{1b}[201~\u{1b}[<0;20;80Mé\u{1b}[Az".as_bytes();
+        let expected = vec![
+            WebEvent::KeyPress('a'),
+            WebEvent::Paste("pasted\ntext".into()),
+            WebEvent::Click { x: 20, y: 80 },
+            WebEvent::KeyPress('é'),
+            WebEvent::KeyPress('z'),
+        ];
+        for split in 0..=input.len() {
+            let mut decoder = Decoder::new();
+            let mut events = decoder.feed(&input[..split]);
+            events.extend(decoder.feed(&input[split..]));
+            events.extend(decoder.finish());
+            assert_eq!(events, expected, "split at {split}");
+        }
+    }
+
+    #[test]
+    fn lone_escape_waits_for_more_input() {
+        let mut decoder = Decoder::new();
+        assert!(decoder.feed(b"\x1b").is_empty());
+        assert_eq!(decoder.finish(), keys("\u{1b}"));
+        assert_eq!(decoder.feed(b"\x1bq"), keys("\u{1b}q"));
+    }
+
+    #[test]
+    fn only_left_button_presses_are_clicks() {
+        let mut decoder = Decoder::new();
+        // Right button press, left button release, left button press
+        let events = decoder.feed(b"\x1b[<2;1;1M\x1b[<0;5;6m\x1b[<0;5;6M");
+        assert_eq!(events, [WebEvent::Click { x: 5, y: 6 }]);
+    }
+
+    #[test]
+    fn invalid_utf8_becomes_replacement_characters() {
+        let mut decoder = Decoder::new();
+        assert_eq!(decoder.feed(b"\xff\xc3("), keys("\u{fffd}\u{fffd}("));
+    }
+}
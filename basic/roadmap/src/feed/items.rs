@@ -0,0 +1,102 @@
+use super::{Summary, Timestamp};
+
+/// The article from the traits lesson, with a publication time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewsArticle {
+    pub headline: String,
+    pub location: String,
+    pub author: String,
+    pub content: String,
+    pub published: Timestamp,
+}
+
+impl Summary for NewsArticle {
+    fn author(&self) -> &str {
+        &self.author
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.published
+    }
+
+    fn summarize_author(&self) -> String {
+        self.author.clone()
+    }
+
+    fn summarize(&self) -> String {
+        format!("{}, by {} ({})", self.headline, self.author, self.location)
+    }
+}
+
+/// The tweet from the traits lesson, with a posting time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tweet {
+    pub username: String,
+    pub content: String,
+    pub reply: bool,
+    pub retweet: bool,
+    pub posted: Timestamp,
+}
+
+impl Summary for Tweet {
+    fn author(&self) -> &str {
+        &self.username
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.posted
+    }
+
+    fn summarize_author(&self) -> String {
+        format!("@{}", self.username)
+    }
+
+    fn summarize(&self) -> String {
+        let verb = match (self.reply, self.retweet) {
+            (_, true) => " retweeted",
+            (true, false) => " replied",
+            (false, false) => "",
+        };
+        format!("{}{verb}: {}", self.summarize_author(), self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tweet(reply: bool, retweet: bool) -> Tweet {
+        Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from("of course"),
+            reply,
+            retweet,
+            posted: Timestamp(0),
+        }
+    }
+
+    #[test]
+    fn summaries() {
+        let article = NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::new(),
+            published: Timestamp(0),
+        };
+        assert_eq!(
+            article.summarize(),
+            "Penguins win the Stanley Cup!, by Iceburgh (Pittsburgh, PA, USA)"
+        );
+        assert_eq!(tweet(false, false).summarize(), "@horse_ebooks: of course");
+        assert_eq!(
+            tweet(true, false).summarize(),
+            "@horse_ebooks replied: of course"
+        );
+        assert_eq!(
+            tweet(true, true).summarize(),
+            "@horse_ebooks retweeted: of course"
+        );
+        assert!(tweet(false, false).is_by("@horse_ebooks"));
+    }
+}
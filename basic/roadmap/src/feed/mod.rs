@@ -0,0 +1,383 @@
+//! A feed of mixed content built on the `Summary` trait from
+//! `constructs/traits.rs`.
+//!
+//! The lesson redefines `Summary`, `NewsArticle` and `Tweet` in almost
+//! every section; this is the one real version. A [`Feed`] owns
+//! `Box<dyn Summary>` items of any type, sorts them by [`Timestamp`],
+//! filters them by author and splits them into [`Page`]s. Items load from
+//! the line format described in [`parse`].
+
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::calendar::Date;
+
+mod items;
+pub mod parse;
+
+pub use items::{NewsArticle, Tweet};
+pub use parse::{ParseFeedError, parse_feed};
+
+/// Something that can be shown as a one-line summary in a feed.
+///
+/// Implementors provide the author, a timestamp and how the author is
+/// shown; every other method has a default. `Summary` is object safe, so a
+/// [`Feed`] can mix types.
+pub trait Summary {
+    /// The author's name as it appears in the source, e.g. `horse_ebooks`.
+    fn author(&self) -> &str;
+
+    fn timestamp(&self) -> Timestamp;
+
+    /// How the author is shown; `@name` for tweets.
+    fn summarize_author(&self) -> String;
+
+    /// Defaults to the teaser from the lesson's default-implementation
+    /// section.
+    fn summarize(&self) -> String {
+        format!("(Read more from {}...)", self.summarize_author())
+    }
+
+    /// [`Summary::summarize`] cut to at most `width` characters.
+    ///
+    /// A cut summary ends in `…` and breaks at a space when one falls in
+    /// the second half of the line, so words are not split needlessly.
+    fn summarize_within(&self, width: usize) -> String {
+        truncate(&self.summarize(), width)
+    }
+
+    /// Whether `name` is the author, ignoring ASCII case and a leading `@`.
+    fn is_by(&self, name: &str) -> bool {
+        let name = name.strip_prefix('@').unwrap_or(name);
+        self.author().eq_ignore_ascii_case(name)
+    }
+}
+
+/// Cuts `text` to at most `width` characters (not bytes), ending in `…`.
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    // Leave room for the ellipsis
+    let end = text
+        .char_indices()
+        .nth(width - 1)
+        .map_or(text.len(), |(i, _)| i);
+    let kept = &text[..end];
+    let kept = match kept.rfind(' ') {
+        Some(space) if kept[..space].chars().count() >= width / 2 => &kept[..space],
+        _ => kept,
+    };
+    format!("{}…", kept.trim_end())
+}
+
+/// Seconds since 1970-01-01 00:00 UTC.
+///
+/// Written and parsed as `2024-02-29T14:05` or `2024-02-29T14:05:30`,
+/// optionally with a trailing `Z`; displayed without seconds when they are
+/// zero, and as plain seconds when the day is outside the range of
+/// [`Date`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    pub fn new(date: Date, hour: u8, minute: u8, second: u8) -> Option<Self> {
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        let seconds = i64::from(hour) * 3600 + i64::from(minute) * 60 + i64::from(second);
+        Some(Self(date.days_since_epoch() * 86_400 + seconds))
+    }
+
+    /// `None` if the day is outside the range of [`Date`].
+    pub fn date(self) -> Option<Date> {
+        Date::from_days_since_epoch(self.0.div_euclid(86_400))
+    }
+
+    /// `(hour, minute, second)`.
+    pub fn time(self) -> (u8, u8, u8) {
+        let seconds = self.0.rem_euclid(86_400);
+        (
+            (seconds / 3600) as u8,
+            (seconds / 60 % 60) as u8,
+            (seconds % 60) as u8,
+        )
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(date) = self.date() else {
+            return f.pad(&self.0.to_string());
+        };
+        let (hour, minute, second) = self.time();
+        let text = if second == 0 {
+            format!("{date}T{hour:02}:{minute:02}")
+        } else {
+            format!("{date}T{hour:02}:{minute:02}:{second:02}")
+        };
+        f.pad(&text)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected a time like 2024-02-29T14:05, found `{s}`");
+        let (date, time) = s.split_once('T').ok_or_else(invalid)?;
+        let date: Date = date.parse().map_err(|e| format!("{e}"))?;
+        let time = time.strip_suffix('Z').unwrap_or(time);
+        let mut fields = time.split(':').map(|field| {
+            (field.len() == 2 && field.bytes().all(|b| b.is_ascii_digit()))
+                .then(|| field.parse::<u8>().ok())
+                .flatten()
+        });
+        let hour = fields.next().flatten().ok_or_else(invalid)?;
+        let minute = fields.next().flatten().ok_or_else(invalid)?;
+        let second = match fields.next() {
+            Some(second) => second.ok_or_else(invalid)?,
+            None => 0,
+        };
+        if fields.next().is_some() {
+            return Err(invalid());
+        }
+        Timestamp::new(date, hour, minute, second).ok_or_else(invalid)
+    }
+}
+
+/// Items of any [`Summary`] type, in the order they were added until
+/// sorted.
+#[derive(Default)]
+pub struct Feed {
+    items: Vec<Box<dyn Summary>>,
+}
+
+impl Feed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, item: impl Summary + 'static) {
+        self.items.push(Box::new(item));
+    }
+
+    pub fn push_boxed(&mut self, item: Box<dyn Summary>) {
+        self.items.push(item);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn items(&self) -> &[Box<dyn Summary>] {
+        &self.items
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Summary> {
+        self.items.iter().map(|item| item.as_ref())
+    }
+
+    /// Newest first; items with the same timestamp keep their order.
+    pub fn sort_newest_first(&mut self) {
+        self.items.sort_by_key(|item| Reverse(item.timestamp()));
+    }
+
+    /// Oldest first; items with the same timestamp keep their order.
+    pub fn sort_oldest_first(&mut self) {
+        self.items.sort_by_key(|item| item.timestamp());
+    }
+
+    /// The items by `author`, see [`Summary::is_by`].
+    pub fn by_author<'a>(&'a self, author: &str) -> Vec<&'a dyn Summary> {
+        self.iter().filter(|item| item.is_by(author)).collect()
+    }
+
+    /// Page `number` (from 1) of `per_page` items; see [`paginate`].
+    pub fn page(&self, number: usize, per_page: usize) -> Option<Page<'_, Box<dyn Summary>>> {
+        paginate(&self.items, number, per_page)
+    }
+}
+
+impl fmt::Debug for Feed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(|item| item.summarize()))
+            .finish()
+    }
+}
+
+impl Extend<Box<dyn Summary>> for Feed {
+    fn extend<I: IntoIterator<Item = Box<dyn Summary>>>(&mut self, iter: I) {
+        self.items.extend(iter);
+    }
+}
+
+/// One page of a longer list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page<'a, T> {
+    pub items: &'a [T],
+    /// From 1.
+    pub number: usize,
+    pub page_count: usize,
+    /// Length of the whole list.
+    pub total: usize,
+}
+
+impl<T> Page<'_, T> {
+    pub fn has_next(&self) -> bool {
+        self.number < self.page_count
+    }
+
+    pub fn has_previous(&self) -> bool {
+        self.number > 1
+    }
+}
+
+/// Page `number`, counting from 1, of `items` split into pages of
+/// `per_page`.
+///
+/// `None` if `per_page` is zero or the page does not exist. An empty list
+/// has no pages.
+pub fn paginate<T>(items: &[T], number: usize, per_page: usize) -> Option<Page<'_, T>> {
+    if per_page == 0 || number == 0 {
+        return None;
+    }
+    let page_count = items.len().div_ceil(per_page);
+    let start = (number - 1).checked_mul(per_page)?;
+    if number > page_count {
+        return None;
+    }
+    Some(Page {
+        items: &items[start..(start + per_page).min(items.len())],
+        number,
+        page_count,
+        total: items.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Implements only the required methods, so every default is in play.
+    struct Note {
+        author: String,
+        at: i64,
+    }
+
+    impl Summary for Note {
+        fn author(&self) -> &str {
+            &self.author
+        }
+
+        fn timestamp(&self) -> Timestamp {
+            Timestamp(self.at)
+        }
+
+        fn summarize_author(&self) -> String {
+            format!("~{}", self.author)
+        }
+    }
+
+    fn note(author: &str, at: i64) -> Note {
+        Note {
+            author: author.to_string(),
+            at,
+        }
+    }
+
+    #[test]
+    fn default_summarize_uses_the_author() {
+        assert_eq!(note("ferris", 0).summarize(), "(Read more from ~ferris...)");
+    }
+
+    #[test]
+    fn default_summarize_within_truncates_the_summary() {
+        let item = note("ferris", 0);
+        assert_eq!(item.summarize_within(100), "(Read more from ~ferris...)");
+        assert_eq!(item.summarize_within(27), "(Read more from ~ferris...)");
+        assert_eq!(item.summarize_within(26), "(Read more from…");
+        assert_eq!(item.summarize_within(1), "…");
+        assert_eq!(item.summarize_within(0), "");
+    }
+
+    #[test]
+    fn default_is_by_ignores_case_and_at_sign() {
+        let item = note("Ferris", 0);
+        assert!(item.is_by("ferris"));
+        assert!(item.is_by("@FERRIS"));
+        assert!(!item.is_by("ferri"));
+    }
+
+    #[test]
+    fn truncation_counts_characters_and_breaks_at_spaces() {
+        assert_eq!(truncate("héllo wörld", 11), "héllo wörld");
+        assert_eq!(truncate("héllo wörld", 10), "héllo…");
+        // No space in the second half, so the word is cut
+        assert_eq!(truncate("a verylongword", 8), "a veryl…");
+        for width in 0..20 {
+            assert!(truncate("the quick brown fox jumps", width).chars().count() <= width);
+        }
+    }
+
+    #[test]
+    fn timestamps_round_trip() {
+        for text in [
+            "2024-02-29T14:05",
+            "1969-12-31T23:59:59",
+            "0001-01-01T00:00",
+        ] {
+            let time: Timestamp = text.parse().unwrap();
+            assert_eq!(time.to_string(), text);
+        }
+        assert_eq!("1970-01-01T00:01Z".parse(), Ok(Timestamp(60)));
+        assert_eq!(Timestamp(i64::MAX).date(), None);
+        assert_eq!(Timestamp(i64::MIN).to_string(), i64::MIN.to_string());
+        for text in [
+            "2024-02-29",
+            "2024-02-29T24:00",
+            "2024-02-29T1:05",
+            "2024-02-29T14:05:",
+        ] {
+            assert!(text.parse::<Timestamp>().is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn sorting_is_stable() {
+        let mut feed = Feed::new();
+        feed.push(note("a", 2));
+        feed.push(note("b", 1));
+        feed.push(note("c", 2));
+        feed.sort_newest_first();
+        let authors: Vec<&str> = feed.iter().map(|item| item.author()).collect();
+        assert_eq!(authors, ["a", "c", "b"]);
+        feed.sort_oldest_first();
+        let authors: Vec<&str> = feed.iter().map(|item| item.author()).collect();
+        assert_eq!(authors, ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn pagination() {
+        let items: Vec<u32> = (1..=7).collect();
+        let page = paginate(&items, 3, 3).unwrap();
+        assert_eq!(page.items, [7]);
+        assert_eq!((page.page_count, page.total), (3, 7));
+        assert!(page.has_previous() && !page.has_next());
+        assert_eq!(paginate(&items, 1, 10).unwrap().items.len(), 7);
+        assert!(paginate(&items, 4, 3).is_none());
+        assert!(paginate(&items, 0, 3).is_none());
+        assert!(paginate(&items, 1, 0).is_none());
+        assert!(paginate::<u32>(&[], 1, 3).is_none());
+        assert!(paginate(&items, usize::MAX, usize::MAX).is_none());
+    }
+}
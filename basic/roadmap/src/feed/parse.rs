@@ -0,0 +1,175 @@
+//! The line-based feed format.
+//!
+//! One item per line: a kind, a [`Timestamp`], then fields separated by
+//! `|`. The last field is the content and may itself contain `|`. Blank
+//! lines and lines starting with `#` are skipped.
+//!
+//! ```text
+//! article 2024-06-12T20:15 | Iceburgh | Pittsburgh, PA, USA | Penguins win the Stanley Cup! | The Penguins are the best hockey team.
+//! tweet   2024-06-12T20:20 | horse_ebooks | of course, as you probably already know, people
+//! reply   2024-06-12T20:31 | ferris | agreed
+//! retweet 2024-06-12T21:02 | iceburgh | of course, as you probably already know, people
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Feed, NewsArticle, Summary, Timestamp, Tweet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFeedError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseFeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseFeedError {}
+
+/// Parses a whole feed, stopping at the first bad line.
+pub fn parse_feed(input: &str) -> Result<Feed, ParseFeedError> {
+    let mut feed = Feed::new();
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let item = parse_item(line).map_err(|message| ParseFeedError {
+            line: index + 1,
+            message,
+        })?;
+        feed.push_boxed(item);
+    }
+    Ok(feed)
+}
+
+impl FromStr for Feed {
+    type Err = ParseFeedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_feed(s)
+    }
+}
+
+fn parse_item(line: &str) -> Result<Box<dyn Summary>, String> {
+    let (head, rest) = line.split_once('|').ok_or("expected `KIND TIME | ...`")?;
+    let mut head = head.split_whitespace();
+    let (Some(kind), Some(time), None) = (head.next(), head.next(), head.next()) else {
+        return Err(format!(
+            "expected `KIND TIME`, found `{}`",
+            line.split('|').next().unwrap_or("").trim()
+        ));
+    };
+    let time: Timestamp = time.parse()?;
+
+    match kind {
+        "article" => {
+            let [author, location, headline, content] = fields(rest)?;
+            Ok(Box::new(NewsArticle {
+                headline,
+                location,
+                author,
+                content,
+                published: time,
+            }))
+        }
+        "tweet" | "reply" | "retweet" => {
+            let [username, content] = fields(rest)?;
+            Ok(Box::new(Tweet {
+                username,
+                content,
+                reply: kind == "reply",
+                retweet: kind == "retweet",
+                posted: time,
+            }))
+        }
+        _ => Err(format!(
+            "unknown kind `{kind}`, expected article, tweet, reply or retweet"
+        )),
+    }
+}
+
+/// Exactly `N` non-empty fields; the last takes the rest of the line.
+fn fields<const N: usize>(rest: &str) -> Result<[String; N], String> {
+    let mut parts = rest.splitn(N, '|').map(|field| field.trim().to_string());
+    let mut out: [String; N] = std::array::from_fn(|_| String::new());
+    for (i, slot) in out.iter_mut().enumerate() {
+        match parts.next() {
+            Some(field) if !field.is_empty() => *slot = field,
+            Some(_) => return Err(format!("field {} is empty", i + 2)),
+            None => return Err(format!("expected {} fields after the time, found {i}", N)),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_each_kind() {
+        let feed = parse_feed(
+            "# sample\n\
+             article 2024-06-12T20:15 | Iceburgh | Pittsburgh | Penguins win! | A | B\n\
+             \n\
+             tweet 2024-06-12T20:20 | horse_ebooks | of course\n\
+             reply 2024-06-12T20:31 | ferris | agreed\n\
+             retweet 2024-06-12T21:02:30 | iceburgh | of course\n",
+        )
+        .unwrap();
+        let summaries: Vec<String> = feed.iter().map(|item| item.summarize()).collect();
+        assert_eq!(
+            summaries,
+            [
+                "Penguins win!, by Iceburgh (Pittsburgh)",
+                "@horse_ebooks: of course",
+                "@ferris replied: agreed",
+                "@iceburgh retweeted: of course",
+            ]
+        );
+        assert_eq!(
+            feed.items()[3].timestamp().to_string(),
+            "2024-06-12T21:02:30"
+        );
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let cases = [
+            (
+                "tweet 2024-06-12T20:20 | ferris",
+                "line 1: expected 2 fields after the time, found 1",
+            ),
+            (
+                "\npost 2024-06-12T20:20 | a | b",
+                "line 2: unknown kind `post`, expected article, tweet, reply or retweet",
+            ),
+            (
+                "tweet | a | b",
+                "line 1: expected `KIND TIME`, found `tweet`",
+            ),
+            (
+                "tweet 2024-06-12 | a | b",
+                "line 1: expected a time like 2024-02-29T14:05, found `2024-06-12`",
+            ),
+            (
+                "tweet 2024-13-01T00:00 | a | b",
+                "line 1: month 13 is not in 1..=12",
+            ),
+            ("tweet 2024-06-12T20:20 |  | b", "line 1: field 2 is empty"),
+            (
+                "tweet 2024-06-12T20:20",
+                "line 1: expected `KIND TIME | ...`",
+            ),
+        ];
+        for (input, expected) in cases {
+            let err = parse_feed(input).map(|_| ()).unwrap_err();
+            assert_eq!(err.to_string(), expected, "{input:?}");
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::f64::consts::PI;
+
+use super::GeometryError;
+use super::point::Point;
+use super::rectangle::Rectangle;
+use super::scalar::{self, Scalar};
+
+/// A circle given by its center and a non-negative radius.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Circle<T> {
+    center: Point<T>,
+    radius: T,
+}
+
+impl<T: Scalar> Circle<T> {
+    /// Panics if `radius` is negative.
+    pub fn new(center: Point<T>, radius: T) -> Self {
+        match Self::try_new(center, radius) {
+            Ok(circle) => circle,
+            Err(err) => panic!("invalid circle of radius {radius} at {center}: {err}"),
+        }
+    }
+
+    pub fn try_new(center: Point<T>, radius: T) -> Result<Self, GeometryError> {
+        if !scalar::is_non_negative(radius) {
+            return Err(GeometryError::NegativeExtent);
+        }
+        Ok(Self { center, radius })
+    }
+
+    pub fn center(&self) -> Point<T> {
+        self.center
+    }
+
+    pub fn radius(&self) -> T {
+        self.radius
+    }
+
+    /// A zero-radius circle is just its center point.
+    pub fn is_empty(&self) -> bool {
+        self.radius == T::ZERO
+    }
+
+    pub fn area(&self) -> f64 {
+        let r = self.radius.to_f64();
+        PI * r * r
+    }
+
+    pub fn circumference(&self) -> f64 {
+        2.0 * PI * self.radius.to_f64()
+    }
+
+    /// Whether `point` lies inside or on the circle.
+    pub fn contains_point(&self, point: Point<T>) -> bool {
+        let exact = || {
+            let dx = scalar::abs_diff(point.x, self.center.x)?;
+            let dy = scalar::abs_diff(point.y, self.center.y)?;
+            let distance_sq = dx.checked_mul(dx)?.checked_add(dy.checked_mul(dy)?)?;
+            Some(distance_sq <= self.radius.checked_mul(self.radius)?)
+        };
+        // Squared distances of far-apart integer points overflow long
+        // before the points are actually out of range, so fall back to
+        // floating point rather than guessing.
+        exact().unwrap_or_else(|| self.center.distance(point) <= self.radius.to_f64())
+    }
+
+    /// Whether the two circles share a region of positive area.
+    /// Circles that touch at a single point do not overlap.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let exact = || {
+            let dx = scalar::abs_diff(other.center.x, self.center.x)?;
+            let dy = scalar::abs_diff(other.center.y, self.center.y)?;
+            let distance_sq = dx.checked_mul(dx)?.checked_add(dy.checked_mul(dy)?)?;
+            let reach = self.radius.checked_add(other.radius)?;
+            Some(distance_sq < reach.checked_mul(reach)?)
+        };
+        exact().unwrap_or_else(|| {
+            self.center.distance(other.center) < self.radius.to_f64() + other.radius.to_f64()
+        })
+    }
+
+    /// The smallest axis-aligned rectangle containing the circle, or
+    /// `None` if it does not fit in `T` (for unsigned coordinates, when the
+    /// circle crosses an axis).
+    pub fn bounding_box(&self) -> Option<Rectangle<T>> {
+        let min = Point::new(
+            self.center.x.checked_sub(self.radius)?,
+            self.center.y.checked_sub(self.radius)?,
+        );
+        let diameter = self.radius.checked_add(self.radius)?;
+        Rectangle::try_at(min, diameter, diameter).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounding_box_needs_room_on_both_sides() {
+        let inside = Circle::new(Point::new(5u32, 5), 5);
+        assert_eq!(inside.bounding_box(), Some(Rectangle::new(10, 10)));
+        // Unsigned coordinates cannot reach below the axes
+        assert_eq!(Circle::new(Point::new(5u32, 20), 10).bounding_box(), None);
+        assert_eq!(Circle::new(Point::new(20u32, 5), 10).bounding_box(), None);
+        assert_eq!(
+            Circle::new(Point::new(0, 0), 3).bounding_box(),
+            Some(Rectangle::at(Point::new(-3, -3), 6, 6))
+        );
+    }
+
+    #[test]
+    fn rejects_negative_and_nan_radii() {
+        assert_eq!(
+            Circle::try_new(Point::new(0, 0), -1),
+            Err(GeometryError::NegativeExtent)
+        );
+        assert_eq!(
+            Circle::try_new(Point::new(0.0, 0.0), f64::NAN),
+            Err(GeometryError::NegativeExtent)
+        );
+        assert!(Circle::new(Point::new(1, 1), 0).is_empty());
+    }
+
+    #[test]
+    fn containment_and_overlap() {
+        // 100 * 100 overflows a u8, so this goes through f64
+        let circle = Circle::new(Point::new(200u8, 200), 100);
+        assert!(circle.contains_point(Point::new(100, 200)));
+        assert!(!circle.contains_point(Point::new(100, 100)));
+
+        let a = Circle::new(Point::new(0, 0), 5);
+        assert!(a.contains_point(Point::new(3, 4)));
+        assert!(!a.contains_point(Point::new(4, 4)));
+        // Tangent circles touch at one point
+        assert!(!a.overlaps(&Circle::new(Point::new(10, 0), 5)));
+        assert!(a.overlaps(&Circle::new(Point::new(9, 0), 5)));
+    }
+}
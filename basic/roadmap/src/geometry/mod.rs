@@ -0,0 +1,45 @@
+//! Generic 2D geometry: points, vectors, axis-aligned rectangles, circles
+//! and polygons over any [`Scalar`] coordinate type.
+//!
+//! This grew out of the `Rectangle` examples in `constructs/structs.rs` and
+//! `constructs/impl_blocks.rs`, which now use it. Arithmetic that can
+//! overflow has a `checked_*` form returning `Option`, mirroring the
+//! integer methods from `syntax_and_semantics/overflow_methods.rs`.
+
+use std::fmt;
+
+mod circle;
+mod point;
+mod polygon;
+mod rectangle;
+mod scalar;
+
+pub use circle::Circle;
+pub use point::{Point, Vector};
+pub use polygon::{Orientation, Polygon};
+pub use rectangle::Rectangle;
+pub use scalar::Scalar;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeometryError {
+    /// A width, height or radius was negative (or NaN).
+    NegativeExtent,
+    /// A coordinate or extent does not fit in the scalar type.
+    Overflow,
+    /// A polygon needs at least three vertices.
+    TooFewVertices { found: usize },
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeometryError::NegativeExtent => write!(f, "extent must not be negative"),
+            GeometryError::Overflow => write!(f, "coordinates overflow the scalar type"),
+            GeometryError::TooFewVertices { found } => {
+                write!(f, "a polygon needs at least 3 vertices, found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GeometryError {}
@@ -0,0 +1,141 @@
+use std::fmt;
+
+use super::scalar::Scalar;
+
+/// A position in the plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A displacement between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Vector<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Scalar> Point<T> {
+    pub const ORIGIN: Self = Self::new(T::ZERO, T::ZERO);
+
+    /// Moves the point by `offset`, or `None` if a coordinate overflows.
+    pub fn checked_translate(self, offset: Vector<T>) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add(offset.x)?,
+            self.y.checked_add(offset.y)?,
+        ))
+    }
+
+    /// The vector from `self` to `other`.
+    ///
+    /// For unsigned coordinates this is `None` whenever `other` lies to the
+    /// left of or below `self`, because the result would be negative.
+    pub fn vector_to(self, other: Self) -> Option<Vector<T>> {
+        Some(Vector::new(
+            other.x.checked_sub(self.x)?,
+            other.y.checked_sub(self.y)?,
+        ))
+    }
+
+    pub fn distance(self, other: Self) -> f64 {
+        let dx = other.x.to_f64() - self.x.to_f64();
+        let dy = other.y.to_f64() - self.y.to_f64();
+        dx.hypot(dy)
+    }
+}
+
+impl<T> Vector<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Scalar> Vector<T> {
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+        ))
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_sub(other.x)?,
+            self.y.checked_sub(other.y)?,
+        ))
+    }
+
+    pub fn checked_scale(self, factor: T) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_mul(factor)?,
+            self.y.checked_mul(factor)?,
+        ))
+    }
+
+    pub fn dot(self, other: Self) -> Option<T> {
+        self.x
+            .checked_mul(other.x)?
+            .checked_add(self.y.checked_mul(other.y)?)
+    }
+
+    /// The z component of the 3D cross product, i.e. twice the signed area
+    /// of the triangle spanned by the two vectors.
+    pub fn cross(self, other: Self) -> Option<T> {
+        self.x
+            .checked_mul(other.y)?
+            .checked_sub(self.y.checked_mul(other.x)?)
+    }
+
+    pub fn length(self) -> f64 {
+        self.x.to_f64().hypot(self.y.to_f64())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translation_and_vectors_are_checked() {
+        let p = Point::new(250u8, 3);
+        assert_eq!(
+            p.checked_translate(Vector::new(5, 1)),
+            Some(Point::new(255, 4))
+        );
+        assert_eq!(p.checked_translate(Vector::new(6, 0)), None);
+        // Unsigned vectors cannot point left or down
+        assert_eq!(p.vector_to(Point::new(255, 10)), Some(Vector::new(5, 7)));
+        assert_eq!(p.vector_to(Point::new(0, 10)), None);
+        assert_eq!(Point::new(0, 0).distance(Point::new(3, 4)), 5.0);
+    }
+
+    #[test]
+    fn products() {
+        let (a, b) = (Vector::new(2, 0), Vector::new(0, 3));
+        assert_eq!(a.dot(b), Some(0));
+        assert_eq!(a.cross(b), Some(6));
+        assert_eq!(b.cross(a), Some(-6));
+        assert_eq!(Vector::new(16u8, 0).dot(Vector::new(16, 0)), None);
+        assert_eq!(Vector::new(3, 4).length(), 5.0);
+        assert_eq!(Vector::new(i32::MAX, 0).checked_scale(2), None);
+    }
+}
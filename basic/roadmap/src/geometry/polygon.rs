@@ -0,0 +1,168 @@
+use super::GeometryError;
+use super::point::Point;
+use super::rectangle::Rectangle;
+use super::scalar::{self, Scalar};
+
+/// Winding direction of a polygon's vertices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    CounterClockwise,
+    Clockwise,
+    /// All vertices are collinear, so the polygon has no area.
+    Degenerate,
+}
+
+/// A simple polygon given by its vertices in order. The closing edge from
+/// the last vertex back to the first is implicit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon<T> {
+    vertices: Vec<Point<T>>,
+}
+
+impl<T: Scalar> Polygon<T> {
+    pub fn new(vertices: Vec<Point<T>>) -> Result<Self, GeometryError> {
+        if vertices.len() < 3 {
+            return Err(GeometryError::TooFewVertices {
+                found: vertices.len(),
+            });
+        }
+        Ok(Self { vertices })
+    }
+
+    pub fn vertices(&self) -> &[Point<T>] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point<T>, Point<T>)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    // The shoelace formula splits into a sum of `x_i * y_(i+1)` terms and
+    // a sum of `x_(i+1) * y_i` terms. Keeping them apart until the end
+    // means unsigned coordinates never need a negative intermediate.
+    fn shoelace_sums(&self) -> Option<(T, T)> {
+        let mut forward = T::ZERO;
+        let mut backward = T::ZERO;
+        for (a, b) in self.edges() {
+            forward = forward.checked_add(a.x.checked_mul(b.y)?)?;
+            backward = backward.checked_add(b.x.checked_mul(a.y)?)?;
+        }
+        Some((forward, backward))
+    }
+
+    fn signed_area_f64(&self) -> f64 {
+        let twice: f64 = self
+            .edges()
+            .map(|(a, b)| a.x.to_f64() * b.y.to_f64() - b.x.to_f64() * a.y.to_f64())
+            .sum();
+        twice / 2.0
+    }
+
+    /// Twice the enclosed area, computed exactly by the shoelace formula.
+    ///
+    /// Doubling keeps the result integral for integer coordinates. `None`
+    /// if an intermediate product or sum overflows `T`.
+    pub fn checked_double_area(&self) -> Option<T> {
+        let (forward, backward) = self.shoelace_sums()?;
+        scalar::abs_diff(forward, backward)
+    }
+
+    /// The enclosed area, exact whenever [`Polygon::checked_double_area`]
+    /// succeeds and computed in `f64` otherwise.
+    pub fn area(&self) -> f64 {
+        match self.checked_double_area() {
+            Some(double) => double.to_f64() / 2.0,
+            None => self.signed_area_f64().abs(),
+        }
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        let sign = match self.shoelace_sums() {
+            Some((forward, backward)) if forward > backward => 1.0,
+            Some((forward, backward)) if forward < backward => -1.0,
+            Some(_) => 0.0,
+            None => self.signed_area_f64(),
+        };
+        if sign > 0.0 {
+            Orientation::CounterClockwise
+        } else if sign < 0.0 {
+            Orientation::Clockwise
+        } else {
+            Orientation::Degenerate
+        }
+    }
+
+    pub fn is_degenerate(&self) -> bool {
+        self.orientation() == Orientation::Degenerate
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        self.edges().map(|(a, b)| a.distance(b)).sum()
+    }
+
+    /// `None` if the extent of the vertices does not fit in `T`.
+    pub fn bounding_box(&self) -> Option<Rectangle<T>> {
+        let first = self.vertices[0];
+        let (min, max) = self.vertices.iter().fold((first, first), |(min, max), p| {
+            (
+                Point::new(scalar::min(min.x, p.x), scalar::min(min.y, p.y)),
+                Point::new(scalar::max(max.x, p.x), scalar::max(max.y, p.y)),
+            )
+        });
+        Rectangle::from_corners(min, max).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon<T: Scalar>(points: &[(T, T)]) -> Polygon<T> {
+        Polygon::new(points.iter().map(|&(x, y)| Point::new(x, y)).collect()).unwrap()
+    }
+
+    #[test]
+    fn shoelace_on_unsigned_coordinates() {
+        let square = [(0u32, 0), (4, 0), (4, 4), (0, 4)];
+        let ccw = polygon(&square);
+        assert_eq!(ccw.checked_double_area(), Some(32));
+        assert_eq!(ccw.area(), 16.0);
+        assert_eq!(ccw.orientation(), Orientation::CounterClockwise);
+
+        let mut reversed = square;
+        reversed.reverse();
+        let cw = polygon(&reversed);
+        assert_eq!(cw.checked_double_area(), Some(32));
+        assert_eq!(cw.orientation(), Orientation::Clockwise);
+
+        let line = polygon(&[(0u32, 0), (1, 1), (3, 3)]);
+        assert_eq!(line.checked_double_area(), Some(0));
+        assert!(line.is_degenerate());
+    }
+
+    #[test]
+    fn overflow_falls_back_to_f64() {
+        // 200 * 200 does not fit in a u8
+        let triangle = polygon(&[(0u8, 0), (200, 0), (0, 200)]);
+        assert_eq!(triangle.checked_double_area(), None);
+        assert_eq!(triangle.area(), 20_000.0);
+        assert_eq!(triangle.orientation(), Orientation::CounterClockwise);
+        let flipped = polygon(&[(0u8, 0), (0, 200), (200, 0)]);
+        assert_eq!(flipped.orientation(), Orientation::Clockwise);
+    }
+
+    #[test]
+    fn needs_three_vertices() {
+        assert_eq!(
+            Polygon::new(vec![Point::new(0, 0), Point::new(1, 1)]),
+            Err(GeometryError::TooFewVertices { found: 2 })
+        );
+        let triangle = polygon(&[(-1, 2), (3, -4), (0, 5)]);
+        assert_eq!(
+            triangle.bounding_box(),
+            Some(Rectangle::at(Point::new(-1, -4), 4, 9))
+        );
+        assert_eq!(polygon(&[(0, 0), (3, 0), (3, 4)]).perimeter(), 12.0);
+    }
+}
@@ -0,0 +1,365 @@
+use std::fmt;
+
+use super::GeometryError;
+use super::point::Point;
+use super::scalar::{self, Scalar};
+
+/// An axis-aligned rectangle anchored at its minimum corner.
+///
+/// The extent is never negative and the far corner `origin + (width,
+/// height)` always fits in `T`, so edge queries cannot overflow once a
+/// rectangle exists. Zero-width or zero-height rectangles are allowed and
+/// reported by [`Rectangle::is_empty`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rectangle<T> {
+    origin: Point<T>,
+    width: T,
+    height: T,
+}
+
+impl<T: Scalar> Rectangle<T> {
+    /// A rectangle with its minimum corner at the origin.
+    ///
+    /// Panics if the extent is negative; use [`Rectangle::try_at`] for a
+    /// fallible constructor.
+    pub fn new(width: T, height: T) -> Self {
+        Self::at(Point::ORIGIN, width, height)
+    }
+
+    pub fn square(size: T) -> Self {
+        Self::new(size, size)
+    }
+
+    /// Panics if the extent is negative or the far corner overflows.
+    pub fn at(origin: Point<T>, width: T, height: T) -> Self {
+        match Self::try_at(origin, width, height) {
+            Ok(rect) => rect,
+            Err(err) => panic!("invalid rectangle {width}x{height} at {origin}: {err}"),
+        }
+    }
+
+    pub fn try_at(origin: Point<T>, width: T, height: T) -> Result<Self, GeometryError> {
+        if !(scalar::is_non_negative(width) && scalar::is_non_negative(height)) {
+            return Err(GeometryError::NegativeExtent);
+        }
+        origin
+            .x
+            .checked_add(width)
+            .zip(origin.y.checked_add(height))
+            .ok_or(GeometryError::Overflow)?;
+        Ok(Self {
+            origin,
+            width,
+            height,
+        })
+    }
+
+    /// The rectangle spanned by two opposite corners, in any order.
+    pub fn from_corners(a: Point<T>, b: Point<T>) -> Result<Self, GeometryError> {
+        let origin = Point::new(scalar::min(a.x, b.x), scalar::min(a.y, b.y));
+        let width = scalar::abs_diff(a.x, b.x).ok_or(GeometryError::Overflow)?;
+        let height = scalar::abs_diff(a.y, b.y).ok_or(GeometryError::Overflow)?;
+        Self::try_at(origin, width, height)
+    }
+
+    pub fn origin(&self) -> Point<T> {
+        self.origin
+    }
+
+    pub fn width(&self) -> T {
+        self.width
+    }
+
+    pub fn height(&self) -> T {
+        self.height
+    }
+
+    /// The corner opposite the origin.
+    pub fn far_corner(&self) -> Point<T> {
+        // Guaranteed by the constructors, see the type-level docs.
+        Point::new(
+            self.origin
+                .x
+                .checked_add(self.width)
+                .expect("far corner fits"),
+            self.origin
+                .y
+                .checked_add(self.height)
+                .expect("far corner fits"),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !(self.width > T::ZERO && self.height > T::ZERO)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+
+    /// Panics on overflow, like the `*` operator in debug builds.
+    pub fn area(&self) -> T {
+        self.checked_area().expect("rectangle area overflowed")
+    }
+
+    pub fn checked_area(&self) -> Option<T> {
+        self.width.checked_mul(self.height)
+    }
+
+    /// Panics on overflow, like the `+` operator in debug builds.
+    pub fn perimeter(&self) -> T {
+        self.checked_perimeter()
+            .expect("rectangle perimeter overflowed")
+    }
+
+    pub fn checked_perimeter(&self) -> Option<T> {
+        let half = self.width.checked_add(self.height)?;
+        half.checked_add(half)
+    }
+
+    /// Whether `other` fits strictly inside `self` when both are aligned,
+    /// ignoring position. This is the `can_hold` from the structs lesson.
+    pub fn can_hold(&self, other: &Self) -> bool {
+        self.width > other.width && self.height > other.height
+    }
+
+    /// Like [`Rectangle::can_hold`], but an exact fit counts. This is the
+    /// test the bin packers in `crate::packing` need.
+    pub fn can_fit(&self, other: &Self) -> bool {
+        self.width >= other.width && self.height >= other.height
+    }
+
+    /// Whether `point` lies inside or on the boundary.
+    pub fn contains_point(&self, point: Point<T>) -> bool {
+        let far = self.far_corner();
+        point.x >= self.origin.x && point.x <= far.x && point.y >= self.origin.y && point.y <= far.y
+    }
+
+    /// Whether `other` lies entirely inside `self`, boundaries included.
+    pub fn contains(&self, other: &Self) -> bool {
+        self.contains_point(other.origin) && self.contains_point(other.far_corner())
+    }
+
+    /// Whether the two rectangles share a region of positive area.
+    /// Rectangles that only touch along an edge or a corner do not overlap.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The overlapping region, or `None` if there is no positive-area overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let (a, b) = (self.far_corner(), other.far_corner());
+        let min = Point::new(
+            scalar::max(self.origin.x, other.origin.x),
+            scalar::max(self.origin.y, other.origin.y),
+        );
+        let max = Point::new(scalar::min(a.x, b.x), scalar::min(a.y, b.y));
+        if !(max.x > min.x && max.y > min.y) {
+            return None;
+        }
+        Self::from_corners(min, max).ok()
+    }
+
+    /// The smallest rectangle containing both.
+    ///
+    /// Empty rectangles contribute nothing, so `a.union(&empty) == Some(a)`.
+    /// `None` if the combined extent does not fit in `T`.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if self.is_empty() {
+            return Some(*other);
+        }
+        let (a, b) = (self.far_corner(), other.far_corner());
+        let min = Point::new(
+            scalar::min(self.origin.x, other.origin.x),
+            scalar::min(self.origin.y, other.origin.y),
+        );
+        let max = Point::new(scalar::max(a.x, b.x), scalar::max(a.y, b.y));
+        Self::from_corners(min, max).ok()
+    }
+
+    /// Panics if the new extent is negative or does not fit.
+    pub fn resize(&mut self, width: T, height: T) {
+        *self = Self::at(self.origin, width, height);
+    }
+
+    pub fn try_resize(&mut self, width: T, height: T) -> Result<(), GeometryError> {
+        *self = Self::try_at(self.origin, width, height)?;
+        Ok(())
+    }
+
+    pub fn set_width(&mut self, width: T) {
+        self.resize(width, self.height);
+    }
+
+    pub fn set_height(&mut self, height: T) {
+        self.resize(self.width, height);
+    }
+
+    /// Scales the extent in place, keeping the origin fixed.
+    /// Panics on overflow; see [`Rectangle::checked_scale`].
+    pub fn scale(&mut self, factor: T) {
+        *self = self
+            .checked_scale(factor)
+            .expect("rectangle scale overflowed");
+    }
+
+    pub fn checked_scale(&self, factor: T) -> Option<Self> {
+        let width = self.width.checked_mul(factor)?;
+        let height = self.height.checked_mul(factor)?;
+        Self::try_at(self.origin, width, height).ok()
+    }
+
+    /// Consumes the rectangle and returns the square with the longer side.
+    /// Panics if the square's far corner does not fit in `T`; see
+    /// [`Rectangle::checked_into_square`].
+    pub fn into_square(self) -> Self {
+        self.checked_into_square()
+            .expect("square's far corner overflowed")
+    }
+
+    /// The square with the longer side, or `None` if it does not fit.
+    pub fn checked_into_square(self) -> Option<Self> {
+        let size = scalar::max(self.width, self.height);
+        Self::try_at(self.origin, size, size).ok()
+    }
+
+    /// Consumes both rectangles and returns one with the larger width and
+    /// the larger height, anchored at `self`'s origin. Panics if its far
+    /// corner does not fit in `T`; see [`Rectangle::checked_max`].
+    pub fn max(self, other: Self) -> Self {
+        self.checked_max(other)
+            .expect("larger rectangle's far corner overflowed")
+    }
+
+    /// Like [`Rectangle::max`], or `None` if the result does not fit.
+    pub fn checked_max(self, other: Self) -> Option<Self> {
+        Self::try_at(
+            self.origin,
+            scalar::max(self.width, other.width),
+            scalar::max(self.height, other.height),
+        )
+        .ok()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Rectangle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{} at {}", self.width, self.height, self.origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_at_rejects_invalid_extents() {
+        let origin = Point::new(0, 0);
+        assert_eq!(
+            Rectangle::try_at(origin, -1, 5),
+            Err(GeometryError::NegativeExtent)
+        );
+        assert_eq!(
+            Rectangle::try_at(Point::new(0.0, 0.0), 1.0, f64::NAN),
+            Err(GeometryError::NegativeExtent)
+        );
+        // The far corner 250 + 10 does not fit in a u8
+        assert_eq!(
+            Rectangle::<u8>::try_at(Point::new(250, 0), 10, 1),
+            Err(GeometryError::Overflow)
+        );
+        assert_eq!(
+            Rectangle::try_at(Point::new(f64::MAX, 0.0), f64::MAX, 1.0),
+            Err(GeometryError::Overflow)
+        );
+        let rect = Rectangle::<u8>::try_at(Point::new(250, 0), 5, 1).unwrap();
+        assert_eq!(rect.far_corner(), Point::new(255, 1));
+    }
+
+    #[test]
+    fn from_corners_in_any_order() {
+        assert_eq!(
+            Rectangle::from_corners(Point::new(5, 1), Point::new(2, 4)),
+            Ok(Rectangle::at(Point::new(2, 1), 3, 3))
+        );
+        // The width i32::MAX - i32::MIN does not fit in an i32
+        assert_eq!(
+            Rectangle::from_corners(Point::new(i32::MIN, 0), Point::new(i32::MAX, 1)),
+            Err(GeometryError::Overflow)
+        );
+    }
+
+    #[test]
+    fn touching_rectangles_do_not_intersect() {
+        let a = Rectangle::new(2, 2);
+        let beside = Rectangle::at(Point::new(2, 0), 2, 2);
+        let corner = Rectangle::at(Point::new(2, 2), 2, 2);
+        assert_eq!(a.intersection(&beside), None);
+        assert_eq!(a.intersection(&corner), None);
+        assert!(!a.overlaps(&beside));
+        let across = Rectangle::at(Point::new(1, 1), 2, 2);
+        assert_eq!(
+            a.intersection(&across),
+            Some(Rectangle::at(Point::new(1, 1), 1, 1))
+        );
+    }
+
+    #[test]
+    fn union_ignores_empty_rectangles() {
+        let a = Rectangle::at(Point::new(1, 1), 2, 3);
+        let empty = Rectangle::at(Point::new(100, 100), 0, 5);
+        assert_eq!(a.union(&empty), Some(a));
+        assert_eq!(empty.union(&a), Some(a));
+        assert_eq!(
+            a.union(&Rectangle::at(Point::new(5, 0), 1, 1)),
+            Some(Rectangle::at(Point::new(1, 0), 5, 4))
+        );
+        // Together they span 220 units, more than an i8 holds
+        let left = Rectangle::<i8>::at(Point::new(-100, 0), 10, 1);
+        let right = Rectangle::<i8>::at(Point::new(100, 0), 10, 1);
+        assert_eq!(left.union(&right), None);
+    }
+
+    #[test]
+    fn checked_arithmetic_reports_overflow() {
+        assert_eq!(Rectangle::<u8>::new(100, 2).checked_scale(3), None);
+        // The extent fits but the far corner does not
+        assert_eq!(
+            Rectangle::<u8>::at(Point::new(200, 0), 10, 1).checked_scale(6),
+            None
+        );
+        assert_eq!(
+            Rectangle::new(3, 4).checked_scale(2),
+            Some(Rectangle::new(6, 8))
+        );
+        assert_eq!(Rectangle::<u8>::new(16, 16).checked_area(), None);
+        assert_eq!(Rectangle::<u8>::new(100, 100).checked_perimeter(), None);
+        assert_eq!(Rectangle::<u8>::new(15, 17).checked_area(), Some(255));
+    }
+
+    #[test]
+    fn squares_and_maxima_that_do_not_fit() {
+        let tall = Rectangle::<u8>::at(Point::new(0, 250), 200, 5);
+        // 200 high from y = 250 passes 255
+        assert_eq!(tall.checked_into_square(), None);
+        let wide = Rectangle::<u8>::at(Point::new(50, 50), 5, 3);
+        assert_eq!(
+            wide.checked_into_square(),
+            Some(Rectangle::at(Point::new(50, 50), 5, 5))
+        );
+        assert_eq!(wide.into_square(), Rectangle::at(Point::new(50, 50), 5, 5));
+
+        let big = Rectangle::<u8>::new(10, 220);
+        assert_eq!(wide.checked_max(big), None);
+        assert_eq!(big.checked_max(wide), Some(Rectangle::new(10, 220)));
+    }
+
+    #[test]
+    #[should_panic(expected = "square's far corner overflowed")]
+    fn into_square_panics_when_it_does_not_fit() {
+        Rectangle::<u8>::at(Point::new(0, 250), 200, 5).into_square();
+    }
+}
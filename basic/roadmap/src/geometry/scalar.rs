@@ -0,0 +1,128 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+
+/// Numeric types that can be used as coordinates and extents.
+///
+/// Integer arithmetic is checked; float arithmetic treats a non-finite
+/// result as overflow. Either way the shapes report overflow as `None`
+/// instead of wrapping or producing infinities.
+pub trait Scalar: Copy + PartialOrd + Debug + Display {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_scalar_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_scalar_for_float {
+    ($($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = 0.0;
+                const ONE: Self = 1.0;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    finite(self + rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    finite(self - rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    finite(self * rhs)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_for_int!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+impl_scalar_for_float!(f32, f64);
+
+fn finite<T: Into<f64> + Copy>(value: T) -> Option<T> {
+    value.into().is_finite().then_some(value)
+}
+
+pub(crate) fn min<T: Scalar>(a: T, b: T) -> T {
+    if b < a { b } else { a }
+}
+
+pub(crate) fn max<T: Scalar>(a: T, b: T) -> T {
+    if b > a { b } else { a }
+}
+
+// |a - b| without going through a signed type, so it works for unsigned
+// coordinates too. `None` only when the distance itself does not fit.
+pub(crate) fn abs_diff<T: Scalar>(a: T, b: T) -> Option<T> {
+    if a >= b {
+        a.checked_sub(b)
+    } else {
+        b.checked_sub(a)
+    }
+}
+
+/// `value >= 0`, and `false` for NaN.
+pub(crate) fn is_non_negative<T: Scalar>(value: T) -> bool {
+    matches!(
+        value.partial_cmp(&T::ZERO),
+        Some(Ordering::Greater | Ordering::Equal)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abs_diff_works_without_a_sign() {
+        assert_eq!(abs_diff(3u8, 250), Some(247));
+        assert_eq!(abs_diff(-100i8, 100), None);
+        assert_eq!(abs_diff(-1.5, 2.0), Some(3.5));
+    }
+
+    #[test]
+    fn floats_treat_infinity_as_overflow() {
+        assert_eq!(Scalar::checked_mul(f32::MAX, 2.0), None);
+        assert_eq!(Scalar::checked_add(1.5f64, 1.0), Some(2.5));
+        assert!(is_non_negative(0.0));
+        assert!(is_non_negative(-0.0));
+        assert!(!is_non_negative(f64::NAN));
+        assert!(!is_non_negative(-1));
+    }
+}
@@ -0,0 +1,329 @@
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::GridError;
+
+/// Offsets of the eight cells around a cell, row by row.
+const MOORE: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangle of cells whose size is chosen at runtime, stored row by
+/// row in one `Vec`.
+///
+/// Rows are contiguous, so [`Grid::row`] is a plain slice; columns are
+/// not, so [`Grid::column`] is an iterator that steps over the rows.
+/// Cells are addressed as `(x, y)`: column, then row. Lookups return
+/// `Option` instead of panicking.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Result<Self, GridError>
+    where
+        T: Clone,
+    {
+        let len = width.checked_mul(height).ok_or(GridError::TooLarge)?;
+        Ok(Self {
+            width,
+            height,
+            cells: vec![value; len],
+        })
+    }
+
+    /// Each cell computed from its `(x, y)`.
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut f: impl FnMut(usize, usize) -> T,
+    ) -> Result<Self, GridError> {
+        let len = width.checked_mul(height).ok_or(GridError::TooLarge)?;
+        let mut cells = Vec::with_capacity(len);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Rows of equal length; the grid is as wide as the first row.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::RaggedRow {
+                    row: y,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            cells.extend(row);
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// Replaces the cell and returns the old value, or gives `value` back
+    /// if `(x, y)` is outside the grid.
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> Result<T, T> {
+        match self.get_mut(x, y) {
+            Some(cell) => Ok(std::mem::replace(cell, value)),
+            None => Err(value),
+        }
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        let start = self.index(0, y)?;
+        Some(&self.cells[start..start + self.width])
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        let start = self.index(0, y)?;
+        Some(&mut self.cells[start..start + self.width])
+    }
+
+    /// Every row as a slice, top to bottom.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[T]> {
+        // `chunks_exact(0)` panics, and a zero-width grid has no cells
+        let size = self.width.max(1);
+        self.cells.chunks_exact(size).take(self.height)
+    }
+
+    /// The cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> Option<Column<'_, T>> {
+        (x < self.width).then(|| Column {
+            cells: &self.cells,
+            width: self.width,
+            next: x,
+        })
+    }
+
+    /// All cells with their coordinates, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// The up to eight cells touching `(x, y)`, with their coordinates.
+    /// Cells off the edge are left out.
+    pub fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        MOORE.iter().filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx)?;
+            let ny = y.checked_add_signed(dy)?;
+            self.get(nx, ny).map(|cell| ((nx, ny), cell))
+        })
+    }
+
+    /// The eight cells touching `(x, y)` on a torus: the left edge wraps
+    /// to the right and the top to the bottom. Empty if `(x, y)` is
+    /// outside the grid.
+    pub fn neighbors_wrapping(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let inside = self.index(x, y).is_some();
+        MOORE.iter().filter(move |_| inside).map(move |&(dx, dy)| {
+            let nx = wrap(x, dx, self.width);
+            let ny = wrap(y, dy, self.height);
+            ((nx, ny), &self.cells[ny * self.width + nx])
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for x in 0..self.width {
+            cells.extend(self.column(x).into_iter().flatten().cloned());
+        }
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+}
+
+/// `coordinate + delta` modulo `size`, for `delta` in `-1..=1`.
+fn wrap(coordinate: usize, delta: isize, size: usize) -> usize {
+    match delta {
+        -1 if coordinate == 0 => size - 1,
+        -1 => coordinate - 1,
+        1 if coordinate + 1 == size => 0,
+        1 => coordinate + 1,
+        _ => coordinate,
+    }
+}
+
+/// Cells right-aligned in columns as wide as the widest cell.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text: Vec<String> = self.cells.iter().map(ToString::to_string).collect();
+        let cell_width = text.iter().map(|t| t.chars().count()).max().unwrap_or(0);
+        for (y, row) in text.chunks(self.width.max(1)).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            let cells: Vec<String> = row.iter().map(|t| format!("{t:>cell_width$}")).collect();
+            write!(f, "{}", cells.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// The cells of one column, from [`Grid::column`].
+pub struct Column<'a, T> {
+    cells: &'a [T],
+    width: usize,
+    next: usize,
+}
+
+impl<'a, T> Iterator for Column<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let cell = self.cells.get(self.next)?;
+        self.next += self.width;
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self
+            .cells
+            .len()
+            .saturating_sub(self.next)
+            .div_ceil(self.width);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Column<'_, T> {}
+impl<T> FusedIterator for Column<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered() -> Grid<usize> {
+        Grid::from_fn(4, 3, |x, y| y * 4 + x).unwrap()
+    }
+
+    #[test]
+    fn rows_are_slices_and_columns_step() {
+        let grid = numbered();
+        assert_eq!(grid.row(1), Some(&[4, 5, 6, 7][..]));
+        assert_eq!(grid.row(3), None);
+        assert_eq!(
+            grid.column(2).unwrap().copied().collect::<Vec<_>>(),
+            [2, 6, 10]
+        );
+        assert_eq!(grid.column(2).unwrap().len(), 3);
+        assert!(grid.column(4).is_none());
+        assert_eq!(grid.rows().len(), 3);
+    }
+
+    #[test]
+    fn transpose_swaps_coordinates() {
+        let grid = numbered();
+        let transposed = grid.transpose();
+        assert_eq!((transposed.width(), transposed.height()), (3, 4));
+        for ((x, y), cell) in grid.iter() {
+            assert_eq!(transposed.get(y, x), Some(cell));
+        }
+        assert_eq!(transposed.transpose(), grid);
+    }
+
+    #[test]
+    fn neighbors_at_edges() {
+        let grid = numbered();
+        let corner: Vec<usize> = grid.neighbors(0, 0).map(|(_, &v)| v).collect();
+        assert_eq!(corner, [1, 4, 5]);
+        assert_eq!(grid.neighbors(1, 1).count(), 8);
+        let wrapped: Vec<usize> = grid.neighbors_wrapping(0, 0).map(|(_, &v)| v).collect();
+        assert_eq!(wrapped, [11, 8, 9, 3, 1, 7, 4, 5]);
+        assert_eq!(grid.neighbors_wrapping(9, 9).count(), 0);
+    }
+
+    #[test]
+    fn construction_errors() {
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2], vec![3]]),
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(Grid::filled(usize::MAX, 2, 0), Err(GridError::TooLarge));
+        let mut grid = Grid::filled(2, 2, 'a').unwrap();
+        assert_eq!(grid.set(1, 1, 'b'), Ok('a'));
+        assert_eq!(grid.set(2, 0, 'c'), Err('c'));
+    }
+
+    #[test]
+    fn display_aligns_columns() {
+        assert_eq!(
+            numbered().to_string(),
+            " 0  1  2  3\n 4  5  6  7\n 8  9 10 11"
+        );
+        let empty: Grid<u8> = Grid::from_rows(Vec::new()).unwrap();
+        assert_eq!(empty.to_string(), "");
+        assert_eq!(empty.rows().len(), 0);
+    }
+}
@@ -0,0 +1,180 @@
+//! Conway's Game of Life on a [`Grid<bool>`] whose edges wrap around.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Grid, GridError};
+
+/// Starting patterns, in the text form [`Life`] parses.
+pub const PATTERNS: &[(&str, &str)] = &[
+    ("blinker", "...\n###\n..."),
+    ("glider", ".#.\n..#\n###"),
+    ("toad", "....\n.###\n###.\n...."),
+    ("r-pentomino", ".##\n##.\n.#."),
+];
+
+/// A board and its generation count.
+///
+/// The board is a torus: a glider leaving the right edge comes back on
+/// the left, so the population never leaks away at the borders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Life {
+    cells: Grid<bool>,
+    generation: u64,
+}
+
+impl Life {
+    pub fn new(cells: Grid<bool>) -> Self {
+        Self {
+            cells,
+            generation: 0,
+        }
+    }
+
+    /// An empty `width` by `height` board with `pattern` copied in at
+    /// `(x, y)`; pattern cells that fall off the board are dropped.
+    pub fn with_pattern(
+        width: usize,
+        height: usize,
+        pattern: &Life,
+        x: usize,
+        y: usize,
+    ) -> Result<Self, GridError> {
+        let mut cells = Grid::filled(width, height, false)?;
+        for ((px, py), &alive) in pattern.cells.iter() {
+            if let (Some(cx), Some(cy)) = (x.checked_add(px), y.checked_add(py))
+                && let Some(cell) = cells.get_mut(cx, cy)
+            {
+                *cell = alive;
+            }
+        }
+        Ok(Self::new(cells))
+    }
+
+    pub fn cells(&self) -> &Grid<bool> {
+        &self.cells
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|(_, alive)| **alive).count()
+    }
+
+    /// Live neighbours of `(x, y)`, counting across the wrapped edges.
+    pub fn live_neighbors(&self, x: usize, y: usize) -> usize {
+        self.cells
+            .neighbors_wrapping(x, y)
+            .filter(|(_, alive)| **alive)
+            .count()
+    }
+
+    /// Advances one generation: a live cell with two or three live
+    /// neighbours survives, a dead cell with exactly three comes alive.
+    ///
+    /// The next board is built from the current one and then replaces
+    /// it, so every cell sees the same generation.
+    pub fn step(&mut self) {
+        let next = Grid::from_fn(self.cells.width(), self.cells.height(), |x, y| {
+            let alive = self.cells.get(x, y) == Some(&true);
+            matches!((alive, self.live_neighbors(x, y)), (true, 2) | (_, 3))
+        })
+        .expect("same size as the current board");
+        self.cells = next;
+        self.generation += 1;
+    }
+}
+
+/// `#` for a live cell and `.` for a dead one, one line per row, as
+/// accepted by `FromStr`.
+impl fmt::Display for Life {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.cells.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            let line: String = row
+                .iter()
+                .map(|&alive| if alive { '#' } else { '.' })
+                .collect();
+            f.write_str(&line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lines of `#` or `O` (alive) and `.` (dead), all the same length.
+impl FromStr for Life {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rows = Vec::new();
+        for (y, line) in s.lines().enumerate() {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(x, c)| match c {
+                    '#' | 'O' => Ok(true),
+                    '.' => Ok(false),
+                    other => Err(GridError::InvalidCell { x, y, found: other }),
+                })
+                .collect::<Result<Vec<bool>, _>>()?;
+            rows.push(row);
+        }
+        Ok(Self::new(Grid::from_rows(rows)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(name: &str) -> Life {
+        let (_, text) = PATTERNS.iter().find(|(n, _)| *n == name).unwrap();
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn blinker_oscillates() {
+        // On a 3x3 torus every cell touches every other, so give it room
+        let mut life = Life::with_pattern(5, 5, &pattern("blinker"), 1, 1).unwrap();
+        let start = life.clone();
+        life.step();
+        assert_eq!(life.to_string(), ".....\n..#..\n..#..\n..#..\n.....");
+        life.step();
+        assert_eq!(life.cells(), start.cells());
+        assert_eq!(life.generation(), 2);
+    }
+
+    #[test]
+    fn glider_wraps_around_the_torus() {
+        // A glider moves one cell diagonally every four generations, so on
+        // an 8x8 torus it is back where it started after 32
+        let glider = pattern("glider");
+        let mut life = Life::with_pattern(8, 8, &glider, 1, 1).unwrap();
+        let start = life.clone();
+        for generation in 1..=32 {
+            life.step();
+            assert_eq!(life.population(), 5, "generation {generation}");
+        }
+        assert_eq!(life.cells(), start.cells());
+    }
+
+    #[test]
+    fn parse_errors_name_the_cell() {
+        assert_eq!(
+            "#.\n.x".parse::<Life>(),
+            Err(GridError::InvalidCell {
+                x: 1,
+                y: 1,
+                found: 'x'
+            })
+        );
+        assert!(matches!(
+            "##\n#".parse::<Life>(),
+            Err(GridError::RaggedRow { row: 1, .. })
+        ));
+    }
+}
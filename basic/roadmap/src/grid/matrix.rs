@@ -0,0 +1,273 @@
+use std::fmt;
+use std::ops::{Add, Mul};
+
+use crate::geometry::Scalar;
+
+/// An `R` by `C` matrix stored as `[[T; C]; R]`, the multidimensional
+/// array from the arrays lesson with its shape in the type.
+///
+/// Because the shape is part of the type, multiplying a 2x3 matrix by
+/// anything but a 3xK matrix is a compile error rather than a runtime
+/// check. Arithmetic uses [`Scalar`]'s checked operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Matrix<T, const R: usize, const C: usize> {
+    rows: [[T; C]; R],
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub const fn from_rows(rows: [[T; C]; R]) -> Self {
+        Self { rows }
+    }
+
+    pub const fn row_count(&self) -> usize {
+        R
+    }
+
+    pub const fn column_count(&self) -> usize {
+        C
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        self.rows.get(row)?.get(column)
+    }
+
+    pub fn get_mut(&mut self, row: usize, column: usize) -> Option<&mut T> {
+        self.rows.get_mut(row)?.get_mut(column)
+    }
+
+    /// Row `row` as a slice of length `C`.
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        self.rows.get(row).map(|r| r.as_slice())
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        self.rows.get_mut(row).map(|r| r.as_mut_slice())
+    }
+
+    pub fn rows(&self) -> &[[T; C]; R] {
+        &self.rows
+    }
+
+    pub fn into_rows(self) -> [[T; C]; R] {
+        self.rows
+    }
+
+    /// The cells of column `column`, top to bottom.
+    pub fn column(&self, column: usize) -> Option<impl ExactSizeIterator<Item = &T>> {
+        (column < C).then(|| self.rows.iter().map(move |row| &row[column]))
+    }
+}
+
+impl<T: Copy, const R: usize, const C: usize> Matrix<T, R, C> {
+    /// A `C` by `R` matrix with rows and columns swapped.
+    pub fn transpose(&self) -> Matrix<T, C, R> {
+        Matrix {
+            rows: std::array::from_fn(|c| std::array::from_fn(|r| self.rows[r][c])),
+        }
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn zero() -> Self {
+        Self {
+            rows: [[T::ZERO; C]; R],
+        }
+    }
+
+    /// `None` if any entry overflows.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let mut sum = *self;
+        for (row, other_row) in sum.rows.iter_mut().zip(&other.rows) {
+            for (cell, &other) in row.iter_mut().zip(other_row) {
+                *cell = cell.checked_add(other)?;
+            }
+        }
+        Some(sum)
+    }
+
+    /// The product with a `C` by `K` matrix, or `None` if any product or
+    /// partial sum overflows.
+    pub fn checked_mul<const K: usize>(&self, other: &Matrix<T, C, K>) -> Option<Matrix<T, R, K>> {
+        let mut product = Matrix::<T, R, K>::zero();
+        for (r, row) in self.rows.iter().enumerate() {
+            for k in 0..K {
+                let mut sum = T::ZERO;
+                for (c, &cell) in row.iter().enumerate() {
+                    sum = sum.checked_add(cell.checked_mul(other.rows[c][k])?)?;
+                }
+                product.rows[r][k] = sum;
+            }
+        }
+        Some(product)
+    }
+
+    /// `None` on overflow.
+    pub fn checked_scale(&self, factor: T) -> Option<Self> {
+        let mut scaled = *self;
+        for cell in scaled.rows.iter_mut().flatten() {
+            *cell = cell.checked_mul(factor)?;
+        }
+        Some(scaled)
+    }
+}
+
+impl<T: Scalar, const N: usize> Matrix<T, N, N> {
+    pub fn identity() -> Self {
+        let mut identity = Self::zero();
+        for (i, row) in identity.rows.iter_mut().enumerate() {
+            row[i] = T::ONE;
+        }
+        identity
+    }
+
+    /// `self` multiplied by itself `exponent` times, by repeated squaring.
+    /// `None` on overflow.
+    pub fn checked_pow(&self, mut exponent: u32) -> Option<Self> {
+        let mut result = Self::identity();
+        let mut base = *self;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.checked_mul(&base)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = base.checked_mul(&base)?;
+            }
+        }
+        Some(result)
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Default for Matrix<T, R, C> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<T, const R: usize, const C: usize> From<[[T; C]; R]> for Matrix<T, R, C> {
+    fn from(rows: [[T; C]; R]) -> Self {
+        Self { rows }
+    }
+}
+
+/// Panics on overflow, like the `+` operator in debug builds.
+impl<T: Scalar, const R: usize, const C: usize> Add for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(&other)
+            .expect("matrix addition overflowed")
+    }
+}
+
+/// Panics on overflow, like the `*` operator in debug builds.
+impl<T: Scalar, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>>
+    for Matrix<T, R, C>
+{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, other: Matrix<T, C, K>) -> Matrix<T, R, K> {
+        self.checked_mul(&other)
+            .expect("matrix multiplication overflowed")
+    }
+}
+
+/// Columns right-aligned between bracket edges:
+///
+/// ```text
+/// ⎡ 1 -2 ⎤
+/// ⎣ 3 40 ⎦
+/// ```
+impl<T: fmt::Display, const R: usize, const C: usize> fmt::Display for Matrix<T, R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(ToString::to_string).collect())
+            .collect();
+        let widths: Vec<usize> = (0..C)
+            .map(|c| {
+                text.iter()
+                    .map(|row| row[c].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        for (r, row) in text.iter().enumerate() {
+            let (open, close) = match (r, R) {
+                (_, 1) => ('[', ']'),
+                (0, _) => ('⎡', '⎤'),
+                (r, _) if r == R - 1 => ('⎣', '⎦'),
+                _ => ('⎢', '⎥'),
+            };
+            if r > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{open}")?;
+            for (cell, width) in row.iter().zip(&widths) {
+                write!(f, " {cell:>width$}")?;
+            }
+            write!(f, " {close}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiply_shapes_follow_the_types() {
+        let a = Matrix::from_rows([[1, 2, 3], [4, 5, 6]]);
+        let b = Matrix::from_rows([[7, 8], [9, 10], [11, 12]]);
+        let product: Matrix<i32, 2, 2> = a * b;
+        assert_eq!(product, Matrix::from_rows([[58, 64], [139, 154]]));
+        assert_eq!(a.transpose(), Matrix::from_rows([[1, 4], [2, 5], [3, 6]]));
+        assert_eq!(a * Matrix::identity(), a);
+    }
+
+    #[test]
+    fn checked_arithmetic_reports_overflow() {
+        let big = Matrix::from_rows([[i32::MAX, 1], [0, 1]]);
+        assert_eq!(big.checked_mul(&Matrix::identity()), Some(big));
+        assert_eq!(big.checked_mul(&big), None);
+        assert_eq!(big.checked_add(&big), None);
+        assert_eq!(big.checked_scale(2), None);
+        let fib = Matrix::from_rows([[1u64, 1], [1, 0]]);
+        assert_eq!(
+            fib.checked_pow(90).unwrap().get(0, 1),
+            Some(&2_880_067_194_370_816_120)
+        );
+        assert_eq!(fib.checked_pow(100), None);
+        assert_eq!(fib.checked_pow(0), Some(Matrix::identity()));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let mut m = Matrix::from_rows([[1, 2], [3, 4], [5, 6]]);
+        assert_eq!(m.row(1), Some(&[3, 4][..]));
+        assert_eq!(m.row(3), None);
+        assert_eq!(m.column(1).unwrap().copied().collect::<Vec<_>>(), [2, 4, 6]);
+        assert!(m.column(2).is_none());
+        m.row_mut(0).unwrap().swap(0, 1);
+        assert_eq!(m.get(0, 0), Some(&2));
+        assert_eq!(m.get(0, 2), None);
+    }
+
+    #[test]
+    fn floats_overflow_to_none() {
+        let m = Matrix::from_rows([[f64::MAX, 0.0], [0.0, 1.0]]);
+        assert_eq!(m.checked_scale(2.0), None);
+        assert_eq!(m.checked_scale(0.5).unwrap().get(1, 1), Some(&0.5));
+    }
+
+    #[test]
+    fn display() {
+        let m = Matrix::from_rows([[1, -2], [3, 40]]);
+        assert_eq!(m.to_string(), "⎡ 1 -2 ⎤\n⎣ 3 40 ⎦");
+        let three = Matrix::from_rows([[1], [2], [3]]);
+        assert_eq!(three.to_string(), "⎡ 1 ⎤\n⎢ 2 ⎥\n⎣ 3 ⎦");
+        assert_eq!(Matrix::from_rows([[1.5, 2.0]]).to_string(), "[ 1.5 2 ]");
+    }
+}
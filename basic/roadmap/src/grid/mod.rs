@@ -0,0 +1,46 @@
+//! Two-dimensional arrays: the capstone for arrays, slices and borrowing.
+//!
+//! [`Grid<T>`] has its size chosen at runtime and hands out rows as
+//! slices; [`Matrix<T, R, C>`] is a `[[T; C]; R]` with its shape checked
+//! by the compiler. [`life`] runs Conway's Game of Life on a grid.
+
+use std::fmt;
+
+mod cells;
+pub mod life;
+mod matrix;
+
+pub use cells::{Column, Grid};
+pub use life::Life;
+pub use matrix::Matrix;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// `width * height` does not fit in `usize`.
+    TooLarge,
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character that is not a cell in the text form of a board.
+    InvalidCell { x: usize, y: usize, found: char },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::TooLarge => write!(f, "grid has more cells than fit in memory"),
+            GridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {row} has {found} cells, expected {expected}"),
+            GridError::InvalidCell { x, y, found } => {
+                write!(f, "unexpected {found:?} at column {x}, row {y}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
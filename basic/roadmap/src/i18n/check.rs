@@ -0,0 +1,205 @@
+//! Compares the catalogs with the message ids the lessons use: an id a
+//! lesson prints that a catalog lacks is missing there, and an id in a
+//! catalog that no lesson prints is unused. `cargo run --bin i18n` prints
+//! both for every language.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{LANGUAGES, catalog, placeholders};
+use crate::lint;
+
+/// One `tr!("id", ...)` in a lesson.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Use {
+    pub id: String,
+    pub path: PathBuf,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// A used id the language's catalog does not define.
+    Missing { lang: &'static str, used: Use },
+    /// An id in the language's catalog that no lesson uses.
+    Unused { lang: &'static str, id: String },
+    /// A translation that uses an argument the English text does not.
+    ExtraArgument {
+        lang: &'static str,
+        id: String,
+        index: usize,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Missing { lang, used } => write!(
+                f,
+                "{}:{}: {lang}: missing `{}`",
+                used.path.display(),
+                used.line,
+                used.id
+            ),
+            Problem::Unused { lang, id } => write!(f, "{lang}: unused `{id}`"),
+            Problem::ExtraArgument { lang, id, index } => write!(
+                f,
+                "{lang}: `{id}` uses {{{index}}}, which the English text does not"
+            ),
+        }
+    }
+}
+
+/// Every `tr!` in `source`, which diagnostics call `path`. Lines that are
+/// comments are skipped, so doc examples do not count as uses.
+pub fn uses(path: &Path, source: &str) -> Vec<Use> {
+    let mut found = Vec::new();
+    let mut line = 1;
+    let mut rest = source;
+    while let Some(at) = rest.find("tr!(") {
+        let before = &rest[..at];
+        line += before.matches('\n').count();
+        let in_comment = before
+            .rsplit('\n')
+            .next()
+            .is_some_and(|start| start.trim_start().starts_with("//"));
+        // Whole words only, so `str!(` would not count
+        let in_word = before
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let args = &rest[at + "tr!(".len()..];
+        let id = args
+            .trim_start()
+            .strip_prefix('"')
+            .and_then(|args| args.split_once('"'))
+            .map(|(id, _)| id);
+        if let Some(id) = id
+            && !in_comment
+            && !in_word
+        {
+            // rustfmt may put the id on the next line
+            let id_line = line + args[..args.find('"').unwrap_or(0)].matches('\n').count();
+            found.push(Use {
+                id: id.to_string(),
+                path: path.to_path_buf(),
+                line: id_line,
+            });
+        }
+        rest = args;
+    }
+    found
+}
+
+/// The `tr!` uses in every lesson under `root/src`, with paths relative to
+/// `root`.
+pub fn lesson_uses(root: &Path) -> io::Result<Vec<Use>> {
+    let mut found = Vec::new();
+    for path in lint::lesson_files(root)? {
+        let source = fs::read_to_string(&path)?;
+        let shown = path.strip_prefix(root).unwrap_or(&path);
+        found.extend(uses(shown, &source));
+    }
+    Ok(found)
+}
+
+/// Checks the compiled-in catalogs against `used`, language by language:
+/// missing ids in the order they are used, then unused and mismatched ids
+/// sorted.
+pub fn check(used: &[Use]) -> Vec<Problem> {
+    let english = catalog(LANGUAGES[0]).expect("English has a catalog");
+    let mut problems = Vec::new();
+    for lang in LANGUAGES {
+        let Some(messages) = catalog(lang) else {
+            continue;
+        };
+        let mut reported = Vec::new();
+        for usage in used {
+            if messages.get(&usage.id).is_none() && !reported.contains(&&usage.id) {
+                reported.push(&usage.id);
+                problems.push(Problem::Missing {
+                    lang,
+                    used: usage.clone(),
+                });
+            }
+        }
+        let mut ids: Vec<&str> = messages.ids().collect();
+        ids.sort_unstable();
+        for id in ids {
+            if !used.iter().any(|usage| usage.id == id) {
+                problems.push(Problem::Unused {
+                    lang,
+                    id: id.to_string(),
+                });
+            }
+            let (Some(text), Some(reference)) = (messages.get(id), english.get(id)) else {
+                continue;
+            };
+            let known = placeholders(reference).unwrap_or_default();
+            let mut extra: Vec<usize> = placeholders(text)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|index| !known.contains(index))
+                .collect();
+            extra.dedup();
+            problems.extend(extra.into_iter().map(|index| Problem::ExtraArgument {
+                lang,
+                id: id.to_string(),
+                index,
+            }));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_uses_outside_comments() {
+        let source = "fn demonstrate_x() {\n    // tr!(\"comment.id\")\n    println!(\"{}\", tr!(\"a.b\", 1));\n    let s = str!(\"no\");\n    println!(\n        \"{}\",\n        tr!(\n            \"c.d\"\n        )\n    );\n}\n";
+        let found = uses(Path::new("x.rs"), source);
+        let found: Vec<(&str, usize)> = found
+            .iter()
+            .map(|usage| (usage.id.as_str(), usage.line))
+            .collect();
+        assert_eq!(found, [("a.b", 3), ("c.d", 8)]);
+    }
+
+    #[test]
+    fn reports_missing_ids_once_per_language() {
+        let at = |line| Use {
+            id: "nowhere.to_be_found".into(),
+            path: PathBuf::from("x.rs"),
+            line,
+        };
+        let problems = check(&[at(3), at(9)]);
+        let missing: Vec<String> = problems
+            .iter()
+            .filter(|problem| matches!(problem, Problem::Missing { .. }))
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            missing,
+            [
+                "x.rs:3: en: missing `nowhere.to_be_found`",
+                "x.rs:3: vi: missing `nowhere.to_be_found`"
+            ]
+        );
+        assert!(problems.contains(&Problem::Unused {
+            lang: "vi",
+            id: "arrays.title".into()
+        }));
+    }
+
+    #[test]
+    fn shipped_catalogs_match_the_lessons() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let problems = check(&lesson_uses(root).unwrap());
+        let shown: Vec<String> = problems.iter().map(ToString::to_string).collect();
+        assert!(shown.is_empty(), "{shown:#?}");
+    }
+}
@@ -0,0 +1,349 @@
+//! Translated lesson text.
+//!
+//! Lessons print prose through [`tr!`], which looks a message id up in the
+//! catalog of the current language and fills in positional arguments:
+//! `tr!("arrays.first", a[0])` with `arrays.first = First element: {0}`.
+//! A message missing from the current catalog falls back to English, and
+//! one missing from English prints its id. The layout around the text
+//! (indentation, blank lines, `N. ` section numbers) stays in the lesson,
+//! so catalogs hold only words.
+//!
+//! Catalogs are compiled in from `src/i18n/<lang>/<topic>.txt`, one
+//! `id = text` per line; see [`Catalog::parse`]. The language is English
+//! unless [`set_language`] picks another, which the CLI does from `--lang`
+//! or the locale environment variables (see [`detect`]).
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub mod check;
+
+/// Language codes with a catalog; English, first, is the fallback.
+pub const LANGUAGES: [&str; 2] = ["en", "vi"];
+
+/// `(language, file, contents)` of every catalog file.
+pub static SOURCES: &[(&str, &str, &str)] = &[
+    (
+        "en",
+        "src/i18n/en/syntax_and_semantics.txt",
+        include_str!("en/syntax_and_semantics.txt"),
+    ),
+    (
+        "vi",
+        "src/i18n/vi/syntax_and_semantics.txt",
+        include_str!("vi/syntax_and_semantics.txt"),
+    ),
+];
+
+/// Looks up `$id` in the current language and fills `{0}`, `{1}`, ... with
+/// the arguments, which may be anything that implements `Display`.
+#[macro_export]
+macro_rules! tr {
+    ($id:literal $(, $arg:expr)* $(,)?) => {
+        $crate::i18n::text($id, &[$(&$arg as &dyn ::std::fmt::Display),*])
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCatalogError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseCatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseCatalogError {}
+
+/// The messages of one language.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// `id = text` lines; blank lines and `#` comments are skipped. Ids are
+    /// lowercase words separated by dots. The text is trimmed and may use
+    /// `\n`, `\t` and `\\` escapes; `{N}` is argument N and `{{`/`}}` are
+    /// literal braces.
+    pub fn parse(text: &str) -> Result<Self, ParseCatalogError> {
+        let mut catalog = Self::default();
+        catalog.extend(text)?;
+        Ok(catalog)
+    }
+
+    /// Adds the messages in `text`, which may not redefine any.
+    pub fn extend(&mut self, text: &str) -> Result<(), ParseCatalogError> {
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| ParseCatalogError {
+                line: index + 1,
+                message,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (id, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected `id = text`".into()))?;
+            let id = id.trim();
+            if !is_id(id) {
+                return Err(error(format!("`{id}` is not a valid message id")));
+            }
+            let value = unescape(value.trim()).map_err(error)?;
+            placeholders(&value).map_err(error)?;
+            if self.messages.insert(id.to_string(), value).is_some() {
+                return Err(error(format!("`{id}` is defined twice")));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.messages.get(id).map(String::as_str)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.messages.keys().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+/// The compiled-in catalog of `lang`, or `None` if there is none.
+pub fn catalog(lang: &str) -> Option<&'static Catalog> {
+    static CATALOGS: OnceLock<Vec<Catalog>> = OnceLock::new();
+    let catalogs = CATALOGS.get_or_init(|| {
+        LANGUAGES
+            .iter()
+            .map(|&lang| {
+                let mut catalog = Catalog::default();
+                for &(_, file, text) in SOURCES.iter().filter(|source| source.0 == lang) {
+                    // A unit test parses every file, so this only fails in
+                    // a build whose tests fail
+                    if let Err(err) = catalog.extend(text) {
+                        panic!("{file}: {err}");
+                    }
+                }
+                catalog
+            })
+            .collect()
+    });
+    let index = LANGUAGES.iter().position(|&known| known == lang)?;
+    Some(&catalogs[index])
+}
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+/// The language [`tr!`] uses.
+pub fn language() -> &'static str {
+    LANGUAGES[CURRENT.load(Ordering::Relaxed)]
+}
+
+/// Switches [`tr!`] to `lang`; `false`, leaving the language as it was,
+/// if there is no catalog for it.
+pub fn set_language(lang: &str) -> bool {
+    match LANGUAGES.iter().position(|&known| known == lang) {
+        Some(index) => {
+            CURRENT.store(index, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// The language code in a locale name: `vi` for `vi_VN.UTF-8`, `None`
+/// for `C`, `POSIX` or an empty value.
+pub fn locale_language(locale: &str) -> Option<String> {
+    let code = locale
+        .split(['_', '.', '@', '-'])
+        .next()?
+        .to_ascii_lowercase();
+    (!code.is_empty() && code != "c" && code != "posix").then_some(code)
+}
+
+/// The language asked for by `flag` (a `--lang` value) if given, otherwise
+/// by the first of `LC_ALL`, `LC_MESSAGES` and `LANG` that is set, as
+/// POSIX orders them. The result may have no catalog.
+pub fn detect(flag: Option<&str>) -> Option<String> {
+    if let Some(flag) = flag {
+        return locale_language(flag);
+    }
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| locale_language(&value))
+}
+
+/// The message `id` in the current language with `args` filled in.
+pub fn text(id: &str, args: &[&dyn fmt::Display]) -> String {
+    let template = catalog(language())
+        .and_then(|catalog| catalog.get(id))
+        .or_else(|| catalog(LANGUAGES[0]).and_then(|catalog| catalog.get(id)));
+    match template {
+        Some(template) => format(template, args),
+        None => id.to_string(),
+    }
+}
+
+/// Fills `{N}` in `template` with `args[N]`; a placeholder without an
+/// argument is left as it is.
+pub fn format(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(at) = rest.find(['{', '}']) {
+        out.push_str(&rest[..at]);
+        let tail = &rest[at..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let argument = tail.find('}').filter(|&end| end > 0).and_then(|end| {
+            let index: usize = tail[1..end].parse().ok()?;
+            Some((args.get(index)?, end))
+        });
+        match argument {
+            Some((arg, end)) => {
+                out.push_str(&arg.to_string());
+                rest = &tail[end + 1..];
+            }
+            None => {
+                out.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The argument numbers `template` uses, or why it is malformed.
+pub fn placeholders(template: &str) -> Result<Vec<usize>, String> {
+    let mut found = Vec::new();
+    let mut rest = template;
+    while let Some(at) = rest.find(['{', '}']) {
+        let tail = &rest[at..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('}') {
+            return Err("unmatched `}`; write `}}` for a brace".into());
+        }
+        let end = tail
+            .find('}')
+            .ok_or("unclosed `{`; write `{{` for a brace")?;
+        let index = tail[1..end].parse().map_err(|_| {
+            format!(
+                "`{}` is not a positional placeholder such as `{{0}}`",
+                &tail[..=end]
+            )
+        })?;
+        found.push(index);
+        rest = &tail[end + 1..];
+    }
+    Ok(found)
+}
+
+fn is_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.split('.').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        })
+}
+
+fn unescape(value: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('\\') => '\\',
+            other => return Err(format!("unknown escape `\\{}`", other.unwrap_or(' '))),
+        });
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_formats_messages() {
+        let catalog = Catalog::parse(
+            "# arrays\narrays.first = First: {0}, then {1}\narrays.braces = {{0}} is {0}\\n\n",
+        )
+        .unwrap();
+        assert_eq!(catalog.len(), 2);
+        let first = catalog.get("arrays.first").unwrap();
+        assert_eq!(format(first, &[&1, &"two"]), "First: 1, then two");
+        assert_eq!(format(first, &[&1]), "First: 1, then {1}");
+        let braces = catalog.get("arrays.braces").unwrap();
+        assert_eq!(format(braces, &[&'x']), "{0} is x\n");
+    }
+
+    #[test]
+    fn rejects_bad_lines() {
+        let err = |text: &str| Catalog::parse(text).unwrap_err().to_string();
+        assert_eq!(err("a = 1\nno equals"), "line 2: expected `id = text`");
+        assert_eq!(
+            err("Arrays.x = 1"),
+            "line 1: `Arrays.x` is not a valid message id"
+        );
+        assert_eq!(err("a = 1\na = 2"), "line 2: `a` is defined twice");
+        assert_eq!(
+            err("a = {name}"),
+            "line 1: `{name}` is not a positional placeholder such as `{0}`"
+        );
+        assert_eq!(
+            err("a = {0"),
+            "line 1: unclosed `{`; write `{{` for a brace"
+        );
+        assert_eq!(err("a = \\q"), "line 1: unknown escape `\\q`");
+    }
+
+    #[test]
+    fn reads_locale_names() {
+        assert_eq!(locale_language("vi_VN.UTF-8").as_deref(), Some("vi"));
+        assert_eq!(locale_language("en").as_deref(), Some("en"));
+        assert_eq!(locale_language("pt-BR").as_deref(), Some("pt"));
+        assert_eq!(locale_language("C.UTF-8"), None);
+        assert_eq!(locale_language("POSIX"), None);
+        assert_eq!(locale_language(""), None);
+    }
+
+    #[test]
+    fn shipped_catalogs_parse() {
+        for &(_, file, text) in SOURCES {
+            if let Err(err) = Catalog::parse(text) {
+                panic!("{file}: {err}");
+            }
+        }
+        for lang in LANGUAGES {
+            assert!(!catalog(lang).unwrap().is_empty(), "{lang}");
+        }
+    }
+}
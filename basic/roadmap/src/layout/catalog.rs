@@ -0,0 +1,430 @@
+//! Mirrors of the lesson types. Each `report!` invocation repeats a lesson
+//! definition verbatim, so when a lesson type changes its entry here should
+//! change with it.
+
+// Names are kept exactly as the lessons spell them, acronyms included.
+#![allow(dead_code, clippy::upper_case_acronyms)]
+
+use std::mem::size_of;
+use std::num::NonZeroU32;
+use std::rc::Rc;
+
+use super::{FieldLayout, Layout, NicheExample, Shape, TypeReport, VariantLayout};
+
+// Types used as fields of other lesson types. They are declared once at
+// module level so the mirrors below can refer to them.
+
+/// `Labeled` in `constructs/impl_blocks.rs` wraps the geometry type.
+use crate::geometry::Rectangle;
+
+#[derive(Clone, Copy)]
+pub enum UsState {
+    Alabama,
+    Alaska,
+    California,
+    Texas,
+}
+
+pub enum IpAddrKind {
+    V4,
+    V6,
+}
+
+pub struct Point {
+    x: f64,
+    y: f64,
+}
+
+macro_rules! report {
+    // Named-field struct
+    ($source:literal, struct $name:ident { $($field:ident : $ty:ty),* $(,)? }) => {
+        report!(@struct $source, $name, [{ $(pub $field: $ty),* }], [$($field : $ty),*])
+    };
+    // Tuple struct; fields are written `index: type` so they can be named
+    ($source:literal, struct $name:ident ( $($field:tt : $ty:ty),* $(,)? )) => {
+        report!(@struct $source, $name, [( $(pub $ty),* );], [$($field : $ty),*])
+    };
+    // Unit struct
+    ($source:literal, struct $name:ident) => {
+        report!(@struct $source, $name, [;], [])
+    };
+    (@struct $source:literal, $name:ident, [$($def:tt)*], [$($field:tt : $ty:ty),*]) => {{
+        mod default_repr {
+            #![allow(dead_code, unused_imports)]
+            use super::*;
+            pub struct $name $($def)*
+            pub fn fields() -> Vec<FieldLayout> {
+                vec![$(FieldLayout {
+                    name: stringify!($field),
+                    ty: stringify!($ty),
+                    offset: std::mem::offset_of!($name, $field),
+                    size: size_of::<$ty>(),
+                }),*]
+            }
+        }
+        mod c_repr {
+            #![allow(dead_code, unused_imports)]
+            use super::*;
+            #[repr(C)]
+            pub struct $name $($def)*
+            pub fn fields() -> Vec<FieldLayout> {
+                vec![$(FieldLayout {
+                    name: stringify!($field),
+                    ty: stringify!($ty),
+                    offset: std::mem::offset_of!($name, $field),
+                    size: size_of::<$ty>(),
+                }),*]
+            }
+        }
+        TypeReport {
+            name: stringify!($name),
+            source: $source,
+            rust: Layout::of::<default_repr::$name>(
+                size_of::<Option<default_repr::$name>>(),
+                Shape::Struct(default_repr::fields()),
+            ),
+            c: Layout::of::<c_repr::$name>(
+                size_of::<Option<c_repr::$name>>(),
+                Shape::Struct(c_repr::fields()),
+            ),
+        }
+    }};
+    // Enum with unit, tuple or struct variants
+    ($source:literal, enum $name:ident {
+        $($variant:ident $( ( $($tty:ty),* ) )? $( { $($fname:ident : $fty:ty),* } )?),* $(,)?
+    }) => {{
+        mod default_repr {
+            #![allow(dead_code, unused_imports)]
+            use super::*;
+            pub enum $name { $($variant $( ( $($tty),* ) )? $( { $($fname: $fty),* } )?),* }
+            pub fn variants() -> Vec<VariantLayout> {
+                vec![$(VariantLayout {
+                    name: stringify!($variant),
+                    payload: size_of::<( $($($tty,)*)? $($($fty,)*)? )>(),
+                }),*]
+            }
+        }
+        mod c_repr {
+            #![allow(dead_code, unused_imports)]
+            use super::*;
+            #[repr(C)]
+            pub enum $name { $($variant $( ( $($tty),* ) )? $( { $($fname: $fty),* } )?),* }
+            // A `#[repr(C)]` enum lays out each variant's fields as a
+            // `#[repr(C)]` struct, so the payloads are measured on those
+            mod payload {
+                use super::*;
+                $(#[repr(C)] pub struct $variant( $($(pub $tty,)*)? $($(pub $fty,)*)? );)*
+            }
+            pub fn variants() -> Vec<VariantLayout> {
+                vec![$(VariantLayout {
+                    name: stringify!($variant),
+                    payload: size_of::<payload::$variant>(),
+                }),*]
+            }
+        }
+        TypeReport {
+            name: stringify!($name),
+            source: $source,
+            rust: Layout::of::<default_repr::$name>(
+                size_of::<Option<default_repr::$name>>(),
+                Shape::Enum(default_repr::variants()),
+            ),
+            c: Layout::of::<c_repr::$name>(
+                size_of::<Option<c_repr::$name>>(),
+                Shape::Enum(c_repr::variants()),
+            ),
+        }
+    }};
+}
+
+/// Every struct and enum defined in the lessons, in topic order.
+pub fn catalog() -> Vec<TypeReport> {
+    vec![
+        report!(
+            "constructs/structs.rs",
+            struct User {
+                active: bool,
+                username: String,
+                email: String,
+                sign_in_count: u64,
+            }
+        ),
+        report!("constructs/structs.rs", struct Color(0: i32, 1: i32, 2: i32)),
+        report!("constructs/structs.rs", struct Point(0: i32, 1: i32, 2: i32)),
+        report!("constructs/structs.rs", struct AlwaysEqual),
+        report!("constructs/structs.rs", struct Marker),
+        report!(
+            "constructs/enums.rs",
+            enum IpAddrKind {
+                V4,
+                V6,
+            }
+        ),
+        report!(
+            "constructs/enums.rs",
+            struct IpAddrStruct {
+                kind: IpAddrKind,
+                address: String,
+            }
+        ),
+        report!(
+            "constructs/enums.rs",
+            enum IpAddr {
+                V4(String),
+                V6(String),
+            }
+        ),
+        report!(
+            "constructs/enums.rs",
+            enum IpAddr {
+                V4(u8, u8, u8, u8),
+                V6(String),
+            }
+        ),
+        report!(
+            "constructs/enums.rs",
+            enum Message {
+                Quit,
+                Move { x: i32, y: i32 },
+                Write(String),
+                ChangeColor(i32, i32, i32),
+            }
+        ),
+        report!(
+            "constructs/impl_blocks.rs",
+            struct Rectangle {
+                width: u32,
+                height: u32,
+            }
+        ),
+        report!(
+            "constructs/impl_blocks.rs",
+            struct Labeled {
+                label: String,
+                rect: Rectangle<u32>,
+            }
+        ),
+        report!(
+            "constructs/traits.rs",
+            struct NewsArticle {
+                headline: String,
+                location: String,
+                author: String,
+                content: String,
+            }
+        ),
+        report!(
+            "constructs/traits.rs",
+            struct Tweet {
+                username: String,
+                content: String,
+                reply: bool,
+                retweet: bool,
+            }
+        ),
+        report!(
+            "constructs/traits.rs",
+            struct Article {
+                title: String,
+            }
+        ),
+        report!(
+            "constructs/traits.rs",
+            struct Article {
+                title: String,
+                content: String,
+            }
+        ),
+        // `Pair<T>` as `Pair<i32>`
+        report!(
+            "constructs/traits.rs",
+            struct Pair {
+                x: i32,
+                y: i32,
+            }
+        ),
+        report!(
+            "constructs/summary_feed.rs",
+            struct Postcard {
+                from: String,
+            }
+        ),
+        report!(
+            "data_structures/boolean.rs",
+            struct Config {
+                debug_mode: bool,
+                verbose: bool,
+                auto_save: bool,
+            }
+        ),
+        report!(
+            "syntax_and_semantics/match.rs",
+            enum Direction {
+                North,
+                South,
+                East,
+                West,
+            }
+        ),
+        report!(
+            "syntax_and_semantics/destructuring.rs",
+            enum Color {
+                Red,
+                Blue,
+                Green,
+                RGB(u8, u8, u8),
+                HSV(u8, u8, u8),
+                CMYK(u8, u8, u8, u8),
+            }
+        ),
+        report!(
+            "syntax_and_semantics/destructuring.rs",
+            enum WebEvent {
+                PageLoad,
+                PageUnload,
+                KeyPress(char),
+                Paste(String),
+                Click { x: i64, y: i64 },
+            }
+        ),
+        report!(
+            "syntax_and_semantics/destructuring.rs",
+            struct Foo {
+                x: (u32, u32),
+                y: u32,
+            }
+        ),
+        report!(
+            "syntax_and_semantics/control_flow_if_let.rs",
+            enum UsState {
+                Alabama,
+                Alaska,
+                California,
+                Texas,
+            }
+        ),
+        report!(
+            "syntax_and_semantics/control_flow_if_let.rs",
+            enum Coin {
+                Penny,
+                Nickel,
+                Dime,
+                Quarter(UsState),
+            }
+        ),
+        report!(
+            "syntax_and_semantics/patterns_and_matching.rs",
+            enum Coin {
+                Penny,
+                Nickel,
+                Dime,
+                Quarter,
+            }
+        ),
+        report!(
+            "syntax_and_semantics/patterns_and_matching.rs",
+            enum Message {
+                Quit,
+                Move { x: i32, y: i32 },
+                Write(String),
+                ChangeColor(i32, i32, i32),
+            }
+        ),
+        report!(
+            "syntax_and_semantics/patterns_and_matching.rs",
+            struct Point {
+                x: i32,
+                y: i32,
+            }
+        ),
+        report!(
+            "syntax_and_semantics/patterns_and_matching.rs",
+            struct Point3D {
+                x: i32,
+                y: i32,
+                z: i32,
+            }
+        ),
+        report!(
+            "syntax_and_semantics/patterns_and_matching.rs",
+            enum Message2 {
+                Hello { id: i32 },
+            }
+        ),
+        report!(
+            "ownership_system/box_stack_heap.rs",
+            struct Point {
+                x: f64,
+                y: f64,
+            }
+        ),
+        report!(
+            "ownership_system/box_stack_heap.rs",
+            struct Rectangle {
+                top_left: Point,
+                bottom_right: Point,
+            }
+        ),
+        report!(
+            "ownership_system/box_stack_heap.rs",
+            enum List {
+                Cons(i32, Box<List>),
+                Nil,
+            }
+        ),
+        report!("ownership_system/box_stack_heap.rs", struct Dog),
+    ]
+}
+
+macro_rules! niche {
+    ($($ty:ty),* $(,)?) => {
+        vec![$(NicheExample {
+            ty: stringify!($ty),
+            size: size_of::<$ty>(),
+            option_size: size_of::<Option<$ty>>(),
+        }),*]
+    };
+}
+
+/// Standard library types whose `Option` does or does not grow.
+pub fn niche_examples() -> Vec<NicheExample> {
+    niche![
+        Box<u64>,
+        &u8,
+        Rc<String>,
+        Vec<u8>,
+        String,
+        fn(),
+        bool,
+        char,
+        NonZeroU32,
+        u32,
+        f64,
+        (u8, bool),
+        [u32; 4],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn c_payloads_keep_declaration_order() {
+        let report = report!(
+            "test",
+            enum Mixed {
+                Empty,
+                Spread(u8, u32, u8),
+            }
+        );
+        let payloads = |layout: &Layout| match &layout.shape {
+            Shape::Enum(variants) => variants.iter().map(|v| v.payload).collect::<Vec<_>>(),
+            Shape::Struct(_) => unreachable!(),
+        };
+        // The default representation moves the u32 first; repr(C) cannot
+        assert_eq!(payloads(&report.rust), [0, 8]);
+        assert_eq!(payloads(&report.c), [0, 12]);
+        assert!(report.to_string().contains("  Spread  "), "{report}");
+    }
+}
@@ -0,0 +1,317 @@
+//! Memory layout of the types used in the lessons.
+//!
+//! The lessons define their structs and enums inside `demonstrate_*`
+//! functions, where nothing else can name them, so [`catalog`] keeps
+//! mirrors of each one. Every mirror is compiled twice, once with the
+//! default representation and once with `#[repr(C)]`, so the two can be
+//! compared side by side.
+
+use std::fmt::{self, Write};
+
+mod catalog;
+
+pub use catalog::{catalog, niche_examples};
+
+/// Cache line size assumed by the reports; true for current x86-64 and
+/// most AArch64 cores.
+pub const CACHE_LINE: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldLayout {
+    pub name: &'static str,
+    pub ty: &'static str,
+    pub offset: usize,
+    pub size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantLayout {
+    pub name: &'static str,
+    /// Size of the variant's fields laid out on their own.
+    pub payload: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shape {
+    /// Fields sorted by offset, which for the default representation is
+    /// usually not declaration order.
+    Struct(Vec<FieldLayout>),
+    Enum(Vec<VariantLayout>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pub size: usize,
+    pub align: usize,
+    /// `size_of::<Option<T>>()`.
+    pub option_size: usize,
+    pub shape: Shape,
+}
+
+impl Layout {
+    pub(crate) fn of<T>(option_size: usize, mut shape: Shape) -> Self {
+        if let Shape::Struct(fields) = &mut shape {
+            fields.sort_by_key(|f| f.offset);
+        }
+        Self {
+            size: std::mem::size_of::<T>(),
+            align: std::mem::align_of::<T>(),
+            option_size,
+            shape,
+        }
+    }
+
+    /// Bytes not covered by any field. For an enum this is everything
+    /// beyond the largest payload, so the discriminant is counted too; the
+    /// default representation does not say how much of it is tag, and may
+    /// hide the tag in a payload's niche.
+    pub fn padding(&self) -> usize {
+        let used = match &self.shape {
+            Shape::Struct(fields) => fields.iter().map(|f| f.size).sum(),
+            Shape::Enum(variants) => variants.iter().map(|v| v.payload).max().unwrap_or(0),
+        };
+        self.size - used
+    }
+
+    /// Whether `Option<T>` is no larger than `T`, i.e. `None` is stored in
+    /// a bit pattern `T` never uses, such as a null pointer or an unused
+    /// enum discriminant.
+    pub fn has_niche(&self) -> bool {
+        self.option_size == self.size
+    }
+
+    pub fn cache_behaviour(&self) -> String {
+        match self.size {
+            0 => "zero-sized".to_string(),
+            size if size <= CACHE_LINE => format!("{} per line", CACHE_LINE / size),
+            size => format!("spans {} lines", size.div_ceil(CACHE_LINE)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeReport {
+    pub name: &'static str,
+    /// Lesson file the type comes from, relative to `src/`.
+    pub source: &'static str,
+    pub rust: Layout,
+    pub c: Layout,
+}
+
+impl TypeReport {
+    pub fn kind(&self) -> &'static str {
+        match self.rust.shape {
+            Shape::Struct(_) => "struct",
+            Shape::Enum(_) => "enum",
+        }
+    }
+}
+
+/// Renders one report as a two-column table: default layout on the left,
+/// `#[repr(C)]` on the right.
+impl fmt::Display for TypeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (rust, c) = (&self.rust, &self.c);
+        writeln!(f, "{} {} ({})", self.kind(), self.name, self.source)?;
+        writeln!(f, "  {:<24}{:>14}{:>14}", "", "default", "#[repr(C)]")?;
+        row(
+            f,
+            "size / align",
+            format!("{} / {}", rust.size, rust.align),
+            format!("{} / {}", c.size, c.align),
+        )?;
+        let padding = match rust.shape {
+            Shape::Struct(_) => "padding bytes",
+            Shape::Enum(_) => "tag + padding bytes",
+        };
+        row(f, padding, rust.padding(), c.padding())?;
+        row(f, "Option<T> size", option(rust), option(c))?;
+        row(
+            f,
+            "64 B cache line",
+            rust.cache_behaviour(),
+            c.cache_behaviour(),
+        )?;
+
+        match (&rust.shape, &c.shape) {
+            (Shape::Struct(rust_fields), Shape::Struct(c_fields)) if !rust_fields.is_empty() => {
+                writeln!(
+                    f,
+                    "  {:<24}{:>14}{:>14}",
+                    "field (size)", "offset", "offset"
+                )?;
+                for field in c_fields {
+                    let rust_offset = rust_fields
+                        .iter()
+                        .find(|r| r.name == field.name)
+                        .map_or("-".to_string(), |r| r.offset.to_string());
+                    let label = format!("{}: {} ({})", field.name, field.ty, field.size);
+                    row(f, &label, rust_offset, field.offset)?;
+                }
+            }
+            (Shape::Enum(rust_variants), Shape::Enum(c_variants)) => {
+                writeln!(f, "  {:<24}{:>14}{:>14}", "variant", "payload", "payload")?;
+                for variant in c_variants {
+                    let rust_payload = rust_variants
+                        .iter()
+                        .find(|r| r.name == variant.name)
+                        .map_or("-".to_string(), |r| r.payload.to_string());
+                    row(f, variant.name, rust_payload, variant.payload)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+fn option(layout: &Layout) -> String {
+    let mut text = layout.option_size.to_string();
+    if layout.has_niche() {
+        text.push_str(" (niche)");
+    }
+    text
+}
+
+fn row(
+    f: &mut fmt::Formatter<'_>,
+    label: &str,
+    rust: impl fmt::Display,
+    c: impl fmt::Display,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "  {:<24}{:>14}{:>14}",
+        label,
+        rust.to_string(),
+        c.to_string()
+    )
+}
+
+/// `size_of::<T>()` next to `size_of::<Option<T>>()` for a type that is
+/// not from a lesson, used to show which types have a niche.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NicheExample {
+    pub ty: &'static str,
+    pub size: usize,
+    pub option_size: usize,
+}
+
+pub fn niche_table(examples: &[NicheExample]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "  {:<22}{:>8}{:>12}", "T", "size", "Option<T>");
+    for example in examples {
+        let note = if example.size == example.option_size {
+            "  niche"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "  {:<22}{:>8}{:>12}{}",
+            example.ty, example.size, example.option_size, note
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+    use crate::lint::{self, scan};
+
+    #[test]
+    fn every_lesson_type_has_a_report() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let reports = catalog();
+        let mut missing = Vec::new();
+        for path in lint::lesson_files(root).unwrap() {
+            let source = fs::read_to_string(&path).unwrap();
+            let relative = path.strip_prefix(root.join("src")).unwrap();
+            let relative = relative.to_str().unwrap().replace('\\', "/");
+            let tokens = scan::tokenize(&scan::mask(&source));
+            for pair in tokens.windows(2) {
+                let (scan::Token::Word(keyword), scan::Token::Word(name)) =
+                    (&pair[0].1, &pair[1].1)
+                else {
+                    continue;
+                };
+                if (keyword == "struct" || keyword == "enum")
+                    && !reports
+                        .iter()
+                        .any(|r| r.name == name && r.source == relative)
+                {
+                    missing.push(format!("{relative}:{}: {keyword} {name}", pair[0].0));
+                }
+            }
+        }
+        assert!(
+            missing.is_empty(),
+            "no report! entry for\n{}",
+            missing.join("\n")
+        );
+    }
+
+    #[test]
+    fn struct_layouts() {
+        let reports = catalog();
+        let find = |name, source| {
+            reports
+                .iter()
+                .find(|r| r.name == name && r.source == source)
+                .unwrap()
+        };
+        // bool, String, String, u64: the default layout moves the bool last
+        let user = find("User", "constructs/structs.rs");
+        assert_eq!((user.rust.size, user.c.size), (64, 64));
+        assert_eq!(user.rust.padding(), 7);
+        assert!(user.rust.has_niche());
+        assert_eq!(user.rust.cache_behaviour(), "1 per line");
+
+        let dog = find("Dog", "ownership_system/box_stack_heap.rs");
+        assert!(dog.to_string().contains("  padding bytes "));
+        assert_eq!((dog.rust.size, dog.rust.option_size), (0, 1));
+        assert_eq!(dog.rust.cache_behaviour(), "zero-sized");
+    }
+
+    #[test]
+    fn enum_tags_are_not_called_padding() {
+        let reports = catalog();
+        let coin = reports
+            .iter()
+            .find(|r| r.name == "Coin" && r.source.ends_with("control_flow_if_let.rs"))
+            .unwrap();
+        // A 4-byte C tag, the 1-byte UsState payload and 3 bytes of padding
+        assert_eq!(coin.c.size, 8);
+        assert_eq!(coin.c.padding(), 7);
+        let text = coin.to_string();
+        assert!(text.contains("tag + padding bytes"), "{text}");
+        assert!(!text.contains("  padding bytes"), "{text}");
+    }
+
+    #[test]
+    fn missing_default_offsets_are_not_shown_as_zero() {
+        let field = |name, offset| FieldLayout {
+            name,
+            ty: "u32",
+            offset,
+            size: 4,
+        };
+        let layout = |fields| Layout::of::<[u32; 2]>(12, Shape::Struct(fields));
+        let report = TypeReport {
+            name: "Pair",
+            source: "test",
+            rust: layout(vec![field("a", 0)]),
+            c: layout(vec![field("a", 0), field("b", 4)]),
+        };
+        let text = report.to_string();
+        let row = text.lines().find(|line| line.contains("b: u32")).unwrap();
+        assert_eq!(
+            row.split_whitespace().collect::<Vec<_>>(),
+            ["b:", "u32", "(4)", "-", "4"]
+        );
+    }
+}
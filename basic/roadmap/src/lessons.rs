@@ -0,0 +1,170 @@
+//! Registry of the lessons that are compiled into the crate and can be run
+//! with `roadmap run <topic>::<lesson>`.
+
+use crate::{constructs, ownership_system, syntax_and_semantics};
+
+pub struct Lesson {
+    pub id: &'static str,
+    pub title: &'static str,
+    /// Estimated time to work through the lesson, in minutes.
+    pub minutes: u16,
+    pub tags: &'static [&'static str],
+    /// Ids of lessons to finish first. `crate::plan` checks that they
+    /// exist and form no cycle.
+    pub requires: &'static [&'static str],
+    pub run: fn(),
+    /// The lesson's `demonstrate_*` functions, for lessons that list them.
+    /// Runs that measure each section (such as `--allocs`) need this.
+    pub sections: &'static [Section],
+}
+
+impl Lesson {
+    pub fn section(&self, name: &str) -> Option<&'static Section> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+}
+
+/// One `demonstrate_*` function of a lesson.
+pub struct Section {
+    pub name: &'static str,
+    pub run: fn(),
+}
+
+pub static LESSONS: &[Lesson] = &[
+    Lesson {
+        id: "constructs::structs",
+        title: "Defining and Using Structs",
+        minutes: 25,
+        tags: &["structs", "types"],
+        requires: &["ownership_system::references_borrowing_slices"],
+        run: constructs::structs::main,
+        sections: &[],
+    },
+    Lesson {
+        id: "constructs::enums",
+        title: "Enums and Pattern Matching",
+        minutes: 25,
+        tags: &["enums", "match"],
+        requires: &["constructs::structs"],
+        run: constructs::enums::main,
+        sections: &[],
+    },
+    Lesson {
+        id: "constructs::impl_blocks",
+        title: "Methods and impl Blocks",
+        minutes: 20,
+        tags: &["methods", "structs"],
+        requires: &["constructs::structs"],
+        run: constructs::impl_blocks::main,
+        sections: &[],
+    },
+    Lesson {
+        id: "constructs::rectangle_packing",
+        title: "Capstone: Packing Rectangles",
+        minutes: 30,
+        tags: &["capstone", "borrowing", "generics"],
+        requires: &[
+            "constructs::impl_blocks",
+            "ownership_system::references_borrowing_slices",
+        ],
+        run: constructs::rectangle_packing::main,
+        sections: &[],
+    },
+    Lesson {
+        id: "constructs::traits",
+        title: "Traits and Trait Bounds",
+        minutes: 35,
+        tags: &["traits", "generics"],
+        requires: &["constructs::enums", "constructs::impl_blocks"],
+        run: constructs::traits::main,
+        sections: &[],
+    },
+    Lesson {
+        id: "constructs::summary_feed",
+        title: "Capstone: A Feed of Summaries",
+        minutes: 30,
+        tags: &["capstone", "traits", "trait-objects"],
+        requires: &["constructs::traits"],
+        run: constructs::summary_feed::main,
+        sections: &[],
+    },
+    Lesson {
+        id: "ownership_system::box_stack_heap",
+        title: "Box, the Stack and the Heap",
+        minutes: 25,
+        tags: &["memory", "box", "smart-pointers"],
+        requires: &["ownership_system::ownership_rules_and_memory_safety"],
+        run: ownership_system::box_stack_heap::main,
+        sections: ownership_system::box_stack_heap::SECTIONS,
+    },
+    Lesson {
+        id: "ownership_system::ownership_rules_and_memory_safety",
+        title: "Ownership Rules and Memory Safety",
+        minutes: 30,
+        tags: &["ownership", "memory"],
+        requires: &["syntax_and_semantics::arrays"],
+        run: ownership_system::ownership_rules_and_memory_safety::main,
+        sections: ownership_system::ownership_rules_and_memory_safety::SECTIONS,
+    },
+    Lesson {
+        id: "ownership_system::references_borrowing_slices",
+        title: "References, Borrowing and Slices",
+        minutes: 30,
+        tags: &["borrowing", "slices"],
+        requires: &["ownership_system::ownership_rules_and_memory_safety"],
+        run: ownership_system::references_borrowing_slices::main,
+        sections: ownership_system::references_borrowing_slices::SECTIONS,
+    },
+    Lesson {
+        id: "ownership_system::grids_and_matrices",
+        title: "Capstone: Grids and Matrices",
+        minutes: 35,
+        tags: &["capstone", "arrays", "slices", "const-generics"],
+        requires: &[
+            "ownership_system::references_borrowing_slices",
+            "constructs::impl_blocks",
+        ],
+        run: ownership_system::grids_and_matrices::main,
+        sections: ownership_system::grids_and_matrices::SECTIONS,
+    },
+    Lesson {
+        id: "syntax_and_semantics::arrays",
+        title: "Arrays",
+        minutes: 15,
+        tags: &["arrays", "types"],
+        requires: &[],
+        run: syntax_and_semantics::arrays::main,
+        sections: &[],
+    },
+    Lesson {
+        id: "syntax_and_semantics::destructuring",
+        title: "Destructuring Patterns",
+        minutes: 20,
+        tags: &["patterns", "match"],
+        requires: &["constructs::enums"],
+        run: syntax_and_semantics::destructuring::main,
+        sections: &[],
+    },
+    Lesson {
+        id: "syntax_and_semantics::numeric_operations",
+        title: "Numeric Operations",
+        minutes: 15,
+        tags: &["numbers", "arithmetic"],
+        requires: &[],
+        run: syntax_and_semantics::numeric_operations::main,
+        sections: &[],
+    },
+    Lesson {
+        id: "syntax_and_semantics::integer_overflow",
+        title: "Integer Overflow",
+        minutes: 15,
+        tags: &["numbers", "overflow"],
+        requires: &["syntax_and_semantics::numeric_operations"],
+        run: syntax_and_semantics::integer_overflow::main,
+        sections: &[],
+    },
+];
+
+pub fn find(id: &str) -> Option<&'static Lesson> {
+    LESSONS.iter().find(|lesson| lesson.id == id)
+}
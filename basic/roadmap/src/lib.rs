@@ -0,0 +1,30 @@
+pub mod alloc_tracker;
+pub mod calendar;
+pub mod challenge;
+pub mod collections;
+pub mod color;
+pub mod commands;
+pub mod constructs;
+pub mod events;
+pub mod feed;
+pub mod geometry;
+pub mod grid;
+pub mod hash;
+pub mod i18n;
+pub mod layout;
+pub mod lessons;
+pub mod lint;
+pub mod net;
+pub mod ordered;
+pub mod ownership_system;
+pub mod packing;
+pub mod packs;
+pub mod plan;
+pub mod progress;
+pub mod render;
+pub mod review;
+pub mod runner;
+pub mod scaffold;
+pub mod syntax_and_semantics;
+pub mod vending;
+pub mod web;
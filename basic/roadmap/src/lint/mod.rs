@@ -0,0 +1,457 @@
+//! Checks lesson files against the conventions in
+//! `.kiro/steering/structure.md`.
+//!
+//! A lesson file has a `main` that calls `demonstrate_*` functions, each
+//! call preceded by a `// N. Title` comment, and each of those functions
+//! prints a matching `N. Title` header, by hand or with `render::section`.
+//! The checks read source text only; nothing is compiled, so they also
+//! cover files that are not yet part of the crate. `cargo run --bin lint`
+//! runs them over every lesson topic.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub(crate) mod scan;
+
+use scan::{Function, ItemKind, Outline};
+
+/// Directories under `src/` that hold lessons, one per topic.
+pub const TOPICS: [&str; 4] = [
+    "syntax_and_semantics",
+    "data_structures",
+    "ownership_system",
+    "constructs",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rule {
+    MissingMain,
+    /// A `demonstrate_*` function that `main` never calls.
+    UncalledDemo,
+    /// `main` calls a function of the file not named `demonstrate_*`.
+    DemoName,
+    /// Section comments that skip a number, or disagree with the header
+    /// the section prints.
+    Numbering,
+    /// Functions not in `snake_case`, types not in `PascalCase`, constants
+    /// not in `SCREAMING_SNAKE_CASE`.
+    Naming,
+    /// A lesson file that is not in `LESSONS`.
+    Unregistered,
+}
+
+impl Rule {
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::MissingMain => "missing-main",
+            Rule::UncalledDemo => "uncalled-demo",
+            Rule::DemoName => "demo-name",
+            Rule::Numbering => "numbering",
+            Rule::Naming => "naming",
+            Rule::Unregistered => "unregistered",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub path: PathBuf,
+    pub line: usize,
+    pub rule: Rule,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.path.display(),
+            self.line,
+            self.rule,
+            self.message
+        )
+    }
+}
+
+/// The id a lesson file would be registered under, `topic::stem`, or
+/// `None` if the path is not a lesson file in one of the [`TOPICS`].
+pub fn lesson_id(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let topic = path.parent()?.file_name()?.to_str()?;
+    (TOPICS.contains(&topic) && stem != "mod" && path.extension()? == "rs")
+        .then(|| format!("{topic}::{stem}"))
+}
+
+/// Every lesson file under `root/src`, sorted.
+pub fn lesson_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for topic in TOPICS {
+        let dir = root.join("src").join(topic);
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if lesson_id(&path).is_some() {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Runs every rule on one file. `registered` are the ids in `LESSONS`;
+/// diagnostics name the file as `path`.
+pub fn check_file(path: &Path, source: &str, registered: &[&str]) -> Vec<Diagnostic> {
+    let mut diagnostics = check_source(path, source);
+    if let Some(id) = lesson_id(path)
+        && !registered.contains(&id.as_str())
+    {
+        diagnostics.push(Diagnostic {
+            path: path.to_path_buf(),
+            line: 1,
+            rule: Rule::Unregistered,
+            message: format!("`{id}` is not in LESSONS (src/lessons.rs)"),
+        });
+    }
+    diagnostics
+}
+
+/// The rules that need only the file's text, sorted by line.
+pub fn check_source(path: &Path, source: &str) -> Vec<Diagnostic> {
+    let outline = scan::outline(source);
+    let lines: Vec<&str> = source.lines().collect();
+    let mut found = Vec::new();
+    let mut report = |line: usize, rule: Rule, message: String| {
+        found.push(Diagnostic {
+            path: path.to_path_buf(),
+            line,
+            rule,
+            message,
+        });
+    };
+
+    check_naming(&outline, &mut report);
+    match outline.function("main") {
+        Some(main) => {
+            check_calls(&outline, main, &mut report);
+            check_numbering(&outline, main, &lines, &mut report);
+        }
+        None => report(
+            1,
+            Rule::MissingMain,
+            "no `main`; lessons start from `pub fn main()`".into(),
+        ),
+    }
+
+    found.sort_by_key(|d| (d.line, d.rule));
+    found
+}
+
+fn check_naming(outline: &Outline, report: &mut impl FnMut(usize, Rule, String)) {
+    for item in &outline.items {
+        let (ok, case) = match item.kind {
+            ItemKind::Fn => (is_snake_case(&item.name), "snake_case"),
+            ItemKind::Type => (is_pascal_case(&item.name), "PascalCase"),
+            ItemKind::Const => (is_screaming_snake_case(&item.name), "SCREAMING_SNAKE_CASE"),
+        };
+        if !ok {
+            report(
+                item.line,
+                Rule::Naming,
+                format!("`{}` should be {case}", item.name),
+            );
+        }
+    }
+}
+
+fn check_calls(outline: &Outline, main: &Function, report: &mut impl FnMut(usize, Rule, String)) {
+    let called = |name: &str| main.calls.iter().any(|(callee, _)| callee == name);
+    for function in &outline.functions {
+        if function.name.starts_with("demonstrate_") && !called(&function.name) {
+            report(
+                function.line,
+                Rule::UncalledDemo,
+                format!("`{}` is never called from `main`", function.name),
+            );
+        }
+    }
+    for (callee, line) in &main.calls {
+        if !callee.starts_with("demonstrate_") && outline.function(callee).is_some() {
+            report(
+                *line,
+                Rule::DemoName,
+                format!("`main` calls `{callee}`; name sections `demonstrate_*`"),
+            );
+        }
+    }
+}
+
+fn check_numbering(
+    outline: &Outline,
+    main: &Function,
+    lines: &[&str],
+    report: &mut impl FnMut(usize, Rule, String),
+) {
+    let (first, last) = main.body;
+    let comments: Vec<(usize, u32)> = (first..=last)
+        .filter_map(|line| Some((line, section_comment(lines.get(line - 1)?)?)))
+        .collect();
+
+    let mut expected = 1;
+    for (index, &(line, number)) in comments.iter().enumerate() {
+        if number != expected {
+            let message = if index == 0 {
+                format!("first section is numbered {number}, not 1")
+            } else {
+                format!("section {number} follows section {}", expected - 1)
+            };
+            report(line, Rule::Numbering, message);
+        }
+        expected = number + 1;
+
+        // The section is the first function of the file called after the
+        // comment and before the next one
+        let end = comments.get(index + 1).map_or(last, |&(next, _)| next);
+        let Some(section) = main
+            .calls
+            .iter()
+            .filter(|&&(_, at)| at > line && at < end)
+            .find_map(|(callee, _)| outline.function(callee))
+        else {
+            continue;
+        };
+        match printed_header(section, lines) {
+            Some((_, printed)) if printed == number => {}
+            Some((at, printed)) => report(
+                at,
+                Rule::Numbering,
+                format!(
+                    "`{}` prints header {printed}, but `main` numbers it {number}",
+                    section.name
+                ),
+            ),
+            None => report(
+                section.line,
+                Rule::Numbering,
+                format!(
+                    "`{}` prints no `{number}. ` header for its section",
+                    section.name
+                ),
+            ),
+        }
+    }
+}
+
+/// `N` from a `// N. Title` comment line.
+fn section_comment(line: &str) -> Option<u32> {
+    leading_number(line.trim_start().strip_prefix("//")?.trim_start())
+}
+
+/// The first numbered header the function prints, and its line: a
+/// `println!` literal starting `N. ` or a `render::section(N, ...)`.
+fn printed_header(function: &Function, lines: &[&str]) -> Option<(usize, u32)> {
+    let (first, last) = function.body;
+    (first..=last).find_map(|line| {
+        let text = lines.get(line - 1)?;
+        if let Some((_, args)) = text.split_once("section(")
+            && let Some((number, _)) = args.split_once(',')
+        {
+            return Some((line, number.trim().parse().ok()?));
+        }
+        let literal = text.split_once("println!(\"")?.1;
+        let mut literal = literal;
+        while let Some(rest) = literal.strip_prefix("\\n") {
+            literal = rest;
+        }
+        // Allow `=== 1. Title ===` and `--- 1. Title ---` banners
+        let literal = literal.trim_start_matches(['=', '-']).trim_start();
+        Some((line, leading_number(literal)?))
+    })
+}
+
+/// `N` from text starting with `N. `.
+fn leading_number(text: &str) -> Option<u32> {
+    let (number, rest) = text.split_once('.')?;
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) || !rest.starts_with(' ') {
+        return None;
+    }
+    number.parse().ok()
+}
+
+fn is_snake_case(name: &str) -> bool {
+    let name = name.trim_start_matches('_');
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase()) && !name.contains('_')
+}
+
+fn is_screaming_snake_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(source: &str) -> Vec<String> {
+        check_file(
+            Path::new("src/constructs/sample.rs"),
+            source,
+            &["constructs::sample"],
+        )
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+    }
+
+    const CLEAN: &str = "\
+pub fn main() {
+    println!(\"=== Sample ===\\n\");
+
+    // 1. First
+    demonstrate_first();
+
+    // 2. Second
+    demonstrate_second();
+}
+
+fn demonstrate_first() {
+    println!(\"1. First\");
+    helper();
+}
+
+fn demonstrate_second() {
+    println!(\"\\n=== 2. Second ===\");
+    const LIMIT: u8 = 3;
+    struct Point;
+}
+
+fn helper() {}
+";
+
+    #[test]
+    fn conventional_lesson_is_clean() {
+        assert_eq!(lint(CLEAN), Vec::<String>::new());
+    }
+
+    #[test]
+    fn reports_each_rule_with_file_and_line() {
+        let source = "\
+pub fn main() {
+    // 1. First
+    demonstrate_first();
+
+    // 3. Third
+    basic_third();
+}
+
+fn demonstrate_first() {
+    println!(\"2. First\");
+}
+
+fn demonstrate_unused() {}
+
+fn basic_third() {
+    println!(\"3. Third\");
+    struct bad_name;
+}
+
+fn camelCase() {}
+";
+        assert_eq!(
+            lint(source),
+            [
+                "src/constructs/sample.rs:5: numbering: section 3 follows section 1",
+                "src/constructs/sample.rs:6: demo-name: `main` calls `basic_third`; name sections `demonstrate_*`",
+                "src/constructs/sample.rs:10: numbering: `demonstrate_first` prints header 2, but `main` numbers it 1",
+                "src/constructs/sample.rs:13: uncalled-demo: `demonstrate_unused` is never called from `main`",
+                "src/constructs/sample.rs:17: naming: `bad_name` should be PascalCase",
+                "src/constructs/sample.rs:20: naming: `camelCase` should be snake_case",
+            ]
+        );
+    }
+
+    #[test]
+    fn section_calls_are_headers() {
+        let first = |call: &str| CLEAN.replace("println!(\"1. First\");", call);
+        assert_eq!(
+            lint(&first("render::section(1, tr!(\"sample.first\"));")),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            lint(&first("render::section(3, \"First\");")),
+            [
+                "src/constructs/sample.rs:12: numbering: `demonstrate_first` prints header 3, but `main` numbers it 1"
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_header_main_and_registration() {
+        let source = CLEAN.replace("println!(\"1. First\");", "println!(\"First\");");
+        assert_eq!(
+            lint(&source),
+            [
+                "src/constructs/sample.rs:11: numbering: `demonstrate_first` prints no `1. ` header for its section"
+            ]
+        );
+
+        let diagnostics = check_file(
+            Path::new("src/data_structures/tuple.rs"),
+            "fn basic_tuples() {}",
+            &[],
+        );
+        let rules: Vec<_> = diagnostics.iter().map(|d| (d.line, d.rule)).collect();
+        assert_eq!(rules, [(1, Rule::MissingMain), (1, Rule::Unregistered)]);
+        assert_eq!(
+            diagnostics[1].message,
+            "`data_structures::tuple` is not in LESSONS (src/lessons.rs)"
+        );
+    }
+
+    #[test]
+    fn registered_lessons_follow_conventions() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let registered: Vec<&str> = crate::lessons::LESSONS.iter().map(|l| l.id).collect();
+        for id in &registered {
+            let (topic, stem) = id.split_once("::").unwrap();
+            let path = Path::new("src").join(topic).join(format!("{stem}.rs"));
+            let source = fs::read_to_string(root.join(&path)).unwrap();
+            let diagnostics: Vec<String> = check_file(&path, &source, &registered)
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            assert_eq!(diagnostics, Vec::<String>::new());
+        }
+    }
+
+    #[test]
+    fn lesson_ids_come_from_topic_directories() {
+        assert_eq!(
+            lesson_id(Path::new("src/constructs/traits.rs")).as_deref(),
+            Some("constructs::traits")
+        );
+        assert_eq!(lesson_id(Path::new("src/constructs/mod.rs")), None);
+        assert_eq!(lesson_id(Path::new("src/feed/items.rs")), None);
+        assert_eq!(lesson_id(Path::new("src/constructs/README.md")), None);
+    }
+}
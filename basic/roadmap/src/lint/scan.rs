@@ -0,0 +1,353 @@
+//! Just enough of a Rust lexer to find items and calls in lesson files.
+//!
+//! Comments and literals are blanked out first, so braces and names inside
+//! them are never mistaken for code. Line numbers are kept.
+
+/// `source` with every comment and string, byte string and char literal
+/// replaced by spaces. Newlines stay where they are.
+pub(crate) fn mask(source: &str) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let mut out = String::with_capacity(source.len());
+    let blank = |out: &mut String, c: char| out.push(if c == '\n' { '\n' } else { ' ' });
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let after_ident = i > 0 && is_ident_char(chars[i - 1]);
+
+        if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                blank(&mut out, chars[i]);
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            // Block comments nest in Rust
+            let mut depth = 0;
+            while i < chars.len() {
+                if chars[i] == '/' && chars.get(i + 1) == Some(&'*') {
+                    depth += 1;
+                    out.push_str("  ");
+                    i += 2;
+                } else if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                    depth -= 1;
+                    out.push_str("  ");
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    blank(&mut out, chars[i]);
+                    i += 1;
+                }
+            }
+        } else if let Some(hashes) = raw_string_start(&chars, i).filter(|_| !after_ident) {
+            // r"..", r#".."#, br".."; the closing quote needs as many `#`s
+            let body = i + chars[i..].iter().position(|&c| c == '"').unwrap_or(0) + 1;
+            let close = (body..chars.len())
+                .find(|&j| {
+                    chars[j] == '"'
+                        && chars[j + 1..]
+                            .iter()
+                            .take(hashes)
+                            .filter(|&&c| c == '#')
+                            .count()
+                            == hashes
+                })
+                .map_or(chars.len(), |j| j + 1 + hashes);
+            for &c in &chars[i..close] {
+                blank(&mut out, c);
+            }
+            i = close;
+        } else if c == '"' {
+            blank(&mut out, c);
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' {
+                    blank(&mut out, chars[i]);
+                    i += 1;
+                }
+                if i < chars.len() {
+                    blank(&mut out, chars[i]);
+                    i += 1;
+                }
+            }
+            if i < chars.len() {
+                blank(&mut out, chars[i]);
+                i += 1;
+            }
+        } else if c == '\''
+            && let Some(len) = char_literal_len(&chars[i..])
+        {
+            for &c in &chars[i..i + len] {
+                blank(&mut out, c);
+            }
+            i += len;
+        } else {
+            // Lifetimes and labels (`'a`) fall through to here as code
+            out.push(c);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// The number of `#`s if a raw string literal starts at `i`.
+fn raw_string_start(chars: &[char], i: usize) -> Option<usize> {
+    let mut j = i;
+    if chars.get(j) == Some(&'b') {
+        j += 1;
+    }
+    if chars.get(j) != Some(&'r') {
+        return None;
+    }
+    j += 1;
+    let hashes = chars[j..].iter().take_while(|&&c| c == '#').count();
+    (chars.get(j + hashes) == Some(&'"')).then_some(hashes)
+}
+
+/// Length of a char literal such as `'a'` or `'\u{1F980}'` at the start of
+/// `chars`, or `None` for a lifetime.
+fn char_literal_len(chars: &[char]) -> Option<usize> {
+    match chars {
+        // The escaped char itself may be a quote, as in '\''
+        ['\'', '\\', _, rest @ ..] => rest
+            .iter()
+            .take(10)
+            .position(|&c| c == '\'')
+            .map(|at| at + 4),
+        ['\'', c, '\'', ..] if *c != '\'' => Some(3),
+        _ => None,
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Token {
+    /// Identifiers, keywords and number literals.
+    Word(String),
+    Punct(char),
+}
+
+/// Tokens of masked source, each with its 1-based line.
+pub(crate) fn tokenize(masked: &str) -> Vec<(usize, Token)> {
+    let mut tokens = Vec::new();
+    for (index, line) in masked.lines().enumerate() {
+        let mut chars = line.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            if is_ident_char(c) {
+                let mut end = start + c.len_utf8();
+                while let Some(&(at, c)) = chars.peek() {
+                    if !is_ident_char(c) {
+                        break;
+                    }
+                    end = at + c.len_utf8();
+                    chars.next();
+                }
+                tokens.push((index + 1, Token::Word(line[start..end].to_string())));
+            } else if !c.is_whitespace() {
+                tokens.push((index + 1, Token::Punct(c)));
+            }
+        }
+    }
+    tokens
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ItemKind {
+    Fn,
+    /// `struct`, `enum`, `trait` or `type`.
+    Type,
+    /// `const` or `static`.
+    Const,
+}
+
+/// A named item at any nesting depth.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Item {
+    pub kind: ItemKind,
+    pub name: String,
+    pub line: usize,
+}
+
+/// A function defined at the top level of the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Function {
+    pub name: String,
+    pub line: usize,
+    /// First and last line of the body, braces included.
+    pub body: (usize, usize),
+    /// Free functions called in the body, in order, with their lines.
+    /// Method calls (`x.f()`) and macros are left out.
+    pub calls: Vec<(String, usize)>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Outline {
+    pub items: Vec<Item>,
+    pub functions: Vec<Function>,
+}
+
+impl Outline {
+    pub fn function(&self, name: &str) -> Option<&Function> {
+        self.functions.iter().find(|f| f.name == name)
+    }
+}
+
+pub(crate) fn outline(source: &str) -> Outline {
+    let tokens = tokenize(&mask(source));
+    let word = |i: usize| match tokens.get(i) {
+        Some((_, Token::Word(w))) => Some(w.as_str()),
+        _ => None,
+    };
+    let punct = |i: usize, p: char| matches!(tokens.get(i), Some((_, Token::Punct(c))) if *c == p);
+
+    let mut outline = Outline::default();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < tokens.len() {
+        if punct(i, '{') {
+            depth += 1;
+        } else if punct(i, '}') {
+            depth = depth.saturating_sub(1);
+        }
+        let kind = match word(i) {
+            Some("fn") => Some(ItemKind::Fn),
+            Some("struct" | "enum" | "trait" | "type") => Some(ItemKind::Type),
+            Some("const" | "static") if word(i + 1) != Some("fn") => Some(ItemKind::Const),
+            _ => None,
+        };
+        // `static mut X`, and skip `fn(` pointer types and `impl Trait`
+        let name_at = if word(i + 1) == Some("mut") {
+            i + 2
+        } else {
+            i + 1
+        };
+        if let (Some(kind), Some(name)) = (kind, word(name_at))
+            && name != "_"
+        {
+            let line = tokens[i].0;
+            outline.items.push(Item {
+                kind,
+                name: name.to_string(),
+                line,
+            });
+            if kind == ItemKind::Fn
+                && depth == 0
+                && let Some(function) = function_body(&tokens, name_at, name, line)
+            {
+                outline.functions.push(function);
+            }
+        }
+        i += 1;
+    }
+    outline
+}
+
+/// The body of the top-level function whose name is at `tokens[at]`, or
+/// `None` for a declaration without one.
+fn function_body(
+    tokens: &[(usize, Token)],
+    at: usize,
+    name: &str,
+    line: usize,
+) -> Option<Function> {
+    let open = (at..tokens.len())
+        .take_while(|&i| tokens[i].1 != Token::Punct(';'))
+        .find(|&i| tokens[i].1 == Token::Punct('{'))?;
+
+    let mut depth = 0;
+    let mut calls = Vec::new();
+    for i in open..tokens.len() {
+        match &tokens[i].1 {
+            Token::Punct('{') => depth += 1,
+            Token::Punct('}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(Function {
+                        name: name.to_string(),
+                        line,
+                        body: (tokens[open].0, tokens[i].0),
+                        calls,
+                    });
+                }
+            }
+            Token::Word(callee)
+                if tokens.get(i + 1).map(|t| &t.1) == Some(&Token::Punct('('))
+                    && !matches!(tokens[i - 1].1, Token::Punct('.') | Token::Punct('!'))
+                    && tokens[i - 1].1 != Token::Word("fn".into()) =>
+            {
+                calls.push((callee.clone(), tokens[i].0));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masking_keeps_code_and_lines() {
+        let source = "let s = \"{ // }\"; // {\nlet c = '{'; /* { /* } */ } */ let r = r#\"\"{\"#;\nfn f<'a>(x: &'a str) {}";
+        let masked = mask(source);
+        assert_eq!(masked.lines().count(), 3);
+        assert_eq!(masked.matches('{').count(), 1);
+        assert!(masked.contains("fn f<'a>(x: &'a str) {}"));
+        assert_eq!(
+            mask("'\\u{1F980}' '\\'' x"),
+            format!("{} {} x", " ".repeat(11), " ".repeat(4))
+        );
+    }
+
+    #[test]
+    fn outline_finds_top_level_functions_and_calls() {
+        let source = "\
+pub fn main() {
+    // demonstrate_gone();
+    demonstrate_a();
+    println!(\"demonstrate_b()\");
+    x.demonstrate_c();
+}
+
+fn demonstrate_a() {
+    fn inner() {}
+    struct Local;
+    inner();
+}
+
+impl Local {
+    const fn new() -> Self { Local }
+}
+";
+        let outline = outline(source);
+        let names: Vec<_> = outline.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["main", "demonstrate_a"]);
+        let main = outline.function("main").unwrap();
+        assert_eq!(main.calls, [("demonstrate_a".to_string(), 3)]);
+        assert_eq!(main.body, (1, 6));
+        assert_eq!(
+            outline.function("demonstrate_a").unwrap().calls[0].0,
+            "inner"
+        );
+        let items: Vec<_> = outline
+            .items
+            .iter()
+            .map(|i| (i.kind, i.name.as_str()))
+            .collect();
+        assert_eq!(
+            items,
+            [
+                (ItemKind::Fn, "main"),
+                (ItemKind::Fn, "demonstrate_a"),
+                (ItemKind::Fn, "inner"),
+                (ItemKind::Type, "Local"),
+                (ItemKind::Fn, "new"),
+            ]
+        );
+    }
+}
@@ -1,4 +1,699 @@
-fn main()
-{
-    
-}
\ No newline at end of file
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, Stdio};
+
+use roadmap::alloc_tracker::{self, CountingAllocator};
+use roadmap::challenge;
+use roadmap::i18n;
+use roadmap::lessons::{self, LESSONS, Lesson};
+use roadmap::packs::{self, Pack, PackLesson};
+use roadmap::plan;
+use roadmap::progress::Progress;
+use roadmap::render::{self, ColorChoice, Settings, pager, term, theme};
+use roadmap::review::{self, Card, Deck, predict, quiz};
+use roadmap::runner::{self, Limits, Outcome};
+use roadmap::scaffold::{self, Scaffold};
+use roadmap::web::{self, Dashboard};
+
+// Counting costs a few thread-local additions per allocation; the numbers
+// are only read when a lesson is run with `--allocs`.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str =
+    "usage: roadmap [--lang <code>] [--color auto|always|never] [--theme dark|light|mono]
+               [--width N] [--no-pager]
+               [list | packs | run <topic>::<lesson> [--allocs]
+               | path --to <topic>::<lesson> | done <topic>::<lesson>
+               | review [--lesson <topic>::<lesson>] [--limit N]
+               | challenge <topic>::<lesson> [--tolerance T]
+               | serve [--port N] [--threads N]
+               | new <topic>/<lesson> --sections \"a,b,c\"]";
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let (Some(lang), Some(color), Some(theme), Some(width)) = (
+        take_value(&mut args, "--lang"),
+        take_value(&mut args, "--color"),
+        take_value(&mut args, "--theme"),
+        take_value(&mut args, "--width"),
+    ) else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+    let no_pager = take_switch(&mut args, "--no-pager");
+    select_language(lang.as_deref());
+    let display = match Display::from_flags(color, theme, width, no_pager) {
+        Ok(display) => display,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    render::configure(display.settings());
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        [] | ["list"] => {
+            for lesson in LESSONS {
+                println!("{}", lesson.id);
+            }
+            for pack in load_packs() {
+                for lesson in &pack.lessons {
+                    println!("{}", lesson.id);
+                }
+            }
+            ExitCode::SUCCESS
+        }
+        ["packs"] => check_packs(),
+        ["run", id] if lessons::find(id).is_none() => run_pack_lesson(id),
+        ["run", id] if display.page => with_lesson(id, |lesson| run_paged(lesson, &display)),
+        ["run", id] => with_lesson(id, |lesson| {
+            (lesson.run)();
+            ExitCode::SUCCESS
+        }),
+        ["run", id, "--allocs"] => with_lesson(id, run_with_allocs),
+        ["path", "--to", id] => print_path(id),
+        ["done", id] => with_lesson(id, mark_done),
+        ["review", options @ ..] => review_command(options),
+        ["challenge", id] => with_lesson(id, |lesson| run_challenge(lesson, 0.01)),
+        ["challenge", id, "--tolerance", t] => match t.parse::<f64>() {
+            Ok(t) if t >= 0.0 => with_lesson(id, |lesson| run_challenge(lesson, t)),
+            _ => {
+                eprintln!("--tolerance takes a number such as 0.01 (1%)");
+                ExitCode::FAILURE
+            }
+        },
+        ["serve", options @ ..] => serve_command(options),
+        ["new", target, "--sections", sections] => new_lesson(target, sections),
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Removes `flag <value>` from anywhere in `args`: `Some(None)` without
+/// the flag, `None` if it has no value.
+fn take_value(args: &mut Vec<String>, flag: &str) -> Option<Option<String>> {
+    let Some(at) = args.iter().position(|arg| arg == flag) else {
+        return Some(None);
+    };
+    if at + 1 >= args.len() {
+        return None;
+    }
+    let value = args.remove(at + 1);
+    args.remove(at);
+    Some(Some(value))
+}
+
+/// Removes `flag` from anywhere in `args`, saying whether it was there.
+fn take_switch(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != before
+}
+
+/// How lesson output is shown. Only a terminal gets color (unless
+/// `--color` says otherwise), wrapping and the pager, so piped output is
+/// exactly what the lessons print.
+struct Display {
+    color: bool,
+    theme: theme::Theme,
+    width: Option<usize>,
+    page: bool,
+}
+
+impl Display {
+    fn from_flags(
+        color: Option<String>,
+        theme: Option<String>,
+        width: Option<String>,
+        no_pager: bool,
+    ) -> Result<Self, String> {
+        let terminal = io::stdout().is_terminal();
+        let choice = match color {
+            Some(color) => color.parse::<ColorChoice>().map_err(|e| e.to_string())?,
+            None => ColorChoice::Auto,
+        };
+        let name = theme
+            .or_else(|| env::var("ROADMAP_THEME").ok())
+            .unwrap_or_else(|| theme::DARK.name.to_string());
+        let theme = theme::find(&name).ok_or_else(|| {
+            let names: Vec<&str> = theme::THEMES.iter().map(|theme| theme.name).collect();
+            format!("unknown theme `{name}` (have {})", names.join(", "))
+        })?;
+        let width = match width {
+            Some(width) => Some(
+                width
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&width| width > 0)
+                    .ok_or(format!("--width takes a number of columns, not `{width}`"))?,
+            ),
+            None => terminal.then(|| term::size().0),
+        };
+        Ok(Display {
+            color: choice.enabled(terminal),
+            theme,
+            width,
+            page: terminal && !no_pager,
+        })
+    }
+
+    fn settings(&self) -> Settings {
+        Settings {
+            theme: self.color.then_some(self.theme),
+            width: self.width,
+        }
+    }
+}
+
+/// Runs the lesson in a child process that renders as this one would,
+/// and pages what it prints. stderr, where panics go, is not paged.
+fn run_paged(lesson: &Lesson, display: &Display) -> ExitCode {
+    let exe = match env::current_exe() {
+        Ok(exe) => exe,
+        Err(err) => {
+            eprintln!("cannot find the roadmap executable: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut command = Command::new(exe);
+    command.args(["--lang", i18n::language()]);
+    command.args(["--color", if display.color { "always" } else { "never" }]);
+    command.args(["--theme", display.theme.name, "--no-pager"]);
+    if let Some(width) = display.width {
+        command.args(["--width", &width.to_string()]);
+    }
+    command.args(["run", lesson.id]).stderr(Stdio::inherit());
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(err) => {
+            eprintln!("cannot run {}: {err}", lesson.id);
+            return ExitCode::FAILURE;
+        }
+    };
+    let text = String::from_utf8_lossy(&output.stdout);
+    let rows = term::size().1;
+    if let Err(err) = pager::page(&text, rows, &mut io::stdin().lock(), &mut io::stdout()) {
+        eprintln!("cannot show {}: {err}", lesson.id);
+        return ExitCode::FAILURE;
+    }
+    if output.status.success() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Prints lesson text in the language from `--lang`, or else the locale.
+/// Without a catalog for it lessons stay in English; only an explicit
+/// `--lang` says so.
+fn select_language(flag: Option<&str>) {
+    let Some(lang) = i18n::detect(flag) else {
+        return;
+    };
+    if !i18n::set_language(&lang) && flag.is_some() {
+        eprintln!(
+            "no `{lang}` translation (have {}); using English",
+            i18n::LANGUAGES.join(", ")
+        );
+    }
+}
+
+fn with_lesson(id: &str, f: impl FnOnce(&Lesson) -> ExitCode) -> ExitCode {
+    match lessons::find(id) {
+        Some(lesson) => f(lesson),
+        None => {
+            eprintln!("unknown lesson: {id} (see `roadmap list`)");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs each section of the lesson separately under the counting
+/// allocator, then prints one summary row per section.
+fn run_with_allocs(lesson: &Lesson) -> ExitCode {
+    if lesson.sections.is_empty() {
+        eprintln!(
+            "{} does not list its sections, so they cannot be measured",
+            lesson.id
+        );
+        return ExitCode::FAILURE;
+    }
+
+    // The first print allocates stdout's buffer; do it before measuring so
+    // that the first section is not charged for it.
+    println!("=== {} (measuring allocations) ===\n", lesson.id);
+
+    let rows: Vec<_> = lesson
+        .sections
+        .iter()
+        .map(|section| (section.name, alloc_tracker::measure(section.run).1))
+        .collect();
+
+    println!("=== Allocation summary ===\n");
+    print!("{}", alloc_tracker::summary_table(&rows));
+    ExitCode::SUCCESS
+}
+
+/// Prints the lessons to work through before `id`, leaving out completed
+/// ones.
+fn print_path(id: &str) -> ExitCode {
+    let progress = match load_progress() {
+        Ok(progress) => progress,
+        Err(code) => return code,
+    };
+    let plan = match plan::path_to(LESSONS, id, progress.completed()) {
+        Ok(plan) => plan,
+        Err(err) => {
+            eprintln!("{err} (see `roadmap list`)");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if plan.lessons.is_empty() {
+        println!("{id} is already completed");
+        return ExitCode::SUCCESS;
+    }
+    let width = plan.lessons.iter().map(|lesson| lesson.id.len()).max();
+    for (i, lesson) in plan.lessons.iter().enumerate() {
+        println!(
+            "{:>2}. {:<width$}  {:>3} min  {}",
+            i + 1,
+            lesson.id,
+            lesson.minutes,
+            lesson.title,
+            width = width.unwrap_or(0)
+        );
+    }
+    println!(
+        "\n{} lessons, about {} min",
+        plan.lessons.len(),
+        plan.minutes()
+    );
+    if !plan.skipped.is_empty() {
+        let skipped: Vec<_> = plan.skipped.iter().map(|lesson| lesson.id).collect();
+        println!("skipped (completed): {}", skipped.join(", "));
+    }
+    ExitCode::SUCCESS
+}
+
+fn mark_done(lesson: &Lesson) -> ExitCode {
+    let Some(path) = Progress::default_path() else {
+        eprintln!("set ROADMAP_HOME or HOME to record progress");
+        return ExitCode::FAILURE;
+    };
+    let mut progress = match load_progress() {
+        Ok(progress) => progress,
+        Err(code) => return code,
+    };
+    if !progress.complete(lesson.id) {
+        println!("{} was already completed", lesson.id);
+        return ExitCode::SUCCESS;
+    }
+    if let Err(err) = progress.save(&path) {
+        eprintln!("cannot write {}: {err}", path.display());
+        return ExitCode::FAILURE;
+    }
+    println!("completed {}", lesson.id);
+    ExitCode::SUCCESS
+}
+
+/// Progress from the default file; nothing completed if there is no
+/// home directory to keep it in.
+fn load_progress() -> Result<Progress, ExitCode> {
+    let Some(path) = Progress::default_path() else {
+        return Ok(Progress::default());
+    };
+    Progress::load(&path).map_err(|err| {
+        eprintln!("cannot read {}: {err}", path.display());
+        ExitCode::FAILURE
+    })
+}
+
+/// Runs the lesson, stopping at each `predict!` value for a guess read
+/// from stdin.
+fn run_challenge(lesson: &Lesson, tolerance: f64) -> ExitCode {
+    println!(
+        "Type the value that replaces `?`, then Enter (blank to skip, Ctrl-D to stop asking).\n"
+    );
+    let respond = Box::new(|prompt: &str| {
+        print!("{}\n   > ", prompt.trim_end_matches('\n'));
+        let _ = io::stdout().flush();
+        let mut guess = String::new();
+        match io::stdin().read_line(&mut guess) {
+            Ok(0) | Err(_) => {
+                println!();
+                None
+            }
+            Ok(_) => Some(guess),
+        }
+    });
+    let score = challenge::run(lesson.run, tolerance, respond);
+    println!("\n=== Score: {score} ===");
+    ExitCode::SUCCESS
+}
+
+/// Packs from the search path; packs that fail to load are reported on
+/// stderr and left out.
+fn load_packs() -> Vec<Pack> {
+    let (packs, errors) = packs::discover(&packs::search_path());
+    for err in errors {
+        eprintln!("skipping pack: {err}");
+    }
+    packs
+}
+
+/// Lists the packs on the search path with their lessons, and every
+/// validation error. Fails if any pack is invalid.
+fn check_packs() -> ExitCode {
+    let dirs = packs::search_path();
+    let (packs, errors) = packs::discover(&dirs);
+    if packs.is_empty() && errors.is_empty() {
+        let dirs: Vec<String> = dirs.iter().map(|dir| dir.display().to_string()).collect();
+        println!("No lesson packs in {}", dirs.join(", "));
+        println!("(set ROADMAP_PACKS to a list of directories to look in)");
+    }
+    for pack in &packs {
+        println!("{} - {} ({})", pack.name, pack.title, pack.dir.display());
+        for lesson in &pack.lessons {
+            println!(
+                "    {}  {} min  {} snippet(s)",
+                lesson.id,
+                lesson.minutes,
+                lesson.snippets.len()
+            );
+        }
+        if !pack.quiz.is_empty() {
+            println!("    {} quiz question(s)", pack.quiz.len());
+        }
+    }
+    for err in &errors {
+        eprintln!("{err}");
+    }
+    if errors.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Prints a pack lesson's notes, then compiles and runs each snippet.
+fn run_pack_lesson(id: &str) -> ExitCode {
+    let packs = load_packs();
+    let Some((pack, lesson)) = packs.iter().find_map(|pack| Some((pack, pack.lesson(id)?))) else {
+        eprintln!("unknown lesson: {id} (see `roadmap list`)");
+        return ExitCode::FAILURE;
+    };
+    println!("=== {} ===\n", lesson.title);
+    if let Some(notes) = &lesson.notes {
+        let path = pack.dir.join(notes);
+        match fs::read_to_string(&path) {
+            Ok(notes) => println!("{}\n", notes.trim_end()),
+            Err(err) => {
+                eprintln!("cannot read {}: {err}", path.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    if run_snippets(pack, lesson) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Runs every snippet in the sandbox, reporting failures on stderr;
+/// `false` if any failed.
+fn run_snippets(pack: &Pack, lesson: &PackLesson) -> bool {
+    let cache = runner::default_cache_dir();
+    let mut ok = true;
+    for relative in &lesson.snippets {
+        let path = pack.dir.join(relative);
+        println!("--- {} ---", relative.display());
+        let result = fs::read_to_string(&path)
+            .and_then(|source| runner::run(&source, &Limits::default(), Some(&cache)));
+        match result {
+            Ok(result) => {
+                print!("{}", result.stdout);
+                if result.outcome != Outcome::Success {
+                    eprintln!("{}: {}", path.display(), result.outcome);
+                    eprint!("{}", result.stderr);
+                    ok = false;
+                }
+            }
+            Err(err) => {
+                eprintln!("{}: cannot run: {err}", path.display());
+                ok = false;
+            }
+        }
+        println!();
+    }
+    ok
+}
+
+/// Generates a lesson in this crate's source tree and registers it.
+fn new_lesson(target: &str, sections: &str) -> ExitCode {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let changes = Scaffold::new(target, sections).and_then(|new| {
+        let changes = new.changes(root)?;
+        scaffold::apply(&changes)?;
+        Ok((new.id(), changes))
+    });
+    let (id, changes) = match changes {
+        Ok(done) => done,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    for change in &changes {
+        let verb = if change.created { "created" } else { "updated" };
+        let path = change.path.strip_prefix(root).unwrap_or(&change.path);
+        println!("{verb} {}", path.display());
+    }
+    println!(
+        "\nNext: fill in the sections, set minutes, tags and prerequisites in src/lessons.rs,"
+    );
+    println!(
+        "then `cargo run -- run {id}` and record its output with `ROADMAP_BLESS=1 cargo test --test golden`."
+    );
+    ExitCode::SUCCESS
+}
+
+/// Serves the dashboard on localhost until interrupted. Lesson output comes
+/// from running `roadmap run <id>` in a child process, so a lesson that
+/// panics only fails its own request.
+fn serve_command(options: &[&str]) -> ExitCode {
+    let mut port: u16 = 7878;
+    let mut threads: usize = 4;
+    let mut options = options.iter();
+    while let Some(&option) = options.next() {
+        match (option, options.next().map(|value| value.parse::<u16>())) {
+            ("--port", Some(Ok(n))) => port = n,
+            ("--threads", Some(Ok(n))) if n > 0 => threads = usize::from(n),
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let listener = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("cannot listen on 127.0.0.1:{port}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let exe = match env::current_exe() {
+        Ok(exe) => exe,
+        Err(err) => {
+            eprintln!("cannot find the roadmap executable: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let run = Box::new(move |lesson: &Lesson| {
+        let output = Command::new(&exe)
+            .args(["--lang", i18n::language(), "run", lesson.id])
+            .output()
+            .map_err(|err| format!("cannot run {}: {err}", lesson.id))?;
+        if !output.status.success() {
+            return Err(format!(
+                "{} failed ({}):\n{}",
+                lesson.id,
+                output.status,
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    });
+    let dashboard = Dashboard::new(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+        Progress::default_path(),
+        run,
+    );
+
+    if let Ok(address) = listener.local_addr() {
+        println!("Serving lessons at http://{address}/ (Ctrl-C to stop)");
+    }
+    match web::serve(listener, threads, move |request| dashboard.handle(request)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("stopped accepting connections: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn review_command(options: &[&str]) -> ExitCode {
+    let mut lesson = None;
+    let mut limit = 20;
+    let mut options = options.iter();
+    while let Some(&option) = options.next() {
+        match (option, options.next()) {
+            ("--lesson", Some(&id)) => lesson = Some(id),
+            ("--limit", Some(n)) if n.parse::<usize>().is_ok_and(|n| n > 0) => {
+                limit = n.parse().unwrap_or(limit);
+            }
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let packs = load_packs();
+    if let Some(id) = lesson
+        && lessons::find(id).is_none()
+        && !packs.iter().any(|pack| pack.lesson(id).is_some())
+    {
+        eprintln!("unknown lesson: {id} (see `roadmap list`)");
+        return ExitCode::FAILURE;
+    }
+
+    let Some(path) = Deck::default_path() else {
+        eprintln!("set ROADMAP_HOME or HOME to keep review schedules");
+        return ExitCode::FAILURE;
+    };
+    let mut deck = match Deck::load(&path) {
+        Ok(deck) => deck,
+        Err(err) => {
+            eprintln!("cannot read {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut cards = build_cards(lesson);
+    for pack in &packs {
+        cards.extend(
+            pack.quiz
+                .iter()
+                .filter(|question| lesson.is_none_or(|id| id == question.lesson))
+                .map(|question| {
+                    Card::quiz(
+                        &question.lesson,
+                        question.prompt.clone(),
+                        question.answers.clone(),
+                    )
+                }),
+        );
+    }
+    let today = roadmap::calendar::Date::today_utc();
+    let due = review::due(&cards, &deck, today);
+    if due.is_empty() {
+        println!("Nothing due today ({} cards in total).", cards.len());
+        return ExitCode::SUCCESS;
+    }
+    let total = due.len().min(limit);
+    println!(
+        "{total} of {} due cards; leave an answer blank to see it.\n",
+        due.len()
+    );
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    for (i, card) in due.iter().take(limit).enumerate() {
+        println!("[{}/{total}] {} ({})", i + 1, card.lesson, card.kind);
+        print!("{}\n> ", card.prompt);
+        let _ = io::stdout().flush();
+        let mut response = String::new();
+        match input.read_line(&mut response) {
+            Ok(0) | Err(_) => {
+                println!();
+                break;
+            }
+            Ok(_) => {}
+        }
+
+        let grade = review::grade_response(card, &response);
+        let next = deck.review(&card.key, grade, today);
+        if card.accepts(&response) {
+            println!("Correct.");
+        } else {
+            println!("Answer: {}", card.answer());
+        }
+        println!("Next review in {} day(s).\n", next.interval_days);
+        // Save as we go so that quitting early keeps what was reviewed
+        if let Err(err) = deck.save(&path) {
+            eprintln!("cannot write {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Quiz cards plus a predict-the-output card for every qualifying
+/// `println!`, answered by running the lesson in a child process.
+fn build_cards(only: Option<&str>) -> Vec<Card> {
+    let wanted = |id: &str| only.is_none_or(|only| only == id);
+    let mut cards: Vec<Card> = quiz::QUIZ
+        .iter()
+        .filter(|question| wanted(question.lesson))
+        .map(Card::from_question)
+        .collect();
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let exe = env::current_exe().ok();
+    for lesson in LESSONS.iter().filter(|lesson| wanted(lesson.id)) {
+        let Some((topic, stem)) = lesson.id.split_once("::") else {
+            continue;
+        };
+        let path = root.join("src").join(topic).join(format!("{stem}.rs"));
+        let Ok(source) = fs::read_to_string(path) else {
+            continue;
+        };
+        let candidates = predict::candidates(&source);
+        if candidates.is_empty() {
+            continue;
+        }
+        let Some(output) = exe.as_ref().and_then(|exe| {
+            // Answers are found by the text around them in the source,
+            // which is English
+            let output = Command::new(exe)
+                .args(["--lang", "en", "run", lesson.id])
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+        }) else {
+            continue;
+        };
+        cards.extend(
+            candidates
+                .iter()
+                .filter_map(|candidate| Card::from_candidate(lesson.id, candidate, &output)),
+        );
+    }
+    // The same `let` and `println!` can appear in two sections
+    let mut seen = std::collections::BTreeSet::new();
+    cards.retain(|card| seen.insert(card.key.clone()));
+    cards
+}
@@ -0,0 +1,465 @@
+use std::fmt;
+use std::iter::FusedIterator;
+use std::str::FromStr;
+
+use super::{AddrKind, AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Splits `"addr/len"` and validates the prefix length against `max`.
+fn split_prefix(s: &str, max: u8) -> Result<(&str, u8), AddrParseError> {
+    let err = |reason| AddrParseError::new(AddrKind::Network, reason);
+    let (addr, len) = s.split_once('/').ok_or(err("missing `/prefix`"))?;
+    if len.is_empty() || !len.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(err("prefix length must be decimal digits"));
+    }
+    if len.len() > 1 && len.starts_with('0') {
+        return Err(err("prefix length must not have leading zeros"));
+    }
+    match len.parse::<u8>() {
+        Ok(len) if len <= max => Ok((addr, len)),
+        _ => Err(err("prefix length too long")),
+    }
+}
+
+/// Mask with the top `prefix_len` of the low `bits` bits set.
+fn mask(prefix_len: u8, bits: u8) -> u128 {
+    let all = u128::MAX >> (128 - bits as u32);
+    let host = all.checked_shr(prefix_len as u32).unwrap_or(0);
+    all & !host
+}
+
+/// The low `count` bits set, for `count` up to 128.
+fn low_bits(count: u32) -> u128 {
+    u128::MAX.checked_shr(128 - count).unwrap_or(0)
+}
+
+/// Merges overlapping and adjacent `(first, last)` ranges and splits the
+/// result back into the fewest aligned blocks, as `(start, prefix_len)`.
+fn aggregate_ranges(mut ranges: Vec<(u128, u128)>, bits: u8) -> Vec<(u128, u8)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(u128, u128)> = Vec::new();
+    for (first, last) in ranges {
+        match merged.last_mut() {
+            Some((_, end)) if end.checked_add(1).is_none_or(|next| first <= next) => {
+                *end = (*end).max(last);
+            }
+            _ => merged.push((first, last)),
+        }
+    }
+
+    let mut blocks = Vec::new();
+    for (mut start, end) in merged {
+        loop {
+            // The largest block aligned at `start` that does not pass `end`
+            let mut size_log2 = start.trailing_zeros().min(bits as u32);
+            while size_log2 > 0 && start + low_bits(size_log2) > end {
+                size_log2 -= 1;
+            }
+            let last = start + low_bits(size_log2);
+            blocks.push((start, bits - size_log2 as u8));
+            if last >= end {
+                // Also stops before `last + 1` overflows for `::/0`
+                break;
+            }
+            start = last + 1;
+        }
+    }
+    blocks
+}
+
+/// The subnets of a network with a longer prefix, in address order.
+/// Created by [`Ipv4Net::subnets`] and [`Ipv6Net::subnets`].
+#[derive(Debug, Clone)]
+pub struct Subnets<N> {
+    next: Option<u128>,
+    last: u128,
+    step_log2: u32,
+    prefix_len: u8,
+    make: fn(u128, u8) -> N,
+}
+
+impl<N> Iterator for Subnets<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        let current = self.next?;
+        self.next = if current == self.last {
+            None
+        } else {
+            Some(current + low_bits(self.step_log2) + 1)
+        };
+        Some((self.make)(current, self.prefix_len))
+    }
+}
+
+impl<N> FusedIterator for Subnets<N> {}
+
+macro_rules! network {
+    ($(#[$doc:meta])* $net:ident, $addr:ident, $bits:ty, $max:literal) => {
+        $(#[$doc])*
+        ///
+        /// The address is kept as given, host bits included, so that
+        /// `10.1.2.3/8` round-trips; [`Self::trunc`] clears them.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $net {
+            addr: $addr,
+            prefix_len: u8,
+        }
+
+        impl $net {
+            pub const MAX_PREFIX_LEN: u8 = $max;
+
+            /// `None` if `prefix_len` is longer than the address.
+            pub fn new(addr: $addr, prefix_len: u8) -> Option<Self> {
+                (prefix_len <= $max).then_some(Self { addr, prefix_len })
+            }
+
+            fn from_parts(bits: u128, prefix_len: u8) -> Self {
+                Self {
+                    addr: $addr::from_bits(bits as $bits),
+                    prefix_len,
+                }
+            }
+
+            fn range(&self) -> (u128, u128) {
+                (self.network().to_bits() as u128, self.last().to_bits() as u128)
+            }
+
+            pub fn addr(&self) -> $addr {
+                self.addr
+            }
+
+            pub fn prefix_len(&self) -> u8 {
+                self.prefix_len
+            }
+
+            pub fn netmask(&self) -> $addr {
+                $addr::from_bits(mask(self.prefix_len, $max) as $bits)
+            }
+
+            pub fn hostmask(&self) -> $addr {
+                $addr::from_bits(!self.netmask().to_bits())
+            }
+
+            /// The first address, with all host bits cleared.
+            pub fn network(&self) -> $addr {
+                $addr::from_bits(self.addr.to_bits() & self.netmask().to_bits())
+            }
+
+            /// The last address, with all host bits set.
+            pub fn last(&self) -> $addr {
+                $addr::from_bits(self.addr.to_bits() | self.hostmask().to_bits())
+            }
+
+            /// The same network with the host bits cleared.
+            pub fn trunc(&self) -> Self {
+                Self {
+                    addr: self.network(),
+                    prefix_len: self.prefix_len,
+                }
+            }
+
+            pub fn contains_addr(&self, addr: $addr) -> bool {
+                addr.to_bits() & self.netmask().to_bits() == self.network().to_bits()
+            }
+
+            /// Whether every address of `other` is also in `self`.
+            pub fn contains(&self, other: &Self) -> bool {
+                other.prefix_len >= self.prefix_len && self.contains_addr(other.addr)
+            }
+
+            pub fn overlaps(&self, other: &Self) -> bool {
+                self.contains(other) || other.contains(self)
+            }
+
+            /// The network one bit shorter, `None` for `/0`.
+            pub fn supernet(&self) -> Option<Self> {
+                let prefix_len = self.prefix_len.checked_sub(1)?;
+                Some(Self::from_parts(self.network().to_bits() as u128, prefix_len).trunc())
+            }
+
+            /// Iterates over the subnets of length `new_prefix_len`, `None` if
+            /// that is shorter than the current prefix or longer than the
+            /// address. The iterator is lazy, so `::/0` into `/64`s is fine.
+            pub fn subnets(&self, new_prefix_len: u8) -> Option<Subnets<Self>> {
+                if new_prefix_len < self.prefix_len || new_prefix_len > $max {
+                    return None;
+                }
+                let step_log2 = ($max - new_prefix_len) as u32;
+                let (first, last) = self.range();
+                Some(Subnets {
+                    next: Some(first),
+                    last: last & !low_bits(step_log2),
+                    step_log2,
+                    prefix_len: new_prefix_len,
+                    make: Self::from_parts,
+                })
+            }
+
+            /// The smallest set of networks covering exactly the same
+            /// addresses as `nets`, sorted by address. Overlapping entries
+            /// are merged and adjacent siblings combined into supernets.
+            pub fn aggregate(nets: &[Self]) -> Vec<Self> {
+                let ranges = nets.iter().map(Self::range).collect();
+                aggregate_ranges(ranges, $max)
+                    .into_iter()
+                    .map(|(start, prefix_len)| Self::from_parts(start, prefix_len))
+                    .collect()
+            }
+        }
+
+        impl FromStr for $net {
+            type Err = AddrParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (addr, prefix_len) = split_prefix(s, $max)?;
+                Ok(Self {
+                    addr: addr.parse()?,
+                    prefix_len,
+                })
+            }
+        }
+
+        impl fmt::Display for $net {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.pad(&format!("{}/{}", self.addr, self.prefix_len))
+            }
+        }
+    };
+}
+
+network!(
+    /// An IPv4 network in CIDR notation, e.g. `192.168.0.0/16`.
+    Ipv4Net, Ipv4Addr, u32, 32
+);
+network!(
+    /// An IPv6 network in CIDR notation, e.g. `2001:db8::/32`.
+    Ipv6Net, Ipv6Addr, u128, 128
+);
+
+impl Ipv4Net {
+    /// Same as [`Ipv4Net::last`]; the traditional name for IPv4.
+    pub fn broadcast(&self) -> Ipv4Addr {
+        self.last()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IpNet {
+    V4(Ipv4Net),
+    V6(Ipv6Net),
+}
+
+impl IpNet {
+    pub fn addr(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.addr()),
+            IpNet::V6(net) => IpAddr::V6(net.addr()),
+        }
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        match self {
+            IpNet::V4(net) => net.prefix_len(),
+            IpNet::V6(net) => net.prefix_len(),
+        }
+    }
+
+    /// Always `false` across address families.
+    pub fn contains_addr(&self, addr: IpAddr) -> bool {
+        match (self, addr) {
+            (IpNet::V4(net), IpAddr::V4(addr)) => net.contains_addr(addr),
+            (IpNet::V6(net), IpAddr::V6(addr)) => net.contains_addr(addr),
+            _ => false,
+        }
+    }
+
+    /// Aggregates each family separately; IPv4 networks come first.
+    pub fn aggregate(nets: &[IpNet]) -> Vec<IpNet> {
+        let mut v4 = Vec::new();
+        let mut v6 = Vec::new();
+        for net in nets {
+            match net {
+                IpNet::V4(net) => v4.push(*net),
+                IpNet::V6(net) => v6.push(*net),
+            }
+        }
+        let v4 = Ipv4Net::aggregate(&v4).into_iter().map(IpNet::V4);
+        let v6 = Ipv6Net::aggregate(&v6).into_iter().map(IpNet::V6);
+        v4.chain(v6).collect()
+    }
+}
+
+impl FromStr for IpNet {
+    type Err = AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains(':') {
+            s.parse().map(IpNet::V6)
+        } else {
+            s.parse().map(IpNet::V4)
+        }
+    }
+}
+
+impl fmt::Display for IpNet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpNet::V4(net) => net.fmt(f),
+            IpNet::V6(net) => net.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v4(s: &str) -> Ipv4Net {
+        s.parse().unwrap()
+    }
+
+    fn v6(s: &str) -> Ipv6Net {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_keeps_host_bits() {
+        let net = v4("10.1.2.3/8");
+        assert_eq!(net.to_string(), "10.1.2.3/8");
+        assert_eq!(net.network(), Ipv4Addr::new(10, 0, 0, 0));
+        assert_eq!(net.broadcast(), Ipv4Addr::new(10, 255, 255, 255));
+        assert_eq!(net.netmask(), Ipv4Addr::new(255, 0, 0, 0));
+        assert_eq!(net.trunc().to_string(), "10.0.0.0/8");
+        assert_eq!(v6("2001:DB8::1/32").trunc().to_string(), "2001:db8::/32");
+    }
+
+    #[test]
+    fn rejects_bad_prefixes() {
+        for input in [
+            "10.0.0.0",
+            "10.0.0.0/",
+            "10.0.0.0/33",
+            "10.0.0.0/08",
+            "10.0.0.0/+8",
+        ] {
+            assert!(input.parse::<Ipv4Net>().is_err(), "{input}");
+        }
+        assert!("::/129".parse::<Ipv6Net>().is_err());
+        assert!("::/128".parse::<Ipv6Net>().is_ok());
+        let err = "1.2.3.4/40".parse::<IpNet>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid network: prefix length too long");
+    }
+
+    #[test]
+    fn zero_and_full_length_prefixes() {
+        let all = v4("0.0.0.0/0");
+        assert!(all.contains_addr(Ipv4Addr::BROADCAST));
+        assert_eq!(all.netmask(), Ipv4Addr::UNSPECIFIED);
+        let host = v4("192.0.2.7/32");
+        assert!(host.contains_addr(Ipv4Addr::new(192, 0, 2, 7)));
+        assert!(!host.contains_addr(Ipv4Addr::new(192, 0, 2, 8)));
+        assert!(v6("::/0").contains_addr(Ipv6Addr::from_bits(u128::MAX)));
+    }
+
+    #[test]
+    fn containment() {
+        let outer = v4("192.168.0.0/16");
+        assert!(outer.contains(&v4("192.168.4.0/24")));
+        assert!(outer.contains(&outer));
+        assert!(!v4("192.168.4.0/24").contains(&outer));
+        assert!(!outer.contains(&v4("192.169.0.0/24")));
+        assert!(outer.overlaps(&v4("192.168.4.0/24")));
+        assert!(!v4("10.0.0.0/8").overlaps(&outer));
+        let net: IpNet = "2001:db8::/32".parse().unwrap();
+        assert!(net.contains_addr("2001:db8:ffff::1".parse().unwrap()));
+        assert!(!net.contains_addr("32.1.13.184".parse().unwrap()));
+    }
+
+    #[test]
+    fn subnet_iteration() {
+        let subnets: Vec<String> = v4("10.0.0.0/22")
+            .subnets(24)
+            .unwrap()
+            .map(|net| net.to_string())
+            .collect();
+        assert_eq!(
+            subnets,
+            ["10.0.0.0/24", "10.0.1.0/24", "10.0.2.0/24", "10.0.3.0/24"]
+        );
+        assert_eq!(v4("10.0.0.0/24").subnets(24).unwrap().count(), 1);
+        assert_eq!(
+            v4("0.0.0.0/0")
+                .subnets(8)
+                .unwrap()
+                .last()
+                .unwrap()
+                .to_string(),
+            "255.0.0.0/8"
+        );
+        assert!(v4("10.0.0.0/24").subnets(23).is_none());
+        assert!(v4("10.0.0.0/24").subnets(33).is_none());
+
+        let mut halves = v6("::/0").subnets(1).unwrap();
+        assert_eq!(halves.next().unwrap().to_string(), "::/1");
+        assert_eq!(halves.next().unwrap().to_string(), "8000::/1");
+        assert!(halves.next().is_none());
+        let mut huge = v6("2001:db8::/32").subnets(64).unwrap();
+        assert_eq!(huge.nth(1).unwrap().to_string(), "2001:db8:0:1::/64");
+    }
+
+    #[test]
+    fn supernets() {
+        assert_eq!(
+            v4("10.1.0.0/16").supernet().unwrap().to_string(),
+            "10.0.0.0/15"
+        );
+        assert!(v4("0.0.0.0/0").supernet().is_none());
+    }
+
+    #[test]
+    fn aggregation() {
+        let nets = [
+            v4("10.0.1.0/24"),
+            v4("10.0.0.0/24"),
+            v4("10.0.2.0/24"),
+            v4("10.0.2.128/25"),
+            v4("192.168.0.0/24"),
+            v4("10.0.3.0/24"),
+            v4("10.0.4.0/24"),
+        ];
+        let aggregated: Vec<String> = Ipv4Net::aggregate(&nets)
+            .iter()
+            .map(|net| net.to_string())
+            .collect();
+        assert_eq!(aggregated, ["10.0.0.0/22", "10.0.4.0/24", "192.168.0.0/24"]);
+
+        // Unaligned neighbours do not combine into a wrongly aligned block
+        let aggregated = Ipv4Net::aggregate(&[v4("10.0.1.0/24"), v4("10.0.2.0/24")]);
+        assert_eq!(aggregated.len(), 2);
+
+        let everything = Ipv6Net::aggregate(&[v6("::/1"), v6("8000::/1")]);
+        assert_eq!(everything, [v6("::/0")]);
+        let everything = Ipv4Net::aggregate(&[v4("0.0.0.0/0"), v4("10.0.0.0/8")]);
+        assert_eq!(everything, [v4("0.0.0.0/0")]);
+        assert!(Ipv4Net::aggregate(&[]).is_empty());
+    }
+
+    #[test]
+    fn aggregation_covers_the_same_addresses() {
+        let nets = [
+            v4("10.0.0.0/25"),
+            v4("10.0.0.128/26"),
+            v4("10.0.0.192/27"),
+            v4("10.0.1.0/30"),
+        ];
+        let aggregated = Ipv4Net::aggregate(&nets);
+        for host in 0..512u32 {
+            let addr = Ipv4Addr::from_bits(0x0a00_0000 + host);
+            let before = nets.iter().any(|net| net.contains_addr(addr));
+            let after = aggregated.iter().any(|net| net.contains_addr(addr));
+            assert_eq!(before, after, "{addr}");
+        }
+        // 0-223 is not one aligned block, so it stays three pieces
+        assert_eq!(aggregated.len(), 4);
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{AddrKind, AddrParseError};
+
+/// An IPv4 address stored as four octets in network order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Ipv4Addr([u8; 4]);
+
+impl Ipv4Addr {
+    pub const UNSPECIFIED: Self = Self([0, 0, 0, 0]);
+    pub const LOCALHOST: Self = Self([127, 0, 0, 1]);
+    pub const BROADCAST: Self = Self([255, 255, 255, 255]);
+
+    pub const fn new(a: u8, b: u8, c: u8, d: u8) -> Self {
+        Self([a, b, c, d])
+    }
+
+    pub const fn octets(&self) -> [u8; 4] {
+        self.0
+    }
+
+    pub const fn to_bits(self) -> u32 {
+        u32::from_be_bytes(self.0)
+    }
+
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits.to_be_bytes())
+    }
+
+    pub fn is_loopback(&self) -> bool {
+        self.0[0] == 127
+    }
+
+    /// RFC 1918 ranges: 10/8, 172.16/12 and 192.168/16.
+    pub fn is_private(&self) -> bool {
+        match self.0 {
+            [10, ..] => true,
+            [172, b, ..] => (16..=31).contains(&b),
+            [192, 168, ..] => true,
+            _ => false,
+        }
+    }
+}
+
+/// Parses dotted-decimal notation. Exactly four decimal octets are
+/// required; leading zeros are rejected because other parsers read them
+/// as octal.
+pub(super) fn parse_octets(s: &str) -> Result<[u8; 4], &'static str> {
+    let mut octets = [0u8; 4];
+    let mut parts = s.split('.');
+    for octet in &mut octets {
+        let part = parts.next().ok_or("expected four octets")?;
+        if part.is_empty() {
+            return Err("empty octet");
+        }
+        if !part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err("octets must be decimal digits");
+        }
+        if part.len() > 1 && part.starts_with('0') {
+            return Err("octets must not have leading zeros");
+        }
+        *octet = part.parse().map_err(|_| "octet out of range")?;
+    }
+    if parts.next().is_some() {
+        return Err("expected four octets");
+    }
+    Ok(octets)
+}
+
+impl FromStr for Ipv4Addr {
+    type Err = AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_octets(s)
+            .map(Self)
+            .map_err(|reason| AddrParseError::new(AddrKind::Ipv4, reason))
+    }
+}
+
+impl fmt::Display for Ipv4Addr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d] = self.0;
+        // Pad as a whole so that `{:>15}` lines up in tables.
+        f.pad(&format!("{a}.{b}.{c}.{d}"))
+    }
+}
+
+impl From<[u8; 4]> for Ipv4Addr {
+    fn from(octets: [u8; 4]) -> Self {
+        Self(octets)
+    }
+}
+
+impl From<std::net::Ipv4Addr> for Ipv4Addr {
+    fn from(addr: std::net::Ipv4Addr) -> Self {
+        Self(addr.octets())
+    }
+}
+
+impl From<Ipv4Addr> for std::net::Ipv4Addr {
+    fn from(addr: Ipv4Addr) -> Self {
+        addr.0.into()
+    }
+}
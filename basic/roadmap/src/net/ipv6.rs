@@ -0,0 +1,222 @@
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use super::ipv4::{self, Ipv4Addr};
+use super::{AddrKind, AddrParseError};
+
+/// An IPv6 address stored as eight 16-bit segments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Ipv6Addr([u16; 8]);
+
+impl Ipv6Addr {
+    pub const UNSPECIFIED: Self = Self([0; 8]);
+    pub const LOCALHOST: Self = Self([0, 0, 0, 0, 0, 0, 0, 1]);
+
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(a: u16, b: u16, c: u16, d: u16, e: u16, f: u16, g: u16, h: u16) -> Self {
+        Self([a, b, c, d, e, f, g, h])
+    }
+
+    pub const fn segments(&self) -> [u16; 8] {
+        self.0
+    }
+
+    pub const fn to_bits(self) -> u128 {
+        let mut bits = 0u128;
+        let mut i = 0;
+        while i < 8 {
+            bits = (bits << 16) | self.0[i] as u128;
+            i += 1;
+        }
+        bits
+    }
+
+    pub const fn from_bits(bits: u128) -> Self {
+        let mut segments = [0u16; 8];
+        let mut i = 0;
+        while i < 8 {
+            segments[i] = (bits >> (112 - 16 * i)) as u16;
+            i += 1;
+        }
+        Self(segments)
+    }
+
+    pub fn is_loopback(&self) -> bool {
+        *self == Self::LOCALHOST
+    }
+
+    /// The embedded address of an IPv4-mapped address (`::ffff:a.b.c.d`).
+    pub fn to_ipv4_mapped(&self) -> Option<Ipv4Addr> {
+        match self.0 {
+            [0, 0, 0, 0, 0, 0xffff, hi, lo] => {
+                let [a, b] = hi.to_be_bytes();
+                let [c, d] = lo.to_be_bytes();
+                Some(Ipv4Addr::new(a, b, c, d))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<Ipv4Addr> for Ipv6Addr {
+    /// The IPv4-mapped form, `::ffff:a.b.c.d`.
+    fn from(addr: Ipv4Addr) -> Self {
+        let [a, b, c, d] = addr.octets();
+        Self([
+            0,
+            0,
+            0,
+            0,
+            0,
+            0xffff,
+            u16::from_be_bytes([a, b]),
+            u16::from_be_bytes([c, d]),
+        ])
+    }
+}
+
+/// Parses the groups on one side of a `::`. `ipv4_tail` allows the final
+/// group to be a dotted IPv4 address, which fills two segments.
+fn parse_groups(part: &str, ipv4_tail: bool) -> Result<Vec<u16>, &'static str> {
+    let mut segments = Vec::new();
+    if part.is_empty() {
+        return Ok(segments);
+    }
+    let groups: Vec<&str> = part.split(':').collect();
+    for (i, group) in groups.iter().enumerate() {
+        let last = i + 1 == groups.len();
+        if group.contains('.') {
+            if !(last && ipv4_tail) {
+                return Err("an embedded IPv4 address must come last");
+            }
+            let [a, b, c, d] = ipv4::parse_octets(group)?;
+            segments.push(u16::from_be_bytes([a, b]));
+            segments.push(u16::from_be_bytes([c, d]));
+        } else {
+            if group.is_empty() {
+                return Err("empty group");
+            }
+            if group.len() > 4 || !group.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err("groups must be 1 to 4 hex digits");
+            }
+            segments.push(u16::from_str_radix(group, 16).map_err(|_| "invalid group")?);
+        }
+    }
+    Ok(segments)
+}
+
+fn parse_segments(s: &str) -> Result<[u16; 8], &'static str> {
+    if s.is_empty() {
+        return Err("empty string");
+    }
+    let mut halves = s.splitn(3, "::");
+    let head = halves.next().unwrap_or("");
+    let tail = halves.next();
+    if halves.next().is_some() {
+        return Err("more than one `::`");
+    }
+
+    let mut segments = [0u16; 8];
+    match tail {
+        None => {
+            let groups = parse_groups(head, true)?;
+            if groups.len() != 8 {
+                return Err("expected 8 groups");
+            }
+            segments.copy_from_slice(&groups);
+        }
+        Some(tail) => {
+            let head = parse_groups(head, false)?;
+            let tail = parse_groups(tail, true)?;
+            // `::` stands for at least one group of zeros
+            if head.len() + tail.len() > 7 {
+                return Err("too many groups around `::`");
+            }
+            segments[..head.len()].copy_from_slice(&head);
+            segments[8 - tail.len()..].copy_from_slice(&tail);
+        }
+    }
+    Ok(segments)
+}
+
+impl FromStr for Ipv6Addr {
+    type Err = AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_segments(s)
+            .map(Self)
+            .map_err(|reason| AddrParseError::new(AddrKind::Ipv6, reason))
+    }
+}
+
+/// The longest run of at least two zero segments, first one on a tie, as
+/// `(start, len)`. RFC 5952 section 4.2.
+fn longest_zero_run(segments: &[u16; 8]) -> Option<(usize, usize)> {
+    let mut best: Option<(usize, usize)> = None;
+    let mut i = 0;
+    while i < 8 {
+        if segments[i] != 0 {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < 8 && segments[i] == 0 {
+            i += 1;
+        }
+        let len = i - start;
+        if len >= 2 && best.is_none_or(|(_, best_len)| len > best_len) {
+            best = Some((start, len));
+        }
+    }
+    best
+}
+
+/// Canonical text form from RFC 5952: lowercase hex without leading zeros,
+/// the longest run of zero groups replaced by `::`, and IPv4-mapped
+/// addresses in mixed notation.
+impl fmt::Display for Ipv6Addr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::with_capacity(39);
+        if let Some(v4) = self.to_ipv4_mapped() {
+            write!(out, "::ffff:{v4}")?;
+            return f.pad(&out);
+        }
+
+        let write_groups = |out: &mut String, groups: &[u16]| -> fmt::Result {
+            for (i, group) in groups.iter().enumerate() {
+                if i > 0 {
+                    out.push(':');
+                }
+                write!(out, "{group:x}")?;
+            }
+            Ok(())
+        };
+        match longest_zero_run(&self.0) {
+            Some((start, len)) => {
+                write_groups(&mut out, &self.0[..start])?;
+                out.push_str("::");
+                write_groups(&mut out, &self.0[start + len..])?;
+            }
+            None => write_groups(&mut out, &self.0)?,
+        }
+        f.pad(&out)
+    }
+}
+
+impl From<[u16; 8]> for Ipv6Addr {
+    fn from(segments: [u16; 8]) -> Self {
+        Self(segments)
+    }
+}
+
+impl From<std::net::Ipv6Addr> for Ipv6Addr {
+    fn from(addr: std::net::Ipv6Addr) -> Self {
+        Self(addr.segments())
+    }
+}
+
+impl From<Ipv6Addr> for std::net::Ipv6Addr {
+    fn from(addr: Ipv6Addr) -> Self {
+        addr.0.into()
+    }
+}
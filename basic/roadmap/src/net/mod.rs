@@ -0,0 +1,101 @@
+//! Typed IPv4 and IPv6 addresses and CIDR networks.
+//!
+//! `constructs/enums.rs` models `enum IpAddr { V4(String), V6(String) }`,
+//! which happily stores `"not an address"`. The types here parse and
+//! validate instead, store addresses as fixed-size integers and format
+//! them canonically (RFC 5952 for IPv6). Parsing follows the same rules as
+//! `std::net`, which `tests/net_agreement.rs` checks.
+
+use std::fmt;
+use std::str::FromStr;
+
+mod cidr;
+mod ipv4;
+mod ipv6;
+
+pub use cidr::{IpNet, Ipv4Net, Ipv6Net, Subnets};
+pub use ipv4::Ipv4Addr;
+pub use ipv6::Ipv6Addr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IpAddr {
+    V4(Ipv4Addr),
+    V6(Ipv6Addr),
+}
+
+impl IpAddr {
+    pub fn is_ipv4(&self) -> bool {
+        matches!(self, IpAddr::V4(_))
+    }
+
+    pub fn is_ipv6(&self) -> bool {
+        matches!(self, IpAddr::V6(_))
+    }
+}
+
+impl FromStr for IpAddr {
+    type Err = AddrParseError;
+
+    /// Anything containing a `:` is parsed as IPv6, everything else as IPv4.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains(':') {
+            s.parse().map(IpAddr::V6)
+        } else {
+            s.parse().map(IpAddr::V4)
+        }
+    }
+}
+
+impl fmt::Display for IpAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpAddr::V4(addr) => addr.fmt(f),
+            IpAddr::V6(addr) => addr.fmt(f),
+        }
+    }
+}
+
+impl From<Ipv4Addr> for IpAddr {
+    fn from(addr: Ipv4Addr) -> Self {
+        IpAddr::V4(addr)
+    }
+}
+
+impl From<Ipv6Addr> for IpAddr {
+    fn from(addr: Ipv6Addr) -> Self {
+        IpAddr::V6(addr)
+    }
+}
+
+/// What was being parsed when an [`AddrParseError`] occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddrKind {
+    Ipv4,
+    Ipv6,
+    Network,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddrParseError {
+    pub kind: AddrKind,
+    pub reason: &'static str,
+}
+
+impl AddrParseError {
+    pub(crate) fn new(kind: AddrKind, reason: &'static str) -> Self {
+        Self { kind, reason }
+    }
+}
+
+impl fmt::Display for AddrParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.kind {
+            AddrKind::Ipv4 => "IPv4 address",
+            AddrKind::Ipv6 => "IPv6 address",
+            AddrKind::Network => "network",
+        };
+        write!(f, "invalid {what}: {}", self.reason)
+    }
+}
+
+impl std::error::Error for AddrParseError {}
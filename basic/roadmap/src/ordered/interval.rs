@@ -0,0 +1,464 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::ops::Sub;
+
+use super::is_comparable;
+
+/// One end of an [`Interval`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bound<T> {
+    /// The value itself is excluded, `(` or `)`.
+    Open(T),
+    /// The value itself is included, `[` or `]`.
+    Closed(T),
+}
+
+impl<T> Bound<T> {
+    pub fn value(&self) -> &T {
+        match self {
+            Bound::Open(value) | Bound::Closed(value) => value,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Bound::Closed(_))
+    }
+
+    /// The bound on the other side of the same value: the upper end of
+    /// what lies below a lower bound, or the reverse.
+    fn flip(self) -> Self {
+        match self {
+            Bound::Open(value) => Bound::Closed(value),
+            Bound::Closed(value) => Bound::Open(value),
+        }
+    }
+}
+
+/// Endpoints are checked to be comparable when an interval is built, so
+/// comparisons between them always succeed.
+fn cmp<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).expect("interval endpoints are comparable")
+}
+
+/// Orders lower bounds: at the same value `[a` starts before `(a`.
+fn cmp_lower<T: PartialOrd>(a: &Bound<T>, b: &Bound<T>) -> Ordering {
+    cmp(a.value(), b.value()).then(b.is_closed().cmp(&a.is_closed()))
+}
+
+/// Orders upper bounds: at the same value `a)` ends before `a]`.
+fn cmp_upper<T: PartialOrd>(a: &Bound<T>, b: &Bound<T>) -> Ordering {
+    cmp(a.value(), b.value()).then(a.is_closed().cmp(&b.is_closed()))
+}
+
+/// Whether something starting at `lower` and ending at `upper` contains
+/// at least one value.
+fn spans<T: PartialOrd>(lower: &Bound<T>, upper: &Bound<T>) -> bool {
+    match cmp(lower.value(), upper.value()) {
+        Ordering::Less => true,
+        Ordering::Equal => lower.is_closed() && upper.is_closed(),
+        Ordering::Greater => false,
+    }
+}
+
+/// A non-empty range of values between two bounds.
+///
+/// An interval over a continuous type such as `f64` is treated as
+/// continuous: `[1, 2)` and `(2, 3]` are not mergeable because 2 is
+/// missing, even for integers where no value lies strictly between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    lower: Bound<T>,
+    upper: Bound<T>,
+}
+
+impl<T: PartialOrd> Interval<T> {
+    /// `None` if the interval would be empty or an endpoint is not
+    /// comparable (NaN).
+    pub fn new(lower: Bound<T>, upper: Bound<T>) -> Option<Self> {
+        if !(is_comparable(lower.value()) && is_comparable(upper.value())) {
+            return None;
+        }
+        spans(&lower, &upper).then_some(Self { lower, upper })
+    }
+
+    /// `[lower, upper]`
+    pub fn closed(lower: T, upper: T) -> Option<Self> {
+        Self::new(Bound::Closed(lower), Bound::Closed(upper))
+    }
+
+    /// `(lower, upper)`
+    pub fn open(lower: T, upper: T) -> Option<Self> {
+        Self::new(Bound::Open(lower), Bound::Open(upper))
+    }
+
+    /// `[lower, upper)`, the shape of `lower..upper`.
+    pub fn closed_open(lower: T, upper: T) -> Option<Self> {
+        Self::new(Bound::Closed(lower), Bound::Open(upper))
+    }
+
+    /// `(lower, upper]`
+    pub fn open_closed(lower: T, upper: T) -> Option<Self> {
+        Self::new(Bound::Open(lower), Bound::Closed(upper))
+    }
+
+    pub fn lower(&self) -> &Bound<T> {
+        &self.lower
+    }
+
+    pub fn upper(&self) -> &Bound<T> {
+        &self.upper
+    }
+
+    pub fn into_bounds(self) -> (Bound<T>, Bound<T>) {
+        (self.lower, self.upper)
+    }
+
+    /// A NaN is in no interval.
+    pub fn contains(&self, value: &T) -> bool {
+        let above = match &self.lower {
+            Bound::Open(lower) => value > lower,
+            Bound::Closed(lower) => value >= lower,
+        };
+        let below = match &self.upper {
+            Bound::Open(upper) => value < upper,
+            Bound::Closed(upper) => value <= upper,
+        };
+        above && below
+    }
+
+    /// Whether every value of `other` is in `self`.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        cmp_lower(&self.lower, &other.lower).is_le() && cmp_upper(&self.upper, &other.upper).is_ge()
+    }
+
+    /// Whether the two share at least one value.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let lower = max_by(&self.lower, &other.lower, cmp_lower);
+        let upper = min_by(&self.upper, &other.upper, cmp_upper);
+        spans(lower, upper)
+    }
+
+    /// Whether the two can be [`merge`](Interval::merge)d: they overlap,
+    /// or one ends where the other starts and that value is included.
+    pub fn is_mergeable(&self, other: &Self) -> bool {
+        let touches = |upper: &Bound<T>, lower: &Bound<T>| {
+            cmp(upper.value(), lower.value()).is_eq() && (upper.is_closed() || lower.is_closed())
+        };
+        self.overlaps(other)
+            || touches(&self.upper, &other.lower)
+            || touches(&other.upper, &self.lower)
+    }
+}
+
+impl<T: PartialOrd + Clone> Interval<T> {
+    /// The values in both, or `None` if they do not overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let lower = max_by(&self.lower, &other.lower, cmp_lower);
+        let upper = min_by(&self.upper, &other.upper, cmp_upper);
+        Self::new(lower.clone(), upper.clone())
+    }
+
+    /// The smallest interval containing both, including any gap between
+    /// them.
+    pub fn hull(&self, other: &Self) -> Self {
+        Self {
+            lower: min_by(&self.lower, &other.lower, cmp_lower).clone(),
+            upper: max_by(&self.upper, &other.upper, cmp_upper).clone(),
+        }
+    }
+
+    /// The union as a single interval, or `None` if there is a gap.
+    pub fn merge(&self, other: &Self) -> Option<Self> {
+        self.is_mergeable(other).then(|| self.hull(other))
+    }
+
+    /// The values of `self` that are not in `other`: none, one or two
+    /// intervals, in order.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if !self.overlaps(other) {
+            return vec![self.clone()];
+        }
+        let below = Self::new(self.lower.clone(), other.lower.clone().flip());
+        let above = Self::new(other.upper.clone().flip(), self.upper.clone());
+        below.into_iter().chain(above).collect()
+    }
+}
+
+/// Only for types whose values can be subtracted, such as numbers.
+impl<T: PartialOrd + Sub<Output = T> + Copy> Interval<T> {
+    /// `upper - lower`; open and closed ends give the same length.
+    pub fn length(&self) -> T {
+        *self.upper.value() - *self.lower.value()
+    }
+}
+
+fn min_by<'a, T>(a: &'a T, b: &'a T, cmp: impl Fn(&T, &T) -> Ordering) -> &'a T {
+    if cmp(a, b).is_le() { a } else { b }
+}
+
+fn max_by<'a, T>(a: &'a T, b: &'a T, cmp: impl Fn(&T, &T) -> Ordering) -> &'a T {
+    if cmp(a, b).is_ge() { a } else { b }
+}
+
+/// `[1, 5)` in the usual notation.
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (open, lower) = match &self.lower {
+            Bound::Open(value) => ('(', value),
+            Bound::Closed(value) => ('[', value),
+        };
+        let (close, upper) = match &self.upper {
+            Bound::Open(value) => (')', value),
+            Bound::Closed(value) => (']', value),
+        };
+        write!(f, "{open}{lower}, {upper}{close}")
+    }
+}
+
+/// A set of values kept as sorted, disjoint, non-mergeable intervals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.intervals
+            .iter()
+            .any(|interval| interval.contains(value))
+    }
+
+    /// Adds `interval`, merging it with every interval it overlaps or
+    /// touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let mut merged = interval;
+        let mut kept = Vec::with_capacity(self.intervals.len() + 1);
+        for existing in self.intervals.drain(..) {
+            match merged.merge(&existing) {
+                Some(wider) => merged = wider,
+                None => kept.push(existing),
+            }
+        }
+        let at = kept.partition_point(|existing| cmp_lower(&existing.lower, &merged.lower).is_lt());
+        kept.insert(at, merged);
+        self.intervals = kept;
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.intervals.iter().cloned());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        for a in &self.intervals {
+            for b in &other.intervals {
+                if let Some(common) = a.intersection(b) {
+                    result.insert(common);
+                }
+            }
+        }
+        result
+    }
+
+    /// The values in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut remaining = self.intervals.clone();
+        for cut in &other.intervals {
+            remaining = remaining
+                .iter()
+                .flat_map(|interval| interval.difference(cut))
+                .collect();
+        }
+        remaining.into_iter().collect()
+    }
+}
+
+impl<T: PartialOrd + Clone> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/// `{[1, 2), (3, 4]}`, or `{}` when empty.
+impl<T: Display> Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, interval) in self.intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{interval}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[Interval<i32>]) -> IntervalSet<i32> {
+        intervals.iter().cloned().collect()
+    }
+
+    #[test]
+    fn construction_rejects_empty_and_nan() {
+        assert!(Interval::closed(1, 1).is_some());
+        assert!(Interval::closed_open(1, 1).is_none());
+        assert!(Interval::open(2, 1).is_none());
+        assert!(Interval::closed(f64::NAN, 1.0).is_none());
+        assert!(Interval::closed(0.0, f64::NAN).is_none());
+        assert!(Interval::closed(f64::NEG_INFINITY, f64::INFINITY).is_some());
+    }
+
+    #[test]
+    fn integer_bounds() {
+        let half_open = Interval::closed_open(1, 5).unwrap();
+        assert!(half_open.contains(&1) && !half_open.contains(&5));
+        assert_eq!(half_open.length(), 4);
+        assert_eq!(half_open.to_string(), "[1, 5)");
+
+        let next = Interval::closed(5, 8).unwrap();
+        assert!(!half_open.overlaps(&next));
+        assert_eq!(half_open.merge(&next).unwrap().to_string(), "[1, 8]");
+        assert_eq!(half_open.intersection(&next), None);
+
+        let gap = Interval::open_closed(5, 8).unwrap();
+        assert_eq!(half_open.merge(&gap), None);
+        assert_eq!(half_open.hull(&gap).to_string(), "[1, 8]");
+    }
+
+    #[test]
+    fn float_bounds_and_nan_policy() {
+        let unit = Interval::closed(0.0, 1.0).unwrap();
+        assert!(unit.contains(&0.5));
+        assert!(!unit.contains(&f64::NAN));
+        let shifted = Interval::open(0.5, 1.5).unwrap();
+        assert_eq!(unit.intersection(&shifted).unwrap().to_string(), "(0.5, 1]");
+        let parts: Vec<String> = unit
+            .difference(&Interval::open(0.25, 0.75).unwrap())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(parts, ["[0, 0.25]", "[0.75, 1]"]);
+        assert_eq!(shifted.length(), 1.0);
+    }
+
+    #[test]
+    fn string_bounds() {
+        let a_to_m = Interval::closed_open("a".to_string(), "n".to_string()).unwrap();
+        assert!(a_to_m.contains(&"mango".to_string()));
+        assert!(!a_to_m.contains(&"nectarine".to_string()));
+        let n_to_z = Interval::closed("n".to_string(), "zz".to_string()).unwrap();
+        assert_eq!(a_to_m.merge(&n_to_z).unwrap().to_string(), "[a, zz]");
+        // Strings cannot be subtracted, so there is no `length` here
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[
+            Interval::closed(0, 10).unwrap(),
+            Interval::closed(20, 30).unwrap(),
+        ]);
+        let b = set(&[Interval::open(5, 25).unwrap()]);
+        assert_eq!(a.union(&b).to_string(), "{[0, 30]}");
+        assert_eq!(a.intersection(&b).to_string(), "{(5, 10], [20, 25)}");
+        assert_eq!(a.difference(&b).to_string(), "{[0, 5], [25, 30]}");
+        assert_eq!(b.difference(&a).to_string(), "{(10, 20)}");
+        assert_eq!(IntervalSet::<i32>::new().to_string(), "{}");
+    }
+
+    #[test]
+    fn insert_merges_touching_intervals() {
+        let touching = set(&[
+            Interval::closed_open(3, 4).unwrap(),
+            Interval::closed_open(1, 2).unwrap(),
+            Interval::closed_open(2, 3).unwrap(),
+        ]);
+        assert_eq!(touching.to_string(), "{[1, 4)}");
+        let gap = set(&[
+            Interval::closed_open(1, 2).unwrap(),
+            Interval::open(2, 3).unwrap(),
+        ]);
+        assert_eq!(gap.intervals().len(), 2);
+    }
+
+    /// Every set operation agrees with the same operation on sampled
+    /// points, at the integers and the half-integers between them.
+    #[test]
+    fn set_operations_agree_with_points() {
+        let mut seed = 0x9e37_79b9_u32;
+        let mut next = move |n: u32| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed % n
+        };
+        let random_set = |next: &mut dyn FnMut(u32) -> u32| -> IntervalSet<f64> {
+            (0..next(4))
+                .filter_map(|_| {
+                    let lower = next(10) as f64;
+                    let upper = lower + next(5) as f64;
+                    let bound = |closed: bool, v: f64| {
+                        if closed {
+                            Bound::Closed(v)
+                        } else {
+                            Bound::Open(v)
+                        }
+                    };
+                    Interval::new(bound(next(2) == 0, lower), bound(next(2) == 0, upper))
+                })
+                .collect()
+        };
+        for _ in 0..500 {
+            let a = random_set(&mut next);
+            let b = random_set(&mut next);
+            let (union, common, minus) = (a.union(&b), a.intersection(&b), a.difference(&b));
+            for half_steps in -2..32 {
+                let x = f64::from(half_steps) / 2.0;
+                let (in_a, in_b) = (a.contains(&x), b.contains(&x));
+                assert_eq!(union.contains(&x), in_a || in_b, "{a} ∪ {b} at {x}");
+                assert_eq!(common.contains(&x), in_a && in_b, "{a} ∩ {b} at {x}");
+                assert_eq!(minus.contains(&x), in_a && !in_b, "{a} - {b} at {x}");
+            }
+            for pair in union.intervals().windows(2) {
+                assert!(
+                    pair[0].merge(&pair[1]).is_none(),
+                    "{union} is not normalized"
+                );
+            }
+        }
+    }
+}
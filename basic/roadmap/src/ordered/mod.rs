@@ -0,0 +1,295 @@
+//! Ordering utilities grown from `Pair<T>` in the traits lesson's
+//! conditional-implementation section.
+//!
+//! Everything here works with `PartialOrd`, so floats are allowed, under
+//! one NaN policy: a value that is not comparable with itself (NaN) never
+//! takes part in a comparison. [`clamp`] returns `None` for it,
+//! [`MinMax`] skips and counts it, and an [`Interval`] cannot have it as an
+//! endpoint. Methods that need a total order, such as [`Pair::largest`],
+//! only exist for `T: Ord`.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+
+mod interval;
+
+pub use interval::{Bound, Interval, IntervalSet};
+
+/// Whether `value` can be compared at all; false only for NaN-like values.
+fn is_comparable<T: PartialOrd>(value: &T) -> bool {
+    value.partial_cmp(value).is_some()
+}
+
+/// Two values of the same type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Pair<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Available for any `T`.
+impl<T> Pair<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn swap(self) -> Self {
+        Self {
+            x: self.y,
+            y: self.x,
+        }
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Pair<U> {
+        Pair {
+            x: f(self.x),
+            y: f(self.y),
+        }
+    }
+
+    pub fn into_tuple(self) -> (T, T) {
+        (self.x, self.y)
+    }
+}
+
+/// Needs only a partial order, so every answer is an `Option`.
+impl<T: PartialOrd> Pair<T> {
+    /// `None` if `x` and `y` are not comparable.
+    pub fn partial_largest(&self) -> Option<&T> {
+        match self.x.partial_cmp(&self.y)? {
+            Ordering::Less => Some(&self.y),
+            _ => Some(&self.x),
+        }
+    }
+
+    /// `None` if `x` and `y` are not comparable.
+    pub fn partial_smallest(&self) -> Option<&T> {
+        match self.x.partial_cmp(&self.y)? {
+            Ordering::Greater => Some(&self.y),
+            _ => Some(&self.x),
+        }
+    }
+}
+
+/// Needs a total order; ties go to `x`.
+impl<T: Ord> Pair<T> {
+    pub fn largest(&self) -> &T {
+        if self.x >= self.y { &self.x } else { &self.y }
+    }
+
+    pub fn smallest(&self) -> &T {
+        if self.x <= self.y { &self.x } else { &self.y }
+    }
+
+    /// The same values with `x <= y`.
+    pub fn sorted(self) -> Self {
+        if self.x <= self.y { self } else { self.swap() }
+    }
+}
+
+/// The lesson's `cmp_display`, returning the line instead of printing it.
+impl<T: Display + PartialOrd> Pair<T> {
+    pub fn cmp_display(&self) -> String {
+        match self.x.partial_cmp(&self.y) {
+            Some(Ordering::Less) => format!("The largest member is y = {}", self.y),
+            Some(_) => format!("The largest member is x = {}", self.x),
+            None => format!("{} and {} cannot be compared", self.x, self.y),
+        }
+    }
+}
+
+impl<T: Display> Display for Pair<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Pair<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+/// `value` limited to `min..=max`.
+///
+/// Unlike `f64::clamp` this does not panic: `None` if `min > max` or if
+/// any of the three is not comparable.
+pub fn clamp<T: PartialOrd>(value: T, min: T, max: T) -> Option<T> {
+    if !(is_comparable(&value) && is_comparable(&min) && is_comparable(&max)) || min > max {
+        return None;
+    }
+    Some(if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    })
+}
+
+/// Running minimum and maximum of the values pushed into it.
+///
+/// Values that are not comparable (NaN) are skipped and counted in
+/// [`MinMax::skipped`]. On ties the first value is kept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinMax<T> {
+    range: Option<Pair<T>>,
+    count: usize,
+    skipped: usize,
+}
+
+impl<T> Default for MinMax<T> {
+    fn default() -> Self {
+        Self {
+            range: None,
+            count: 0,
+            skipped: 0,
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone> MinMax<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, value: T) {
+        if !is_comparable(&value) {
+            self.skipped += 1;
+            return;
+        }
+        self.count += 1;
+        match &mut self.range {
+            None => self.range = Some(Pair::new(value.clone(), value)),
+            Some(range) => {
+                if value < range.x {
+                    range.x = value;
+                } else if value > range.y {
+                    range.y = value;
+                }
+            }
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.range.as_ref().map(|range| &range.x)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.range.as_ref().map(|range| &range.y)
+    }
+
+    /// Values that took part, not counting skipped ones.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// `Pair { x: min, y: max }`, or `None` if nothing comparable was
+    /// pushed.
+    pub fn into_pair(self) -> Option<Pair<T>> {
+        self.range
+    }
+
+    /// The closed interval from the minimum to the maximum.
+    pub fn to_interval(&self) -> Option<Interval<T>> {
+        let range = self.range.as_ref()?;
+        Interval::closed(range.x.clone(), range.y.clone())
+    }
+}
+
+impl<T: PartialOrd + Clone> Extend<T> for MinMax<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone> FromIterator<T> for MinMax<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut min_max = Self::new();
+        min_max.extend(iter);
+        min_max
+    }
+}
+
+/// The smallest and largest comparable values of `iter` in one pass.
+pub fn min_max<T: PartialOrd + Clone>(iter: impl IntoIterator<Item = T>) -> Option<Pair<T>> {
+    iter.into_iter().collect::<MinMax<T>>().into_pair()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pair_with_integers() {
+        let pair = Pair::new(10, 20);
+        assert_eq!(pair.largest(), &20);
+        assert_eq!(pair.smallest(), &10);
+        assert_eq!(pair.swap().sorted(), pair);
+        assert_eq!(pair.cmp_display(), "The largest member is y = 20");
+        assert_eq!(pair.map(|v| v * 2).to_string(), "(20, 40)");
+        assert_eq!(
+            Pair::from((3, 3)).cmp_display(),
+            "The largest member is x = 3"
+        );
+    }
+
+    #[test]
+    fn pair_with_floats_needs_partial_methods() {
+        let pair = Pair::new(1.5, -0.5);
+        assert_eq!(pair.partial_largest(), Some(&1.5));
+        assert_eq!(pair.partial_smallest(), Some(&-0.5));
+        let nan = Pair::new(f64::NAN, 1.0);
+        assert_eq!(nan.partial_largest(), None);
+        assert_eq!(nan.cmp_display(), "NaN and 1 cannot be compared");
+    }
+
+    #[test]
+    fn pair_with_strings() {
+        let pair = Pair::new(String::from("pear"), String::from("apple"));
+        assert_eq!(pair.largest(), "pear");
+        assert_eq!(pair.clone().sorted().into_tuple().0, "apple");
+        assert_eq!(pair.cmp_display(), "The largest member is x = pear");
+    }
+
+    #[test]
+    fn clamp_policy() {
+        assert_eq!(clamp(15, 0, 10), Some(10));
+        assert_eq!(clamp(-3, 0, 10), Some(0));
+        assert_eq!(clamp(5, 10, 0), None);
+        assert_eq!(clamp(0.25, 0.0, 1.0), Some(0.25));
+        assert_eq!(clamp(f64::NAN, 0.0, 1.0), None);
+        assert_eq!(clamp(0.5, f64::NAN, 1.0), None);
+        assert_eq!(clamp(f64::INFINITY, 0.0, 1.0), Some(1.0));
+        assert_eq!(clamp("m", "a", "f"), Some("f"));
+    }
+
+    #[test]
+    fn min_max_skips_nan() {
+        let values = [3.0, f64::NAN, -1.0, 7.5, f64::NAN, 2.0];
+        let min_max: MinMax<f64> = values.into_iter().collect();
+        assert_eq!((min_max.min(), min_max.max()), (Some(&-1.0), Some(&7.5)));
+        assert_eq!((min_max.count(), min_max.skipped()), (4, 2));
+        assert_eq!(min_max.to_interval().unwrap().to_string(), "[-1, 7.5]");
+
+        let only_nan: MinMax<f64> = [f64::NAN].into_iter().collect();
+        assert_eq!(only_nan.min(), None);
+        assert_eq!(only_nan.into_pair(), None);
+    }
+
+    #[test]
+    fn min_max_with_integers_and_strings() {
+        assert_eq!(min_max([4, 1, 9, 1, 9]), Some(Pair::new(1, 9)));
+        assert_eq!(min_max(Vec::<i32>::new()), None);
+        assert_eq!(
+            min_max(["kiwi", "apple", "pear"]),
+            Some(Pair::new("apple", "pear"))
+        );
+    }
+}
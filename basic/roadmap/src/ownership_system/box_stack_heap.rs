@@ -1,6 +1,9 @@
 use std::mem;
 
-fn main() {
+use crate::collections::List as GenericList;
+use crate::lessons::Section;
+
+pub fn main() {
     println!("=== Box, Stack, and Heap ===\n");
 
     // 1. Stack allocation basics
@@ -25,6 +28,38 @@ fn main() {
     demonstrate_box_ownership();
 }
 
+// Each section on its own, for runs that measure sections separately
+pub const SECTIONS: &[Section] = &[
+    Section {
+        name: "demonstrate_stack_allocation",
+        run: demonstrate_stack_allocation,
+    },
+    Section {
+        name: "demonstrate_heap_allocation",
+        run: demonstrate_heap_allocation,
+    },
+    Section {
+        name: "demonstrate_memory_sizes",
+        run: demonstrate_memory_sizes,
+    },
+    Section {
+        name: "demonstrate_box_dereferencing",
+        run: demonstrate_box_dereferencing,
+    },
+    Section {
+        name: "demonstrate_when_to_use_box",
+        run: demonstrate_when_to_use_box,
+    },
+    Section {
+        name: "demonstrate_stack_frames",
+        run: demonstrate_stack_frames,
+    },
+    Section {
+        name: "demonstrate_box_ownership",
+        run: demonstrate_box_ownership,
+    },
+];
+
 // 1. Stack allocation basics
 fn demonstrate_stack_allocation() {
     println!("1. Stack Allocation Basics");
@@ -99,7 +134,10 @@ fn demonstrate_memory_sizes() {
         bottom_right: Point { x: 3.0, y: -4.0 },
     });
 
-    println!("   Point occupies {} bytes on stack", mem::size_of_val(&point));
+    println!(
+        "   Point occupies {} bytes on stack",
+        mem::size_of_val(&point)
+    );
     println!(
         "   Rectangle occupies {} bytes on stack",
         mem::size_of_val(&rectangle)
@@ -114,7 +152,8 @@ fn demonstrate_memory_sizes() {
     );
 
     println!("\n   Box size = pointer size (8 bytes on 64-bit systems)");
-    println!("   Actual data lives on the heap\n");
+    println!("   Actual data lives on the heap");
+    println!("   Run `cargo run --bin layout` for offsets, padding and niches\n");
 }
 
 // 4. Box dereferencing
@@ -167,6 +206,12 @@ fn demonstrate_when_to_use_box() {
     let _list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
     println!("   Created recursive list: 1 -> 2 -> 3 -> Nil");
 
+    // crate::collections::List is the same idea made generic, with a
+    // non-recursive Drop so long lists cannot overflow the stack
+    let mut generic: GenericList<i32> = (1..=3).collect();
+    generic.push(0);
+    println!("   collections::List after push(0): {}", generic);
+
     // Use case 3: Trait objects (dynamic dispatch)
     println!("\n   Use Case 3: Trait objects");
     trait Animal {
@@ -244,4 +289,4 @@ fn demonstrate_box_ownership() {
     println!("   - One owner at a time");
     println!("   - Automatic cleanup when owner goes out of scope");
     println!("   - Prevents memory leaks and double-free errors\n");
-}
\ No newline at end of file
+}
@@ -0,0 +1,172 @@
+use crate::grid::{Grid, Life, Matrix};
+use crate::lessons::Section;
+
+pub fn main() {
+    println!("=== Capstone: Grids and Matrices ===\n");
+
+    // 1. Rows are slices
+    demonstrate_rows_are_slices();
+
+    // 2. A mutable row borrows the whole grid
+    demonstrate_mutable_rows();
+
+    // 3. Columns are views, not slices
+    demonstrate_column_views();
+
+    // 4. Shapes in the type
+    demonstrate_const_generic_shapes();
+
+    // 5. Checked multiplication
+    demonstrate_checked_multiply();
+
+    // 6. Neighbours and the Game of Life
+    demonstrate_game_of_life();
+}
+
+// Each section on its own, for runs that measure sections separately
+pub const SECTIONS: &[Section] = &[
+    Section {
+        name: "demonstrate_rows_are_slices",
+        run: demonstrate_rows_are_slices,
+    },
+    Section {
+        name: "demonstrate_mutable_rows",
+        run: demonstrate_mutable_rows,
+    },
+    Section {
+        name: "demonstrate_column_views",
+        run: demonstrate_column_views,
+    },
+    Section {
+        name: "demonstrate_const_generic_shapes",
+        run: demonstrate_const_generic_shapes,
+    },
+    Section {
+        name: "demonstrate_checked_multiply",
+        run: demonstrate_checked_multiply,
+    },
+    Section {
+        name: "demonstrate_game_of_life",
+        run: demonstrate_game_of_life,
+    },
+];
+
+// A function that takes a slice works on arrays, Vecs and grid rows alike
+fn total(values: &[u32]) -> u32 {
+    values.iter().sum()
+}
+
+// 1. Rows are slices
+fn demonstrate_rows_are_slices() {
+    println!("1. Rows Are Slices");
+    println!("   A grid stores rows back to back, so each row is a &[T]\n");
+
+    let grid = Grid::from_fn(4, 3, |x, y| (y * 4 + x) as u32).unwrap();
+    println!("{}", indent(&grid.to_string()));
+
+    let array = [1, 2, 3];
+    let vec = vec![4, 5, 6];
+    println!("\n   total(&array)            = {}", total(&array));
+    println!("   total(&vec)              = {}", total(&vec));
+    println!(
+        "   total(grid.row(1)?)      = {}",
+        total(grid.row(1).unwrap())
+    );
+    // Out of range is None, not a panic
+    println!("   grid.row(3)              = {:?}", grid.row(3));
+    println!();
+}
+
+// 2. A mutable row borrows the whole grid
+fn demonstrate_mutable_rows() {
+    println!("2. A Mutable Row Borrows the Whole Grid");
+    println!("   row_mut takes &mut self, so the grid is locked while the row lives\n");
+
+    let mut grid = Grid::filled(3, 2, 0).unwrap();
+    let row = grid.row_mut(0).unwrap();
+    row.copy_from_slice(&[7, 8, 9]);
+    // println!("{}", grid.width()); // Error: `grid` is mutably borrowed by `row`
+    row.reverse();
+
+    // `row` is not used any more, so reading the grid is allowed again
+    println!("{}", indent(&grid.to_string()));
+    println!();
+}
+
+// 3. Columns are views, not slices
+fn demonstrate_column_views() {
+    println!("3. Columns Are Views, Not Slices");
+    println!("   Column cells are `width` apart, so a column is an iterator of &T\n");
+
+    let grid = Grid::from_fn(3, 3, |x, y| x + 10 * y).unwrap();
+    let column: Vec<&usize> = grid.column(1).unwrap().collect();
+    println!("   grid.column(1) = {:?}", column);
+
+    // transpose copies the cells so that the columns become rows
+    let transposed = grid.transpose();
+    println!("   transposed.row(1) = {:?}", transposed.row(1).unwrap());
+    println!();
+}
+
+// 4. Shapes in the type
+fn demonstrate_const_generic_shapes() {
+    println!("4. Shapes in the Type");
+    println!("   Matrix<T, R, C> wraps [[T; C]; R]; R and C are const generics\n");
+
+    let a: Matrix<i32, 2, 3> = Matrix::from_rows([[1, 2, 3], [4, 5, 6]]);
+    let b: Matrix<i32, 3, 2> = a.transpose();
+    println!("   a (2x3):\n{}", indent(&a.to_string()));
+    println!("   a.transpose() (3x2):\n{}", indent(&b.to_string()));
+
+    let product: Matrix<i32, 2, 2> = a * b;
+    println!(
+        "   a * a.transpose() (2x2):\n{}",
+        indent(&product.to_string())
+    );
+    // let wrong = a * a; // Error: expected `Matrix<i32, 3, _>`, found `Matrix<i32, 2, 3>`
+    println!();
+}
+
+// 5. Checked multiplication
+fn demonstrate_checked_multiply() {
+    println!("5. Checked Multiplication");
+    println!("   checked_mul returns None instead of overflowing\n");
+
+    // [[1, 1], [1, 0]] to the power n is [[F(n+1), F(n)], [F(n), F(n-1)]]
+    let fibonacci = Matrix::from_rows([[1u64, 1], [1, 0]]);
+    for n in [10, 50, 92, 93] {
+        match fibonacci.checked_pow(n) {
+            Some(power) => println!("   M^{} gives F({}) = {}", n, n, power.get(0, 1).unwrap()),
+            // F(94) in the top-left corner does not fit
+            None => println!("   M^{} overflows u64", n),
+        }
+    }
+    println!();
+}
+
+// 6. Neighbours and the Game of Life
+fn demonstrate_game_of_life() {
+    println!("6. Neighbours and the Game of Life");
+    println!("   Each step reads the old grid and builds a new one\n");
+
+    let glider: Life = ".#.\n..#\n###".parse().unwrap();
+    let mut life = Life::with_pattern(6, 6, &glider, 0, 0).unwrap();
+    for _ in 0..3 {
+        println!(
+            "   generation {} ({} alive):",
+            life.generation(),
+            life.population()
+        );
+        println!("{}", indent(&life.to_string()));
+        life.step();
+    }
+    println!("   Run it in the terminal with: cargo run --bin life -- glider");
+    println!();
+}
+
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("   {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,10 @@
+// Lessons deliberately show verbose and non-idiomatic forms next to the
+// idiomatic ones: style lints are off, and so is the one against `&s[..]`,
+// which the Book's `first_word` returns. The capstones here, such as
+// `grids_and_matrices.rs`, are still checked by everything else.
+#![allow(clippy::style, clippy::redundant_slicing)]
+
+pub mod box_stack_heap;
+pub mod grids_and_matrices;
+pub mod ownership_rules_and_memory_safety;
+pub mod references_borrowing_slices;
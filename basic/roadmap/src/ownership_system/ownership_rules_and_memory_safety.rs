@@ -1,4 +1,6 @@
-fn main() {
+use crate::lessons::Section;
+
+pub fn main() {
     println!("=== Ownership in Rust ===\n");
 
     // 1. Variable scope
@@ -26,6 +28,42 @@ fn main() {
     demonstrate_scope_and_assignment();
 }
 
+// Each section on its own, for runs that measure sections separately
+pub const SECTIONS: &[Section] = &[
+    Section {
+        name: "demonstrate_variable_scope",
+        run: demonstrate_variable_scope,
+    },
+    Section {
+        name: "demonstrate_string_type",
+        run: demonstrate_string_type,
+    },
+    Section {
+        name: "demonstrate_move_semantics",
+        run: demonstrate_move_semantics,
+    },
+    Section {
+        name: "demonstrate_clone",
+        run: demonstrate_clone,
+    },
+    Section {
+        name: "demonstrate_copy_trait",
+        run: demonstrate_copy_trait,
+    },
+    Section {
+        name: "demonstrate_ownership_and_functions",
+        run: demonstrate_ownership_and_functions,
+    },
+    Section {
+        name: "demonstrate_return_values",
+        run: demonstrate_return_values,
+    },
+    Section {
+        name: "demonstrate_scope_and_assignment",
+        run: demonstrate_scope_and_assignment,
+    },
+];
+
 // 1. Variable scope
 fn demonstrate_variable_scope() {
     println!("1. Variable Scope");
@@ -140,7 +178,7 @@ fn demonstrate_ownership_and_functions() {
     println!("   Before function: s = {}", s);
 
     takes_ownership(s); // s's value moves into the function
-                        // s is no longer valid here
+    // s is no longer valid here
     // println!("   After function: s = {}", s); // Error!
 
     let x = 5; // x comes into scope
@@ -211,4 +249,4 @@ fn demonstrate_scope_and_assignment() {
 fn calculate_length_with_tuple(s: String) -> (String, usize) {
     let length = s.len();
     (s, length) // Return both the String and its length
-}
\ No newline at end of file
+}
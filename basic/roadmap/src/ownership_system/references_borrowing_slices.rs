@@ -1,4 +1,6 @@
-fn main() {
+use crate::lessons::Section;
+
+pub fn main() {
     println!("=== References, Borrowing, and Slices ===\n");
 
     // 1. Basic references (immutable borrowing)
@@ -23,6 +25,38 @@ fn main() {
     demonstrate_practical_examples();
 }
 
+// Each section on its own, for runs that measure sections separately
+pub const SECTIONS: &[Section] = &[
+    Section {
+        name: "demonstrate_basic_references",
+        run: demonstrate_basic_references,
+    },
+    Section {
+        name: "demonstrate_mutable_references",
+        run: demonstrate_mutable_references,
+    },
+    Section {
+        name: "demonstrate_reference_rules",
+        run: demonstrate_reference_rules,
+    },
+    Section {
+        name: "demonstrate_no_dangling_references",
+        run: demonstrate_no_dangling_references,
+    },
+    Section {
+        name: "demonstrate_string_slices",
+        run: demonstrate_string_slices,
+    },
+    Section {
+        name: "demonstrate_array_slices",
+        run: demonstrate_array_slices,
+    },
+    Section {
+        name: "demonstrate_practical_examples",
+        run: demonstrate_practical_examples,
+    },
+];
+
 // 1. Basic references (immutable borrowing)
 fn demonstrate_basic_references() {
     println!("1. Basic References (Immutable Borrowing)");
@@ -260,4 +294,4 @@ fn find_longest_word(s: &str) -> &str {
         }
     }
     longest
-}
\ No newline at end of file
+}
@@ -0,0 +1,88 @@
+use super::{Bin, orientations};
+use crate::geometry::{Point, Rectangle};
+
+/// Free space as disjoint rectangles. Each placement cuts its free
+/// rectangle into at most two smaller ones, like a guillotine cut.
+pub(super) struct GuillotineBin {
+    free: Vec<Rectangle<u32>>,
+}
+
+impl GuillotineBin {
+    pub(super) fn new(width: u32, height: u32) -> Self {
+        Self {
+            free: vec![Rectangle::new(width, height)],
+        }
+    }
+}
+
+impl Bin for GuillotineBin {
+    fn insert(
+        &mut self,
+        width: u32,
+        height: u32,
+        allow_rotation: bool,
+    ) -> Option<(Rectangle<u32>, bool)> {
+        // Best area fit: the free rectangle with the least area left over.
+        let (index, (w, h, rotated)) = self
+            .free
+            .iter()
+            .enumerate()
+            .flat_map(|(i, free)| {
+                orientations(width, height, allow_rotation)
+                    .filter(|&(w, h, _)| free.can_fit(&Rectangle::new(w, h)))
+                    .map(move |o| (i, o))
+            })
+            .min_by_key(|&(i, (w, h, _))| {
+                let free = &self.free[i];
+                u64::from(free.width()) * u64::from(free.height()) - u64::from(w) * u64::from(h)
+            })?;
+
+        let free = self.free.swap_remove(index);
+        let origin = free.origin();
+        let leftover_w = free.width() - w;
+        let leftover_h = free.height() - h;
+
+        // Split along the shorter leftover axis so the larger piece stays
+        // as square as possible.
+        let (right, top) = if leftover_w <= leftover_h {
+            (
+                Rectangle::at(Point::new(origin.x + w, origin.y), leftover_w, h),
+                Rectangle::at(Point::new(origin.x, origin.y + h), free.width(), leftover_h),
+            )
+        } else {
+            (
+                Rectangle::at(
+                    Point::new(origin.x + w, origin.y),
+                    leftover_w,
+                    free.height(),
+                ),
+                Rectangle::at(Point::new(origin.x, origin.y + h), w, leftover_h),
+            )
+        };
+        self.free
+            .extend([right, top].into_iter().filter(|r| !r.is_empty()));
+
+        Some((Rectangle::at(origin, w, h), rotated))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cuts_leave_disjoint_free_space() {
+        let mut bin = GuillotineBin::new(100, 80);
+        assert_eq!(
+            bin.insert(30, 80, false),
+            Some((Rectangle::new(30, 80), false))
+        );
+        // The cut left exactly the 70x80 strip to the right
+        assert_eq!(bin.free, [Rectangle::at(Point::new(30, 0), 70, 80)]);
+        assert_eq!(
+            bin.insert(80, 70, true),
+            Some((Rectangle::at(Point::new(30, 0), 70, 80), true))
+        );
+        assert_eq!(bin.insert(1, 1, true), None);
+    }
+}
@@ -0,0 +1,130 @@
+use super::{Bin, orientations};
+use crate::geometry::{Point, Rectangle};
+
+/// Free space as the list of all maximal free rectangles. They overlap,
+/// which is what lets MaxRects find placements guillotine cuts rule out.
+pub(super) struct MaxRectsBin {
+    free: Vec<Rectangle<u32>>,
+}
+
+impl MaxRectsBin {
+    pub(super) fn new(width: u32, height: u32) -> Self {
+        Self {
+            free: vec![Rectangle::new(width, height)],
+        }
+    }
+
+    /// Cuts `used` out of every free rectangle it overlaps, keeping the up
+    /// to four maximal pieces around it.
+    fn split_free(&mut self, used: &Rectangle<u32>) {
+        let (u0, u1) = (used.origin(), used.far_corner());
+        let mut pieces = Vec::new();
+        self.free.retain(|free| {
+            if !free.overlaps(used) {
+                return true;
+            }
+            let (f0, f1) = (free.origin(), free.far_corner());
+            if u0.x > f0.x {
+                pieces.push(Rectangle::at(f0, u0.x - f0.x, free.height()));
+            }
+            if u1.x < f1.x {
+                pieces.push(Rectangle::at(
+                    Point::new(u1.x, f0.y),
+                    f1.x - u1.x,
+                    free.height(),
+                ));
+            }
+            if u0.y > f0.y {
+                pieces.push(Rectangle::at(f0, free.width(), u0.y - f0.y));
+            }
+            if u1.y < f1.y {
+                pieces.push(Rectangle::at(
+                    Point::new(f0.x, u1.y),
+                    free.width(),
+                    f1.y - u1.y,
+                ));
+            }
+            false
+        });
+        self.free.extend(pieces);
+    }
+
+    /// Drops free rectangles contained in another one.
+    fn prune(&mut self) {
+        let mut i = 0;
+        while i < self.free.len() {
+            let redundant = (0..self.free.len()).any(|j| {
+                j != i
+                    && self.free[j].contains(&self.free[i])
+                    // Of two identical rectangles keep the first.
+                    && (self.free[i] != self.free[j] || j < i)
+            });
+            if redundant {
+                self.free.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+impl Bin for MaxRectsBin {
+    fn insert(
+        &mut self,
+        width: u32,
+        height: u32,
+        allow_rotation: bool,
+    ) -> Option<(Rectangle<u32>, bool)> {
+        // Best short side fit: minimise the smaller leftover, then the larger.
+        let (origin, (w, h, rotated)) = self
+            .free
+            .iter()
+            .flat_map(|free| {
+                orientations(width, height, allow_rotation)
+                    .filter(|&(w, h, _)| free.can_fit(&Rectangle::new(w, h)))
+                    .map(move |o| (free, o))
+            })
+            .min_by_key(|&(free, (w, h, _))| {
+                let (dw, dh) = (free.width() - w, free.height() - h);
+                (dw.min(dh), dw.max(dh))
+            })
+            .map(|(free, o)| (free.origin(), o))?;
+
+        let used = Rectangle::at(origin, w, h);
+        self.split_free(&used);
+        self.prune();
+        Some((used, rotated))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_maximal_free_rectangles() {
+        let mut bin = MaxRectsBin::new(10, 10);
+        bin.insert(4, 10, false).unwrap();
+        assert_eq!(bin.free, [Rectangle::at(Point::new(4, 0), 6, 10)]);
+        assert_eq!(
+            bin.insert(6, 4, false),
+            Some((Rectangle::at(Point::new(4, 0), 6, 4), false))
+        );
+        assert_eq!(
+            bin.insert(6, 6, false),
+            Some((Rectangle::at(Point::new(4, 4), 6, 6), false))
+        );
+        assert!(bin.free.is_empty());
+        assert_eq!(bin.insert(1, 1, true), None);
+    }
+
+    #[test]
+    fn overlapping_free_rectangles_are_pruned() {
+        let mut bin = MaxRectsBin::new(10, 10);
+        bin.insert(3, 3, false).unwrap();
+        // The L-shaped rest is two overlapping maximal rectangles
+        assert_eq!(bin.free.len(), 2);
+        assert!(bin.free.contains(&Rectangle::at(Point::new(3, 0), 7, 10)));
+        assert!(bin.free.contains(&Rectangle::at(Point::new(0, 3), 10, 7)));
+    }
+}
@@ -0,0 +1,424 @@
+//! Rectangle bin packing and a quadtree for region queries.
+//!
+//! `Rectangle::can_hold` answers whether one rectangle fits inside another;
+//! this module answers where a whole list of them should go. Every
+//! heuristic places items into fixed-size bins, opening a new bin only when
+//! an item fits in none of the open ones.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::geometry::Rectangle;
+
+mod guillotine;
+mod maxrects;
+mod quadtree;
+mod shelf;
+
+pub use quadtree::QuadTree;
+
+/// How free space inside a bin is tracked and chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heuristic {
+    /// Rows of items; a new shelf starts above the tallest item of the last.
+    Shelf,
+    /// Disjoint free rectangles, best area fit, split along the shorter
+    /// leftover axis.
+    Guillotine,
+    /// Overlapping maximal free rectangles, best short side fit.
+    MaxRects,
+}
+
+impl Heuristic {
+    pub const ALL: [Heuristic; 3] = [Heuristic::Shelf, Heuristic::Guillotine, Heuristic::MaxRects];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Heuristic::Shelf => "shelf",
+            Heuristic::Guillotine => "guillotine",
+            Heuristic::MaxRects => "maxrects",
+        }
+    }
+}
+
+impl fmt::Display for Heuristic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownHeuristic(pub String);
+
+impl fmt::Display for UnknownHeuristic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown heuristic `{}` (expected shelf, guillotine or maxrects)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownHeuristic {}
+
+impl FromStr for Heuristic {
+    type Err = UnknownHeuristic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Heuristic::ALL
+            .into_iter()
+            .find(|h| h.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownHeuristic(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackOptions {
+    pub heuristic: Heuristic,
+    /// Allow items to be turned 90 degrees.
+    pub allow_rotation: bool,
+}
+
+impl Default for PackOptions {
+    fn default() -> Self {
+        Self {
+            heuristic: Heuristic::MaxRects,
+            allow_rotation: false,
+        }
+    }
+}
+
+/// Where one input item ended up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    /// Index of the item in the slice passed to [`pack`].
+    pub item: usize,
+    pub bin: usize,
+    /// Position and extent inside the bin; already rotated if `rotated`.
+    pub rect: Rectangle<u32>,
+    pub rotated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Packing {
+    pub bin_width: u32,
+    pub bin_height: u32,
+    /// Placements in the order they were made, which is not input order.
+    pub placements: Vec<Placement>,
+    /// Indexes of items that are empty or larger than a bin.
+    pub unplaced: Vec<usize>,
+    pub bin_count: usize,
+}
+
+impl Packing {
+    fn bin_area(&self) -> u64 {
+        u64::from(self.bin_width) * u64::from(self.bin_height)
+    }
+
+    fn used_area(&self, bin: Option<usize>) -> u64 {
+        self.placements
+            .iter()
+            .filter(|p| bin.is_none_or(|b| p.bin == b))
+            .map(|p| u64::from(p.rect.width()) * u64::from(p.rect.height()))
+            .sum()
+    }
+
+    /// Fraction of one bin's area covered by items, between 0 and 1.
+    pub fn bin_utilization(&self, bin: usize) -> f64 {
+        ratio(self.used_area(Some(bin)), self.bin_area())
+    }
+
+    /// Fraction of the area of all opened bins covered by items.
+    pub fn utilization(&self) -> f64 {
+        ratio(
+            self.used_area(None),
+            self.bin_area() * self.bin_count as u64,
+        )
+    }
+}
+
+fn ratio(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        used as f64 / total as f64
+    }
+}
+
+/// Free-space bookkeeping for a single bin.
+trait Bin {
+    /// Places a `width` x `height` item, returning its rectangle and whether
+    /// it was rotated, or `None` if it does not fit anywhere in this bin.
+    fn insert(
+        &mut self,
+        width: u32,
+        height: u32,
+        allow_rotation: bool,
+    ) -> Option<(Rectangle<u32>, bool)>;
+}
+
+fn new_bin(heuristic: Heuristic, width: u32, height: u32) -> Box<dyn Bin> {
+    match heuristic {
+        Heuristic::Shelf => Box::new(shelf::ShelfBin::new(width, height)),
+        Heuristic::Guillotine => Box::new(guillotine::GuillotineBin::new(width, height)),
+        Heuristic::MaxRects => Box::new(maxrects::MaxRectsBin::new(width, height)),
+    }
+}
+
+/// The orientations worth trying for an item, unrotated first.
+fn orientations(
+    width: u32,
+    height: u32,
+    allow_rotation: bool,
+) -> impl Iterator<Item = (u32, u32, bool)> {
+    let rotated = (allow_rotation && width != height).then_some((height, width, true));
+    std::iter::once((width, height, false)).chain(rotated)
+}
+
+/// Packs `items` into bins of `bin`'s size. Only the extents of `items` and
+/// `bin` matter; their origins are ignored.
+///
+/// Items are placed largest first (by longer side, then area), which is
+/// what makes the greedy heuristics work well.
+pub fn pack(items: &[Rectangle<u32>], bin: &Rectangle<u32>, options: &PackOptions) -> Packing {
+    let (bin_width, bin_height) = (bin.width(), bin.height());
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by_key(|&i| {
+        let (w, h) = (items[i].width(), items[i].height());
+        std::cmp::Reverse((w.max(h), u64::from(w) * u64::from(h)))
+    });
+
+    let mut bins: Vec<Box<dyn Bin>> = Vec::new();
+    let mut placements = Vec::with_capacity(items.len());
+    let mut unplaced = Vec::new();
+
+    for index in order {
+        let item = &items[index];
+        let fits_empty_bin = orientations(item.width(), item.height(), options.allow_rotation)
+            .any(|(w, h, _)| bin.can_fit(&Rectangle::new(w, h)));
+        if item.is_empty() || !fits_empty_bin {
+            unplaced.push(index);
+            continue;
+        }
+
+        let mut placed = bins.iter_mut().enumerate().find_map(|(bin_index, b)| {
+            b.insert(item.width(), item.height(), options.allow_rotation)
+                .map(|found| (bin_index, found))
+        });
+        if placed.is_none() {
+            let mut fresh = new_bin(options.heuristic, bin_width, bin_height);
+            let found = fresh
+                .insert(item.width(), item.height(), options.allow_rotation)
+                .expect("item fits an empty bin");
+            bins.push(fresh);
+            placed = Some((bins.len() - 1, found));
+        }
+
+        let (bin_index, (rect, rotated)) = placed.expect("placed above");
+        placements.push(Placement {
+            item: index,
+            bin: bin_index,
+            rect,
+            rotated,
+        });
+    }
+
+    unplaced.sort_unstable();
+    Packing {
+        bin_width,
+        bin_height,
+        placements,
+        unplaced,
+        bin_count: bins.len(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSizesError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseSizesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseSizesError {}
+
+/// Parses a size written as `WxH` or `W H`.
+pub fn parse_size(text: &str) -> Option<Rectangle<u32>> {
+    let (w, h) = text
+        .split_once(['x', 'X'])
+        .or_else(|| text.split_once(char::is_whitespace))?;
+    Some(Rectangle::new(
+        w.trim().parse().ok()?,
+        h.trim().parse().ok()?,
+    ))
+}
+
+/// Parses one size per line. Blank lines and `#` comments are skipped.
+pub fn parse_sizes(input: &str) -> Result<Vec<Rectangle<u32>>, ParseSizesError> {
+    let mut sizes = Vec::new();
+    for (number, line) in input.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let size = parse_size(line).ok_or_else(|| ParseSizesError {
+            line: number + 1,
+            message: format!("expected `WIDTHxHEIGHT`, found `{line}`"),
+        })?;
+        sizes.push(size);
+    }
+    Ok(sizes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fixed mix of sizes from a small linear congruential generator,
+    /// plus items that are empty, too large either way, or only fit a
+    /// 100x80 bin when turned.
+    fn items() -> Vec<Rectangle<u32>> {
+        let mut seed = 7u32;
+        let mut next = |limit: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 16) % limit
+        };
+        let mut items: Vec<_> = (0..60)
+            .map(|_| Rectangle::new(next(60) + 1, next(50) + 1))
+            .collect();
+        items.extend([
+            Rectangle::new(0, 10),
+            Rectangle::new(120, 10),
+            Rectangle::new(70, 95),
+            Rectangle::new(100, 80),
+        ]);
+        items
+    }
+
+    fn check(items: &[Rectangle<u32>], bin: &Rectangle<u32>, options: &PackOptions) {
+        let packing = pack(items, bin, options);
+        let area = Rectangle::new(bin.width(), bin.height());
+        let context = format!("{} rotation={}", options.heuristic, options.allow_rotation);
+
+        for p in &packing.placements {
+            assert!(p.bin < packing.bin_count, "{context}");
+            assert!(area.contains(&p.rect), "{context}: {} outside", p.rect);
+            let item = &items[p.item];
+            let extent = (p.rect.width(), p.rect.height());
+            if p.rotated {
+                assert!(options.allow_rotation, "{context}");
+                assert_eq!(extent, (item.height(), item.width()), "{context}");
+            } else {
+                assert_eq!(extent, (item.width(), item.height()), "{context}");
+            }
+        }
+        for (i, p) in packing.placements.iter().enumerate() {
+            for q in &packing.placements[i + 1..] {
+                assert!(
+                    p.bin != q.bin || !p.rect.overlaps(&q.rect),
+                    "{context}: {} overlaps {}",
+                    p.rect,
+                    q.rect
+                );
+            }
+        }
+
+        let fits = |w, h| area.can_fit(&Rectangle::new(w, h));
+        let expected_unplaced: Vec<usize> = (0..items.len())
+            .filter(|&i| {
+                let (w, h) = (items[i].width(), items[i].height());
+                items[i].is_empty() || !(fits(w, h) || options.allow_rotation && fits(h, w))
+            })
+            .collect();
+        assert_eq!(packing.unplaced, expected_unplaced, "{context}");
+        let mut seen: Vec<usize> = packing.placements.iter().map(|p| p.item).collect();
+        seen.extend(&packing.unplaced);
+        seen.sort_unstable();
+        assert_eq!(seen, (0..items.len()).collect::<Vec<_>>(), "{context}");
+
+        let used: u64 = packing
+            .placements
+            .iter()
+            .map(|p| u64::from(items[p.item].width()) * u64::from(items[p.item].height()))
+            .sum();
+        let total = packing.bin_count as u64 * u64::from(bin.width()) * u64::from(bin.height());
+        assert_eq!(
+            packing.utilization(),
+            used as f64 / total as f64,
+            "{context}"
+        );
+        for b in 0..packing.bin_count {
+            let fraction = packing.bin_utilization(b);
+            assert!(fraction > 0.0 && fraction <= 1.0, "{context}: bin {b}");
+        }
+    }
+
+    #[test]
+    fn every_heuristic_packs_validly() {
+        let items = items();
+        let bin = Rectangle::new(100, 80);
+        for heuristic in Heuristic::ALL {
+            for allow_rotation in [false, true] {
+                check(
+                    &items,
+                    &bin,
+                    &PackOptions {
+                        heuristic,
+                        allow_rotation,
+                    },
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn only_rotation_places_a_turned_item() {
+        let items = [Rectangle::new(70, 95)];
+        let bin = Rectangle::new(100, 80);
+        for heuristic in Heuristic::ALL {
+            let mut options = PackOptions {
+                heuristic,
+                allow_rotation: false,
+            };
+            assert_eq!(pack(&items, &bin, &options).unplaced, [0]);
+            options.allow_rotation = true;
+            let packing = pack(&items, &bin, &options);
+            assert!(packing.placements[0].rotated, "{heuristic}");
+            assert_eq!(packing.bin_count, 1);
+        }
+    }
+
+    #[test]
+    fn nothing_to_pack() {
+        let packing = pack(&[], &Rectangle::new(10, 10), &PackOptions::default());
+        assert_eq!(packing.bin_count, 0);
+        assert_eq!(packing.utilization(), 0.0);
+    }
+
+    #[test]
+    fn parses_sizes_with_line_numbers() {
+        assert_eq!(
+            parse_sizes("4x3\n\n# a comment\n5 6\n2X3 # trailing\n"),
+            Ok(vec![
+                Rectangle::new(4, 3),
+                Rectangle::new(5, 6),
+                Rectangle::new(2, 3)
+            ])
+        );
+        let err = parse_sizes("4x3\n\n# a comment\n5 by 6\n").unwrap_err();
+        assert_eq!(err.line, 4);
+        assert_eq!(
+            err.to_string(),
+            "line 4: expected `WIDTHxHEIGHT`, found `5 by 6`"
+        );
+        assert_eq!(parse_sizes("-1x3").unwrap_err().line, 1);
+        assert_eq!("MaxRects".parse(), Ok(Heuristic::MaxRects));
+        assert!("skyline".parse::<Heuristic>().is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::geometry::{Point, Rectangle};
+
+const NODE_CAPACITY: usize = 8;
+const MAX_DEPTH: usize = 8;
+
+/// A region quadtree over `u32` rectangles.
+///
+/// Each node keeps the items that do not fit entirely inside one of its
+/// quadrants; everything else is pushed down once a node holds more than
+/// a handful of items. The tree owns its values and hands out references.
+#[derive(Debug)]
+pub struct QuadTree<T> {
+    bounds: Rectangle<u32>,
+    depth: usize,
+    items: Vec<(Rectangle<u32>, T)>,
+    children: Option<Box<[QuadTree<T>; 4]>>,
+}
+
+impl<T> QuadTree<T> {
+    pub fn new(bounds: Rectangle<u32>) -> Self {
+        Self::with_depth(bounds, 0)
+    }
+
+    fn with_depth(bounds: Rectangle<u32>, depth: usize) -> Self {
+        Self {
+            bounds,
+            depth,
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    pub fn bounds(&self) -> &Rectangle<u32> {
+        &self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        let below = self
+            .children
+            .iter()
+            .flat_map(|c| c.iter())
+            .map(QuadTree::len);
+        self.items.len() + below.sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Stores `value` under `rect`. If `rect` is not inside the tree's
+    /// bounds the value is handed back unchanged.
+    pub fn insert(&mut self, rect: Rectangle<u32>, value: T) -> Result<(), T> {
+        if !self.bounds.contains(&rect) {
+            return Err(value);
+        }
+        self.insert_contained(rect, value);
+        Ok(())
+    }
+
+    fn insert_contained(&mut self, rect: Rectangle<u32>, value: T) {
+        if let Some(children) = &mut self.children
+            && let Some(child) = children.iter_mut().find(|c| c.bounds.contains(&rect))
+        {
+            child.insert_contained(rect, value);
+            return;
+        }
+        self.items.push((rect, value));
+        if self.children.is_none() && self.items.len() > NODE_CAPACITY {
+            self.subdivide();
+        }
+    }
+
+    fn subdivide(&mut self) {
+        let (w, h) = (self.bounds.width(), self.bounds.height());
+        if self.depth >= MAX_DEPTH || w < 2 || h < 2 {
+            return;
+        }
+        let Point { x, y } = self.bounds.origin();
+        let (hw, hh) = (w / 2, h / 2);
+        let depth = self.depth + 1;
+        self.children = Some(Box::new([
+            QuadTree::with_depth(Rectangle::at(Point::new(x, y), hw, hh), depth),
+            QuadTree::with_depth(Rectangle::at(Point::new(x + hw, y), w - hw, hh), depth),
+            QuadTree::with_depth(Rectangle::at(Point::new(x, y + hh), hw, h - hh), depth),
+            QuadTree::with_depth(
+                Rectangle::at(Point::new(x + hw, y + hh), w - hw, h - hh),
+                depth,
+            ),
+        ]));
+        // Move every item out and back in; the ones that fit a quadrant now
+        // land there, the rest return to this node.
+        for (rect, value) in std::mem::take(&mut self.items) {
+            self.insert_contained(rect, value);
+        }
+    }
+
+    /// Items whose rectangle overlaps `region` with positive area.
+    pub fn query(&self, region: &Rectangle<u32>) -> Vec<(&Rectangle<u32>, &T)> {
+        let mut found = Vec::new();
+        self.collect(&mut found, &|rect| rect.overlaps(region), &|bounds| {
+            bounds.overlaps(region)
+        });
+        found
+    }
+
+    /// Items whose rectangle contains `point`, boundaries included.
+    pub fn query_point(&self, point: Point<u32>) -> Vec<(&Rectangle<u32>, &T)> {
+        let mut found = Vec::new();
+        self.collect(&mut found, &|rect| rect.contains_point(point), &|bounds| {
+            bounds.contains_point(point)
+        });
+        found
+    }
+
+    fn collect<'a>(
+        &'a self,
+        found: &mut Vec<(&'a Rectangle<u32>, &'a T)>,
+        matches: &dyn Fn(&Rectangle<u32>) -> bool,
+        visit: &dyn Fn(&Rectangle<u32>) -> bool,
+    ) {
+        found.extend(
+            self.items
+                .iter()
+                .filter(|(rect, _)| matches(rect))
+                .map(|(rect, value)| (rect, value)),
+        );
+        for child in self.children.iter().flat_map(|c| c.iter()) {
+            if visit(&child.bounds) {
+                child.collect(found, matches, visit);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(found: Vec<(&Rectangle<u32>, &usize)>) -> Vec<usize> {
+        let mut ids: Vec<usize> = found.into_iter().map(|(_, &id)| id).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn queries_match_a_brute_force_scan() {
+        let mut seed = 11u32;
+        let mut next = |limit: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 16) % limit
+        };
+        let mut tree = QuadTree::new(Rectangle::new(256, 256));
+        let mut rects = Vec::new();
+        for id in 0..300 {
+            let (w, h) = (next(40), next(40));
+            let rect = Rectangle::at(Point::new(next(256 - w), next(256 - h)), w, h);
+            tree.insert(rect, id).unwrap();
+            rects.push(rect);
+        }
+        assert_eq!(tree.len(), rects.len());
+        assert!(tree.children.is_some(), "300 items force a subdivide");
+
+        for _ in 0..200 {
+            let (w, h) = (next(100), next(100));
+            let region = Rectangle::at(Point::new(next(256 - w), next(256 - h)), w, h);
+            let expected: Vec<usize> = (0..rects.len())
+                .filter(|&i| rects[i].overlaps(&region))
+                .collect();
+            assert_eq!(ids(tree.query(&region)), expected, "{region}");
+
+            let point = Point::new(next(257), next(257));
+            let expected: Vec<usize> = (0..rects.len())
+                .filter(|&i| rects[i].contains_point(point))
+                .collect();
+            assert_eq!(ids(tree.query_point(point)), expected, "{point}");
+        }
+    }
+
+    #[test]
+    fn rejects_rectangles_outside_its_bounds() {
+        let mut tree = QuadTree::new(Rectangle::new(10, 10));
+        assert_eq!(
+            tree.insert(Rectangle::at(Point::new(5, 5), 6, 1), 'a'),
+            Err('a')
+        );
+        assert!(tree.is_empty());
+        assert_eq!(
+            tree.insert(Rectangle::at(Point::new(5, 5), 5, 5), 'b'),
+            Ok(())
+        );
+        assert_eq!(tree.len(), 1);
+    }
+}
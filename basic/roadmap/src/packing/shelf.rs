@@ -0,0 +1,93 @@
+use super::{Bin, orientations};
+use crate::geometry::{Point, Rectangle};
+
+struct Shelf {
+    y: u32,
+    height: u32,
+    used_width: u32,
+}
+
+pub(super) struct ShelfBin {
+    width: u32,
+    height: u32,
+    shelves: Vec<Shelf>,
+}
+
+impl ShelfBin {
+    pub(super) fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            shelves: Vec::new(),
+        }
+    }
+
+    fn next_shelf_y(&self) -> u32 {
+        self.shelves.last().map_or(0, |s| s.y + s.height)
+    }
+}
+
+impl Bin for ShelfBin {
+    fn insert(
+        &mut self,
+        width: u32,
+        height: u32,
+        allow_rotation: bool,
+    ) -> Option<(Rectangle<u32>, bool)> {
+        // First shelf with room, using the orientation that wastes the least
+        // shelf height.
+        for shelf in &mut self.shelves {
+            let best = orientations(width, height, allow_rotation)
+                .filter(|&(w, h, _)| h <= shelf.height && w <= self.width - shelf.used_width)
+                .min_by_key(|&(_, h, _)| shelf.height - h);
+            if let Some((w, h, rotated)) = best {
+                let rect = Rectangle::at(Point::new(shelf.used_width, shelf.y), w, h);
+                shelf.used_width += w;
+                return Some((rect, rotated));
+            }
+        }
+
+        // Otherwise open a new shelf, as low as possible.
+        let y = self.next_shelf_y();
+        let (w, h, rotated) = orientations(width, height, allow_rotation)
+            .filter(|&(w, h, _)| w <= self.width && h <= self.height - y)
+            .min_by_key(|&(_, h, _)| h)?;
+        self.shelves.push(Shelf {
+            y,
+            height: h,
+            used_width: w,
+        });
+        Some((Rectangle::at(Point::new(0, y), w, h), rotated))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_shelves_left_to_right_then_upwards() {
+        let mut bin = ShelfBin::new(100, 80);
+        let place = |bin: &mut ShelfBin, w, h| bin.insert(w, h, false).map(|(rect, _)| rect);
+        assert_eq!(place(&mut bin, 60, 30), Some(Rectangle::new(60, 30)));
+        // 50 more does not fit beside the first item, so a shelf opens above
+        assert_eq!(
+            place(&mut bin, 50, 20),
+            Some(Rectangle::at(Point::new(0, 30), 50, 20))
+        );
+        assert_eq!(
+            place(&mut bin, 40, 20),
+            Some(Rectangle::at(Point::new(60, 0), 40, 20))
+        );
+        assert_eq!(place(&mut bin, 10, 31), None);
+    }
+
+    #[test]
+    fn rotates_to_waste_less_shelf_height() {
+        let mut bin = ShelfBin::new(100, 80);
+        bin.insert(50, 10, true).unwrap();
+        let (rect, rotated) = bin.insert(10, 40, true).unwrap();
+        assert!(rotated);
+        assert_eq!(rect, Rectangle::at(Point::new(50, 0), 40, 10));
+    }
+}
@@ -0,0 +1,257 @@
+//! The subset of TOML that `pack.toml` is written in: `key = value` lines
+//! with string, integer and string-list values, and `[[lesson]]` headers
+//! that start a new lesson table. Comments start with `#`.
+//!
+//! ```toml
+//! name = "acme"
+//! title = "ACME conventions"
+//!
+//! [[lesson]]
+//! id = "serialization"
+//! minutes = 20
+//! tags = ["serde", "conventions"]
+//! ```
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    List(Vec<String>),
+}
+
+impl Value {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::String(_) => "a string",
+            Value::Integer(_) => "an integer",
+            Value::List(_) => "a list of strings",
+        }
+    }
+}
+
+/// One key and where it was set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub key: String,
+    pub line: usize,
+    pub value: Value,
+}
+
+/// The top-level keys, or one `[[lesson]]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    /// Line of the `[[lesson]]` header; 0 for the top level.
+    pub line: usize,
+    pub fields: Vec<Field>,
+}
+
+impl Table {
+    pub fn get(&self, key: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.key == key)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub pack: Table,
+    pub lessons: Vec<Table>,
+}
+
+/// A syntax error: the line, the key if one was read, and what is wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub key: Option<String>,
+    pub message: String,
+}
+
+pub fn parse(text: &str) -> Result<Manifest, SyntaxError> {
+    let mut manifest = Manifest::default();
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let error = |key: Option<&str>, message: String| SyntaxError {
+            line,
+            key: key.map(str::to_string),
+            message,
+        };
+        let text = strip_comment(raw).trim();
+        if text.is_empty() {
+            continue;
+        }
+        if text.starts_with('[') {
+            if text != "[[lesson]]" {
+                return Err(error(
+                    None,
+                    format!("unknown table `{text}`; expected `[[lesson]]`"),
+                ));
+            }
+            manifest.lessons.push(Table {
+                line,
+                fields: Vec::new(),
+            });
+            continue;
+        }
+
+        let (key, value) = text
+            .split_once('=')
+            .ok_or_else(|| error(None, "expected `key = value`".into()))?;
+        let key = key.trim();
+        let valid = !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(error(None, format!("`{key}` is not a bare key")));
+        }
+        let value = parse_value(value.trim()).map_err(|message| error(Some(key), message))?;
+        let table = manifest.lessons.last_mut().unwrap_or(&mut manifest.pack);
+        if table.get(key).is_some() {
+            return Err(error(Some(key), "set twice".into()));
+        }
+        table.fields.push(Field {
+            key: key.to_string(),
+            line,
+            value,
+        });
+    }
+    Ok(manifest)
+}
+
+/// `line` up to a `#` that is not inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (at, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..at],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(text: &str) -> Result<Value, String> {
+    if text.starts_with('"') {
+        let (value, rest) = parse_string(text)?;
+        return match rest.trim() {
+            "" => Ok(Value::String(value)),
+            rest => Err(format!("unexpected `{rest}` after the string")),
+        };
+    }
+    if let Some(inner) = text.strip_prefix('[') {
+        let mut items = Vec::new();
+        let mut rest = inner.trim_start();
+        loop {
+            if let Some(after) = rest.strip_prefix(']') {
+                if !after.trim().is_empty() {
+                    return Err(format!("unexpected `{}` after the list", after.trim()));
+                }
+                return Ok(Value::List(items));
+            }
+            if !rest.starts_with('"') {
+                return Err("lists hold strings in double quotes, on one line".into());
+            }
+            let (item, after) = parse_string(rest)?;
+            items.push(item);
+            rest = after.trim_start();
+            if let Some(after) = rest.strip_prefix(',') {
+                rest = after.trim_start();
+            } else if !rest.starts_with(']') {
+                return Err("expected `,` or `]` in the list".into());
+            }
+        }
+    }
+    text.parse::<i64>()
+        .map(Value::Integer)
+        .map_err(|_| format!("`{text}` is not a string, integer or list"))
+}
+
+/// A `"..."` string at the start of `text` and whatever follows it.
+fn parse_string(text: &str) -> Result<(String, &str), String> {
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1);
+    while let Some((at, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &text[at + 1..])),
+            '\\' => value.push(match chars.next().map(|(_, c)| c) {
+                Some('n') => '\n',
+                Some('t') => '\t',
+                Some('"') => '"',
+                Some('\\') => '\\',
+                other => return Err(format!("unknown escape `\\{}`", other.unwrap_or(' '))),
+            }),
+            c => value.push(c),
+        }
+    }
+    Err("unterminated string".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_tables_and_values() {
+        let manifest = parse(
+            "# ACME\nname = \"acme\" # trailing\n\n[[lesson]]\nid = \"a#b\"\nminutes = 20\n\
+             tags = [\"x\", \"y\\\"z\"]\nrequires = []\n[[lesson]]\nid = \"c\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            manifest.pack.get("name").unwrap().value,
+            Value::String("acme".into())
+        );
+        assert_eq!(manifest.lessons.len(), 2);
+        let lesson = &manifest.lessons[0];
+        assert_eq!(lesson.line, 4);
+        assert_eq!(lesson.get("id").unwrap().value, Value::String("a#b".into()));
+        assert_eq!(lesson.get("minutes").unwrap().value, Value::Integer(20));
+        assert_eq!(
+            lesson.get("tags").unwrap().value,
+            Value::List(vec!["x".into(), "y\"z".into()])
+        );
+        assert_eq!(lesson.get("requires").unwrap().value, Value::List(vec![]));
+        assert_eq!(lesson.get("tags").unwrap().line, 7);
+    }
+
+    #[test]
+    fn errors_name_the_line_and_key() {
+        let err = |text: &str| {
+            let err = parse(text).unwrap_err();
+            (err.line, err.key, err.message)
+        };
+        assert_eq!(
+            err("name = \"a\"\n[lesson]\n"),
+            (
+                2,
+                None,
+                "unknown table `[lesson]`; expected `[[lesson]]`".into()
+            )
+        );
+        assert_eq!(
+            err("minutes = twenty"),
+            (
+                1,
+                Some("minutes".into()),
+                "`twenty` is not a string, integer or list".into()
+            )
+        );
+        assert_eq!(
+            err("tags = [\"a\" \"b\"]"),
+            (
+                1,
+                Some("tags".into()),
+                "expected `,` or `]` in the list".into()
+            )
+        );
+        assert_eq!(
+            err("id = \"a\"\nid = \"b\""),
+            (2, Some("id".into()), "set twice".into())
+        );
+        assert_eq!(err("title = \"open").2, "unterminated string");
+        assert_eq!(err("just words").2, "expected `key = value`");
+    }
+}
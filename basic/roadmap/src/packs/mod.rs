@@ -0,0 +1,559 @@
+//! Lesson packs: lessons kept outside the crate and loaded at run time.
+//!
+//! A pack is a directory with a `pack.toml` manifest (see [`manifest`]),
+//! Markdown notes, an optional quiz file (see [`quiz`]) and Rust snippets,
+//! each a whole program that `roadmap run` compiles and runs with
+//! [`crate::runner`]:
+//!
+//! ```text
+//! acme/
+//! ├── pack.toml
+//! ├── quiz.txt
+//! ├── notes/serialization.md
+//! └── snippets/round_trip.rs
+//! ```
+//!
+//! Packs are found in the directories of `$ROADMAP_PACKS` (a path list),
+//! or in `packs` inside [`data_dir`] when it is unset. Their lessons are
+//! named `<pack>::<lesson>` and may require built-in lessons or lessons of
+//! the same pack.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+pub mod manifest;
+pub mod quiz;
+
+use crate::lessons;
+use crate::lint::TOPICS;
+use crate::progress::data_dir;
+use crate::scaffold::is_module_name;
+use manifest::{Table, Value};
+use quiz::PackQuestion;
+
+pub const MANIFEST: &str = "pack.toml";
+
+/// A problem with a pack, pointing at the file, and where known the line
+/// and field, e.g. `acme/pack.toml:9: lesson[1].minutes: expected ...`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackError {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+        }
+        if let Some(field) = &self.field {
+            write!(f, ": {field}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for PackError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pack {
+    pub name: String,
+    pub title: String,
+    pub dir: PathBuf,
+    pub lessons: Vec<PackLesson>,
+    pub quiz: Vec<PackQuestion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackLesson {
+    /// `<pack>::<lesson>`.
+    pub id: String,
+    pub title: String,
+    pub minutes: u16,
+    pub tags: Vec<String>,
+    pub requires: Vec<String>,
+    /// Paths inside the pack directory.
+    pub notes: Option<PathBuf>,
+    pub snippets: Vec<PathBuf>,
+}
+
+impl Pack {
+    /// Reads and validates the pack in `dir`. Notes and snippets must
+    /// exist, but are only read when the lesson is run.
+    pub fn load(dir: &Path) -> Result<Self, PackError> {
+        let path = dir.join(MANIFEST);
+        let text = fs::read_to_string(&path).map_err(|err| PackError {
+            path: path.clone(),
+            line: None,
+            field: None,
+            message: err.to_string(),
+        })?;
+        let manifest = manifest::parse(&text).map_err(|err| PackError {
+            path: path.clone(),
+            line: Some(err.line),
+            field: err.key,
+            message: err.message,
+        })?;
+
+        let top = Fields {
+            dir,
+            path: &path,
+            table: &manifest.pack,
+            prefix: String::new(),
+        };
+        top.only(&["name", "title", "quiz"])?;
+        let name = top.required("name")?;
+        if !is_module_name(&name.0) || TOPICS.contains(&name.0.as_str()) {
+            return Err(top.error(
+                "name",
+                Some(name.1),
+                format!(
+                    "`{}` must be a snake_case name other than a built-in topic",
+                    name.0
+                ),
+            ));
+        }
+        let name = name.0;
+        let title = top.required("title")?.0;
+
+        let mut lessons: Vec<PackLesson> = Vec::new();
+        for (i, table) in manifest.lessons.iter().enumerate() {
+            let fields = Fields {
+                dir,
+                path: &path,
+                table,
+                prefix: format!("lesson[{}].", i + 1),
+            };
+            let lesson = fields.lesson(&name)?;
+            if lessons.iter().any(|other| other.id == lesson.id) {
+                let line = table.get("id").map(|field| field.line);
+                return Err(fields.error("id", line, format!("`{}` is defined twice", lesson.id)));
+            }
+            lessons.push(lesson);
+        }
+        if lessons.is_empty() {
+            return Err(top.error("lesson", None, "the pack has no `[[lesson]]`".into()));
+        }
+
+        for (i, table) in manifest.lessons.iter().enumerate() {
+            let lesson = &lessons[i];
+            for required in &lesson.requires {
+                let known = lessons::find(required).is_some()
+                    || (required != &lesson.id
+                        && lessons.iter().any(|other| &other.id == required));
+                if !known {
+                    let line = table.get("requires").map(|field| field.line);
+                    return Err(PackError {
+                        path: path.clone(),
+                        line,
+                        field: Some(format!("lesson[{}].requires", i + 1)),
+                        message: format!(
+                            "`{required}` is neither a built-in lesson nor another lesson of `{name}`"
+                        ),
+                    });
+                }
+            }
+        }
+
+        let quiz = match top.file("quiz", "")? {
+            Some(quiz_path) => load_quiz(&dir.join(quiz_path), &name, &lessons)?,
+            None => Vec::new(),
+        };
+        Ok(Self {
+            name,
+            title,
+            dir: dir.to_path_buf(),
+            lessons,
+            quiz,
+        })
+    }
+
+    pub fn lesson(&self, id: &str) -> Option<&PackLesson> {
+        self.lessons.iter().find(|lesson| lesson.id == id)
+    }
+}
+
+/// The quiz questions, with lesson ids made `<pack>::<lesson>`.
+fn load_quiz(
+    path: &Path,
+    pack: &str,
+    lessons: &[PackLesson],
+) -> Result<Vec<PackQuestion>, PackError> {
+    let error = |line, field: &str, message| PackError {
+        path: path.to_path_buf(),
+        line,
+        field: Some(field.to_string()),
+        message,
+    };
+    let text = fs::read_to_string(path).map_err(|err| error(None, "quiz", err.to_string()))?;
+    let mut questions =
+        quiz::parse(&text).map_err(|(line, field, message)| error(Some(line), field, message))?;
+    for question in &mut questions {
+        let id = format!("{pack}::{}", question.lesson);
+        if !lessons.iter().any(|lesson| lesson.id == id) {
+            return Err(error(
+                Some(question.line),
+                "lesson",
+                format!("`{}` is not a lesson of `{pack}`", question.lesson),
+            ));
+        }
+        question.lesson = id;
+    }
+    Ok(questions)
+}
+
+/// Typed access to one table of a manifest, with errors naming the field.
+struct Fields<'a> {
+    dir: &'a Path,
+    path: &'a Path,
+    table: &'a Table,
+    /// `lesson[N].` for lesson tables.
+    prefix: String,
+}
+
+impl Fields<'_> {
+    fn error(&self, key: &str, line: Option<usize>, message: String) -> PackError {
+        PackError {
+            path: self.path.to_path_buf(),
+            line,
+            field: Some(format!("{}{key}", self.prefix)),
+            message,
+        }
+    }
+
+    /// Where a missing key would go: the table's header, if it has one.
+    fn header(&self) -> Option<usize> {
+        (self.table.line > 0).then_some(self.table.line)
+    }
+
+    fn only(&self, known: &[&str]) -> Result<(), PackError> {
+        match self
+            .table
+            .fields
+            .iter()
+            .find(|field| !known.contains(&field.key.as_str()))
+        {
+            Some(field) => Err(self.error(
+                &field.key,
+                Some(field.line),
+                format!("unknown field; expected one of {}", known.join(", ")),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn string(&self, key: &str) -> Result<Option<(String, usize)>, PackError> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(field) => match &field.value {
+                Value::String(value) if !value.trim().is_empty() => {
+                    Ok(Some((value.clone(), field.line)))
+                }
+                Value::String(_) => {
+                    Err(self.error(key, Some(field.line), "must not be empty".into()))
+                }
+                other => Err(self.error(
+                    key,
+                    Some(field.line),
+                    format!("expected a string, found {}", other.kind()),
+                )),
+            },
+        }
+    }
+
+    fn required(&self, key: &str) -> Result<(String, usize), PackError> {
+        self.string(key)?
+            .ok_or_else(|| self.error(key, self.header(), "missing".into()))
+    }
+
+    fn list(&self, key: &str) -> Result<Vec<String>, PackError> {
+        match self.table.get(key) {
+            None => Ok(Vec::new()),
+            Some(field) => match &field.value {
+                Value::List(items) => Ok(items.clone()),
+                other => Err(self.error(
+                    key,
+                    Some(field.line),
+                    format!("expected a list of strings, found {}", other.kind()),
+                )),
+            },
+        }
+    }
+
+    /// A relative path to an existing file in the pack, with `extension`
+    /// unless that is empty.
+    fn check_file(
+        &self,
+        key: &str,
+        line: usize,
+        relative: &str,
+        extension: &str,
+    ) -> Result<PathBuf, PackError> {
+        let path = PathBuf::from(relative);
+        let inside = path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if !inside {
+            return Err(self.error(
+                key,
+                Some(line),
+                format!("`{relative}` must be a relative path inside the pack"),
+            ));
+        }
+        if !extension.is_empty() && path.extension().is_none_or(|ext| ext != extension) {
+            return Err(self.error(
+                key,
+                Some(line),
+                format!("`{relative}` is not a .{extension} file"),
+            ));
+        }
+        if !self.dir.join(&path).is_file() {
+            return Err(self.error(key, Some(line), format!("`{relative}` does not exist")));
+        }
+        Ok(path)
+    }
+
+    fn file(&self, key: &str, extension: &str) -> Result<Option<PathBuf>, PackError> {
+        match self.string(key)? {
+            Some((relative, line)) => self.check_file(key, line, &relative, extension).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn lesson(&self, pack: &str) -> Result<PackLesson, PackError> {
+        self.only(&[
+            "id", "title", "minutes", "tags", "requires", "notes", "snippets",
+        ])?;
+        let (id, line) = self.required("id")?;
+        if !is_module_name(&id) {
+            return Err(self.error("id", Some(line), format!("`{id}` is not a snake_case name")));
+        }
+        let title = self.required("title")?.0;
+        let minutes = match self.table.get("minutes") {
+            None => return Err(self.error("minutes", self.header(), "missing".into())),
+            Some(field) => match field.value {
+                Value::Integer(n) if (1..=600).contains(&n) => n as u16,
+                ref other => {
+                    let found = match other {
+                        Value::Integer(n) => n.to_string(),
+                        other => other.kind().to_string(),
+                    };
+                    return Err(self.error(
+                        "minutes",
+                        Some(field.line),
+                        format!("expected whole minutes from 1 to 600, found {found}"),
+                    ));
+                }
+            },
+        };
+
+        let snippets = match self.table.get("snippets") {
+            None => Vec::new(),
+            Some(field) => {
+                let mut seen = BTreeSet::new();
+                let mut snippets = Vec::new();
+                for relative in self.list("snippets")? {
+                    if !seen.insert(relative.clone()) {
+                        return Err(self.error(
+                            "snippets",
+                            Some(field.line),
+                            format!("`{relative}` is listed twice"),
+                        ));
+                    }
+                    snippets.push(self.check_file("snippets", field.line, &relative, "rs")?);
+                }
+                snippets
+            }
+        };
+        Ok(PackLesson {
+            id: format!("{pack}::{id}"),
+            title,
+            minutes,
+            tags: self.list("tags")?,
+            requires: self.list("requires")?,
+            notes: self.file("notes", "md")?,
+            snippets,
+        })
+    }
+}
+
+/// Directories to look for packs in.
+pub fn search_path() -> Vec<PathBuf> {
+    match env::var_os("ROADMAP_PACKS") {
+        Some(paths) => env::split_paths(&paths)
+            .filter(|path| !path.as_os_str().is_empty())
+            .collect(),
+        None => data_dir()
+            .map(|dir| dir.join("packs"))
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Loads every pack in `dirs`: a directory that holds a `pack.toml` is a
+/// pack, otherwise each of its subdirectories that does is. Directories
+/// that do not exist are skipped. Packs that fail to load are reported and
+/// left out, as is a second pack with an existing name.
+pub fn discover(dirs: &[PathBuf]) -> (Vec<Pack>, Vec<PackError>) {
+    let mut packs: Vec<Pack> = Vec::new();
+    let mut errors = Vec::new();
+    for dir in dirs {
+        let candidates = if dir.join(MANIFEST).is_file() {
+            vec![dir.clone()]
+        } else {
+            let Ok(entries) = fs::read_dir(dir) else {
+                continue;
+            };
+            let mut subdirs: Vec<PathBuf> = entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.join(MANIFEST).is_file())
+                .collect();
+            subdirs.sort();
+            subdirs
+        };
+        for candidate in candidates {
+            match Pack::load(&candidate) {
+                Ok(pack) => match packs.iter().find(|other| other.name == pack.name) {
+                    Some(other) => errors.push(PackError {
+                        path: candidate.join(MANIFEST),
+                        line: None,
+                        field: Some("name".into()),
+                        message: format!(
+                            "pack `{}` is already loaded from {}",
+                            pack.name,
+                            other.dir.display()
+                        ),
+                    }),
+                    None => packs.push(pack),
+                },
+                Err(err) => errors.push(err),
+            }
+        }
+    }
+    (packs, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `files` into a fresh directory named after the test.
+    fn pack_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = env::temp_dir().join(format!("roadmap-pack-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for (path, contents) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    const MANIFEST_TEXT: &str = "name = \"acme\"\ntitle = \"ACME\"\nquiz = \"quiz.txt\"\n\n\
+        [[lesson]]\nid = \"basics\"\ntitle = \"Basics\"\nminutes = 10\nnotes = \"notes/basics.md\"\n\n\
+        [[lesson]]\nid = \"wire\"\ntitle = \"Wire format\"\nminutes = 20\n\
+        requires = [\"acme::basics\", \"constructs::traits\"]\nsnippets = [\"snippets/wire.rs\"]\n";
+
+    fn files(manifest: &str) -> Vec<(&str, &str)> {
+        vec![
+            ("pack.toml", manifest),
+            ("notes/basics.md", "# Basics\n"),
+            ("snippets/wire.rs", "fn main() {}\n"),
+            ("quiz.txt", "[wire]\nQ: Trait?\nA: Encode\n"),
+        ]
+    }
+
+    #[test]
+    fn loads_a_valid_pack() {
+        let dir = pack_dir("valid", &files(MANIFEST_TEXT));
+        let pack = Pack::load(&dir).unwrap();
+        assert_eq!((pack.name.as_str(), pack.title.as_str()), ("acme", "ACME"));
+        let wire = pack.lesson("acme::wire").unwrap();
+        assert_eq!(wire.minutes, 20);
+        assert_eq!(wire.requires, ["acme::basics", "constructs::traits"]);
+        assert_eq!(wire.snippets, [PathBuf::from("snippets/wire.rs")]);
+        assert_eq!(
+            pack.lesson("acme::basics").unwrap().notes,
+            Some("notes/basics.md".into())
+        );
+        assert_eq!(pack.quiz[0].lesson, "acme::wire");
+
+        let (packs, errors) = discover(&[dir.clone(), dir.clone(), dir.join("missing")]);
+        assert_eq!(packs.len(), 1);
+        assert_eq!(errors[0].field.as_deref(), Some("name"));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn errors_name_the_file_and_field() {
+        let cases = [
+            (
+                MANIFEST_TEXT.replace("minutes = 20", "minutes = 0"),
+                "pack.toml:14: lesson[2].minutes: expected whole minutes from 1 to 600, found 0",
+            ),
+            (
+                MANIFEST_TEXT.replace("minutes = 10\n", ""),
+                "pack.toml:5: lesson[1].minutes: missing",
+            ),
+            (
+                MANIFEST_TEXT.replace("title = \"ACME\"\n", ""),
+                "pack.toml: title: missing",
+            ),
+            (
+                MANIFEST_TEXT.replace("name = \"acme\"", "name = \"constructs\""),
+                "pack.toml:1: name: `constructs` must be a snake_case name other than a built-in topic",
+            ),
+            (
+                MANIFEST_TEXT.replace("basics.md", "gone.md"),
+                "pack.toml:9: lesson[1].notes: `notes/gone.md` does not exist",
+            ),
+            (
+                MANIFEST_TEXT.replace("\"snippets/wire.rs\"", "\"../wire.rs\""),
+                "pack.toml:16: lesson[2].snippets: `../wire.rs` must be a relative path inside the pack",
+            ),
+            (
+                MANIFEST_TEXT.replace("\"constructs::traits\"", "\"constructs::nope\""),
+                "pack.toml:15: lesson[2].requires: `constructs::nope` is neither a built-in lesson nor another lesson of `acme`",
+            ),
+            (
+                MANIFEST_TEXT.replace("id = \"wire\"", "id = \"basics\""),
+                "pack.toml:12: lesson[2].id: `acme::basics` is defined twice",
+            ),
+            (
+                MANIFEST_TEXT.replace("minutes = 10", "mins = 10"),
+                "pack.toml:8: lesson[1].mins: unknown field; expected one of id, title, minutes, tags, requires, notes, snippets",
+            ),
+            (
+                MANIFEST_TEXT
+                    .replace("tags", "x")
+                    .replace("title = \"Basics\"", "title = [\"Basics\"]"),
+                "pack.toml:7: lesson[1].title: expected a string, found a list of strings",
+            ),
+        ];
+        for (i, (manifest, expected)) in cases.iter().enumerate() {
+            let dir = pack_dir(&format!("invalid-{i}"), &files(manifest));
+            let err = Pack::load(&dir).unwrap_err().to_string();
+            assert_eq!(err, format!("{}/{expected}", dir.display()));
+            let _ = fs::remove_dir_all(dir);
+        }
+
+        let mut quiz = files(MANIFEST_TEXT);
+        quiz[3].1 = "[wire]\nQ: Trait?\nA: Encode\n[other]\nQ: ?\nA: x\n";
+        let dir = pack_dir("quiz", &quiz);
+        let err = Pack::load(&dir).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            format!(
+                "{}/quiz.txt:5: lesson: `other` is not a lesson of `acme`",
+                dir.display()
+            )
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+}
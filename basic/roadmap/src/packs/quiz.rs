@@ -0,0 +1,136 @@
+//! A pack's quiz file: questions grouped under `[lesson]` headers, each a
+//! `Q:` line followed by one or more `A:` lines. The first answer is the
+//! one shown after a review.
+//!
+//! ```text
+//! [serialization]
+//! Q: Which trait do our wire types implement?
+//! A: Encode
+//! A: acme::Encode
+//! ```
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackQuestion {
+    /// Lesson id within the pack, without the pack name.
+    pub lesson: String,
+    pub line: usize,
+    pub prompt: String,
+    pub answers: Vec<String>,
+}
+
+/// A problem on one line: the line, the field (`lesson`, `Q` or `A`) and
+/// what is wrong.
+pub type QuizError = (usize, &'static str, String);
+
+pub fn parse(text: &str) -> Result<Vec<PackQuestion>, QuizError> {
+    let mut questions: Vec<PackQuestion> = Vec::new();
+    let mut lesson: Option<String> = None;
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let text = raw.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        if let Some(header) = text.strip_prefix('[') {
+            let name = header
+                .strip_suffix(']')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .ok_or((line, "lesson", "expected `[lesson_id]`".to_string()))?;
+            close(questions.last())?;
+            lesson = Some(name.to_string());
+            continue;
+        }
+        if let Some(prompt) = text.strip_prefix("Q:") {
+            let lesson = lesson.clone().ok_or((
+                line,
+                "lesson",
+                "question before any `[lesson_id]` header".to_string(),
+            ))?;
+            close(questions.last())?;
+            let prompt = prompt.trim();
+            if prompt.is_empty() {
+                return Err((line, "Q", "empty question".into()));
+            }
+            questions.push(PackQuestion {
+                lesson,
+                line,
+                prompt: prompt.to_string(),
+                answers: Vec::new(),
+            });
+            continue;
+        }
+        if let Some(answer) = text.strip_prefix("A:") {
+            let question = questions.last_mut().ok_or((
+                line,
+                "A",
+                "answer before any `Q:` line".to_string(),
+            ))?;
+            let answer = answer.trim();
+            if answer.is_empty() {
+                return Err((line, "A", "empty answer".into()));
+            }
+            question.answers.push(answer.to_string());
+            continue;
+        }
+        return Err((
+            line,
+            "Q",
+            format!("expected `Q:`, `A:` or `[lesson_id]`, found `{text}`"),
+        ));
+    }
+    close(questions.last())?;
+    Ok(questions)
+}
+
+/// Checks that the previous question got an answer.
+fn close(question: Option<&PackQuestion>) -> Result<(), QuizError> {
+    match question {
+        Some(question) if question.answers.is_empty() => {
+            Err((question.line, "A", "question has no `A:` line".to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_questions_by_lesson() {
+        let questions = parse(
+            "# quiz\n[serialization]\nQ: Trait?\nA: Encode\nA: acme::Encode\n\n[errors]\nQ: Type?\nA: AcmeError\n",
+        )
+        .unwrap();
+        assert_eq!(questions.len(), 2);
+        assert_eq!(questions[0].lesson, "serialization");
+        assert_eq!(questions[0].answers, ["Encode", "acme::Encode"]);
+        assert_eq!(
+            (questions[1].lesson.as_str(), questions[1].line),
+            ("errors", 8)
+        );
+    }
+
+    #[test]
+    fn errors_name_the_line_and_field() {
+        assert_eq!(
+            parse("Q: Trait?\n").unwrap_err(),
+            (
+                1,
+                "lesson",
+                "question before any `[lesson_id]` header".into()
+            )
+        );
+        assert_eq!(
+            parse("[a]\nQ: One?\nQ: Two?\nA: 2\n").unwrap_err(),
+            (2, "A", "question has no `A:` line".into())
+        );
+        assert_eq!(
+            parse("[a]\nQ: One?\n").unwrap_err(),
+            (2, "A", "question has no `A:` line".into())
+        );
+        assert_eq!(parse("[a]\nA: 1\n").unwrap_err().0, 2);
+        assert_eq!(parse("[a]\nwhat\n").unwrap_err().1, "Q");
+    }
+}
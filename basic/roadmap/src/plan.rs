@@ -0,0 +1,272 @@
+//! The prerequisite graph between lessons, and learning paths through it.
+//!
+//! Each [`Lesson`] lists the lessons it `requires`. [`check`] verifies the
+//! graph (a unit test runs it over [`LESSONS`](crate::lessons::LESSONS)),
+//! and [`path_to`] orders everything a lesson depends on so that every
+//! lesson comes after its prerequisites.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use crate::lessons::Lesson;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    DuplicateId(&'static str),
+    UnknownPrerequisite {
+        lesson: &'static str,
+        requires: &'static str,
+    },
+    /// Lessons that require each other in a loop; the first id is
+    /// repeated at the end.
+    Cycle(Vec<&'static str>),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::DuplicateId(id) => write!(f, "lesson {id} is registered twice"),
+            GraphError::UnknownPrerequisite { lesson, requires } => {
+                write!(f, "{lesson} requires {requires}, which is not a lesson")
+            }
+            GraphError::Cycle(ids) => write!(f, "prerequisite cycle: {}", ids.join(" -> ")),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanError {
+    UnknownLesson(String),
+    Graph(GraphError),
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanError::UnknownLesson(id) => write!(f, "unknown lesson: {id}"),
+            PlanError::Graph(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+impl From<GraphError> for PlanError {
+    fn from(err: GraphError) -> Self {
+        PlanError::Graph(err)
+    }
+}
+
+/// Lessons by id, or the first problem that makes the graph unusable.
+fn index(lessons: &[Lesson]) -> Result<HashMap<&'static str, &Lesson>, GraphError> {
+    let mut by_id = HashMap::new();
+    for lesson in lessons {
+        if by_id.insert(lesson.id, lesson).is_some() {
+            return Err(GraphError::DuplicateId(lesson.id));
+        }
+    }
+    for lesson in lessons {
+        if let Some(&missing) = lesson.requires.iter().find(|id| !by_id.contains_key(*id)) {
+            return Err(GraphError::UnknownPrerequisite {
+                lesson: lesson.id,
+                requires: missing,
+            });
+        }
+    }
+    Ok(by_id)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mark {
+    /// On the current depth-first path.
+    Visiting,
+    Done,
+}
+
+/// Checks that ids are unique, every prerequisite exists and no lesson
+/// depends on itself, directly or through others.
+pub fn check(lessons: &[Lesson]) -> Result<(), GraphError> {
+    let by_id = index(lessons)?;
+    let mut marks = HashMap::new();
+    let mut stack = Vec::new();
+    for lesson in lessons {
+        visit(lesson.id, &by_id, &mut marks, &mut stack)?;
+    }
+    Ok(())
+}
+
+fn visit(
+    id: &'static str,
+    by_id: &HashMap<&'static str, &Lesson>,
+    marks: &mut HashMap<&'static str, Mark>,
+    stack: &mut Vec<&'static str>,
+) -> Result<(), GraphError> {
+    match marks.get(id) {
+        Some(Mark::Done) => return Ok(()),
+        Some(Mark::Visiting) => {
+            let start = stack.iter().position(|&on_path| on_path == id).unwrap_or(0);
+            let mut cycle = stack[start..].to_vec();
+            cycle.push(id);
+            return Err(GraphError::Cycle(cycle));
+        }
+        None => {}
+    }
+    marks.insert(id, Mark::Visiting);
+    stack.push(id);
+    for &required in by_id[id].requires {
+        visit(required, by_id, marks, stack)?;
+    }
+    stack.pop();
+    marks.insert(id, Mark::Done);
+    Ok(())
+}
+
+/// The lessons to work through, in order, to finish a target lesson.
+pub struct Plan<'a> {
+    /// Prerequisites before the lessons that need them; the target last.
+    pub lessons: Vec<&'a Lesson>,
+    /// Completed lessons on the way; their own prerequisites are not
+    /// followed.
+    pub skipped: Vec<&'a Lesson>,
+}
+
+impl Plan<'_> {
+    pub fn minutes(&self) -> u32 {
+        self.lessons
+            .iter()
+            .map(|lesson| u32::from(lesson.minutes))
+            .sum()
+    }
+}
+
+/// Orders `target` and everything it needs, leaving out lessons in
+/// `completed`.
+///
+/// The order is a depth-first walk of each lesson's `requires`, in the
+/// order they are listed, so the same registry always gives the same plan.
+pub fn path_to<'a>(
+    lessons: &'a [Lesson],
+    target: &str,
+    completed: &BTreeSet<String>,
+) -> Result<Plan<'a>, PlanError> {
+    check(lessons)?;
+    let by_id = index(lessons)?;
+    let target = lessons
+        .iter()
+        .find(|lesson| lesson.id == target)
+        .ok_or_else(|| PlanError::UnknownLesson(target.to_string()))?;
+
+    let mut plan = Plan {
+        lessons: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let mut seen = BTreeSet::new();
+    collect(target, &by_id, completed, &mut seen, &mut plan);
+    Ok(plan)
+}
+
+fn collect<'a>(
+    lesson: &'a Lesson,
+    by_id: &HashMap<&'static str, &'a Lesson>,
+    completed: &BTreeSet<String>,
+    seen: &mut BTreeSet<&'static str>,
+    plan: &mut Plan<'a>,
+) {
+    if !seen.insert(lesson.id) {
+        return;
+    }
+    if completed.contains(lesson.id) {
+        plan.skipped.push(lesson);
+        return;
+    }
+    // `check` passed, so there is no cycle and every id resolves
+    for &required in lesson.requires {
+        collect(by_id[required], by_id, completed, seen, plan);
+    }
+    plan.lessons.push(lesson);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lessons::LESSONS;
+
+    fn lesson(id: &'static str, requires: &'static [&'static str]) -> Lesson {
+        Lesson {
+            id,
+            title: id,
+            minutes: 10,
+            tags: &[],
+            requires,
+            run: || {},
+            sections: &[],
+        }
+    }
+
+    fn ids(lessons: &[&Lesson]) -> Vec<&'static str> {
+        lessons.iter().map(|lesson| lesson.id).collect()
+    }
+
+    #[test]
+    fn registered_lessons_form_an_acyclic_graph() {
+        check(LESSONS).unwrap();
+        for lesson in LESSONS {
+            let plan = path_to(LESSONS, lesson.id, &BTreeSet::new()).unwrap();
+            assert_eq!(plan.lessons.last().map(|l| l.id), Some(lesson.id));
+        }
+    }
+
+    #[test]
+    fn detects_cycles_and_bad_references() {
+        let cycle = [
+            lesson("a", &["b"]),
+            lesson("b", &["c"]),
+            lesson("c", &["a"]),
+        ];
+        assert_eq!(
+            check(&cycle),
+            Err(GraphError::Cycle(vec!["a", "b", "c", "a"]))
+        );
+        let own = [lesson("a", &[]), lesson("b", &["b"])];
+        assert_eq!(
+            check(&own).unwrap_err().to_string(),
+            "prerequisite cycle: b -> b"
+        );
+        let unknown = [lesson("a", &["z"])];
+        assert_eq!(
+            check(&unknown).unwrap_err().to_string(),
+            "a requires z, which is not a lesson"
+        );
+        let twice = [lesson("a", &[]), lesson("a", &[])];
+        assert_eq!(check(&twice), Err(GraphError::DuplicateId("a")));
+    }
+
+    #[test]
+    fn plans_put_prerequisites_first_and_skip_completed() {
+        // d needs b and c, which both need a
+        let lessons = [
+            lesson("a", &[]),
+            lesson("b", &["a"]),
+            lesson("c", &["a"]),
+            lesson("d", &["b", "c"]),
+            lesson("unrelated", &[]),
+        ];
+        let plan = path_to(&lessons, "d", &BTreeSet::new()).unwrap();
+        assert_eq!(ids(&plan.lessons), ["a", "b", "c", "d"]);
+        assert_eq!(plan.minutes(), 40);
+
+        let done: BTreeSet<String> = ["b".to_string()].into();
+        let plan = path_to(&lessons, "d", &done).unwrap();
+        assert_eq!(ids(&plan.lessons), ["a", "c", "d"]);
+        assert_eq!(ids(&plan.skipped), ["b"]);
+
+        let done: BTreeSet<String> = ["d".to_string()].into();
+        assert!(path_to(&lessons, "d", &done).unwrap().lessons.is_empty());
+        assert_eq!(
+            path_to(&lessons, "e", &done).err(),
+            Some(PlanError::UnknownLesson("e".into()))
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Which lessons the learner has finished, kept in a small text file.
+//!
+//! Local state lives in [`data_dir`]: `$ROADMAP_HOME` if set, otherwise
+//! `~/.roadmap`. The progress file there lists one completed lesson id per
+//! line.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Where the CLI keeps local state, or `None` if neither `ROADMAP_HOME`
+/// nor `HOME` is set.
+pub fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("ROADMAP_HOME").filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(".roadmap"))
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Progress {
+    completed: BTreeSet<String>,
+}
+
+impl Progress {
+    /// `progress` inside [`data_dir`].
+    pub fn default_path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join("progress"))
+    }
+
+    /// One id per line; blank lines and `#` comments are ignored.
+    pub fn parse(text: &str) -> Self {
+        let completed = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        Self { completed }
+    }
+
+    /// A missing file is no progress yet, not an error.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(Self::parse(&text)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# completed lessons, one id per line\n");
+        for id in &self.completed {
+            text.push_str(id);
+            text.push('\n');
+        }
+        text
+    }
+
+    /// `true` if the lesson was not already completed.
+    pub fn complete(&mut self, id: &str) -> bool {
+        self.completed.insert(id.to_string())
+    }
+
+    /// `true` if the lesson had been completed.
+    pub fn reset(&mut self, id: &str) -> bool {
+        self.completed.remove(id)
+    }
+
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.completed.contains(id)
+    }
+
+    pub fn completed(&self) -> &BTreeSet<String> {
+        &self.completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_round_trip() {
+        let mut progress =
+            Progress::parse("# done\nconstructs::structs\n\n  constructs::enums  \n");
+        assert!(progress.is_completed("constructs::enums"));
+        assert!(progress.complete("constructs::traits"));
+        assert!(!progress.complete("constructs::traits"));
+        assert!(progress.reset("constructs::structs"));
+        assert_eq!(Progress::parse(&progress.to_text()), progress);
+        assert_eq!(progress.completed().len(), 2);
+    }
+
+    #[test]
+    fn missing_file_is_empty_progress() {
+        let path = env::temp_dir().join(format!("roadmap-progress-{}", std::process::id()));
+        assert_eq!(
+            Progress::load(&path.join("none")).unwrap(),
+            Progress::default()
+        );
+        let mut progress = Progress::default();
+        progress.complete("syntax_and_semantics::arrays");
+        progress.save(&path.join("progress")).unwrap();
+        assert_eq!(Progress::load(&path.join("progress")).unwrap(), progress);
+        fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,306 @@
+//! Styled lesson output.
+//!
+//! Lessons describe what they print instead of how it looks:
+//! `render::section(2, ...)`, `render::note(...)`, `render::code(...)`,
+//! `render::result(...)`, `render::warning(...)`. Unconfigured, or with
+//! [`Settings::PLAIN`], each call prints exactly the text the lessons
+//! used to write by hand (`N. Title`, three-space indents, `→ ` before
+//! results), so output that is not going to a terminal is unchanged and
+//! golden files keep matching. [`configure`] turns on a [`Theme`]'s ANSI
+//! colors and wrapping at the terminal width; `roadmap run` does that when
+//! stdout is a terminal, and also pages long lessons with [`pager`].
+
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+pub mod pager;
+pub mod term;
+pub mod theme;
+
+pub use theme::{Style, Theme};
+
+const INDENT: &str = "   ";
+
+/// When to use color: `--color auto|always|never`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorChoiceError(pub String);
+
+impl fmt::Display for ParseColorChoiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown color choice `{}` (expected auto, always or never)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseColorChoiceError {}
+
+impl FromStr for ColorChoice {
+    type Err = ParseColorChoiceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(ParseColorChoiceError(s.to_string())),
+        }
+    }
+}
+
+impl ColorChoice {
+    /// Whether to color output. `Auto` colors only a terminal, and not
+    /// when `NO_COLOR` is set to anything (see no-color.org) or `TERM` is
+    /// `dumb`.
+    pub fn enabled(self, terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                let dumb = env::var("TERM").is_ok_and(|term| term == "dumb");
+                terminal && !no_color && !dumb
+            }
+        }
+    }
+}
+
+/// How the semantic calls print.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    /// Colors, or `None` for plain text.
+    pub theme: Option<Theme>,
+    /// Wrap prose at this many columns; `None` never wraps.
+    pub width: Option<usize>,
+}
+
+impl Settings {
+    /// The text lessons printed before they had a renderer.
+    pub const PLAIN: Settings = Settings {
+        theme: None,
+        width: None,
+    };
+
+    /// `=== text ===` and a blank line.
+    pub fn title(&self, text: &str) -> String {
+        let banner = format!("=== {text} ===");
+        format!("{}\n\n", self.paint(|theme| theme.title, &banner))
+    }
+
+    /// `N. text`.
+    pub fn section(&self, number: u32, text: &str) -> String {
+        let header = format!("{number}. {text}");
+        format!("{}\n", self.paint(|theme| theme.section, &header))
+    }
+
+    /// Indented prose, wrapped.
+    pub fn note(&self, text: &str) -> String {
+        self.block(text, INDENT, INDENT, |theme| theme.note, true)
+    }
+
+    /// `- text`, indented, with wrapped lines under the text.
+    pub fn item(&self, text: &str) -> String {
+        self.block(text, "   - ", "     ", |theme| theme.note, true)
+    }
+
+    /// Indented source code, never wrapped.
+    pub fn code(&self, text: &str) -> String {
+        self.block(text, INDENT, INDENT, |theme| theme.code, false)
+    }
+
+    /// `→ text`, indented, with wrapped lines under the text.
+    pub fn result(&self, text: &str) -> String {
+        self.block(text, "   → ", "     ", |theme| theme.result, true)
+    }
+
+    /// Indented prose about something that goes wrong, wrapped.
+    pub fn warning(&self, text: &str) -> String {
+        self.block(text, INDENT, INDENT, |theme| theme.warning, true)
+    }
+
+    fn paint(&self, style: impl Fn(&Theme) -> Style, text: &str) -> String {
+        match &self.theme {
+            Some(theme) => style(theme).paint(text),
+            None => text.to_string(),
+        }
+    }
+
+    /// Each line of `text` after `first` (the first line) or `rest`; empty
+    /// lines stay empty, so `"text\n"` ends with a blank line.
+    fn block(
+        &self,
+        text: &str,
+        first: &str,
+        rest: &str,
+        style: impl Fn(&Theme) -> Style,
+        wrap: bool,
+    ) -> String {
+        let mut out = String::new();
+        let mut indent = first;
+        for line in text.split('\n') {
+            if line.is_empty() {
+                out.push('\n');
+                continue;
+            }
+            let lines = match self.width {
+                Some(width) if wrap => {
+                    self::wrap(line, width.saturating_sub(indent.chars().count()))
+                }
+                _ => vec![line.to_string()],
+            };
+            for line in lines {
+                out.push_str(indent);
+                out.push_str(&self.paint(&style, &line));
+                out.push('\n');
+                indent = rest;
+            }
+        }
+        out
+    }
+}
+
+/// Splits `text` into lines of at most `width` characters at spaces. A
+/// word longer than `width` gets a line of its own.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut length = 0;
+    for word in text.split(' ') {
+        let size = word.chars().count();
+        if length > 0 && length + 1 + size > width {
+            lines.push(std::mem::take(&mut line));
+            length = 0;
+        }
+        if length > 0 {
+            line.push(' ');
+            length += 1;
+        }
+        line.push_str(word);
+        length += size;
+    }
+    lines.push(line);
+    lines
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Sets how the semantic calls print for the rest of the process; `false`
+/// if they were already configured.
+pub fn configure(settings: Settings) -> bool {
+    SETTINGS.set(settings).is_ok()
+}
+
+pub fn settings() -> Settings {
+    SETTINGS.get().copied().unwrap_or(Settings::PLAIN)
+}
+
+/// Prints `text` as the lesson's title.
+pub fn title(text: impl AsRef<str>) {
+    print!("{}", settings().title(text.as_ref()));
+}
+
+/// Prints the `N. Title` header that starts a section; `lint` checks the
+/// number against `main`.
+pub fn section(number: u32, text: impl AsRef<str>) {
+    print!("{}", settings().section(number, text.as_ref()));
+}
+
+/// Prints explanatory text. A trailing `\n` adds a blank line, as it did
+/// in the `println!` the call replaces.
+pub fn note(text: impl AsRef<str>) {
+    print!("{}", settings().note(text.as_ref()));
+}
+
+/// Prints a bullet point.
+pub fn item(text: impl AsRef<str>) {
+    print!("{}", settings().item(text.as_ref()));
+}
+
+/// Prints code as written.
+pub fn code(text: impl AsRef<str>) {
+    print!("{}", settings().code(text.as_ref()));
+}
+
+/// Prints what the code above it produced or means.
+pub fn result(text: impl AsRef<str>) {
+    print!("{}", settings().result(text.as_ref()));
+}
+
+/// Prints a panic, error or pitfall.
+pub fn warning(text: impl AsRef<str>) {
+    print!("{}", settings().warning(text.as_ref()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_output_matches_hand_written_lines() {
+        let plain = Settings::PLAIN;
+        assert_eq!(plain.title("Arrays"), "=== Arrays ===\n\n");
+        assert_eq!(plain.section(2, "Syntax"), "2. Syntax\n");
+        assert_eq!(plain.note("Arrays are fixed\n"), "   Arrays are fixed\n\n");
+        assert_eq!(plain.item("Fixed length"), "   - Fixed length\n");
+        assert_eq!(plain.code("let a = [1, 2];"), "   let a = [1, 2];\n");
+        assert_eq!(plain.result("a[0] = 1"), "   → a[0] = 1\n");
+        assert_eq!(plain.warning("\nPANIC"), "\n   PANIC\n");
+    }
+
+    #[test]
+    fn wraps_prose_under_its_indent() {
+        let narrow = Settings {
+            theme: None,
+            width: Some(16),
+        };
+        assert_eq!(
+            narrow.result("one two three four"),
+            "   → one two\n     three four\n"
+        );
+        assert_eq!(
+            narrow.note("a-very-long-word here"),
+            "   a-very-long-word\n   here\n"
+        );
+        // Code keeps its lines
+        assert_eq!(narrow.code("let total = 1 + 2;"), "   let total = 1 + 2;\n");
+        assert_eq!(wrap("", 10), [""]);
+        assert_eq!(wrap("Mảng có độ dài", 9), ["Mảng có", "độ dài"]);
+    }
+
+    #[test]
+    fn themes_color_the_text_not_the_indent() {
+        let dark = Settings {
+            theme: Some(theme::DARK),
+            width: None,
+        };
+        assert_eq!(dark.section(1, "Arrays"), "\x1b[1;96m1. Arrays\x1b[0m\n");
+        assert_eq!(
+            dark.code("let a = 1;\n"),
+            "   \x1b[93mlet a = 1;\x1b[0m\n\n"
+        );
+        assert_eq!(dark.note("plain"), "   plain\n");
+    }
+
+    #[test]
+    fn color_choices() {
+        assert_eq!("always".parse(), Ok(ColorChoice::Always));
+        assert_eq!(
+            "sometimes".parse::<ColorChoice>().unwrap_err().to_string(),
+            "unknown color choice `sometimes` (expected auto, always or never)"
+        );
+        assert!(ColorChoice::Always.enabled(false));
+        assert!(!ColorChoice::Never.enabled(true));
+        assert!(!ColorChoice::Auto.enabled(false));
+    }
+}
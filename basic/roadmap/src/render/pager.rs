@@ -0,0 +1,76 @@
+//! A built-in pager for lessons longer than the terminal, in the manner
+//! of `more`: a screenful at a time, Enter for the next one, `q` to stop.
+//! stdin stays line-buffered, so every answer ends with Enter.
+
+use std::io::{self, BufRead, Write};
+
+const PROMPT: &str = "--More-- ";
+/// Moves up over the line the user's Enter left and clears it.
+const ERASE_PROMPT: &str = "\x1b[1A\x1b[2K";
+
+/// Writes `text` to `output`, pausing after every `rows - 1` lines (the
+/// last row holds the prompt) to read a command from `input`: an empty
+/// line shows the next page, `q` stops. Text that fits is written at once.
+pub fn page(
+    text: &str,
+    rows: usize,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let per_page = rows.saturating_sub(1).max(1);
+    let mut shown = 0;
+    while shown < lines.len() {
+        let end = (shown + per_page).min(lines.len());
+        for line in &lines[shown..end] {
+            output.write_all(line.as_bytes())?;
+        }
+        shown = end;
+        if shown == lines.len() {
+            break;
+        }
+        write!(
+            output,
+            "{PROMPT}({}%) [Enter: next page, q: quit] ",
+            shown * 100 / lines.len()
+        )?;
+        output.flush()?;
+        let mut answer = String::new();
+        let read = input.read_line(&mut answer)?;
+        output.write_all(ERASE_PROMPT.as_bytes())?;
+        if read == 0 || answer.trim().eq_ignore_ascii_case("q") {
+            break;
+        }
+    }
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paged(text: &str, rows: usize, input: &str) -> String {
+        let mut output = Vec::new();
+        page(text, rows, &mut input.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn short_text_is_written_without_a_prompt() {
+        assert_eq!(paged("a\nb\n", 3, ""), "a\nb\n");
+    }
+
+    #[test]
+    fn pauses_between_pages_and_stops_on_q() {
+        let text = "1\n2\n3\n4\n5\n";
+        let prompt =
+            |percent| format!("{PROMPT}({percent}%) [Enter: next page, q: quit] {ERASE_PROMPT}");
+        assert_eq!(
+            paged(text, 3, "\n\n"),
+            format!("1\n2\n{}3\n4\n{}5\n", prompt(40), prompt(80))
+        );
+        assert_eq!(paged(text, 3, "q\n"), format!("1\n2\n{}", prompt(40)));
+        // End of input stops too
+        assert_eq!(paged(text, 3, ""), format!("1\n2\n{}", prompt(40)));
+    }
+}
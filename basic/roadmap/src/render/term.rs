@@ -0,0 +1,66 @@
+//! Terminal size. The crate has no `libc` dependency, so the `ioctl` call
+//! and `TIOCGWINSZ` are declared here; they match 64-bit Linux on x86-64,
+//! AArch64 and RISC-V. Elsewhere only `COLUMNS` and `LINES` are read.
+
+use std::env;
+
+#[cfg(all(
+    target_os = "linux",
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )
+))]
+mod sys {
+    #[repr(C)]
+    #[derive(Default)]
+    struct Winsize {
+        rows: u16,
+        cols: u16,
+        x_pixels: u16,
+        y_pixels: u16,
+    }
+
+    unsafe extern "C" {
+        fn ioctl(fd: i32, request: u64, ...) -> i32;
+    }
+
+    const TIOCGWINSZ: u64 = 0x5413;
+    const STDOUT: i32 = 1;
+
+    pub fn size() -> Option<(usize, usize)> {
+        let mut size = Winsize::default();
+        // The kernel writes one `Winsize`; nothing else is touched
+        let ok = unsafe { ioctl(STDOUT, TIOCGWINSZ, &mut size as *mut Winsize) } == 0;
+        (ok && size.cols > 0 && size.rows > 0).then(|| (size.cols.into(), size.rows.into()))
+    }
+}
+
+#[cfg(not(all(
+    target_os = "linux",
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )
+)))]
+mod sys {
+    pub fn size() -> Option<(usize, usize)> {
+        None
+    }
+}
+
+/// `(columns, rows)` of the terminal on stdout: `COLUMNS` and `LINES` if
+/// set, as shells let users override them, then the terminal's own size,
+/// then 80 by 24.
+pub fn size() -> (usize, usize) {
+    let var = |name| {
+        env::var(name)
+            .ok()
+            .and_then(|value| value.parse::<usize>().ok())
+            .filter(|&n| n > 0)
+    };
+    let (cols, rows) = sys::size().unwrap_or((80, 24));
+    (var("COLUMNS").unwrap_or(cols), var("LINES").unwrap_or(rows))
+}
@@ -0,0 +1,69 @@
+//! ANSI color themes for the semantic output calls.
+
+/// An SGR parameter string such as `1;36` (bold cyan); empty leaves the
+/// text as it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style(pub &'static str);
+
+impl Style {
+    pub const PLAIN: Style = Style("");
+
+    /// `text` between this style's escape and a reset.
+    pub fn paint(self, text: &str) -> String {
+        if self.0.is_empty() || text.is_empty() {
+            text.to_string()
+        } else {
+            format!("\x1b[{}m{text}\x1b[0m", self.0)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub name: &'static str,
+    pub title: Style,
+    pub section: Style,
+    pub note: Style,
+    pub code: Style,
+    pub warning: Style,
+    pub result: Style,
+}
+
+/// Bright colors for dark backgrounds; the default.
+pub const DARK: Theme = Theme {
+    name: "dark",
+    title: Style("1;95"),
+    section: Style("1;96"),
+    note: Style::PLAIN,
+    code: Style("93"),
+    warning: Style("1;91"),
+    result: Style("92"),
+};
+
+/// Darker colors that stay readable on white.
+pub const LIGHT: Theme = Theme {
+    name: "light",
+    title: Style("1;35"),
+    section: Style("1;34"),
+    note: Style::PLAIN,
+    code: Style("33"),
+    warning: Style("1;31"),
+    result: Style("32"),
+};
+
+/// Bold, underline and dim only, for terminals with unhelpful palettes.
+pub const MONO: Theme = Theme {
+    name: "mono",
+    title: Style("1;4"),
+    section: Style("1"),
+    note: Style::PLAIN,
+    code: Style("2"),
+    warning: Style("1"),
+    result: Style::PLAIN,
+};
+
+pub const THEMES: [Theme; 3] = [DARK, LIGHT, MONO];
+
+pub fn find(name: &str) -> Option<Theme> {
+    THEMES.into_iter().find(|theme| theme.name == name)
+}
@@ -0,0 +1,169 @@
+//! Flashcards for `roadmap review`, drawn from the lessons.
+//!
+//! Cards come from two places: the [`quiz`] questions, and "predict the
+//! output" prompts built by [`predict`] from `println!` lines in the
+//! lessons, answered by the lessons' real output. [`schedule`] decides
+//! when each card is due again and keeps that in a local file.
+
+use std::fmt;
+
+pub mod predict;
+pub mod quiz;
+pub mod schedule;
+
+pub use schedule::{Deck, Grade, Schedule};
+
+use crate::calendar::Date;
+use crate::hash::fnv1a;
+use predict::Candidate;
+use quiz::Question;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardKind {
+    Quiz,
+    Predict,
+}
+
+impl fmt::Display for CardKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            CardKind::Quiz => "quiz",
+            CardKind::Predict => "predict",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    /// Stable across runs as long as the card's text is unchanged; the
+    /// deck file is keyed by it.
+    pub key: String,
+    pub lesson: String,
+    pub kind: CardKind,
+    pub prompt: String,
+    /// The first answer is the one shown.
+    pub answers: Vec<String>,
+}
+
+impl Card {
+    pub fn from_question(question: &Question) -> Self {
+        Self::quiz(
+            question.lesson,
+            question.prompt.to_string(),
+            question.answers.iter().map(|a| a.to_string()).collect(),
+        )
+    }
+
+    /// A quiz card from questions that are not compiled in, such as a
+    /// lesson pack's.
+    pub fn quiz(lesson: &str, prompt: String, answers: Vec<String>) -> Self {
+        Self::new(CardKind::Quiz, lesson, prompt, answers)
+    }
+
+    /// `None` if the lesson's output does not pin down the answer.
+    pub fn from_candidate(lesson: &str, candidate: &Candidate, output: &str) -> Option<Self> {
+        let answer = candidate.answer_in(output)?;
+        Some(Self::new(
+            CardKind::Predict,
+            lesson,
+            candidate.prompt(),
+            vec![answer],
+        ))
+    }
+
+    fn new(kind: CardKind, lesson: &str, prompt: String, answers: Vec<String>) -> Self {
+        Self {
+            key: format!("{kind}:{lesson}:{:016x}", fnv1a(&prompt)),
+            lesson: lesson.to_string(),
+            kind,
+            prompt,
+            answers,
+        }
+    }
+
+    pub fn answer(&self) -> &str {
+        &self.answers[0]
+    }
+
+    /// Whitespace is collapsed; quiz answers also ignore case, printed
+    /// values must match exactly.
+    pub fn accepts(&self, response: &str) -> bool {
+        let normalize = |text: &str| {
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            match self.kind {
+                CardKind::Quiz => text.to_lowercase(),
+                CardKind::Predict => text,
+            }
+        };
+        let response = normalize(response);
+        !response.is_empty()
+            && self
+                .answers
+                .iter()
+                .any(|answer| normalize(answer) == response)
+    }
+}
+
+/// The grade for an automatically checked answer: 4 when right, 1 when
+/// wrong, 0 when left blank.
+pub fn grade_response(card: &Card, response: &str) -> Grade {
+    let value = if card.accepts(response) {
+        4
+    } else if response.trim().is_empty() {
+        0
+    } else {
+        1
+    };
+    Grade::new(value).expect("grades are at most 5")
+}
+
+/// Cards due on `today`, soonest first; new cards count as due today.
+pub fn due<'a>(cards: &'a [Card], deck: &Deck, today: Date) -> Vec<&'a Card> {
+    let mut due: Vec<_> = cards
+        .iter()
+        .map(|card| (deck.schedule(&card.key, today).due, card))
+        .filter(|&(date, _)| date <= today)
+        .collect();
+    due.sort_by(|a, b| (a.0, &a.1.key).cmp(&(b.0, &b.1.key)));
+    due.into_iter().map(|(_, card)| card).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_are_normalized_by_kind() {
+        let quiz = Card::from_question(&quiz::QUIZ[0]);
+        assert!(quiz.accepts("  [U8;   5] "));
+        assert!(!quiz.accepts(""));
+
+        let candidate = &predict::candidates(
+            "fn demonstrate_x() {\n    println!(\"   v = {:?}\", Some(\"A\"));\n}",
+        )[0];
+        let card =
+            Card::from_candidate("constructs::enums", candidate, "   v = Some(\"A\")\n").unwrap();
+        assert!(card.accepts("Some(\"A\")"));
+        assert!(!card.accepts("some(\"a\")"));
+        assert_eq!(grade_response(&card, "Some(\"A\")").value(), 4);
+        assert_eq!(grade_response(&card, "None").value(), 1);
+        assert_eq!(grade_response(&card, " ").value(), 0);
+        assert!(card.key.starts_with("predict:constructs::enums:"));
+    }
+
+    #[test]
+    fn due_cards_come_soonest_first() {
+        let today: Date = "2024-03-01".parse().unwrap();
+        let cards: Vec<Card> = quiz::QUIZ.iter().take(3).map(Card::from_question).collect();
+        let mut deck = Deck::default();
+        assert_eq!(due(&cards, &deck, today).len(), 3);
+
+        deck.review(&cards[0].key, Grade::new(5).unwrap(), today);
+        deck.review(&cards[1].key, Grade::new(0).unwrap(), today.sub_days(5));
+        let keys: Vec<&str> = due(&cards, &deck, today)
+            .iter()
+            .map(|c| c.key.as_str())
+            .collect();
+        assert_eq!(keys, [cards[1].key.as_str(), cards[2].key.as_str()]);
+    }
+}
@@ -0,0 +1,355 @@
+//! "Predict the output" prompts taken from the lessons' own `println!`s.
+//!
+//! A line qualifies when it prints a single value and every local it reads
+//! was bound earlier in the function by a one-line, non-`mut` `let` that
+//! qualifies the same way, such as
+//! `println!("   200u8.saturating_add(100) = {}", 200u8.saturating_add(100));`
+//! or `let v = Some(10);` followed by `println!("{}", v.unwrap_or(0));`.
+//! Those `let`s are shown with the prompt. The answer is not worked out
+//! here: it is read from the lesson's actual output, by finding the printed
+//! line with the same text around the placeholder.
+
+use std::collections::BTreeMap;
+
+use crate::lint::scan::{self, Token};
+
+/// A qualifying `println!` in a `demonstrate_*` function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub function: String,
+    pub line: usize,
+    /// The placeholder, e.g. `{}` or `{:?}`.
+    pub spec: String,
+    pub expression: String,
+    /// The `let` statements the expression depends on, in source order.
+    pub context: Vec<String>,
+    /// Format string text before and after the placeholder, unescaped and
+    /// cut at newlines, as it appears on the printed line.
+    before: String,
+    after: String,
+}
+
+impl Candidate {
+    /// The value as printed in `output`, or `None` if no line matches or
+    /// matching lines disagree (the `println!` runs in a loop).
+    pub fn answer_in(&self, output: &str) -> Option<String> {
+        let mut values = output.lines().filter_map(|line| {
+            let value = line.strip_prefix(&self.before)?.strip_suffix(&self.after)?;
+            Some(value.to_string())
+        });
+        let first = values.next()?;
+        values.all(|value| value == first).then_some(first)
+    }
+
+    pub fn prompt(&self) -> String {
+        let call = format!("println!(\"{}\", {})", self.spec, self.expression);
+        if self.context.is_empty() {
+            return format!("What does `{call}` print?");
+        }
+        let mut prompt = String::from("What does the last line print?\n");
+        for line in &self.context {
+            prompt.push_str(&format!("    {line}\n"));
+        }
+        prompt.push_str(&format!("    {call};"));
+        prompt
+    }
+}
+
+/// A `let` a candidate may depend on.
+struct Binding {
+    line: usize,
+    text: String,
+    /// Earlier bindings its value reads.
+    uses: Vec<String>,
+}
+
+/// Every qualifying line in `source`, in order.
+pub fn candidates(source: &str) -> Vec<Candidate> {
+    let outline = scan::outline(source);
+    let lines: Vec<&str> = source.lines().collect();
+    let mut found = Vec::new();
+    for function in &outline.functions {
+        if !function.name.starts_with("demonstrate_") {
+            continue;
+        }
+        let mut bindings = BTreeMap::new();
+        let (first, last) = function.body;
+        for line in first..=last {
+            let Some(text) = lines.get(line - 1).map(|text| text.trim()) else {
+                continue;
+            };
+            if let Some(rest) = text.strip_prefix("let ") {
+                bind(&mut bindings, line, text, rest);
+            } else if let Some(mut candidate) = parse_println(text)
+                && let Some(uses) = locals(&candidate.expression, &bindings)
+            {
+                candidate.context = context(&uses, &bindings);
+                candidate.function = function.name.clone();
+                candidate.line = line;
+                found.push(candidate);
+            }
+        }
+    }
+    found
+}
+
+/// Records `let name[: Type] = value;` if the value qualifies. Any other
+/// `let` of the same name hides the earlier binding.
+fn bind(bindings: &mut BTreeMap<String, Binding>, line: usize, text: &str, rest: &str) {
+    let parsed = rest.strip_suffix(';').and_then(|rest| {
+        let (target, value) = rest.split_once(" = ")?;
+        let name = target.split(':').next()?.trim();
+        let simple = name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        (simple && !name.is_empty() && name != "_").then_some((name, value))
+    });
+    let Some((name, value)) = parsed else {
+        // `let mut x`, patterns and multi-line values; forget what the
+        // names on the left used to mean
+        for word in rest.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+            bindings.remove(word);
+        }
+        return;
+    };
+    match locals(value, bindings) {
+        Some(uses) => {
+            let binding = Binding {
+                line,
+                text: text.to_string(),
+                uses,
+            };
+            bindings.insert(name.to_string(), binding);
+        }
+        None => {
+            bindings.remove(name);
+        }
+    }
+}
+
+/// The `let` lines behind `uses`, following their own uses, in source
+/// order.
+fn context(uses: &[String], bindings: &BTreeMap<String, Binding>) -> Vec<String> {
+    let mut needed: BTreeMap<usize, &str> = BTreeMap::new();
+    let mut pending: Vec<&str> = uses.iter().map(String::as_str).collect();
+    while let Some(name) = pending.pop() {
+        if let Some(binding) = bindings.get(name)
+            && needed.insert(binding.line, &binding.text).is_none()
+        {
+            pending.extend(binding.uses.iter().map(String::as_str));
+        }
+    }
+    needed.into_values().map(str::to_string).collect()
+}
+
+/// A whole `println!("...", expr);` (or `predict!`) on one line, with one
+/// placeholder.
+fn parse_println(text: &str) -> Option<Candidate> {
+    let text = text.trim();
+    let rest = text
+        .strip_prefix("println!(\"")
+        .or_else(|| text.strip_prefix("predict!(\""))?;
+    let rest = rest.strip_suffix(");")?;
+    let (literal, expression) = split_literal(rest)?;
+    let expression = expression.trim_start().strip_prefix(',')?.trim();
+    // A `tr!` prints translated prose, which is nothing to work out
+    if expression.is_empty() || has_top_level_comma(expression) || expression.starts_with("tr!(") {
+        return None;
+    }
+
+    let format = unescape(literal)?;
+    if format.contains("{{") || format.contains("}}") {
+        return None;
+    }
+    let open = format.find('{')?;
+    let close = open + format[open..].find('}')?;
+    let spec = &format[open..=close];
+    // Only positional placeholders with at most a format spec, e.g. `{:>5}`
+    if !(spec == "{}" || spec.starts_with("{:")) || spec.contains('#') {
+        return None;
+    }
+    let (before, after) = (&format[..open], &format[close + 1..]);
+    if after.contains(['{', '}']) {
+        return None;
+    }
+    let before = before.rsplit('\n').next().unwrap_or_default();
+    let after = after.split('\n').next().unwrap_or_default();
+    if before.trim().is_empty() && after.trim().is_empty() {
+        return None;
+    }
+    Some(Candidate {
+        function: String::new(),
+        line: 0,
+        spec: spec.to_string(),
+        expression: expression.to_string(),
+        context: Vec::new(),
+        before: before.to_string(),
+        after: after.to_string(),
+    })
+}
+
+/// Splits `text` after the opening quote into the literal's contents and
+/// whatever follows its closing quote.
+fn split_literal(text: &str) -> Option<(&str, &str)> {
+    let mut escaped = false;
+    for (at, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some((&text[..at], &text[at + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn unescape(literal: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut chars = literal.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            '\\' => '\\',
+            '"' => '"',
+            '\'' => '\'',
+            _ => return None,
+        });
+    }
+    Some(out)
+}
+
+fn has_top_level_comma(expression: &str) -> bool {
+    let mut depth = 0i32;
+    for c in scan::mask(expression).chars() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+const PRIMITIVES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
+    "f64", "bool", "char", "str",
+];
+
+/// The bound locals the expression reads, or `None` if it reads anything
+/// else that could be a local. Literals, keywords, primitive types,
+/// capitalized names, methods, paths and macros are fine.
+fn locals(expression: &str, bindings: &BTreeMap<String, Binding>) -> Option<Vec<String>> {
+    let tokens: Vec<Token> = scan::tokenize(&scan::mask(expression))
+        .into_iter()
+        .map(|(_, token)| token)
+        .collect();
+    let punct = |i: Option<usize>, p: char| {
+        i.and_then(|i| tokens.get(i))
+            .is_some_and(|t| *t == Token::Punct(p))
+    };
+    let mut uses = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let Token::Word(word) = token else {
+            continue;
+        };
+        let prev = i.checked_sub(1);
+        let known = word.starts_with(|c: char| c.is_ascii_digit() || c.is_ascii_uppercase())
+            || matches!(word.as_str(), "as" | "true" | "false")
+            || PRIMITIVES.contains(&word.as_str())
+            || punct(prev, '.')
+            || punct(prev, ':')
+            || punct(Some(i + 1), ':')
+            || punct(Some(i + 1), '!');
+        if known {
+            continue;
+        }
+        if !bindings.contains_key(word) {
+            return None;
+        }
+        if !uses.contains(word) {
+            uses.push(word.clone());
+        }
+    }
+    Some(uses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+pub fn main() {
+    demonstrate_methods();
+}
+
+fn demonstrate_methods() {
+    let x = 5;
+    let doubled: i32 = x * 2;
+    let mut z = 1;
+    println!("1. Methods");
+    println!("   200u8.saturating_add(100) = {}", 200u8.saturating_add(100));
+    println!("   size: {:?} bytes", std::mem::size_of::<char>());
+    println!("\n   max = {}!", i32::MAX.checked_add(1).is_none());
+    println!("   doubled = {}", doubled + 1);
+    println!("   z = {}", z);
+    println!("   {} and {}", 1, 2);
+    println!("   {}", 7);
+    println!("   sum = {}", add(1, 2));
+    for i in 0..3 {
+        println!("   loop: {}", i * 0 + 1u8.pow(2));
+    }
+    println!("   spin: {}", 3u8.rotate_left(i32::BITS));
+    let x = (1, 2);
+    let (x, y) = x;
+    println!("   x = {}", x);
+    println!("2. {}", tr!("methods.title"));
+}
+
+fn add(a: i32, b: i32) -> i32 {
+    println!("   not a demo: {}", 1 + 1);
+    a + b
+}
+"#;
+
+    #[test]
+    fn picks_single_values_of_known_expressions() {
+        let found: Vec<(usize, String)> = candidates(SOURCE)
+            .into_iter()
+            .map(|c| (c.line, c.prompt()))
+            .collect();
+        // `z` is mut, `add` and the loop's `i` are unknown, the last `x`
+        // comes from a pattern; two values, none or a `tr!` are skipped
+        let prompt = |call: &str| format!("What does `println!({call})` print?");
+        assert_eq!(
+            found,
+            [
+                (11, prompt("\"{}\", 200u8.saturating_add(100)")),
+                (12, prompt("\"{:?}\", std::mem::size_of::<char>()")),
+                (13, prompt("\"{}\", i32::MAX.checked_add(1).is_none()")),
+                (
+                    14,
+                    "What does the last line print?\n    let x = 5;\n    let doubled: i32 = x * 2;\n    println!(\"{}\", doubled + 1);"
+                        .to_string()
+                ),
+                (22, prompt("\"{}\", 3u8.rotate_left(i32::BITS)")),
+            ]
+        );
+    }
+
+    #[test]
+    fn answers_come_from_the_printed_lines() {
+        let output = "1. Methods\n   200u8.saturating_add(100) = 255\n   size: 4 bytes\n\n   max = true!\n   doubled = 11\n   spin: 3\n   spin: 4\n";
+        let answers: Vec<Option<String>> = candidates(SOURCE)
+            .iter()
+            .map(|c| c.answer_in(output))
+            .collect();
+        let expected = [Some("255"), Some("4"), Some("true"), Some("11"), None];
+        assert_eq!(answers, expected.map(|a| a.map(String::from)));
+    }
+}
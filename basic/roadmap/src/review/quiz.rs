@@ -0,0 +1,152 @@
+//! Short-answer questions about the registered lessons.
+//!
+//! Answers are compared after trimming, collapsing whitespace and ignoring
+//! case, so list only genuinely different spellings as alternatives.
+
+/// One question. The first answer is the one shown after a review.
+pub struct Question {
+    pub lesson: &'static str,
+    pub prompt: &'static str,
+    pub answers: &'static [&'static str],
+}
+
+pub static QUIZ: &[Question] = &[
+    Question {
+        lesson: "syntax_and_semantics::arrays",
+        prompt: "What is the type of `[0u8; 5]`?",
+        answers: &["[u8; 5]"],
+    },
+    Question {
+        lesson: "syntax_and_semantics::arrays",
+        prompt: "What happens at run time when an array is indexed past its end?",
+        answers: &["it panics", "panic", "panics"],
+    },
+    Question {
+        lesson: "syntax_and_semantics::destructuring",
+        prompt: "Which pattern binds the first element of a slice and ignores the rest?",
+        answers: &["[first, ..]"],
+    },
+    Question {
+        lesson: "syntax_and_semantics::destructuring",
+        prompt: "In a struct pattern, what skips the remaining fields?",
+        answers: &["..", "`..`"],
+    },
+    Question {
+        lesson: "ownership_system::ownership_rules_and_memory_safety",
+        prompt: "How many owners can a value have at a time?",
+        answers: &["one", "1"],
+    },
+    Question {
+        lesson: "ownership_system::ownership_rules_and_memory_safety",
+        prompt: "After `let s2 = s1;` with `s1: String`, can `s1` still be used?",
+        answers: &["no"],
+    },
+    Question {
+        lesson: "ownership_system::references_borrowing_slices",
+        prompt: "How many `&mut` references to a value may be live at once?",
+        answers: &["one", "1"],
+    },
+    Question {
+        lesson: "ownership_system::references_borrowing_slices",
+        prompt: "What is the type of a string slice?",
+        answers: &["&str"],
+    },
+    Question {
+        lesson: "ownership_system::box_stack_heap",
+        prompt: "Where does `Box::new(5)` store the 5?",
+        answers: &["on the heap", "heap", "the heap"],
+    },
+    Question {
+        lesson: "ownership_system::box_stack_heap",
+        prompt: "Which operator reads the value inside a `Box<i32>`?",
+        answers: &["*", "`*`", "deref", "the deref operator"],
+    },
+    Question {
+        lesson: "ownership_system::grids_and_matrices",
+        prompt: "In a row-major grid of width `w`, what is the index of cell (x, y)?",
+        answers: &["y * w + x", "y*w + x", "y*w+x", "x + y * w"],
+    },
+    Question {
+        lesson: "ownership_system::grids_and_matrices",
+        prompt: "Multiplying a `Matrix<_, 2, 3>` by a `Matrix<_, 2, 3>`: compile error or run-time panic?",
+        answers: &["compile error", "a compile error"],
+    },
+    Question {
+        lesson: "constructs::structs",
+        prompt: "What syntax copies the remaining fields from another instance, as in `User { email, ..user1 }`?",
+        answers: &["struct update syntax", "struct update"],
+    },
+    Question {
+        lesson: "constructs::structs",
+        prompt: "What is a struct with no fields, like `struct AlwaysEqual;`, called?",
+        answers: &["a unit-like struct", "unit-like struct", "unit-like"],
+    },
+    Question {
+        lesson: "constructs::enums",
+        prompt: "Which enum does Rust use instead of null?",
+        answers: &["Option", "Option<T>"],
+    },
+    Question {
+        lesson: "constructs::enums",
+        prompt: "What must a `match` on an enum cover?",
+        answers: &["every variant", "all variants", "all cases", "every case"],
+    },
+    Question {
+        lesson: "constructs::impl_blocks",
+        prompt: "What is a function in an `impl` block without a `self` parameter called?",
+        answers: &["an associated function", "associated function"],
+    },
+    Question {
+        lesson: "constructs::impl_blocks",
+        prompt: "Which keyword names the implementing type inside an `impl` block?",
+        answers: &["Self"],
+    },
+    Question {
+        lesson: "constructs::rectangle_packing",
+        prompt: "`pack` returns placements that refer to items how: by reference or by index?",
+        answers: &["by index", "index"],
+    },
+    Question {
+        lesson: "constructs::rectangle_packing",
+        prompt: "What does `QuadTree::insert` hand back when the rectangle does not fit?",
+        answers: &["the value", "the value it was given", "Err(value)"],
+    },
+    Question {
+        lesson: "constructs::traits",
+        prompt: "Which clause moves trait bounds after the signature?",
+        answers: &["where", "a where clause", "where clause"],
+    },
+    Question {
+        lesson: "constructs::traits",
+        prompt: "A function returning `impl Summary` may return how many different concrete types?",
+        answers: &["one", "1"],
+    },
+    Question {
+        lesson: "constructs::summary_feed",
+        prompt: "What type lets one `Vec` hold both `NewsArticle` and `Tweet`?",
+        answers: &["Box<dyn Summary>"],
+    },
+    Question {
+        lesson: "constructs::summary_feed",
+        prompt: "Which `Summary` method must every implementor write: `summarize` or `summarize_author`?",
+        answers: &["summarize_author"],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lessons;
+
+    #[test]
+    fn every_question_belongs_to_a_lesson() {
+        for question in QUIZ {
+            assert!(
+                lessons::find(question.lesson).is_some(),
+                "{}",
+                question.lesson
+            );
+            assert!(!question.answers.is_empty(), "{}", question.prompt);
+        }
+    }
+}
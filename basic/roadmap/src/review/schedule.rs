@@ -0,0 +1,300 @@
+//! SM-2 scheduling and the file that remembers it.
+//!
+//! Each card has a repetition count, an interval in days and an ease
+//! factor, which start at 0, 0 and 2.5. A review is graded 0 to 5; grades
+//! of 3 and above pass. Passing reviews grow the interval (1 day, then 6,
+//! then the previous interval times the ease), failing ones start the card
+//! over. The ease moves with every grade and never drops below 1.3.
+//! Ease is kept in thousandths so that the file round-trips exactly.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::calendar::Date;
+use crate::progress;
+
+const INITIAL_EASE: u16 = 2500;
+const MIN_EASE: u16 = 1300;
+
+/// How well a card was recalled, from 0 (blank) to 5 (perfect).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Grade(u8);
+
+impl Grade {
+    /// `None` above 5.
+    pub fn new(grade: u8) -> Option<Self> {
+        (grade <= 5).then_some(Grade(grade))
+    }
+
+    pub fn value(self) -> u8 {
+        self.0
+    }
+
+    pub fn passed(self) -> bool {
+        self.0 >= 3
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Schedule {
+    pub repetitions: u32,
+    pub interval_days: u32,
+    /// Ease factor in thousandths; 2500 is 2.5.
+    pub ease: u16,
+    pub due: Date,
+}
+
+impl Schedule {
+    /// A card never reviewed, due on `today`.
+    pub fn new(today: Date) -> Self {
+        Self {
+            repetitions: 0,
+            interval_days: 0,
+            ease: INITIAL_EASE,
+            due: today,
+        }
+    }
+
+    pub fn is_due(&self, today: Date) -> bool {
+        self.due <= today
+    }
+
+    /// The schedule after a review graded `grade` on `today`.
+    pub fn review(self, grade: Grade, today: Date) -> Self {
+        let (repetitions, interval_days) = if grade.passed() {
+            let interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (u64::from(self.interval_days) * u64::from(self.ease)).div_ceil(1000),
+            };
+            (
+                self.repetitions + 1,
+                u32::try_from(interval).unwrap_or(u32::MAX),
+            )
+        } else {
+            (0, 1)
+        };
+
+        // EF' = EF + (0.1 - (5 - q) * (0.08 + (5 - q) * 0.02)), in thousandths
+        let miss = i32::from(5 - grade.value());
+        let ease = i32::from(self.ease) + 100 - miss * (80 + miss * 20);
+        let ease = u16::try_from(ease.max(i32::from(MIN_EASE))).unwrap_or(u16::MAX);
+
+        Self {
+            repetitions,
+            interval_days,
+            ease,
+            due: today
+                .checked_add_days(i64::from(interval_days))
+                .unwrap_or(Date::MAX),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDeckError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseDeckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseDeckError {}
+
+/// Schedules by card key. Cards missing from the deck are new.
+///
+/// Stored one card per line: `KEY DUE REPETITIONS INTERVAL EASE`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Deck {
+    schedules: BTreeMap<String, Schedule>,
+}
+
+impl Deck {
+    /// `review` inside [`progress::data_dir`].
+    pub fn default_path() -> Option<PathBuf> {
+        progress::data_dir().map(|dir| dir.join("review"))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseDeckError> {
+        let mut deck = Self::default();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, schedule) = parse_line(line).map_err(|message| ParseDeckError {
+                line: index + 1,
+                message,
+            })?;
+            deck.schedules.insert(key, schedule);
+        }
+        Ok(deck)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# card due repetitions interval ease\n");
+        for (key, s) in &self.schedules {
+            text.push_str(&format!(
+                "{key} {} {} {} {}\n",
+                s.due, s.repetitions, s.interval_days, s.ease
+            ));
+        }
+        text
+    }
+
+    /// A missing file is an empty deck. Parse errors become
+    /// [`io::ErrorKind::InvalidData`].
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                Self::parse(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    /// The card's schedule, or a new one due `today`.
+    pub fn schedule(&self, key: &str, today: Date) -> Schedule {
+        self.schedules
+            .get(key)
+            .copied()
+            .unwrap_or_else(|| Schedule::new(today))
+    }
+
+    /// Records a review and returns the new schedule.
+    pub fn review(&mut self, key: &str, grade: Grade, today: Date) -> Schedule {
+        let next = self.schedule(key, today).review(grade, today);
+        self.schedules.insert(key.to_string(), next);
+        next
+    }
+
+    pub fn len(&self) -> usize {
+        self.schedules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schedules.is_empty()
+    }
+}
+
+fn parse_line(line: &str) -> Result<(String, Schedule), String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [key, due, repetitions, interval, ease] = fields[..] else {
+        return Err(format!(
+            "expected `KEY DUE REPETITIONS INTERVAL EASE`, found {} fields",
+            fields.len()
+        ));
+    };
+    let number = |field: &str, name: &str| {
+        field
+            .parse::<u32>()
+            .map_err(|_| format!("{name} `{field}` is not a whole number"))
+    };
+    let ease = number(ease, "ease")?;
+    let schedule = Schedule {
+        due: due.parse().map_err(|err| format!("due date: {err}"))?,
+        repetitions: number(repetitions, "repetitions")?,
+        interval_days: number(interval, "interval")?,
+        ease: u16::try_from(ease)
+            .ok()
+            .filter(|&ease| ease >= MIN_EASE)
+            .ok_or(format!("ease {ease} is not in {MIN_EASE}..=65535"))?,
+    };
+    Ok((key.to_string(), schedule))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(text: &str) -> Date {
+        text.parse().unwrap()
+    }
+
+    fn grade(value: u8) -> Grade {
+        Grade::new(value).unwrap()
+    }
+
+    #[test]
+    fn intervals_follow_sm2() {
+        let today = day("2024-03-01");
+        let mut schedule = Schedule::new(today);
+        let mut intervals = Vec::new();
+        for _ in 0..5 {
+            schedule = schedule.review(grade(4), schedule.due);
+            intervals.push(schedule.interval_days);
+        }
+        // Grade 4 leaves the ease at 2.5
+        assert_eq!(intervals, [1, 6, 15, 38, 95]);
+        assert_eq!(schedule.ease, 2500);
+        assert_eq!(
+            schedule.due,
+            day("2024-03-01").add_days(1 + 6 + 15 + 38 + 95)
+        );
+
+        let failed = schedule.review(grade(1), schedule.due);
+        assert_eq!((failed.repetitions, failed.interval_days), (0, 1));
+        assert_eq!(failed.ease, 2500 - 540);
+    }
+
+    #[test]
+    fn ease_has_a_floor() {
+        let today = day("2024-03-01");
+        let mut schedule = Schedule::new(today);
+        for _ in 0..10 {
+            schedule = schedule.review(grade(0), today);
+        }
+        assert_eq!(schedule.ease, MIN_EASE);
+        assert_eq!(schedule.review(grade(5), today).ease, MIN_EASE + 100);
+        assert!(Grade::new(6).is_none());
+    }
+
+    #[test]
+    fn deck_round_trips_and_reports_bad_lines() {
+        let today = day("2024-03-01");
+        let mut deck = Deck::default();
+        deck.review("quiz:1234", grade(5), today);
+        deck.review("predict:abcd", grade(2), today);
+        let text = deck.to_text();
+        assert_eq!(Deck::parse(&text).unwrap(), deck);
+        assert!(deck.schedule("new", today).is_due(today));
+        assert!(!deck.schedule("quiz:1234", today).is_due(today));
+
+        let cases = [
+            (
+                "a 2024-03-01 1 1",
+                "line 1: expected `KEY DUE REPETITIONS INTERVAL EASE`, found 4 fields",
+            ),
+            (
+                "\na 2024-03-01 1 x 2500",
+                "line 2: interval `x` is not a whole number",
+            ),
+            (
+                "a 2024-02-30 1 1 2500",
+                "line 1: due date: February 2024 has 29 days, not 30",
+            ),
+            (
+                "a 2024-03-01 1 1 900",
+                "line 1: ease 900 is not in 1300..=65535",
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(Deck::parse(input).unwrap_err().to_string(), expected);
+        }
+    }
+}
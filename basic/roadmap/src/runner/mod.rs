@@ -0,0 +1,468 @@
+//! Compiles and runs untrusted Rust snippets with limits.
+//!
+//! [`run`] writes the snippet as `src/main.rs` of a throwaway crate in the
+//! temp directory, builds it with the local `cargo` under a timeout, then
+//! runs the binary with CPU-time, memory and output limits (see
+//! [`rlimit`]) and a wall-clock timeout. Output goes to files rather than
+//! pipes so that the output limit can be enforced by the kernel. The
+//! result is classified as an [`Outcome`] and, unless it timed out, cached
+//! by a hash of the source, the toolchain version and the limits.
+
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub mod rlimit;
+
+use crate::hash::fnv1a;
+use crate::progress::data_dir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limits {
+    pub compile_timeout: Duration,
+    /// Wall-clock time the program may run, including time spent waiting.
+    pub run_timeout: Duration,
+    pub cpu_seconds: u64,
+    /// Address space, which bounds what the program can allocate.
+    pub memory_bytes: u64,
+    /// For stdout and stderr each.
+    pub output_bytes: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            compile_timeout: Duration::from_secs(60),
+            run_timeout: Duration::from_secs(10),
+            cpu_seconds: 5,
+            memory_bytes: 512 << 20,
+            output_bytes: 1 << 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    CompileError,
+    /// Compiling or running took longer than allowed, in CPU or wall time.
+    Timeout,
+    Panic,
+    /// An allocation failed at the memory limit.
+    OutOfMemory,
+    /// Wrote more than the output limit.
+    OutputLimit,
+    /// Any other failure: a non-zero exit or a signal.
+    Crashed,
+}
+
+impl Outcome {
+    pub fn name(self) -> &'static str {
+        match self {
+            Outcome::Success => "success",
+            Outcome::CompileError => "compile-error",
+            Outcome::Timeout => "timeout",
+            Outcome::Panic => "panic",
+            Outcome::OutOfMemory => "out-of-memory",
+            Outcome::OutputLimit => "output-limit",
+            Outcome::Crashed => "crashed",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            Outcome::Success,
+            Outcome::CompileError,
+            Outcome::Timeout,
+            Outcome::Panic,
+            Outcome::OutOfMemory,
+            Outcome::OutputLimit,
+            Outcome::Crashed,
+        ]
+        .into_iter()
+        .find(|outcome| outcome.name() == name)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunResult {
+    pub outcome: Outcome,
+    /// The program's output; empty if it did not compile.
+    pub stdout: String,
+    /// The compiler's diagnostics for [`Outcome::CompileError`], otherwise
+    /// the program's stderr.
+    pub stderr: String,
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+    /// `true` if the result came from the cache without compiling.
+    pub cached: bool,
+}
+
+/// Where results are cached by default: `cache/snippets` in the data
+/// directory, or the temp directory without one.
+pub fn default_cache_dir() -> PathBuf {
+    data_dir()
+        .map(|dir| dir.join("cache").join("snippets"))
+        .unwrap_or_else(|| env::temp_dir().join("roadmap-snippets"))
+}
+
+/// Compiles and runs `source`, a program with its own `fn main`. Errors are
+/// for the runner itself, such as `cargo` not starting; anything the
+/// snippet does wrong is an [`Outcome`].
+pub fn run(source: &str, limits: &Limits, cache: Option<&Path>) -> io::Result<RunResult> {
+    let entry = match cache {
+        Some(dir) => Some(dir.join(cache_key(source, limits)?)),
+        None => None,
+    };
+    if let Some(result) = entry.as_deref().and_then(read_cached) {
+        return Ok(result);
+    }
+
+    let work = TempCrate::new(source)?;
+    let result = compile_and_run(&work.dir, limits)?;
+    if let Some(entry) = &entry
+        && result.outcome != Outcome::Timeout
+    {
+        // A failed cache write only costs a recompile next time
+        let _ = write_cached(entry, &result);
+    }
+    Ok(result)
+}
+
+fn cargo() -> OsString {
+    env::var_os("CARGO").unwrap_or_else(|| "cargo".into())
+}
+
+fn cache_key(source: &str, limits: &Limits) -> io::Result<String> {
+    let version = Command::new(cargo()).arg("--version").output()?;
+    let text = format!(
+        "{}\n{limits:?}\n{source}",
+        String::from_utf8_lossy(&version.stdout)
+    );
+    Ok(format!("{:016x}", fnv1a(&text)))
+}
+
+/// A crate directory removed on drop.
+struct TempCrate {
+    dir: PathBuf,
+}
+
+impl TempCrate {
+    fn new(source: &str) -> io::Result<Self> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let dir = env::temp_dir().join(format!(
+            "roadmap-run-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        let work = Self { dir };
+        fs::create_dir_all(work.dir.join("src"))?;
+        fs::write(
+            work.dir.join("Cargo.toml"),
+            "[package]\nname = \"snippet\"\nversion = \"0.0.0\"\nedition = \"2024\"\n\n[dependencies]\n",
+        )?;
+        fs::write(work.dir.join("src/main.rs"), source)?;
+        Ok(work)
+    }
+}
+
+impl Drop for TempCrate {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn compile_and_run(dir: &Path, limits: &Limits) -> io::Result<RunResult> {
+    let log = dir.join("build.log");
+    let mut build = Command::new(cargo());
+    build
+        .args(["build", "--quiet", "--offline", "--color", "never"])
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", dir.join("target"))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(fs::File::create(&log)?);
+    let (status, timed_out) = wait_with_timeout(build.spawn()?, limits.compile_timeout)?;
+    let diagnostics = fs::read_to_string(&log).unwrap_or_default();
+    if timed_out || !status.success() {
+        return Ok(RunResult {
+            outcome: if timed_out {
+                Outcome::Timeout
+            } else {
+                Outcome::CompileError
+            },
+            stdout: String::new(),
+            stderr: diagnostics,
+            exit_code: status.code(),
+            signal: rlimit::signal(&status),
+            cached: false,
+        });
+    }
+
+    let binary = dir
+        .join("target/debug/snippet")
+        .with_extension(env::consts::EXE_EXTENSION);
+    let (stdout_path, stderr_path) = (dir.join("stdout"), dir.join("stderr"));
+    let mut program = Command::new(binary);
+    program
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(fs::File::create(&stdout_path)?)
+        .stderr(fs::File::create(&stderr_path)?);
+    rlimit::apply(&mut program, limits);
+    let (status, timed_out) = wait_with_timeout(program.spawn()?, limits.run_timeout)?;
+    let read = |path: &Path| {
+        let bytes = fs::read(path).unwrap_or_default();
+        let end = bytes.len().min(limits.output_bytes as usize);
+        String::from_utf8_lossy(&bytes[..end]).into_owned()
+    };
+    let stderr = read(&stderr_path);
+    let (code, signal) = (status.code(), rlimit::signal(&status));
+    Ok(RunResult {
+        outcome: classify(code, signal, &stderr, timed_out),
+        stdout: read(&stdout_path),
+        stderr,
+        exit_code: code,
+        signal,
+        cached: false,
+    })
+}
+
+/// Waits for `child`, killing it after `timeout`; `true` if it was killed.
+fn wait_with_timeout(mut child: Child, timeout: Duration) -> io::Result<(ExitStatus, bool)> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok((status, false));
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            return Ok((child.wait()?, true));
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+fn classify(code: Option<i32>, signal: Option<i32>, stderr: &str, timed_out: bool) -> Outcome {
+    if timed_out {
+        return Outcome::Timeout;
+    }
+    if code == Some(0) {
+        return Outcome::Success;
+    }
+    // The messages alone prove nothing, since the snippet can print them
+    // itself; they have to come with the way the runtime ends the process
+    match signal {
+        Some(rlimit::SIGXCPU | rlimit::SIGKILL) => Outcome::Timeout,
+        Some(rlimit::SIGXFSZ) => Outcome::OutputLimit,
+        // The default allocation error handler prints this, then aborts
+        Some(rlimit::SIGABRT)
+            if stderr.contains("memory allocation of") && stderr.contains("failed") =>
+        {
+            Outcome::OutOfMemory
+        }
+        // A panic that unwinds out of `main` exits with 101
+        None if code == Some(101) && stderr.contains("panicked at") => Outcome::Panic,
+        _ => Outcome::Crashed,
+    }
+}
+
+/// A cache entry is a directory with `result` (`OUTCOME CODE SIGNAL`, `-`
+/// for none), `stdout` and `stderr`.
+fn write_cached(entry: &Path, result: &RunResult) -> io::Result<()> {
+    let number = |n: Option<i32>| n.map_or("-".to_string(), |n| n.to_string());
+    fs::create_dir_all(entry)?;
+    fs::write(entry.join("stdout"), &result.stdout)?;
+    fs::write(entry.join("stderr"), &result.stderr)?;
+    // Written last, so an entry without it is incomplete and ignored
+    fs::write(
+        entry.join("result"),
+        format!(
+            "{} {} {}\n",
+            result.outcome,
+            number(result.exit_code),
+            number(result.signal)
+        ),
+    )
+}
+
+fn read_cached(entry: &Path) -> Option<RunResult> {
+    let line = fs::read_to_string(entry.join("result")).ok()?;
+    let mut fields = line.split_whitespace();
+    let outcome = Outcome::from_name(fields.next()?)?;
+    let mut number = || match fields.next()? {
+        "-" => Some(None),
+        n => n.parse().ok().map(Some),
+    };
+    let (exit_code, signal) = (number()?, number()?);
+    Some(RunResult {
+        outcome,
+        stdout: fs::read_to_string(entry.join("stdout")).ok()?,
+        stderr: fs::read_to_string(entry.join("stderr")).ok()?,
+        exit_code,
+        signal,
+        cached: true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> Limits {
+        Limits {
+            run_timeout: Duration::from_secs(5),
+            cpu_seconds: 1,
+            memory_bytes: 256 << 20,
+            output_bytes: 4096,
+            ..Limits::default()
+        }
+    }
+
+    fn outcome(source: &str) -> RunResult {
+        run(source, &limits(), None).unwrap()
+    }
+
+    #[test]
+    fn success_is_cached() {
+        let cache = env::temp_dir().join(format!("roadmap-runner-cache-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache);
+        let source = "fn main() {\n    println!(\"{}\", 6 * 7);\n    eprintln!(\"note\");\n}\n";
+        let first = run(source, &limits(), Some(&cache)).unwrap();
+        assert_eq!(
+            (first.outcome, first.stdout.as_str(), first.stderr.as_str()),
+            (Outcome::Success, "42\n", "note\n")
+        );
+        assert_eq!((first.exit_code, first.cached), (Some(0), false));
+
+        let second = run(source, &limits(), Some(&cache)).unwrap();
+        assert_eq!(
+            second,
+            RunResult {
+                cached: true,
+                ..first
+            }
+        );
+        let _ = fs::remove_dir_all(&cache);
+    }
+
+    #[test]
+    fn classifies_failures() {
+        let result = outcome("fn main() { let x: u8 = \"no\"; }");
+        assert_eq!(result.outcome, Outcome::CompileError);
+        assert!(result.stderr.contains("mismatched types"));
+
+        let result = outcome("fn main() { let v: Vec<u8> = Vec::new(); println!(\"{}\", v[1]); }");
+        assert_eq!(
+            (result.outcome, result.exit_code),
+            (Outcome::Panic, Some(101))
+        );
+        assert!(result.stderr.contains("index out of bounds"));
+
+        let result = outcome("fn main() { std::process::exit(3) }");
+        assert_eq!(
+            (result.outcome, result.exit_code),
+            (Outcome::Crashed, Some(3))
+        );
+
+        // Printing a panic message is not panicking
+        let result = outcome(
+            "fn main() { eprintln!(\"thread 'main' panicked at x\"); std::process::exit(2) }",
+        );
+        assert_eq!(
+            (result.outcome, result.exit_code),
+            (Outcome::Crashed, Some(2))
+        );
+    }
+
+    #[test]
+    fn wall_clock_timeout() {
+        let limits = Limits {
+            run_timeout: Duration::from_millis(300),
+            ..limits()
+        };
+        let source = "fn main() { println!(\"start\"); std::thread::sleep(std::time::Duration::from_secs(30)); }";
+        let result = run(source, &limits, None).unwrap();
+        assert_eq!(
+            (result.outcome, result.stdout.as_str()),
+            (Outcome::Timeout, "start\n")
+        );
+    }
+
+    #[cfg(all(target_os = "linux", target_pointer_width = "64"))]
+    #[test]
+    fn resource_limits() {
+        let spin = "fn main() { let mut n: u64 = 0; loop { n = std::hint::black_box(n.wrapping_add(1)); } }";
+        let result = outcome(spin);
+        assert_eq!(
+            (result.outcome, result.signal),
+            (Outcome::Timeout, Some(rlimit::SIGXCPU))
+        );
+
+        let result =
+            outcome("fn main() { let v = vec![1u8; 1 << 30]; println!(\"{}\", v.len()); }");
+        assert_eq!(result.outcome, Outcome::OutOfMemory);
+
+        let result = outcome("fn main() { loop { println!(\"yes\"); } }");
+        assert_eq!(
+            (result.outcome, result.signal),
+            (Outcome::OutputLimit, Some(rlimit::SIGXFSZ))
+        );
+        assert_eq!(result.stdout.len(), 4096);
+    }
+
+    #[test]
+    fn classification() {
+        assert_eq!(classify(None, None, "", true), Outcome::Timeout);
+        assert_eq!(
+            classify(Some(0), None, "thread 'main' panicked at", false),
+            Outcome::Success
+        );
+        assert_eq!(
+            classify(
+                None,
+                Some(rlimit::SIGABRT),
+                "memory allocation of 1024 bytes failed\n",
+                false
+            ),
+            Outcome::OutOfMemory
+        );
+        assert_eq!(
+            classify(None, Some(rlimit::SIGKILL), "", false),
+            Outcome::Timeout
+        );
+        assert_eq!(
+            classify(
+                Some(101),
+                None,
+                "thread 'main' panicked at src/main.rs:1:1:",
+                false
+            ),
+            Outcome::Panic
+        );
+        assert_eq!(classify(None, Some(11), "", false), Outcome::Crashed);
+        // The right messages with the wrong ending
+        assert_eq!(
+            classify(Some(1), None, "memory allocation of 8 bytes failed", false),
+            Outcome::Crashed
+        );
+        assert_eq!(
+            classify(Some(3), None, "thread 'main' panicked at", false),
+            Outcome::Crashed
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Resource limits for the snippet process, set with `setrlimit(2)` between
+//! `fork` and `exec`. The crate has no `libc` dependency, so the call and
+//! the constants are declared here; they match 64-bit Linux on x86-64,
+//! AArch64 and RISC-V. Elsewhere [`apply`] does nothing and only the
+//! runner's wall-clock timeout applies.
+
+use std::process::Command;
+
+use super::Limits;
+
+/// Signals the runner tells apart.
+pub const SIGABRT: i32 = 6;
+pub const SIGKILL: i32 = 9;
+/// Sent when the soft CPU-time limit is reached.
+pub const SIGXCPU: i32 = 24;
+/// Sent on a write past the file-size limit.
+pub const SIGXFSZ: i32 = 25;
+
+#[cfg(all(
+    target_os = "linux",
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )
+))]
+mod sys {
+    use std::io;
+    use std::os::unix::process::CommandExt;
+    use std::process::Command;
+
+    #[repr(C)]
+    struct Rlimit {
+        soft: u64,
+        hard: u64,
+    }
+
+    unsafe extern "C" {
+        fn setrlimit(resource: i32, limit: *const Rlimit) -> i32;
+    }
+
+    const RLIMIT_CPU: i32 = 0;
+    const RLIMIT_FSIZE: i32 = 1;
+    const RLIMIT_CORE: i32 = 4;
+    const RLIMIT_AS: i32 = 9;
+
+    pub fn apply(command: &mut Command, cpu_seconds: u64, memory: u64, output: u64) {
+        // The hard CPU limit is a second later, so a process that ignores
+        // SIGXCPU is killed; no core files for crashing snippets.
+        let limits = [
+            (RLIMIT_CPU, cpu_seconds, cpu_seconds + 1),
+            (RLIMIT_AS, memory, memory),
+            (RLIMIT_FSIZE, output, output),
+            (RLIMIT_CORE, 0, 0),
+        ];
+        // Only `setrlimit` runs in the child before `exec`: it is
+        // async-signal-safe and the closure neither allocates nor locks.
+        unsafe {
+            command.pre_exec(move || {
+                for (resource, soft, hard) in limits {
+                    if setrlimit(resource, &Rlimit { soft, hard }) != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+#[cfg(not(all(
+    target_os = "linux",
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )
+)))]
+mod sys {
+    pub fn apply(_command: &mut std::process::Command, _cpu: u64, _memory: u64, _output: u64) {}
+}
+
+/// Makes `command` start with the CPU-time, address-space and file-size
+/// limits in `limits`. Output goes to files, so the file-size limit caps it.
+pub fn apply(command: &mut Command, limits: &Limits) {
+    sys::apply(
+        command,
+        limits.cpu_seconds,
+        limits.memory_bytes,
+        limits.output_bytes,
+    );
+}
+
+/// The signal that ended the process, where the platform has them.
+pub fn signal(status: &std::process::ExitStatus) -> Option<i32> {
+    #[cfg(unix)]
+    {
+        std::os::unix::process::ExitStatusExt::signal(status)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        None
+    }
+}
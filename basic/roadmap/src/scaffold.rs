@@ -0,0 +1,527 @@
+//! `roadmap new <topic>/<lesson> --sections "a,b,c"`: a new lesson that
+//! already follows the conventions in `.kiro/steering/structure.md`.
+//!
+//! Besides the lesson file, the lesson is declared in its topic's `mod.rs`,
+//! added to `LESSONS` after the other lessons of the topic, given a section
+//! in the topic's `README.md` and a placeholder golden output under
+//! `tests/golden/`. Every change is worked out before anything is written,
+//! so a refusal leaves the tree as it was.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::lint::TOPICS;
+
+/// First line of a golden output that has not been recorded yet.
+pub const GOLDEN_PLACEHOLDER: &str =
+    "# placeholder: record with `ROADMAP_BLESS=1 cargo test --test golden`";
+
+/// Minutes a new lesson is estimated at until someone edits the registry.
+const DEFAULT_MINUTES: u16 = 15;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// Not `topic/lesson`, or a lesson name that cannot be a module.
+    InvalidTarget(String),
+    UnknownTopic(String),
+    /// A topic directory without a `mod.rs` is not compiled into the crate.
+    NoModule(String),
+    InvalidSection(String),
+    DuplicateSection(String),
+    NoSections,
+    Exists(PathBuf),
+    AlreadyRegistered(String),
+    /// `src/lessons.rs` does not have the layout edits rely on.
+    Registry(String),
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::InvalidTarget(target) => write!(
+                f,
+                "`{target}` is not <topic>/<lesson> with a snake_case lesson name"
+            ),
+            ScaffoldError::UnknownTopic(topic) => {
+                write!(f, "unknown topic `{topic}` (one of {})", TOPICS.join(", "))
+            }
+            ScaffoldError::NoModule(topic) => write!(
+                f,
+                "src/{topic} has no mod.rs, so its lessons are not compiled into the crate"
+            ),
+            ScaffoldError::InvalidSection(name) => {
+                write!(f, "section `{name}` is not a snake_case name")
+            }
+            ScaffoldError::DuplicateSection(name) => write!(f, "section `{name}` is listed twice"),
+            ScaffoldError::NoSections => f.pad("a lesson needs at least one section"),
+            ScaffoldError::Exists(path) => {
+                write!(f, "{} already exists; not overwriting it", path.display())
+            }
+            ScaffoldError::AlreadyRegistered(id) => write!(f, "`{id}` is already registered"),
+            ScaffoldError::Registry(message) => write!(f, "src/lessons.rs: {message}"),
+            ScaffoldError::Io { path, error } => write!(f, "{}: {error}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScaffoldError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// A validated request for a new lesson.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scaffold {
+    pub topic: String,
+    pub lesson: String,
+    /// Section names without the `demonstrate_` prefix, in order.
+    pub sections: Vec<String>,
+}
+
+/// One file to write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub path: PathBuf,
+    pub contents: String,
+    /// `false` for files that exist and are being extended.
+    pub created: bool,
+}
+
+impl Scaffold {
+    /// Parses `topic/lesson` and a comma-separated section list; sections
+    /// may be given with or without the `demonstrate_` prefix.
+    pub fn new(target: &str, sections: &str) -> Result<Self, ScaffoldError> {
+        let invalid = || ScaffoldError::InvalidTarget(target.to_string());
+        let (topic, lesson) = target.split_once('/').ok_or_else(invalid)?;
+        if !TOPICS.contains(&topic) {
+            return Err(ScaffoldError::UnknownTopic(topic.to_string()));
+        }
+        if !is_module_name(lesson) || lesson == "mod" {
+            return Err(invalid());
+        }
+
+        let mut names: Vec<String> = Vec::new();
+        for name in sections.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let name = name.strip_prefix("demonstrate_").unwrap_or(name);
+            if !is_module_name(name) {
+                return Err(ScaffoldError::InvalidSection(name.to_string()));
+            }
+            if names.iter().any(|seen| seen == name) {
+                return Err(ScaffoldError::DuplicateSection(name.to_string()));
+            }
+            names.push(name.to_string());
+        }
+        if names.is_empty() {
+            return Err(ScaffoldError::NoSections);
+        }
+        Ok(Self {
+            topic: topic.to_string(),
+            lesson: lesson.to_string(),
+            sections: names,
+        })
+    }
+
+    pub fn id(&self) -> String {
+        format!("{}::{}", self.topic, self.lesson)
+    }
+
+    pub fn title(&self) -> String {
+        title_case(&self.lesson)
+    }
+
+    /// The new lesson file.
+    pub fn lesson_source(&self) -> String {
+        let title = self.title();
+        let mut out = format!(
+            "// {title}\n// This file demonstrates ... (say what the lesson shows and why it matters).\n\n\
+             use crate::lessons::Section;\n\npub fn main() {{\n    println!(\"=== {title} ===\\n\");\n"
+        );
+        for (i, name) in self.sections.iter().enumerate() {
+            out.push_str(&format!(
+                "\n    // {}. {}\n    demonstrate_{name}();\n",
+                i + 1,
+                title_case(name)
+            ));
+        }
+        out.push_str(
+            "}\n\n// Each section on its own, for runs that measure sections separately\n\
+             pub const SECTIONS: &[Section] = &[\n",
+        );
+        for name in &self.sections {
+            out.push_str(&format!(
+                "    Section {{\n        name: \"demonstrate_{name}\",\n        run: demonstrate_{name},\n    }},\n"
+            ));
+        }
+        out.push_str("];\n");
+        for (i, name) in self.sections.iter().enumerate() {
+            let newline = if i == 0 { "" } else { "\\n" };
+            out.push_str(&format!(
+                "\nfn demonstrate_{name}() {{\n    println!(\"{newline}{}. {}\");\n    \
+                 // Show the idea with printed results; use `predict!` for values worth guessing\n}}\n",
+                i + 1,
+                title_case(name)
+            ));
+        }
+        out
+    }
+
+    /// Everything to write under `root`, the crate directory. Fails without
+    /// touching anything if the lesson or its golden output already exist.
+    pub fn changes(&self, root: &Path) -> Result<Vec<Change>, ScaffoldError> {
+        let id = self.id();
+        let topic_dir = root.join("src").join(&self.topic);
+        let lesson_path = topic_dir.join(format!("{}.rs", self.lesson));
+        let golden_path = root
+            .join("tests/golden")
+            .join(&self.topic)
+            .join(format!("{}.txt", self.lesson));
+        for path in [&lesson_path, &golden_path] {
+            if path.exists() {
+                return Err(ScaffoldError::Exists(path.clone()));
+            }
+        }
+
+        let mod_path = topic_dir.join("mod.rs");
+        if !mod_path.is_file() {
+            return Err(ScaffoldError::NoModule(self.topic.clone()));
+        }
+        let module = declare_module(&read(&mod_path)?, &self.lesson)
+            .ok_or_else(|| ScaffoldError::AlreadyRegistered(id.clone()))?;
+
+        let registry_path = root.join("src/lessons.rs");
+        let registry = register(&read(&registry_path)?, self)?;
+
+        let readme_path = topic_dir.join("README.md");
+        let readme_exists = readme_path.is_file();
+        let readme = if readme_exists {
+            read(&readme_path)?
+        } else {
+            String::new()
+        };
+
+        Ok(vec![
+            Change {
+                path: lesson_path,
+                contents: self.lesson_source(),
+                created: true,
+            },
+            Change {
+                path: mod_path,
+                contents: module,
+                created: false,
+            },
+            Change {
+                path: registry_path,
+                contents: registry,
+                created: false,
+            },
+            Change {
+                path: readme_path,
+                contents: self.readme_section(&readme),
+                created: !readme_exists,
+            },
+            Change {
+                path: golden_path,
+                contents: format!("{GOLDEN_PLACEHOLDER}\n"),
+                created: true,
+            },
+        ])
+    }
+
+    /// `readme` with a numbered stub for this lesson appended.
+    fn readme_section(&self, readme: &str) -> String {
+        let number = readme
+            .lines()
+            .filter(|line| line.starts_with("## "))
+            .count()
+            + 1;
+        let mut out = readme.trim_end().to_string();
+        if !out.is_empty() {
+            out.push_str("\n\n");
+        }
+        out.push_str(&format!(
+            "## {number}. {}\n\n- run: `cargo run -- run {}`\n",
+            self.title(),
+            self.id()
+        ));
+        for name in &self.sections {
+            out.push_str(&format!("\n**{}**:\n\n- TODO\n", title_case(name)));
+        }
+        out.push_str("\n**questions**:\n\n- TODO\n");
+        out
+    }
+}
+
+/// Writes every change, creating directories as needed.
+pub fn apply(changes: &[Change]) -> Result<(), ScaffoldError> {
+    for change in changes {
+        let io_error = |error| ScaffoldError::Io {
+            path: change.path.clone(),
+            error,
+        };
+        if let Some(dir) = change.path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        fs::write(&change.path, &change.contents).map_err(io_error)?;
+    }
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|error| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// `mod_rs` with `pub mod <lesson>;` among the others in sorted order, or
+/// `None` if it is already declared.
+fn declare_module(mod_rs: &str, lesson: &str) -> Option<String> {
+    let declaration = format!("pub mod {lesson};");
+    let lines: Vec<&str> = mod_rs.lines().collect();
+    if lines.contains(&declaration.as_str()) {
+        return None;
+    }
+    let modules = |line: &&str| line.starts_with("pub mod ");
+    let at = match lines
+        .iter()
+        .position(|line| modules(line) && *line > declaration.as_str())
+    {
+        Some(at) => at,
+        None => match lines.iter().rposition(modules) {
+            Some(last) => last + 1,
+            None => lines.len(),
+        },
+    };
+    let mut out: Vec<&str> = lines[..at].to_vec();
+    out.push(&declaration);
+    out.extend_from_slice(&lines[at..]);
+    Some(out.join("\n") + "\n")
+}
+
+/// `registry` with a `Lesson` entry after the last one of the same topic,
+/// or at the end of `LESSONS` for a topic with none yet.
+fn register(registry: &str, scaffold: &Scaffold) -> Result<String, ScaffoldError> {
+    let id = scaffold.id();
+    if registry.contains(&format!("id: \"{id}\",")) {
+        return Err(ScaffoldError::AlreadyRegistered(id));
+    }
+    let lines: Vec<&str> = registry.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub static LESSONS"))
+        .ok_or_else(|| ScaffoldError::Registry("no `pub static LESSONS`".into()))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| *line == "];")
+            .ok_or_else(|| ScaffoldError::Registry("`LESSONS` is not closed by `];`".into()))?;
+
+    let topic_id = format!("id: \"{}::", scaffold.topic);
+    let last_of_topic = lines[start..end]
+        .iter()
+        .rposition(|line| line.trim_start().starts_with(&topic_id))
+        .map(|at| start + at);
+    let at = match last_of_topic {
+        Some(at) => {
+            at + 1
+                + lines[at..end]
+                    .iter()
+                    .position(|line| *line == "    },")
+                    .ok_or_else(|| {
+                        ScaffoldError::Registry(format!(
+                            "entry at line {} is not closed by `    }},`",
+                            at + 1
+                        ))
+                    })?
+        }
+        None => end,
+    };
+
+    let module = format!("{}::{}", scaffold.topic, scaffold.lesson);
+    let entry = format!(
+        "    Lesson {{\n        id: \"{id}\",\n        title: \"{}\",\n        minutes: {DEFAULT_MINUTES},\n        \
+         tags: &[],\n        requires: &[],\n        run: {module}::main,\n        sections: {module}::SECTIONS,\n    }},",
+        scaffold.title()
+    );
+    let mut out: Vec<&str> = lines[..at].to_vec();
+    out.push(&entry);
+    out.extend_from_slice(&lines[at..]);
+    Ok(out.join("\n") + "\n")
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while",
+];
+
+/// A snake_case identifier that starts with a letter and is not a keyword.
+pub(crate) fn is_module_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !name.ends_with('_')
+        && !name.contains("__")
+        && !KEYWORDS.contains(&name)
+}
+
+/// `box_and_heap` as `Box And Heap`.
+fn title_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::lint;
+
+    fn scaffold() -> Scaffold {
+        Scaffold::new(
+            "constructs/trait_objects",
+            "dyn_dispatch, demonstrate_vtables",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn validates_names() {
+        let s = scaffold();
+        assert_eq!(s.id(), "constructs::trait_objects");
+        assert_eq!(s.title(), "Trait Objects");
+        assert_eq!(s.sections, ["dyn_dispatch", "vtables"]);
+
+        let err =
+            |target: &str, sections: &str| Scaffold::new(target, sections).unwrap_err().to_string();
+        assert_eq!(
+            err("constructs", "a"),
+            "`constructs` is not <topic>/<lesson> with a snake_case lesson name"
+        );
+        assert!(err("misc/x", "a").starts_with("unknown topic `misc`"));
+        assert!(err("constructs/Traits", "a").contains("snake_case"));
+        assert!(err("constructs/match", "a").contains("snake_case"));
+        assert_eq!(
+            err("constructs/x", "a, B"),
+            "section `B` is not a snake_case name"
+        );
+        assert_eq!(
+            err("constructs/x", "a,demonstrate_a"),
+            "section `a` is listed twice"
+        );
+        assert_eq!(
+            err("constructs/x", " , "),
+            "a lesson needs at least one section"
+        );
+    }
+
+    #[test]
+    fn generated_lesson_passes_the_lint() {
+        let path = Path::new("src/constructs/trait_objects.rs");
+        let source = scaffold().lesson_source();
+        assert_eq!(
+            lint::check_file(path, &source, &["constructs::trait_objects"]),
+            []
+        );
+        assert!(source.contains("    // 2. Vtables\n    demonstrate_vtables();\n"));
+        assert!(source.contains("fn demonstrate_vtables() {\n    println!(\"\\n2. Vtables\");\n"));
+    }
+
+    #[test]
+    fn declares_modules_in_order() {
+        let header = "// Lessons show non-idiomatic forms.\n#![allow(clippy::style)]\n\n";
+        let mod_rs = &format!("{header}pub mod enums;\npub mod traits;\n");
+        assert_eq!(
+            declare_module(mod_rs, "structs").unwrap(),
+            format!("{header}pub mod enums;\npub mod structs;\npub mod traits;\n")
+        );
+        assert!(
+            declare_module(mod_rs, "zebra")
+                .unwrap()
+                .ends_with("pub mod traits;\npub mod zebra;\n")
+        );
+        assert_eq!(declare_module(mod_rs, "enums"), None);
+    }
+
+    #[test]
+    fn registers_after_the_topic() {
+        let registry = include_str!("lessons.rs");
+        let updated = register(registry, &scaffold()).unwrap();
+        let entry = updated.find("id: \"constructs::trait_objects\"").unwrap();
+        assert!(updated.find("id: \"constructs::summary_feed\"").unwrap() < entry);
+        assert!(
+            entry
+                < updated
+                    .find("id: \"ownership_system::box_stack_heap\"")
+                    .unwrap()
+        );
+        assert!(
+            updated.contains("        sections: constructs::trait_objects::SECTIONS,\n    },\n")
+        );
+
+        let existing = Scaffold::new("constructs/traits", "a").unwrap();
+        assert!(matches!(
+            register(registry, &existing),
+            Err(ScaffoldError::AlreadyRegistered(_))
+        ));
+    }
+
+    #[test]
+    fn writes_everything_once() {
+        let root = env::temp_dir().join(format!("roadmap-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/constructs")).unwrap();
+        fs::write(
+            root.join("src/lessons.rs"),
+            "pub static LESSONS: &[Lesson] = &[\n];\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/constructs/mod.rs"), "pub mod enums;\n").unwrap();
+        fs::write(
+            root.join("src/constructs/README.md"),
+            "## 1. Enums\n\n- notes\n",
+        )
+        .unwrap();
+
+        let changes = scaffold().changes(&root).unwrap();
+        apply(&changes).unwrap();
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+        assert_eq!(
+            read("src/constructs/mod.rs"),
+            "pub mod enums;\npub mod trait_objects;\n"
+        );
+        assert!(read("src/lessons.rs").contains("        id: \"constructs::trait_objects\",\n"));
+        assert!(read("src/constructs/README.md").starts_with(
+            "## 1. Enums\n\n- notes\n\n## 2. Trait Objects\n\n- run: `cargo run -- run constructs::trait_objects`\n\n**Dyn Dispatch**:\n"
+        ));
+        assert!(read("tests/golden/constructs/trait_objects.txt").starts_with(GOLDEN_PLACEHOLDER));
+
+        let again = scaffold().changes(&root).unwrap_err();
+        assert!(
+            matches!(again, ScaffoldError::Exists(ref path) if path.ends_with("trait_objects.rs"))
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -1,5 +1,9 @@
-fn main() {
-    println!("=== Array Type in Rust ===\n");
+use crate::calendar::{Date, Month, WeekStart, month_calendar};
+use crate::collections::ArrayVec;
+use crate::{render, tr};
+
+pub fn main() {
+    render::title(tr!("arrays.title"));
 
     // 1. Creating arrays
     demonstrate_creating_arrays();
@@ -25,105 +29,136 @@ fn main() {
 
 // Arrays: fixed-length collection of same type elements
 fn demonstrate_creating_arrays() {
-    println!("1. Creating Arrays");
-    println!("   Arrays have fixed length and all elements must be the same type\n");
+    render::section(1, tr!("arrays.creating"));
+    render::note(tr!("arrays.creating.intro"));
+    println!();
 
     // Basic array creation
     let a = [1, 2, 3, 4, 5];
-    println!("   let a = [1, 2, 3, 4, 5];");
-    println!("   → Array with 5 elements, type inferred as [i32; 5]");
-    println!("   → First element: a[0] = {}\n", a[0]);
+    render::code("let a = [1, 2, 3, 4, 5];");
+    render::result(tr!("arrays.creating.inferred"));
+    render::result(tr!("arrays.creating.first", a[0]));
+    println!();
 
     // Arrays are allocated on the stack (not heap)
-    println!("   Key characteristics:");
-    println!("   - Fixed length (cannot grow or shrink)");
-    println!("   - All elements must be the same type");
-    println!("   - Stored on the stack (fast access)");
-    println!("   - Useful when you know the size at compile time\n");
+    render::note(tr!("arrays.creating.characteristics"));
+    render::item(tr!("arrays.creating.fixed_length"));
+    render::item(tr!("arrays.creating.same_type"));
+    render::item(tr!("arrays.creating.stack"));
+    render::item(tr!("arrays.creating.known_size"));
+    println!();
 }
 
 // Array type syntax: [type; length]
 fn demonstrate_array_type_syntax() {
-    println!("2. Array Type Syntax");
-    println!("   Format: [element_type; length]\n");
+    render::section(2, tr!("arrays.type_syntax"));
+    render::note(tr!("arrays.type_syntax.format"));
+    println!();
 
     // Explicit type annotation
     let a: [i32; 5] = [1, 2, 3, 4, 5];
-    println!("   let a: [i32; 5] = [1, 2, 3, 4, 5];");
-    println!("   → [i32; 5] means: array of i32, with 5 elements");
-    println!("   → Example access: a[0] = {}\n", a[0]);
+    render::code("let a: [i32; 5] = [1, 2, 3, 4, 5];");
+    render::result(tr!("arrays.type_syntax.meaning"));
+    render::result(tr!("arrays.type_syntax.access", "a[0]", a[0]));
+    println!();
 
     // Different types
     let floats: [f64; 3] = [1.1, 2.2, 3.3];
-    println!("   let floats: [f64; 3] = [1.1, 2.2, 3.3];");
-    println!("   → Array of f64 with 3 elements");
-    println!("   → Example access: floats[1] = {}\n", floats[1]);
+    render::code("let floats: [f64; 3] = [1.1, 2.2, 3.3];");
+    render::result(tr!("arrays.type_syntax.of_type", "f64", 3));
+    render::result(tr!("arrays.type_syntax.access", "floats[1]", floats[1]));
+    println!();
 
     let chars: [char; 4] = ['R', 'u', 's', 't'];
-    println!("   let chars: [char; 4] = ['R', 'u', 's', 't'];");
-    println!("   → Array of char with 4 elements");
-    println!("   → Example access: chars[0] = '{}'\n", chars[0]);
+    render::code("let chars: [char; 4] = ['R', 'u', 's', 't'];");
+    render::result(tr!("arrays.type_syntax.of_type", "char", 4));
+    let quoted = format!("'{}'", chars[0]);
+    render::result(tr!("arrays.type_syntax.access", "chars[0]", quoted));
+    println!();
 }
 
 // Initialize array with same value for all elements
 fn demonstrate_array_initialization() {
-    println!("3. Initializing Arrays with Same Value");
-    println!("   Syntax: [value; length]\n");
+    render::section(3, tr!("arrays.initializing"));
+    render::note(tr!("arrays.initializing.syntax"));
+    println!();
 
     // Create array with 5 elements, all set to 3
     let a = [3; 5];
-    println!("   let a = [3; 5];");
-    println!("   → Creates: [3, 3, 3, 3, 3]");
-    println!("   → Equivalent to: let a = [3, 3, 3, 3, 3];");
-    println!("   → Example: a[0] = {}, a[4] = {}\n", a[0], a[4]);
+    render::code("let a = [3; 5];");
+    render::result(tr!("arrays.initializing.creates", "[3, 3, 3, 3, 3]"));
+    render::result(tr!("arrays.initializing.equivalent"));
+    render::result(tr!(
+        "arrays.initializing.example",
+        "a[0]",
+        a[0],
+        "a[4]",
+        a[4]
+    ));
+    println!();
 
     // More examples
     let zeros: [i32; 10] = [0; 10];
-    println!("   let zeros: [i32; 10] = [0; 10];");
-    println!("   → Creates array of 10 zeros");
-    println!(
-        "   → Example: zeros[0] = {}, zeros[9] = {}\n",
-        zeros[0], zeros[9]
-    );
+    render::code("let zeros: [i32; 10] = [0; 10];");
+    render::result(tr!("arrays.initializing.zeros"));
+    render::result(tr!(
+        "arrays.initializing.example",
+        "zeros[0]",
+        zeros[0],
+        "zeros[9]",
+        zeros[9]
+    ));
+    println!();
 
     let trues = [true; 4];
-    println!("   let trues = [true; 4];");
-    println!("   → Creates: [true, true, true, true]");
-    println!(
-        "   → Example: trues[0] = {}, trues[3] = {}\n",
-        trues[0], trues[3]
-    );
+    render::code("let trues = [true; 4];");
+    render::result(tr!(
+        "arrays.initializing.creates",
+        "[true, true, true, true]"
+    ));
+    render::result(tr!(
+        "arrays.initializing.example",
+        "trues[0]",
+        trues[0],
+        "trues[3]",
+        trues[3]
+    ));
+    println!();
 }
 
 // Accessing array elements using indexing
 fn demonstrate_accessing_elements() {
-    println!("4. Accessing Array Elements");
-    println!("   Use square brackets with index: array[index]\n");
+    render::section(4, tr!("arrays.accessing"));
+    render::note(tr!("arrays.accessing.brackets"));
+    println!();
 
     let a = [1, 2, 3, 4, 5];
-    println!("   let a = [1, 2, 3, 4, 5];\n");
+    render::code("let a = [1, 2, 3, 4, 5];");
+    println!();
 
     // Access elements by index (starting at 0)
     let first = a[0];
     let second = a[1];
     let last = a[4];
 
-    println!("   Accessing elements:");
-    println!("   a[0] = {} (first element)", first);
-    println!("   a[1] = {} (second element)", second);
-    println!("   a[4] = {} (last element)\n", last);
+    render::note(tr!("arrays.accessing.elements"));
+    render::note(tr!("arrays.accessing.first", first));
+    render::note(tr!("arrays.accessing.second", second));
+    render::note(tr!("arrays.accessing.last", last));
+    println!();
 
     // Using array elements in expressions
     let sum = a[0] + a[1] + a[2];
-    println!("   Using in expressions:");
-    println!(
-        "   a[0] + a[1] + a[2] = {} + {} + {} = {}\n",
+    render::note(tr!("arrays.accessing.expressions"));
+    render::code(format!(
+        "a[0] + a[1] + a[2] = {} + {} + {} = {}",
         a[0], a[1], a[2], sum
-    );
+    ));
+    println!();
 
     // Iterating over array
-    println!("   Iterating over array:");
-    print!("   Elements: ");
+    render::note(tr!("arrays.accessing.iterating"));
+    print!("   {} ", tr!("arrays.accessing.elements_label"));
     for element in a.iter() {
         print!("{} ", element);
     }
@@ -132,29 +167,50 @@ fn demonstrate_accessing_elements() {
 
 // Arrays vs tuples
 fn demonstrate_arrays_vs_tuples() {
-    println!("5. Arrays vs Tuples");
-    println!("   Key differences:\n");
+    render::section(5, tr!("arrays.vs_tuples"));
+    render::note(tr!("arrays.vs_tuples.differences"));
+    println!();
 
     // Array: same type, fixed length
     let array: [i32; 3] = [1, 2, 3];
-    println!("   Array: [i32; 3] = {:?}", array);
-    println!("   → All elements must be the same type (i32)\n");
+    render::note(format!(
+        "{} [i32; 3] = {:?}",
+        tr!("arrays.vs_tuples.array"),
+        array
+    ));
+    render::result(tr!("arrays.vs_tuples.same_type"));
+    println!();
 
     // Tuple: different types, fixed length
     let tuple: (i32, f64, char) = (1, 2.0, '3');
-    println!("   Tuple: (i32, f64, char) = {:?}", tuple);
-    println!("   → Elements can be different types\n");
+    render::note(format!(
+        "{} (i32, f64, char) = {:?}",
+        tr!("arrays.vs_tuples.tuple"),
+        tuple
+    ));
+    render::result(tr!("arrays.vs_tuples.different_types"));
+    println!();
 
     // Access syntax
-    println!("   Access syntax:");
-    println!("   Array: array[0] = {}", array[0]);
-    println!("   Tuple: tuple.0 = {}\n", tuple.0);
+    render::note(tr!("arrays.vs_tuples.access"));
+    render::note(format!(
+        "{} array[0] = {}",
+        tr!("arrays.vs_tuples.array"),
+        array[0]
+    ));
+    render::note(format!(
+        "{} tuple.0 = {}",
+        tr!("arrays.vs_tuples.tuple"),
+        tuple.0
+    ));
+    println!();
 }
 
 // Practical example: months array
 fn demonstrate_months_example() {
-    println!("6. Practical Example: Months Array");
-    println!("   Arrays are perfect when you know the size won't change\n");
+    render::section(6, tr!("arrays.months"));
+    render::note(tr!("arrays.months.intro"));
+    println!();
 
     let months = [
         "January",
@@ -171,55 +227,126 @@ fn demonstrate_months_example() {
         "December",
     ];
 
-    println!("   let months = [\"January\", \"February\", ...];");
-    println!("   → Always 12 months, perfect for an array!\n");
+    render::code("let months = [\"January\", \"February\", ...];");
+    render::result(tr!("arrays.months.always_12"));
+    println!();
 
-    println!("   Accessing months:");
-    println!("   months[0] = {} (first month)", months[0]);
-    println!("   months[11] = {} (last month)\n", months[11]);
+    render::note(tr!("arrays.months.accessing"));
+    render::note(tr!("arrays.months.first", months[0]));
+    render::note(tr!("arrays.months.last", months[11]));
+    println!();
 
     // Type of months array
-    println!("   Type: [&str; 12]");
-    println!("   → Array of string slices, 12 elements\n");
+    render::note(format!("{} [&str; 12]", tr!("arrays.months.type")));
+    render::result(tr!("arrays.months.string_slices"));
+    println!();
+
+    // A parallel array indexed the same way; crate::calendar grows this
+    // into a full date type
+    let days_in_month: [u8; 12] = Month::ALL.map(|month| month.days_in(2024));
+    render::note(tr!("arrays.months.days_in", 2024));
+    for (name, days) in months.iter().zip(days_in_month) {
+        println!("   {:<9} {}", name, days);
+    }
+    let total = days_in_month.iter().map(|&d| d as u32).sum::<u32>();
+    render::result(tr!("arrays.months.total", total));
+    println!();
+
+    // months[date.month() as usize - 1] is what Month::name does
+    let date = Date::new(2024, 2, 29).unwrap();
+    render::note(tr!(
+        "arrays.months.weekday",
+        date,
+        date.weekday(),
+        months[date.month().number() as usize - 1]
+    ));
+    render::note(tr!("arrays.months.year_later", date.add_months(12)));
+    println!();
+    for line in month_calendar(2024, date.month(), WeekStart::Sunday).lines() {
+        println!("   {}", line);
+    }
+    println!();
 }
 
 // Array bounds checking - Rust's memory safety
 fn demonstrate_bounds_checking() {
-    println!("7. Array Bounds Checking");
-    println!("   Rust checks array bounds at runtime for memory safety\n");
+    render::section(7, tr!("arrays.bounds"));
+    render::note(tr!("arrays.bounds.intro"));
+    println!();
 
     let a = [1, 2, 3, 4, 5];
-    println!("   let a = [1, 2, 3, 4, 5];");
-    println!("   → Valid indices: 0, 1, 2, 3, 4\n");
+    render::code("let a = [1, 2, 3, 4, 5];");
+    render::result(tr!("arrays.bounds.valid_indices"));
+    println!();
 
     // Valid access
     let valid = a[2];
-    println!("   Valid access: a[2] = {} ✓", valid);
+    render::note(format!(
+        "{} a[2] = {} ✓",
+        tr!("arrays.bounds.valid_access"),
+        valid
+    ));
 
     // Invalid access would cause panic
-    println!("\n   Invalid access example:");
-    println!("   If you try: a[10]");
-    println!("   → Rust will PANIC at runtime:");
-    println!("   → 'index out of bounds: the len is 5 but the index is 10'\n");
+    println!();
+    render::note(tr!("arrays.bounds.invalid_example"));
+    render::note(format!("{} a[10]", tr!("arrays.bounds.if_you_try")));
+    render::warning(format!("→ {}", tr!("arrays.bounds.panic")));
+    render::warning("→ 'index out of bounds: the len is 5 but the index is 10'");
+    println!();
 
     // Safe way to access (using get method)
-    println!("   Safe access using .get() method:");
+    render::note(tr!("arrays.bounds.safe_access"));
     match a.get(2) {
-        Some(value) => println!("   a.get(2) = Some({}) ✓", value),
-        None => println!("   a.get(2) = None (out of bounds)"),
+        Some(value) => render::code(format!("a.get(2) = Some({}) ✓", value)),
+        None => render::code(format!(
+            "a.get(2) = None ({})",
+            tr!("arrays.bounds.out_of_bounds")
+        )),
     }
 
     match a.get(10) {
-        Some(value) => println!("   a.get(10) = Some({})", value),
-        None => println!("   a.get(10) = None (out of bounds) ✓"),
+        Some(value) => render::code(format!("a.get(10) = Some({})", value)),
+        None => render::code(format!(
+            "a.get(10) = None ({}) ✓",
+            tr!("arrays.bounds.out_of_bounds")
+        )),
     }
 
-    println!("\n   Memory safety:");
-    println!("   - Rust prevents invalid memory access");
-    println!("   - Panics instead of allowing undefined behavior");
-    println!("   - Use .get() for safe access that returns Option\n");
+    println!();
+    render::note(tr!("arrays.bounds.memory_safety"));
+    render::item(tr!("arrays.bounds.prevents"));
+    render::item(tr!("arrays.bounds.panics_instead"));
+    render::item(tr!("arrays.bounds.use_get"));
+    println!();
 
     // Array length
-    println!("   Getting array length:");
-    println!("   a.len() = {} (number of elements)", a.len());
+    render::note(tr!("arrays.bounds.length"));
+    render::code(format!(
+        "a.len() = {} ({})",
+        a.len(),
+        tr!("arrays.bounds.element_count")
+    ));
+
+    // An array's length is fixed; crate::collections::ArrayVec keeps the
+    // fixed capacity but tracks how many slots are used
+    println!();
+    render::note(tr!("arrays.bounds.fixed_capacity"));
+    let mut stack: ArrayVec<i32, 3> = ArrayVec::new();
+    for value in [1, 2, 3, 4] {
+        match stack.try_push(value) {
+            Ok(()) => render::code(format!(
+                "try_push({}) = Ok, {}",
+                value,
+                tr!("arrays.bounds.pushed", stack.len())
+            )),
+            Err(err) => render::code(format!(
+                "try_push({}) = Err({}), {}",
+                value,
+                err,
+                tr!("arrays.bounds.rejected", err.0)
+            )),
+        }
+    }
+    render::code(format!("stack.get(3) = {:?}", stack.get(3)));
 }
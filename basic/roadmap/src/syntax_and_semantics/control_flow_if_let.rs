@@ -8,6 +8,8 @@ enum UsState {
     Texas,
 }
 
+// crate::vending has all fifty states and uses existed_in to reject
+// counterfeit quarters
 impl UsState {
     fn existed_in(&self, year: u16) -> bool {
         match self {
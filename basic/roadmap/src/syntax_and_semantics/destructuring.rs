@@ -1,3 +1,6 @@
+use crate::color::{Cmyk, Hsv, Rgb};
+use crate::{render, tr};
+
 // Define types for destructuring examples
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -10,6 +13,7 @@ enum Color {
     CMYK(u8, u8, u8, u8),
 }
 
+// crate::events dispatches the same variants through an event bus
 #[allow(dead_code)]
 #[derive(Debug)]
 enum WebEvent {
@@ -20,8 +24,8 @@ enum WebEvent {
     Click { x: i64, y: i64 },
 }
 
-fn main() {
-    println!("=== Destructuring in Rust ===\n");
+pub fn main() {
+    render::title(tr!("destructuring.title"));
 
     // 1. Destructuring tuples
     demonstrate_tuple_destructuring();
@@ -41,24 +45,25 @@ fn main() {
 
 // 1. Destructuring Tuples
 fn demonstrate_tuple_destructuring() {
-    println!("1. Destructuring Tuples");
-    println!("   Extract values from tuples\n");
+    render::section(1, tr!("destructuring.tuples"));
+    render::note(tr!("destructuring.tuples.intro"));
+    println!();
 
     let triple = (0, -2, 3);
 
     match triple {
-        (0, y, z) => println!("   First is 0, y = {}, z = {}", y, z),
-        (1, ..) => println!("   First is 1 and the rest doesn't matter"),
-        (.., 2) => println!("   Last is 2 and the rest doesn't matter"),
-        (3, .., 4) => println!("   First is 3, last is 4, middle doesn't matter"),
-        _ => println!("   It doesn't matter what they are"),
+        (0, y, z) => render::note(tr!("destructuring.tuples.first_zero", y, z)),
+        (1, ..) => render::note(tr!("destructuring.tuples.first_one")),
+        (.., 2) => render::note(tr!("destructuring.tuples.last_two")),
+        (3, .., 4) => render::note(tr!("destructuring.tuples.three_four")),
+        _ => render::note(tr!("destructuring.tuples.anything")),
     }
 
     // Nested tuple destructuring
     let nested = ((1, 2), (3, 4));
     match nested {
         ((a, b), (c, d)) => {
-            println!("   Nested tuple: a={}, b={}, c={}, d={}", a, b, c, d);
+            render::note(tr!("destructuring.tuples.nested", a, b, c, d));
         }
     }
     println!();
@@ -66,35 +71,41 @@ fn demonstrate_tuple_destructuring() {
 
 // 2. Destructuring Arrays and Slices
 fn demonstrate_array_slice_destructuring() {
-    println!("2. Destructuring Arrays and Slices");
-    println!("   Pattern match on arrays and slices\n");
+    render::section(2, tr!("destructuring.arrays"));
+    render::note(tr!("destructuring.arrays.intro"));
+    println!();
 
     let array = [1, -2, 6];
 
     match array {
         [0, second, third] => {
-            println!("   array[0] = 0, array[1] = {}, array[2] = {}", second, third);
+            render::code(format!(
+                "array[0] = 0, array[1] = {}, array[2] = {}",
+                second, third
+            ));
         }
-        [1, _, third] => println!("   array[0] = 1, array[2] = {} (middle ignored)", third),
+        [1, _, third] => render::note(tr!("destructuring.arrays.middle_ignored", third)),
         [-1, second, ..] => {
-            println!("   array[0] = -1, array[1] = {} (rest ignored)", second);
+            render::note(tr!("destructuring.arrays.rest_ignored", second));
         }
         [first, middle @ .., last] => {
-            println!(
-                "   array[0] = {}, middle = {:?}, array[last] = {}",
+            render::code(format!(
+                "array[0] = {}, middle = {:?}, array[last] = {}",
                 first, middle, last
-            );
+            ));
         }
     }
 
     // Slice destructuring with different patterns
     fn match_slice(slice: &[i32]) {
         match slice {
-            [] => println!("   Empty slice"),
-            [first] => println!("   Single element: {}", first),
-            [first, second] => println!("   Two elements: {}, {}", first, second),
+            [] => render::note(tr!("destructuring.arrays.empty")),
+            [first] => render::note(tr!("destructuring.arrays.single", first)),
+            [first, second] => {
+                render::note(tr!("destructuring.arrays.two", first, second));
+            }
             [first, second, ..] => {
-                println!("   Slice first two: {}, {} (and more)", first, second);
+                render::note(tr!("destructuring.arrays.first_two", first, second));
             }
         }
     }
@@ -107,36 +118,47 @@ fn demonstrate_array_slice_destructuring() {
 
 // 3. Destructuring Enums
 fn demonstrate_enum_destructuring() {
-    println!("3. Destructuring Enums");
-    println!("   Extract data from enum variants\n");
+    render::section(3, tr!("destructuring.enums"));
+    render::note(tr!("destructuring.enums.intro"));
+    println!();
 
     let color = Color::RGB(122, 17, 40);
 
     match color {
-        Color::Red => println!("   The color is Red!"),
-        Color::Blue => println!("   The color is Blue!"),
-        Color::Green => println!("   The color is Green!"),
+        Color::Red => render::note(tr!("destructuring.enums.red")),
+        Color::Blue => render::note(tr!("destructuring.enums.blue")),
+        Color::Green => render::note(tr!("destructuring.enums.green")),
         Color::RGB(r, g, b) => {
-            println!("   Red: {}, green: {}, blue: {}", r, g, b);
+            render::note(tr!("destructuring.enums.rgb", r, g, b));
         }
         Color::HSV(h, s, v) => {
-            println!("   Hue: {}, saturation: {}, value: {}", h, s, v);
+            render::note(tr!("destructuring.enums.hsv", h, s, v));
         }
         Color::CMYK(c, m, y, k) => {
-            println!("   Cyan: {}, magenta: {}, yellow: {}, key: {}", c, m, y, k);
+            render::note(tr!("destructuring.enums.cmyk", c, m, y, k));
         }
     }
 
+    // The variants above only hold numbers. crate::color gives each model
+    // its own type, so the same color can be converted between them
+    let rgb = Rgb::new(122, 17, 40);
+    render::note(tr!(
+        "destructuring.enums.models",
+        rgb,
+        Hsv::from(rgb),
+        Cmyk::from(rgb)
+    ));
+
     // Destructuring enum with different variant types
     let event = WebEvent::Click { x: 20, y: 80 };
 
     match event {
-        WebEvent::PageLoad => println!("   Page loaded"),
-        WebEvent::PageUnload => println!("   Page unloaded"),
-        WebEvent::KeyPress(c) => println!("   Pressed '{}'", c),
-        WebEvent::Paste(s) => println!("   Pasted \"{}\"", s),
+        WebEvent::PageLoad => render::note(tr!("destructuring.enums.page_load")),
+        WebEvent::PageUnload => render::note(tr!("destructuring.enums.page_unload")),
+        WebEvent::KeyPress(c) => render::note(tr!("destructuring.enums.key_press", c)),
+        WebEvent::Paste(s) => render::note(tr!("destructuring.enums.paste", s)),
         WebEvent::Click { x, y } => {
-            println!("   Clicked at x={}, y={}", x, y);
+            render::note(tr!("destructuring.enums.click", x, y));
         }
     }
     println!();
@@ -144,24 +166,25 @@ fn demonstrate_enum_destructuring() {
 
 // 4. Destructuring Pointers/References
 fn demonstrate_pointer_destructuring() {
-    println!("4. Destructuring Pointers/References");
-    println!("   Dereference and match references\n");
+    render::section(4, tr!("destructuring.pointers"));
+    render::note(tr!("destructuring.pointers.intro"));
+    println!();
 
     let reference = &4;
 
     match reference {
-        &val => println!("   Got a value via destructuring: {:?}", val),
+        &val => render::note(tr!("destructuring.pointers.destructured", val)),
     }
 
     // Dereferencing vs destructuring
     match *reference {
-        val => println!("   Got a value via dereferencing: {:?}", val),
+        val => render::note(tr!("destructuring.pointers.dereferenced", val)),
     }
 
     // ref creates a reference in patterns
     let value = 5;
     match value {
-        ref r => println!("   Got a reference to a value: {:?}", r),
+        ref r => render::note(tr!("destructuring.pointers.reference", r)),
     }
 
     // ref mut for mutable references
@@ -169,16 +192,18 @@ fn demonstrate_pointer_destructuring() {
     match mut_value {
         ref mut m => {
             *m += 10;
-            println!("   We added 10. `mut_value`: {:?}", m);
+            render::note(tr!("destructuring.pointers.added", m));
         }
     }
-    println!("   Final mut_value: {}\n", mut_value);
+    render::note(tr!("destructuring.pointers.final", mut_value));
+    println!();
 }
 
 // 5. Destructuring Structures
 fn demonstrate_struct_destructuring() {
-    println!("5. Destructuring Structures");
-    println!("   Extract fields from structs\n");
+    render::section(5, tr!("destructuring.structs"));
+    render::note(tr!("destructuring.structs.intro"));
+    println!();
 
     struct Foo {
         x: (u32, u32),
@@ -189,18 +214,18 @@ fn demonstrate_struct_destructuring() {
 
     match foo {
         Foo { x: (1, b), y } => {
-            println!("   First of x is 1, b = {}, y = {}", b, y);
+            render::note(tr!("destructuring.structs.first_of_x", b, y));
         }
         Foo { y: 2, x: i } => {
-            println!("   y is 2, i = {:?}", i);
+            render::note(tr!("destructuring.structs.y_is_two", format!("{:?}", i)));
         }
         Foo { y, .. } => {
-            println!("   y = {}, we don't care about x", y);
+            render::note(tr!("destructuring.structs.ignore_x", y));
         }
     }
 
     // Destructuring with let
     let Foo { x: (a, b), y } = foo;
-    println!("   Destructured with let: a={}, b={}, y={}", a, b, y);
+    render::note(tr!("destructuring.structs.with_let", a, b, y));
     println!();
-}
\ No newline at end of file
+}
@@ -0,0 +1,10 @@
+// Lessons deliberately show verbose and non-idiomatic forms next to the
+// idiomatic ones. Style lints are off, along with the one against
+// single-arm matches, which `destructuring.rs` uses to show each pattern;
+// any other lint a lesson trips is allowed where it happens.
+#![allow(clippy::style, clippy::match_single_binding)]
+
+pub mod arrays;
+pub mod destructuring;
+pub mod integer_overflow;
+pub mod numeric_operations;
@@ -1,161 +1,176 @@
-fn main() {
-    println!("=== Numeric Operations in Rust ===\n");
+use crate::{predict, tr};
 
-    // Basic arithmetic operations
+pub fn main() {
+    println!("=== {} ===\n", tr!("numeric_operations.title"));
+
+    // 1. Basic arithmetic operations
     demonstrate_basic_operations();
 
-    // Integer division (truncation)
+    // 2. Integer division (truncation)
     demonstrate_integer_division();
 
-    // Floating-point operations
+    // 3. Floating-point operations
     demonstrate_floating_point();
 
-    // Remainder operation
+    // 4. Remainder operation
     demonstrate_remainder();
 
-    // Operations with different integer types
+    // 5. Operations with different integer types
     demonstrate_different_types();
 }
 
 // Rust supports basic mathematical operations for all number types
 fn demonstrate_basic_operations() {
-    println!("1. Basic Arithmetic Operations");
-    println!("   Rust supports: +, -, *, /, %\n");
+    println!("1. {}", tr!("numeric_operations.basic"));
+    println!("   {}\n", tr!("numeric_operations.basic.supports"));
 
     // Addition
     let sum = 5 + 10;
-    println!("   Addition: 5 + 10 = {}", sum);
+    predict!("   Addition: 5 + 10 = {}", sum);
 
     // Subtraction
     let difference = 95.5 - 4.3;
-    println!("   Subtraction: 95.5 - 4.3 = {}", difference);
+    predict!("   Subtraction: 95.5 - 4.3 = {}", difference);
 
     // Multiplication
     let product = 4 * 30;
-    println!("   Multiplication: 4 * 30 = {}", product);
+    predict!("   Multiplication: 4 * 30 = {}", product);
 
     // Division
     let quotient = 56.7 / 32.2;
-    println!("   Division: 56.7 / 32.2 = {}", quotient);
+    predict!("   Division: 56.7 / 32.2 = {}", quotient);
 
     // Remainder
     let remainder = 43 % 5;
-    println!("   Remainder: 43 % 5 = {}\n", remainder);
+    predict!("   Remainder: 43 % 5 = {}\n", remainder);
 }
 
 // Integer division truncates toward zero
 fn demonstrate_integer_division() {
-    println!("2. Integer Division (Truncation)");
-    println!("   Integer division truncates toward zero to the nearest integer\n");
+    println!("2. {}", tr!("numeric_operations.division"));
+    println!("   {}\n", tr!("numeric_operations.division.intro"));
 
     // Positive numbers
     let quotient1 = 7 / 3;
-    println!("   7 / 3 = {} (not 2.333...)", quotient1);
-    println!("   → Truncated from 2.333... to 2\n");
+    predict!("   7 / 3 = {} (not 2.333...)", quotient1);
+    println!("   → {}\n", tr!("numeric_operations.division.truncated"));
 
     // Negative numbers (also truncates toward zero)
     let quotient2 = -7 / 3;
-    println!("   -7 / 3 = {} (not -2.333...)", quotient2);
-    println!("   → Truncated from -2.333... to -2 (toward zero)\n");
+    predict!("   -7 / 3 = {} (not -2.333...)", quotient2);
+    println!("   → {}\n", tr!("numeric_operations.division.toward_zero"));
 
     // Compare with floating-point division
     let quotient3 = 7.0 / 3.0;
-    println!(
+    predict!(
         "   7.0 / 3.0 = {} (floating-point, no truncation)",
         quotient3
     );
-    println!("   → Floating-point preserves decimal precision\n");
+    println!("   → {}\n", tr!("numeric_operations.division.float"));
 }
 
 // Floating-point operations
 fn demonstrate_floating_point() {
-    println!("3. Floating-Point Operations");
-    println!("   f32 (32-bit) and f64 (64-bit, default)\n");
+    println!("3. {}", tr!("numeric_operations.floating"));
+    println!("   {}\n", tr!("numeric_operations.floating.intro"));
 
     let x = 2.0; // f64 by default
     let y: f32 = 3.0; // f32 explicitly
 
-    println!("   f64 (default): x = {}", x);
-    println!("   f32 (explicit): y = {}", y);
+    println!("   {}", tr!("numeric_operations.floating.default", x));
+    println!("   {}", tr!("numeric_operations.floating.explicit", y));
 
     // Operations work the same way
     let sum = x + y as f64; // Need to cast f32 to f64 for operation
-    println!("   x + y = {} (after casting)", sum);
+    predict!("   x + y = {} (after casting)", sum);
 
     let product = x * 4.5;
-    println!("   x * 4.5 = {}\n", product);
+    predict!("   x * 4.5 = {}\n", product);
 }
 
 // Remainder operation
 fn demonstrate_remainder() {
-    println!("4. Remainder Operation (%)");
-    println!("   Returns the remainder after division\n");
+    println!("4. {}", tr!("numeric_operations.remainder"));
+    println!("   {}\n", tr!("numeric_operations.remainder.intro"));
 
     // Basic remainder
     let remainder1 = 43 % 5;
-    println!("   43 % 5 = {} (43 = 8*5 + 3)", remainder1);
+    predict!("   43 % 5 = {} (43 = 8*5 + 3)", remainder1);
 
     let remainder2 = 100 % 7;
-    println!("   100 % 7 = {} (100 = 14*7 + 2)", remainder2);
+    predict!("   100 % 7 = {} (100 = 14*7 + 2)", remainder2);
 
     // Useful for checking divisibility
     let number = 15;
     if number % 3 == 0 {
-        println!("   {} is divisible by 3", number);
+        println!(
+            "   {}",
+            tr!("numeric_operations.remainder.divisible", number, 3)
+        );
     }
 
     // Works with negative numbers too
     let remainder3 = -7 % 3;
-    println!(
+    predict!(
         "   -7 % 3 = {} (Rust's remainder can be negative)",
         remainder3
     );
-    println!("   Note: Rust's % is remainder, not modulo\n");
+    println!("   {}\n", tr!("numeric_operations.remainder.not_modulo"));
 }
 
 // Operations with different integer types
 fn demonstrate_different_types() {
-    println!("5. Operations with Different Integer Types");
-    println!("   All integer types support the same operations\n");
+    println!("5. {}", tr!("numeric_operations.types"));
+    println!("   {}\n", tr!("numeric_operations.types.intro"));
 
     // u8 (0-255)
     let a: u8 = 100;
     let b: u8 = 50;
-    println!("   u8: {} + {} = {}", a, b, a + b);
+    predict!("   u8: {} + {} = {}", a, b; a + b);
 
     // i32 (signed, default for integers)
     let c: i32 = -50;
     let d: i32 = 25;
-    println!("   i32: {} + {} = {}", c, d, c + d);
+    predict!("   i32: {} + {} = {}", c, d; c + d);
 
     // i64
     let e: i64 = 1_000_000;
     let f: i64 = 500_000;
-    println!("   i64: {} * {} = {}", e, f, e * f);
+    predict!("   i64: {} * {} = {}", e, f; e * f);
 
     // usize (architecture-dependent, used for indexing)
     let g: usize = 10;
     let h: usize = 20;
-    println!("   usize: {} + {} = {}\n", g, h, g + h);
+    predict!("   usize: {} + {} = {}\n", g, h; g + h);
 
-    println!("6. Type Inference in Operations");
-    println!("   Rust infers types from the operation context\n");
+    println!("6. {}", tr!("numeric_operations.inference"));
+    println!("   {}\n", tr!("numeric_operations.inference.intro"));
 
     // Type is inferred as i32 (default)
     let result1 = 5 + 10;
-    println!("   5 + 10 = {} (type: i32, inferred)", result1);
+    println!(
+        "   5 + 10 = {} ({})",
+        result1,
+        tr!("numeric_operations.inference.inferred", "i32")
+    );
 
     // Type annotation required when ambiguous
     let result2: u8 = 100 + 50;
-    println!("   100 + 50 = {} (type: u8, explicit)", result2);
+    println!(
+        "   100 + 50 = {} ({})",
+        result2,
+        tr!("numeric_operations.inference.explicit", "u8")
+    );
 
     // Mixing types requires explicit casting
     let int_val: i32 = 100;
+    // Any float will do; this one is not meant to be π
+    #[allow(clippy::approx_constant)]
     let float_val: f64 = 3.14;
     // let mix = int_val + float_val; // ERROR: cannot add i32 and f64
     let mix = int_val as f64 + float_val; // OK: explicit cast
-    println!(
+    predict!(
         "   {} (i32) + {} (f64) = {} (after casting)",
-        int_val, float_val, mix
+        int_val, float_val; mix
     );
 }
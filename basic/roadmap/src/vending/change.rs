@@ -0,0 +1,507 @@
+//! Making change with as few coins as possible.
+//!
+//! Greedy (take the largest coin that fits, repeat) is optimal for
+//! *canonical* systems such as US coins, but not in general: with coins
+//! 1, 3 and 4, greedy pays 6 as 4+1+1 where 3+3 is shorter. A
+//! [`CoinSystem`] checks itself once on construction and falls back to
+//! dynamic programming when greedy cannot be trusted.
+
+use std::fmt;
+
+/// Coins handed out, largest denomination first.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Change {
+    /// `(denomination, count)` pairs with a non-zero count.
+    pub coins: Vec<(u32, u32)>,
+}
+
+impl Change {
+    pub fn coin_count(&self) -> u32 {
+        self.coins.iter().map(|&(_, count)| count).sum()
+    }
+
+    pub fn total(&self) -> u32 {
+        self.coins.iter().map(|&(coin, count)| coin * count).sum()
+    }
+
+    pub fn count_of(&self, denomination: u32) -> u32 {
+        self.coins
+            .iter()
+            .find(|&&(coin, _)| coin == denomination)
+            .map_or(0, |&(_, count)| count)
+    }
+
+    /// From counts parallel to ascending `denominations`.
+    fn from_counts(denominations: &[u32], counts: &[u32]) -> Self {
+        let coins = denominations
+            .iter()
+            .zip(counts)
+            .rev()
+            .filter(|&(_, &count)| count > 0)
+            .map(|(&coin, &count)| (coin, count))
+            .collect();
+        Self { coins }
+    }
+}
+
+impl fmt::Display for Change {
+    /// `2x25 + 1x10`, or `nothing` when no coins are needed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.coins.is_empty() {
+            return f.write_str("nothing");
+        }
+        for (i, (coin, count)) in self.coins.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" + ")?;
+            }
+            write!(f, "{count}x{coin}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidCoinSystem {
+    Empty,
+    ZeroDenomination,
+}
+
+impl fmt::Display for InvalidCoinSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidCoinSystem::Empty => f.write_str("a coin system needs at least one coin"),
+            InvalidCoinSystem::ZeroDenomination => f.write_str("coins must be worth more than 0"),
+        }
+    }
+}
+
+impl std::error::Error for InvalidCoinSystem {}
+
+/// Most entries any of the dynamic programs below may allocate, about
+/// 16 MiB. Amounts, and canonicity checks, that would need a larger table
+/// are not attempted.
+pub const MAX_TABLE: usize = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoinSystem {
+    /// Ascending and without duplicates.
+    denominations: Vec<u32>,
+    canonical: bool,
+}
+
+impl CoinSystem {
+    /// Denominations may be given in any order; duplicates are ignored.
+    pub fn new(denominations: &[u32]) -> Result<Self, InvalidCoinSystem> {
+        let mut sorted = denominations.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        match sorted.first() {
+            None => return Err(InvalidCoinSystem::Empty),
+            Some(0) => return Err(InvalidCoinSystem::ZeroDenomination),
+            Some(_) => {}
+        }
+        let mut system = Self {
+            denominations: sorted,
+            canonical: false,
+        };
+        system.canonical = system.check_canonical();
+        Ok(system)
+    }
+
+    /// Pennies, nickels, dimes and quarters.
+    pub fn us() -> Self {
+        Self::new(&[1, 5, 10, 25]).expect("valid system")
+    }
+
+    pub fn denominations(&self) -> &[u32] {
+        &self.denominations
+    }
+
+    /// Whether greedy change is known to be optimal for every amount.
+    pub fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Kozen and Zaks (1994): with a unit coin, if greedy is optimal for
+    /// every amount below the sum of the two largest coins it is optimal
+    /// for all amounts. Systems are first divided by the gcd of their
+    /// coins; one that still has no unit coin is treated as non-canonical,
+    /// which only means change is always computed by dynamic programming.
+    /// So is a system whose check would need more than [`MAX_TABLE`]
+    /// entries.
+    fn check_canonical(&self) -> bool {
+        let gcd = self.denominations.iter().copied().fold(0, gcd);
+        if self.denominations[0] != gcd {
+            return false;
+        }
+        let n = self.denominations.len();
+        if n < 3 {
+            // {1} and {1, c} are always canonical
+            return true;
+        }
+        let bound = u64::from(self.denominations[n - 1]) + u64::from(self.denominations[n - 2]);
+        if bound > MAX_TABLE as u64 {
+            return false;
+        }
+        let bound = bound as u32;
+        let best = self.fewest_table(bound - 1);
+        (gcd..bound).step_by(gcd as usize).all(|amount| {
+            let greedy = self.greedy(amount).map(|c| c.coin_count());
+            greedy == best[amount as usize].map(|(count, _)| count)
+        })
+    }
+
+    /// Largest coin first. `None` if greedy gets stuck, which can happen
+    /// without a unit coin even when the amount can be paid.
+    pub fn greedy(&self, amount: u32) -> Option<Change> {
+        let mut left = amount;
+        let mut counts = vec![0; self.denominations.len()];
+        for (i, &coin) in self.denominations.iter().enumerate().rev() {
+            counts[i] = left / coin;
+            left -= counts[i] * coin;
+        }
+        (left == 0).then(|| Change::from_counts(&self.denominations, &counts))
+    }
+
+    /// Fewest coins with an unlimited supply, by dynamic programming. `None`
+    /// if it cannot be paid exactly, or if the table would need more than
+    /// [`MAX_TABLE`] entries.
+    ///
+    /// An optimal answer has fewer than `largest` smaller coins: among any
+    /// `largest` of them some run sums to a multiple of `largest`, which
+    /// fewer large coins pay. Those coins total less than `largest *
+    /// second largest`, so only an amount below that plus one more large
+    /// coin goes through the table; the rest is paid in large coins.
+    pub fn optimal(&self, amount: u32) -> Option<Change> {
+        let n = self.denominations.len();
+        let largest = self.denominations[n - 1];
+        let bound = u64::from(largest) * u64::from(self.second_largest());
+        // Same remainder modulo `largest`, so the answers differ only in
+        // large coins
+        let rest = match u32::try_from(bound) {
+            Ok(bound) if amount > bound => bound + (amount - bound) % largest,
+            _ => amount,
+        };
+        if rest as usize >= MAX_TABLE {
+            return None;
+        }
+        let best = self.fewest_table(rest);
+        best[rest as usize]?;
+        let mut counts = vec![0; n];
+        counts[n - 1] = (amount - rest) / largest;
+        let mut v = rest as usize;
+        while v > 0 {
+            let (_, i) = best[v].expect("reachable");
+            counts[i] += 1;
+            v -= self.denominations[i] as usize;
+        }
+        Some(Change::from_counts(&self.denominations, &counts))
+    }
+
+    /// `best[v]` = (fewest coins, index of a coin they use) for paying each
+    /// `v` up to `limit`, or `None` where `v` cannot be paid.
+    fn fewest_table(&self, limit: u32) -> Vec<Option<(u32, usize)>> {
+        let limit = limit as usize;
+        let mut best: Vec<Option<(u32, usize)>> = vec![None; limit + 1];
+        best[0] = Some((0, 0));
+        for v in 1..=limit {
+            best[v] = self
+                .denominations
+                .iter()
+                .enumerate()
+                .filter(|&(_, &coin)| coin as usize <= v)
+                .filter_map(|(i, &coin)| best[v - coin as usize].map(|(n, _)| (n + 1, i)))
+                .min();
+        }
+        best
+    }
+
+    /// The second largest coin, or 0 for a one-coin system.
+    fn second_largest(&self) -> u32 {
+        let n = self.denominations.len();
+        if n < 2 { 0 } else { self.denominations[n - 2] }
+    }
+
+    /// Fewest coins with an unlimited supply: greedy when the system is
+    /// canonical, dynamic programming otherwise.
+    pub fn make_change(&self, amount: u32) -> Option<Change> {
+        if self.canonical {
+            self.greedy(amount)
+        } else {
+            self.optimal(amount)
+        }
+    }
+
+    /// Fewest coins using at most `stock[i]` of `denominations()[i]`.
+    ///
+    /// Greedy is still tried first for canonical systems: if its answer
+    /// fits in the stock it is the unlimited optimum, which no limited
+    /// answer can beat. Otherwise a bounded dynamic program runs over the
+    /// smaller coins, for amounts no larger than their stock is worth, and
+    /// the largest coin makes up the difference. `None` if it cannot be
+    /// paid, or if that would need more than [`MAX_TABLE`] entries.
+    pub fn make_change_from(&self, amount: u32, stock: &[u32]) -> Option<Change> {
+        assert_eq!(
+            stock.len(),
+            self.denominations.len(),
+            "one stock count per coin"
+        );
+        if self.canonical
+            && let Some(change) = self.greedy(amount)
+            && self
+                .denominations
+                .iter()
+                .zip(stock)
+                .all(|(&coin, &have)| change.count_of(coin) <= have)
+        {
+            return Some(change);
+        }
+        self.bounded(amount, stock)
+    }
+
+    fn bounded(&self, amount: u32, stock: &[u32]) -> Option<Change> {
+        let n = self.denominations.len();
+        let (largest, large_stock) = (self.denominations[n - 1], stock[n - 1]);
+        let smaller = &self.denominations[..n - 1];
+        // The trade in `optimal` takes at most `second` more large coins, so
+        // an optimal answer whose smaller coins total `bound` or more is one
+        // where that many are not left: it already uses over `large_stock -
+        // second` of them
+        let second = u64::from(self.second_largest());
+        let bound = u64::from(largest) * second;
+        let beside = (u64::from(large_stock) + 1).saturating_sub(second) * u64::from(largest);
+        let worth: u64 = smaller
+            .iter()
+            .zip(stock)
+            .map(|(&coin, &have)| u64::from(coin) * u64::from(have))
+            .sum();
+        let limit = u64::from(amount)
+            .min(worth)
+            .min(bound.max(u64::from(amount).saturating_sub(beside)));
+        if limit >= MAX_TABLE as u64 {
+            return None;
+        }
+        let limit = limit as usize;
+
+        // fewest[v] after considering the first i smaller coins; taken[i][v]
+        // says how many of coin i that answer used
+        let mut fewest: Vec<Option<u32>> = vec![None; limit + 1];
+        fewest[0] = Some(0);
+        let mut taken = Vec::with_capacity(smaller.len());
+        for (&coin, &have) in smaller.iter().zip(stock) {
+            let coin = coin as usize;
+            let mut next = vec![None; limit + 1];
+            let mut used = vec![0u32; limit + 1];
+            for v in 0..=limit {
+                let most = have.min((v / coin) as u32);
+                for j in 0..=most {
+                    let Some(before) = fewest[v - j as usize * coin] else {
+                        continue;
+                    };
+                    if next[v].is_none_or(|n| before + j < n) {
+                        next[v] = Some(before + j);
+                        used[v] = j;
+                    }
+                }
+            }
+            fewest = next;
+            taken.push(used);
+        }
+
+        // Every count of large coins that leaves at most `limit` to the
+        // smaller ones
+        let first = (amount - limit as u32).div_ceil(largest);
+        let last = large_stock.min(amount / largest);
+        let (large, _) = (first..=last)
+            .filter_map(|k| {
+                let rest = (amount - k * largest) as usize;
+                fewest[rest].map(|count| (k, count + k))
+            })
+            .min_by_key(|&(_, count)| count)?;
+
+        let mut counts = vec![0; n];
+        counts[n - 1] = large;
+        let mut v = (amount - large * largest) as usize;
+        for i in (0..smaller.len()).rev() {
+            counts[i] = taken[i][v];
+            v -= counts[i] as usize * self.denominations[i] as usize;
+        }
+        Some(Change::from_counts(&self.denominations, &counts))
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fewest coins by trying every combination, for small amounts.
+    fn brute_force(coins: &[u32], stock: Option<&[u32]>, amount: u32) -> Option<u32> {
+        fn go(coins: &[u32], stock: Option<&[u32]>, amount: u32) -> Option<u32> {
+            let Some((&coin, rest)) = coins.split_last() else {
+                return (amount == 0).then_some(0);
+            };
+            let (limit, rest_stock) = match stock {
+                Some(stock) => {
+                    let (&have, rest_stock) = stock.split_last().unwrap();
+                    (have, Some(rest_stock))
+                }
+                None => (u32::MAX, None),
+            };
+            (0..=limit.min(amount / coin))
+                .filter_map(|n| go(rest, rest_stock, amount - n * coin).map(|m| m + n))
+                .min()
+        }
+        go(coins, stock, amount)
+    }
+
+    fn system(coins: &[u32]) -> CoinSystem {
+        CoinSystem::new(coins).unwrap()
+    }
+
+    #[test]
+    fn detects_canonical_systems() {
+        assert!(CoinSystem::us().is_canonical());
+        assert!(system(&[1, 2, 5, 10, 20, 50, 100, 200]).is_canonical());
+        assert!(system(&[5, 10, 25]).is_canonical());
+        assert!(system(&[1, 7]).is_canonical());
+        assert!(!system(&[1, 3, 4]).is_canonical());
+        // The 20-cent piece proposal: 40 = 20+20, but greedy pays 25+10+5
+        assert!(!system(&[1, 5, 10, 20, 25]).is_canonical());
+        assert!(!system(&[3, 5]).is_canonical());
+    }
+
+    #[test]
+    fn invalid_systems() {
+        assert_eq!(CoinSystem::new(&[]), Err(InvalidCoinSystem::Empty));
+        assert_eq!(
+            CoinSystem::new(&[0, 1]),
+            Err(InvalidCoinSystem::ZeroDenomination)
+        );
+        assert_eq!(system(&[25, 1, 5, 10, 5]).denominations(), [1, 5, 10, 25]);
+    }
+
+    #[test]
+    fn greedy_is_not_always_optimal() {
+        let coins = system(&[1, 3, 4]);
+        assert_eq!(coins.greedy(6).unwrap().to_string(), "1x4 + 2x1");
+        assert_eq!(coins.make_change(6).unwrap().to_string(), "2x3");
+        assert_eq!(coins.make_change(0).unwrap().to_string(), "nothing");
+        assert!(system(&[3, 5]).make_change(7).is_none());
+        assert_eq!(system(&[3, 5]).make_change(9).unwrap().to_string(), "3x3");
+    }
+
+    #[test]
+    fn make_change_is_optimal() {
+        let systems: &[&[u32]] = &[
+            &[1, 5, 10, 25],
+            &[1, 3, 4],
+            &[1, 5, 10, 20, 25],
+            &[1, 7, 10],
+            &[2, 5],
+            &[4, 6, 9],
+        ];
+        for coins in systems {
+            let system = system(coins);
+            for amount in 0..=60 {
+                let change = system.make_change(amount);
+                assert_eq!(
+                    change.as_ref().map(Change::total),
+                    change.as_ref().map(|_| amount)
+                );
+                assert_eq!(
+                    change.map(|c| c.coin_count()),
+                    brute_force(coins, None, amount),
+                    "{coins:?} paying {amount}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn limited_stock_is_optimal() {
+        let stocks: &[(&[u32], &[u32])] = &[
+            (&[1, 5, 10, 25], &[5, 0, 3, 1]),
+            (&[1, 5, 10, 25], &[0, 2, 1, 4]),
+            (&[1, 5, 10, 25], &[3, 3, 3, 3]),
+            (&[1, 3, 4], &[2, 1, 5]),
+            (&[1, 5, 10, 20, 25], &[4, 1, 2, 2, 1]),
+        ];
+        for &(coins, stock) in stocks {
+            let system = system(coins);
+            for amount in 0..=80 {
+                let change = system.make_change_from(amount, stock);
+                if let Some(change) = &change {
+                    assert_eq!(change.total(), amount);
+                    for (&coin, &have) in coins.iter().zip(stock) {
+                        assert!(change.count_of(coin) <= have, "{coin} over stock");
+                    }
+                }
+                assert_eq!(
+                    change.map(|c| c.coin_count()),
+                    brute_force(coins, Some(stock), amount),
+                    "{coins:?} with {stock:?} paying {amount}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn greedy_that_runs_out_falls_back() {
+        // Greedy wants 25 + 5 but there are no nickels; three dimes work
+        let change = CoinSystem::us()
+            .make_change_from(30, &[0, 0, 3, 1])
+            .unwrap();
+        assert_eq!(change.to_string(), "3x10");
+    }
+
+    #[test]
+    fn large_amounts_only_tabulate_a_remainder() {
+        // 4_000_000_002 = 1_000_000_000x4 + 2x1, but trading one 4 for
+        // the 3s saves a coin
+        let coins = system(&[1, 3, 4]);
+        let change = coins.make_change(4_000_000_002).unwrap();
+        assert_eq!(change.to_string(), "999999999x4 + 2x3");
+        assert_eq!(change.total(), 4_000_000_002);
+        assert_eq!(
+            coins.make_change(u32::MAX).unwrap().to_string(),
+            "1073741823x4 + 1x3"
+        );
+        assert!(system(&[4, 6, 9]).make_change(4_000_000_001).is_some());
+        assert!(system(&[4, 6]).make_change(4_000_000_001).is_none());
+
+        let stock = [2, 2, 1_000_000_000];
+        let change = coins.make_change_from(4_000_000_002, &stock).unwrap();
+        assert_eq!(change.to_string(), "999999999x4 + 2x3");
+        // Too few 4s: the rest has to come from the smaller coins
+        assert_eq!(
+            coins
+                .make_change_from(40_000, &[2, 2, 9_999])
+                .unwrap()
+                .to_string(),
+            "9999x4 + 1x3 + 1x1"
+        );
+        assert!(coins.make_change_from(40_010, &[2, 2, 9_999]).is_none());
+    }
+
+    #[test]
+    fn huge_coins_stay_within_the_table_limit() {
+        // The two largest coins sum past u32::MAX
+        let coins = system(&[1, 3_000_000_000, 4_000_000_000]);
+        assert!(!coins.is_canonical());
+        assert_eq!(coins.optimal(u32::MAX), None);
+        assert_eq!(coins.make_change(u32::MAX), None);
+        assert_eq!(coins.make_change_from(u32::MAX, &[u32::MAX, 1, 1]), None);
+        assert_eq!(coins.optimal(12).unwrap().to_string(), "12x1");
+        assert_eq!(
+            coins.greedy(u32::MAX).unwrap().to_string(),
+            "1x4000000000 + 294967295x1"
+        );
+        // Canonical, but proving it would take a 2 million entry table
+        assert!(!system(&[1, 2, 2_000_000]).is_canonical());
+        assert!(system(&[1, 5, 10, 20, 50, 100, 200]).is_canonical());
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::UsState;
+
+/// State quarters seen, grouped by the year on the coin.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Collection {
+    by_year: BTreeMap<u16, BTreeMap<UsState, u32>>,
+}
+
+impl Collection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, state: UsState, year: u16) {
+        *self
+            .by_year
+            .entry(year)
+            .or_default()
+            .entry(state)
+            .or_insert(0) += 1;
+    }
+
+    pub fn count(&self, state: UsState, year: u16) -> u32 {
+        self.by_year
+            .get(&year)
+            .and_then(|states| states.get(&state))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn total(&self) -> u32 {
+        self.by_year
+            .values()
+            .flat_map(|states| states.values())
+            .sum()
+    }
+
+    /// Years with at least one quarter, oldest first.
+    pub fn years(&self) -> impl Iterator<Item = u16> + '_ {
+        self.by_year.keys().copied()
+    }
+
+    /// `(state, count)` for one year, in state order.
+    pub fn states_in(&self, year: u16) -> Vec<(UsState, u32)> {
+        self.by_year
+            .get(&year)
+            .map(|states| states.iter().map(|(&s, &n)| (s, n)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether a quarter from `state` has been seen in any year.
+    pub fn has_state(&self, state: UsState) -> bool {
+        self.by_year
+            .values()
+            .any(|states| states.contains_key(&state))
+    }
+
+    /// States still needed for a complete set of fifty.
+    pub fn missing(&self) -> Vec<UsState> {
+        UsState::ALL
+            .iter()
+            .copied()
+            .filter(|&state| !self.has_state(state))
+            .collect()
+    }
+}
+
+impl fmt::Display for Collection {
+    /// One line per year: `2008: Alaska x2, Hawaii`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (year, states) in &self.by_year {
+            write!(f, "{year}:")?;
+            for (i, (state, count)) in states.iter().enumerate() {
+                let separator = if i == 0 { " " } else { ", " };
+                match count {
+                    1 => write!(f, "{separator}{state}")?,
+                    n => write!(f, "{separator}{state} x{n}")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
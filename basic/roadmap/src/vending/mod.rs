@@ -0,0 +1,545 @@
+//! A coin-operated vending machine.
+//!
+//! [`Coin`] and [`UsState`] are deliberately separate copies of the enums
+//! in `syntax_and_semantics/control_flow_if_let.rs`, not the same types:
+//! that lesson is a standalone program that is not compiled into this
+//! crate, and its `UsState` has only four states. Changes to one are not
+//! picked up by the other.
+//!
+//! Coins are checked as they go in ([`UsState::existed_in`] catches a
+//! state quarter dated before its state joined the Union), change comes
+//! from [`change::CoinSystem`], and every state quarter is logged in a
+//! [`Collection`].
+
+use std::fmt;
+
+pub mod change;
+mod collector;
+mod states;
+
+pub use collector::Collection;
+pub use states::{UnknownState, UsState};
+
+use change::CoinSystem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Denomination {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter,
+}
+
+impl Denomination {
+    pub const ALL: [Denomination; 4] = [
+        Denomination::Penny,
+        Denomination::Nickel,
+        Denomination::Dime,
+        Denomination::Quarter,
+    ];
+
+    pub fn cents(self) -> u32 {
+        match self {
+            Denomination::Penny => 1,
+            Denomination::Nickel => 5,
+            Denomination::Dime => 10,
+            Denomination::Quarter => 25,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Denomination::Penny => "penny",
+            Denomination::Nickel => "nickel",
+            Denomination::Dime => "dime",
+            Denomination::Quarter => "quarter",
+        }
+    }
+
+    fn plural(self) -> &'static str {
+        match self {
+            Denomination::Penny => "pennies",
+            Denomination::Nickel => "nickels",
+            Denomination::Dime => "dimes",
+            Denomination::Quarter => "quarters",
+        }
+    }
+}
+
+impl fmt::Display for Denomination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// A coin as in the lesson: quarters carry the state on their reverse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter(UsState),
+}
+
+impl Coin {
+    pub fn denomination(self) -> Denomination {
+        match self {
+            Coin::Penny => Denomination::Penny,
+            Coin::Nickel => Denomination::Nickel,
+            Coin::Dime => Denomination::Dime,
+            Coin::Quarter(_) => Denomination::Quarter,
+        }
+    }
+
+    pub fn value_in_cents(self) -> u32 {
+        self.denomination().cents()
+    }
+}
+
+impl fmt::Display for Coin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Coin::Quarter(state) => write!(f, "{state} quarter"),
+            other => other.denomination().fmt(f),
+        }
+    }
+}
+
+/// Why a coin was pushed back out of the slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoinError {
+    NotAccepted(Denomination),
+    /// A state quarter dated before the state existed is counterfeit.
+    StateDidNotExist {
+        state: UsState,
+        year: u16,
+    },
+    FromTheFuture {
+        year: u16,
+    },
+    TubeFull(Denomination),
+}
+
+impl fmt::Display for CoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoinError::NotAccepted(denomination) => {
+                write!(f, "{} are not accepted", denomination.plural())
+            }
+            CoinError::StateDidNotExist { state, year } => write!(
+                f,
+                "counterfeit: {state} was not a state in {year} (admitted {})",
+                state.admitted()
+            ),
+            CoinError::FromTheFuture { year } => write!(f, "counterfeit: dated {year}"),
+            CoinError::TubeFull(denomination) => write!(f, "the {denomination} tube is full"),
+        }
+    }
+}
+
+impl std::error::Error for CoinError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VendError {
+    UnknownSlot(String),
+    SoldOut(String),
+    InsufficientCredit {
+        price: u32,
+        credit: u32,
+    },
+    /// The machine cannot pay the difference back exactly, so it keeps
+    /// the product and the credit.
+    CannotMakeChange {
+        amount: u32,
+    },
+}
+
+impl fmt::Display for VendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VendError::UnknownSlot(slot) => write!(f, "no slot `{slot}`"),
+            VendError::SoldOut(name) => write!(f, "{name} is sold out"),
+            VendError::InsufficientCredit { price, credit } => write!(
+                f,
+                "costs {}, credit is {}; insert {} more",
+                Cents(*price),
+                Cents(*credit),
+                Cents(price - credit)
+            ),
+            VendError::CannotMakeChange { amount } => {
+                write!(f, "exact change only: cannot return {}", Cents(*amount))
+            }
+        }
+    }
+}
+
+impl std::error::Error for VendError {}
+
+/// Formats an amount of cents as dollars, `$1.05`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cents(pub u32);
+
+impl fmt::Display for Cents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!("${}.{:02}", self.0 / 100, self.0 % 100))
+    }
+}
+
+/// Coins handed back, largest first.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Payout {
+    pub coins: Vec<(Denomination, u32)>,
+}
+
+impl Payout {
+    pub fn total(&self) -> u32 {
+        self.coins.iter().map(|&(d, n)| d.cents() * n).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.coins.is_empty()
+    }
+}
+
+impl fmt::Display for Payout {
+    /// `2 quarters, 1 dime`, or `no coins`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.coins.is_empty() {
+            return f.write_str("no coins");
+        }
+        for (i, &(denomination, count)) in self.coins.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            let name = if count == 1 {
+                denomination.name()
+            } else {
+                denomination.plural()
+            };
+            write!(f, "{count} {name}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Product {
+    pub slot: String,
+    pub name: String,
+    pub price: u32,
+    pub stock: u32,
+}
+
+/// The result of a successful purchase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vend {
+    pub product: String,
+    pub change: Payout,
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine {
+    products: Vec<Product>,
+    accepted: Vec<Denomination>,
+    coins: CoinSystem,
+    /// Coins in each tube, parallel to `accepted`.
+    tubes: Vec<u32>,
+    tube_capacity: u32,
+    credit: u32,
+    current_year: u16,
+    collection: Collection,
+}
+
+impl Machine {
+    /// An empty machine taking nickels, dimes and quarters, like most real
+    /// ones. `current_year` bounds the dates on accepted coins.
+    pub fn new(current_year: u16) -> Self {
+        Self::with_coins(
+            current_year,
+            &[
+                Denomination::Nickel,
+                Denomination::Dime,
+                Denomination::Quarter,
+            ],
+        )
+    }
+
+    /// Panics if `accepted` is empty.
+    pub fn with_coins(current_year: u16, accepted: &[Denomination]) -> Self {
+        let mut accepted = accepted.to_vec();
+        accepted.sort();
+        accepted.dedup();
+        let cents: Vec<u32> = accepted.iter().map(|d| d.cents()).collect();
+        Self {
+            coins: CoinSystem::new(&cents).expect("at least one accepted coin"),
+            tubes: vec![0; accepted.len()],
+            accepted,
+            products: Vec::new(),
+            tube_capacity: 50,
+            credit: 0,
+            current_year,
+            collection: Collection::new(),
+        }
+    }
+
+    pub fn add_product(&mut self, slot: &str, name: &str, price: u32, stock: u32) {
+        self.products.push(Product {
+            slot: slot.to_string(),
+            name: name.to_string(),
+            price,
+            stock,
+        });
+    }
+
+    /// Fills a tube from the operator's bag; extra coins beyond capacity
+    /// stay in the bag.
+    pub fn load(&mut self, denomination: Denomination, count: u32) {
+        if let Some(i) = self.tube(denomination) {
+            self.tubes[i] = (self.tubes[i] + count).min(self.tube_capacity);
+        }
+    }
+
+    fn tube(&self, denomination: Denomination) -> Option<usize> {
+        self.accepted.iter().position(|&d| d == denomination)
+    }
+
+    pub fn products(&self) -> &[Product] {
+        &self.products
+    }
+
+    pub fn credit(&self) -> u32 {
+        self.credit
+    }
+
+    pub fn collection(&self) -> &Collection {
+        &self.collection
+    }
+
+    /// Coins in each tube.
+    pub fn tubes(&self) -> Vec<(Denomination, u32)> {
+        self.accepted
+            .iter()
+            .copied()
+            .zip(self.tubes.iter().copied())
+            .collect()
+    }
+
+    /// Checks and accepts a coin minted in `year`, returning the new credit.
+    pub fn insert(&mut self, coin: Coin, year: u16) -> Result<u32, CoinError> {
+        if year > self.current_year {
+            return Err(CoinError::FromTheFuture { year });
+        }
+        if let Coin::Quarter(state) = coin
+            && !state.existed_in(year)
+        {
+            return Err(CoinError::StateDidNotExist { state, year });
+        }
+        let denomination = coin.denomination();
+        let i = self
+            .tube(denomination)
+            .ok_or(CoinError::NotAccepted(denomination))?;
+        if self.tubes[i] >= self.tube_capacity {
+            return Err(CoinError::TubeFull(denomination));
+        }
+        self.tubes[i] += 1;
+        self.credit += denomination.cents();
+        if let Coin::Quarter(state) = coin {
+            self.collection.add(state, year);
+        }
+        Ok(self.credit)
+    }
+
+    /// Takes `amount` out of the tubes with as few coins as possible.
+    fn pay_out(&mut self, amount: u32) -> Option<Payout> {
+        let change = self.coins.make_change_from(amount, &self.tubes)?;
+        let mut payout = Payout::default();
+        for (i, &denomination) in self.accepted.iter().enumerate().rev() {
+            let count = change.count_of(denomination.cents());
+            if count > 0 {
+                self.tubes[i] -= count;
+                payout.coins.push((denomination, count));
+            }
+        }
+        Some(payout)
+    }
+
+    pub fn select(&mut self, slot: &str) -> Result<Vend, VendError> {
+        let index = self
+            .products
+            .iter()
+            .position(|p| p.slot.eq_ignore_ascii_case(slot))
+            .ok_or_else(|| VendError::UnknownSlot(slot.to_string()))?;
+        let product = &self.products[index];
+        if product.stock == 0 {
+            return Err(VendError::SoldOut(product.name.clone()));
+        }
+        if self.credit < product.price {
+            return Err(VendError::InsufficientCredit {
+                price: product.price,
+                credit: self.credit,
+            });
+        }
+        let (price, name) = (product.price, product.name.clone());
+        let amount = self.credit - price;
+        let change = self
+            .pay_out(amount)
+            .ok_or(VendError::CannotMakeChange { amount })?;
+        self.products[index].stock -= 1;
+        self.credit = 0;
+        Ok(Vend {
+            product: name,
+            change,
+        })
+    }
+
+    /// Returns all credit. This cannot fail: the coins that made up the
+    /// credit are still in the tubes.
+    pub fn refund(&mut self) -> Payout {
+        let payout = self
+            .pay_out(self.credit)
+            .expect("inserted coins can always be returned");
+        self.credit = 0;
+        payout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stocked() -> Machine {
+        let mut machine = Machine::new(2024);
+        machine.add_product("A1", "Water", 100, 2);
+        machine.add_product("A2", "Chips", 65, 1);
+        machine.load(Denomination::Nickel, 4);
+        machine.load(Denomination::Dime, 4);
+        machine.load(Denomination::Quarter, 4);
+        machine
+    }
+
+    #[test]
+    fn rejects_invalid_coins_with_typed_errors() {
+        let mut machine = stocked();
+        assert_eq!(
+            machine.insert(Coin::Quarter(UsState::Alaska), 1950),
+            Err(CoinError::StateDidNotExist {
+                state: UsState::Alaska,
+                year: 1950
+            })
+        );
+        assert_eq!(
+            machine.insert(Coin::Dime, 2031),
+            Err(CoinError::FromTheFuture { year: 2031 })
+        );
+        assert_eq!(
+            machine.insert(Coin::Penny, 2000),
+            Err(CoinError::NotAccepted(Denomination::Penny))
+        );
+        assert_eq!(machine.credit(), 0);
+        assert_eq!(machine.insert(Coin::Quarter(UsState::Alaska), 2008), Ok(25));
+    }
+
+    #[test]
+    fn full_tubes_reject_coins() {
+        let mut machine = Machine::new(2024);
+        machine.load(Denomination::Dime, 1000);
+        assert_eq!(
+            machine.insert(Coin::Dime, 2000),
+            Err(CoinError::TubeFull(Denomination::Dime))
+        );
+    }
+
+    #[test]
+    fn vending_gives_change_and_clears_credit() {
+        let mut machine = stocked();
+        for _ in 0..4 {
+            machine.insert(Coin::Quarter(UsState::Texas), 2004).unwrap();
+        }
+        let vend = machine.select("a2").unwrap();
+        assert_eq!(vend.product, "Chips");
+        assert_eq!(vend.change.to_string(), "1 quarter, 1 dime");
+        assert_eq!(machine.credit(), 0);
+        assert_eq!(
+            machine.select("A2"),
+            Err(VendError::SoldOut("Chips".into()))
+        );
+        assert_eq!(machine.collection().count(UsState::Texas, 2004), 4);
+    }
+
+    #[test]
+    fn insufficient_credit_and_unknown_slots() {
+        let mut machine = stocked();
+        machine.insert(Coin::Dime, 2010).unwrap();
+        assert_eq!(
+            machine.select("A1"),
+            Err(VendError::InsufficientCredit {
+                price: 100,
+                credit: 10
+            })
+        );
+        assert_eq!(
+            machine.select("Z9"),
+            Err(VendError::UnknownSlot("Z9".into()))
+        );
+        assert_eq!(machine.credit(), 10);
+    }
+
+    #[test]
+    fn exact_change_only_keeps_the_credit() {
+        let mut machine = Machine::new(2024);
+        machine.add_product("B1", "Gum", 20, 5);
+        machine.insert(Coin::Quarter(UsState::Ohio), 2002).unwrap();
+        assert_eq!(
+            machine.select("B1"),
+            Err(VendError::CannotMakeChange { amount: 5 })
+        );
+        assert_eq!(machine.credit(), 25);
+        assert_eq!(machine.refund().to_string(), "1 quarter");
+        assert_eq!(machine.credit(), 0);
+    }
+
+    #[test]
+    fn refund_returns_the_fewest_coins() {
+        let mut machine = stocked();
+        for _ in 0..5 {
+            machine.insert(Coin::Nickel, 1999).unwrap();
+        }
+        // Five nickels went in, one quarter comes out
+        assert_eq!(machine.refund().to_string(), "1 quarter");
+    }
+
+    #[test]
+    fn collection_tracks_quarters_by_year() {
+        let mut machine = stocked();
+        let quarters = [
+            (UsState::Delaware, 1999),
+            (UsState::Hawaii, 2008),
+            (UsState::Alaska, 2008),
+            (UsState::Alaska, 2008),
+        ];
+        for (state, year) in quarters {
+            machine.insert(Coin::Quarter(state), year).unwrap();
+        }
+        let collection = machine.collection();
+        assert_eq!(collection.total(), 4);
+        assert_eq!(collection.years().collect::<Vec<_>>(), [1999, 2008]);
+        assert_eq!(
+            collection.states_in(2008),
+            [(UsState::Alaska, 2), (UsState::Hawaii, 1)]
+        );
+        assert_eq!(collection.missing().len(), 47);
+        assert_eq!(
+            collection.to_string(),
+            "1999: Delaware\n2008: Alaska x2, Hawaii\n"
+        );
+    }
+
+    #[test]
+    fn state_names_parse_loosely() {
+        assert_eq!("new york".parse(), Ok(UsState::NewYork));
+        assert_eq!("North_Dakota".parse(), Ok(UsState::NorthDakota));
+        assert_eq!("TEXAS".parse(), Ok(UsState::Texas));
+        assert!("Atlantis".parse::<UsState>().is_err());
+        assert_eq!(UsState::ALL.len(), 50);
+    }
+}
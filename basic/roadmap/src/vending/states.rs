@@ -0,0 +1,127 @@
+use std::fmt;
+use std::str::FromStr;
+
+macro_rules! states {
+    ($($state:ident $name:literal $admitted:literal,)*) => {
+        /// The fifty states, as on the 1999-2008 state quarters.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum UsState {
+            $($state,)*
+        }
+
+        impl UsState {
+            pub const ALL: &[UsState] = &[$(UsState::$state,)*];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(UsState::$state => $name,)*
+                }
+            }
+
+            /// The year the state was admitted to the Union (or ratified
+            /// the Constitution, for the original thirteen).
+            pub fn admitted(self) -> u16 {
+                match self {
+                    $(UsState::$state => $admitted,)*
+                }
+            }
+        }
+    };
+}
+
+states! {
+    Alabama "Alabama" 1819,
+    Alaska "Alaska" 1959,
+    Arizona "Arizona" 1912,
+    Arkansas "Arkansas" 1836,
+    California "California" 1850,
+    Colorado "Colorado" 1876,
+    Connecticut "Connecticut" 1788,
+    Delaware "Delaware" 1787,
+    Florida "Florida" 1845,
+    Georgia "Georgia" 1788,
+    Hawaii "Hawaii" 1959,
+    Idaho "Idaho" 1890,
+    Illinois "Illinois" 1818,
+    Indiana "Indiana" 1816,
+    Iowa "Iowa" 1846,
+    Kansas "Kansas" 1861,
+    Kentucky "Kentucky" 1792,
+    Louisiana "Louisiana" 1812,
+    Maine "Maine" 1820,
+    Maryland "Maryland" 1788,
+    Massachusetts "Massachusetts" 1788,
+    Michigan "Michigan" 1837,
+    Minnesota "Minnesota" 1858,
+    Mississippi "Mississippi" 1817,
+    Missouri "Missouri" 1821,
+    Montana "Montana" 1889,
+    Nebraska "Nebraska" 1867,
+    Nevada "Nevada" 1864,
+    NewHampshire "New Hampshire" 1788,
+    NewJersey "New Jersey" 1787,
+    NewMexico "New Mexico" 1912,
+    NewYork "New York" 1788,
+    NorthCarolina "North Carolina" 1789,
+    NorthDakota "North Dakota" 1889,
+    Ohio "Ohio" 1803,
+    Oklahoma "Oklahoma" 1907,
+    Oregon "Oregon" 1859,
+    Pennsylvania "Pennsylvania" 1787,
+    RhodeIsland "Rhode Island" 1790,
+    SouthCarolina "South Carolina" 1788,
+    SouthDakota "South Dakota" 1889,
+    Tennessee "Tennessee" 1796,
+    Texas "Texas" 1845,
+    Utah "Utah" 1896,
+    Vermont "Vermont" 1791,
+    Virginia "Virginia" 1788,
+    Washington "Washington" 1889,
+    WestVirginia "West Virginia" 1863,
+    Wisconsin "Wisconsin" 1848,
+    Wyoming "Wyoming" 1890,
+}
+
+impl UsState {
+    /// The same check as in `control_flow_if_let.rs`, for every state.
+    pub fn existed_in(self, year: u16) -> bool {
+        year >= self.admitted()
+    }
+}
+
+impl fmt::Display for UsState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownState(pub String);
+
+impl fmt::Display for UnknownState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown state `{}`", self.0)
+    }
+}
+
+impl std::error::Error for UnknownState {}
+
+impl FromStr for UsState {
+    type Err = UnknownState;
+
+    /// Case-insensitive; spaces, `-` and `_` between words are optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let squash = |s: &str| -> String {
+            s.chars()
+                .filter(|c| !matches!(c, ' ' | '-' | '_'))
+                .flat_map(char::to_lowercase)
+                .collect()
+        };
+        let wanted = squash(s);
+        UsState::ALL
+            .iter()
+            .copied()
+            .find(|state| squash(state.name()) == wanted)
+            .ok_or_else(|| UnknownState(s.to_string()))
+    }
+}
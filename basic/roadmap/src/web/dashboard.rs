@@ -0,0 +1,309 @@
+//! Pages and JSON API for the lesson dashboard.
+//!
+//! | Route | |
+//! |---|---|
+//! | `GET /` | lesson list, filled in from `/api/lessons` |
+//! | `GET /lessons/{id}` | source, and output from `/api/lessons/{id}/output` |
+//! | `GET /progress` | completed lessons, from `/api/progress` |
+//! | `GET /api/lessons`, `GET /api/lessons/{id}` | lesson metadata |
+//! | `GET /api/lessons/{id}/output` | what the lesson prints |
+//! | `GET /api/progress` | completed ids and minutes |
+//! | `POST`/`DELETE /api/progress/{id}` | mark or unmark a lesson |
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use super::{Json, Request, Response};
+use crate::lessons::{self, LESSONS, Lesson};
+use crate::progress::Progress;
+
+/// Runs a lesson and returns what it printed.
+pub type RunLesson = Box<dyn Fn(&Lesson) -> Result<String, String> + Send + Sync>;
+
+pub struct Dashboard {
+    /// The crate directory; lesson sources are read from `src/` under it.
+    root: PathBuf,
+    /// `None` shows nothing completed and refuses changes.
+    progress_path: Option<PathBuf>,
+    run: RunLesson,
+    /// Lessons print the same thing every run, so run each once.
+    outputs: Mutex<HashMap<&'static str, String>>,
+    /// Held across load, change and save of the progress file.
+    progress_lock: Mutex<()>,
+}
+
+impl Dashboard {
+    pub fn new(root: PathBuf, progress_path: Option<PathBuf>, run: RunLesson) -> Self {
+        Self {
+            root,
+            progress_path,
+            run,
+            outputs: Mutex::new(HashMap::new()),
+            progress_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn handle(&self, request: &Request) -> Response {
+        let segments: Vec<&str> = request.path.split('/').skip(1).collect();
+        let method = match request.method.as_str() {
+            "HEAD" => "GET",
+            method => method,
+        };
+        let (allowed, response): (&str, Option<Response>) = match segments.as_slice() {
+            [""] => ("GET", (method == "GET").then(|| Response::html(INDEX_PAGE))),
+            ["progress"] => (
+                "GET",
+                (method == "GET").then(|| Response::html(PROGRESS_PAGE)),
+            ),
+            ["lessons", id] => ("GET", (method == "GET").then(|| self.lesson_page(id))),
+            ["api", "lessons"] => ("GET", (method == "GET").then(|| self.lesson_list())),
+            ["api", "lessons", id] => ("GET", (method == "GET").then(|| self.lesson_json(id))),
+            ["api", "lessons", id, "output"] => ("GET", (method == "GET").then(|| self.output(id))),
+            ["api", "progress"] => ("GET", (method == "GET").then(|| self.progress_json())),
+            ["api", "progress", id] => (
+                "POST, DELETE",
+                matches!(method, "POST" | "DELETE")
+                    .then(|| self.set_completed(id, method == "POST")),
+            ),
+            _ => return Response::error(404, &format!("nothing at {}", request.path)),
+        };
+        response.unwrap_or_else(|| {
+            Response::error(405, &format!("{} takes {allowed}", request.path))
+                .with_header("Allow", allowed)
+        })
+    }
+
+    fn lesson_page(&self, id: &str) -> Response {
+        let Some(lesson) = lessons::find(id) else {
+            return unknown_lesson(id);
+        };
+        let source = match self.source(lesson) {
+            Ok(source) => source,
+            Err(err) => return Response::error(500, &err),
+        };
+        let body = format!(
+            "<p><a href=\"/\">All lessons</a></p>\n<h1>{title}</h1>\n<p class=\"meta\">{id} &middot; {minutes} min &middot; {tags}</p>\n\
+             <p><button id=\"done\">Mark complete</button> <span id=\"status\"></span></p>\n\
+             <h2>Output</h2>\n<pre id=\"output\">Running&hellip;</pre>\n<h2>Source</h2>\n<pre>{source}</pre>\n\
+             <script>const ID = {id_json};\n{LESSON_SCRIPT}</script>",
+            title = escape(lesson.title),
+            id = escape(lesson.id),
+            minutes = lesson.minutes,
+            tags = escape(&lesson.tags.join(", ")),
+            source = escape(&source),
+            id_json = Json::from(lesson.id),
+        );
+        Response::html(page(lesson.title, &body))
+    }
+
+    fn source(&self, lesson: &Lesson) -> Result<String, String> {
+        let (topic, stem) = lesson.id.split_once("::").unwrap_or(("", lesson.id));
+        let path = self.root.join("src").join(topic).join(format!("{stem}.rs"));
+        fs::read_to_string(&path).map_err(|err| format!("cannot read {}: {err}", path.display()))
+    }
+
+    fn lesson_list(&self) -> Response {
+        let progress = self.progress();
+        let lessons = LESSONS
+            .iter()
+            .map(|lesson| summary(lesson, &progress))
+            .collect();
+        Response::json(200, &Json::Array(lessons))
+    }
+
+    fn lesson_json(&self, id: &str) -> Response {
+        match lessons::find(id) {
+            Some(lesson) => Response::json(200, &summary(lesson, &self.progress())),
+            None => unknown_lesson(id),
+        }
+    }
+
+    fn output(&self, id: &str) -> Response {
+        let Some(lesson) = lessons::find(id) else {
+            return unknown_lesson(id);
+        };
+        let cached = self
+            .outputs
+            .lock()
+            .ok()
+            .and_then(|outputs| outputs.get(lesson.id).cloned());
+        let output = match cached {
+            Some(output) => output,
+            None => match (self.run)(lesson) {
+                Ok(output) => {
+                    if let Ok(mut outputs) = self.outputs.lock() {
+                        outputs.insert(lesson.id, output.clone());
+                    }
+                    output
+                }
+                Err(err) => return Response::error(500, &err),
+            },
+        };
+        Response::json(
+            200,
+            &Json::object([
+                ("id", Json::from(lesson.id)),
+                ("output", Json::from(output)),
+            ]),
+        )
+    }
+
+    fn progress(&self) -> Progress {
+        let _guard = self.progress_lock.lock();
+        self.progress_path
+            .as_deref()
+            .and_then(|path| Progress::load(path).ok())
+            .unwrap_or_default()
+    }
+
+    fn progress_json(&self) -> Response {
+        Response::json(200, &progress_summary(&self.progress()))
+    }
+
+    fn set_completed(&self, id: &str, completed: bool) -> Response {
+        let Some(lesson) = lessons::find(id) else {
+            return unknown_lesson(id);
+        };
+        let Some(path) = &self.progress_path else {
+            return Response::error(500, "no place to keep progress; set ROADMAP_HOME or HOME");
+        };
+        let _guard = self.progress_lock.lock();
+        let saved = Progress::load(path).and_then(|mut progress| {
+            if completed {
+                progress.complete(lesson.id);
+            } else {
+                progress.reset(lesson.id);
+            }
+            progress.save(path).map(|()| progress)
+        });
+        match saved {
+            Ok(progress) => Response::json(200, &progress_summary(&progress)),
+            Err(err) => Response::error(500, &format!("cannot update {}: {err}", path.display())),
+        }
+    }
+}
+
+fn unknown_lesson(id: &str) -> Response {
+    Response::error(404, &format!("no lesson {id}"))
+}
+
+fn summary(lesson: &Lesson, progress: &Progress) -> Json {
+    Json::object([
+        ("id", Json::from(lesson.id)),
+        ("title", Json::from(lesson.title)),
+        ("minutes", Json::from(i64::from(lesson.minutes))),
+        ("tags", Json::strings(lesson.tags.iter().copied())),
+        ("requires", Json::strings(lesson.requires.iter().copied())),
+        (
+            "sections",
+            Json::strings(lesson.sections.iter().map(|s| s.name)),
+        ),
+        ("completed", Json::from(progress.is_completed(lesson.id))),
+    ])
+}
+
+fn progress_summary(progress: &Progress) -> Json {
+    let minutes = |done: bool| {
+        LESSONS
+            .iter()
+            .filter(|lesson| !done || progress.is_completed(lesson.id))
+            .map(|lesson| i64::from(lesson.minutes))
+            .sum::<i64>()
+    };
+    // Ids of lessons that are no longer registered are left out
+    let completed = LESSONS
+        .iter()
+        .filter(|lesson| progress.is_completed(lesson.id))
+        .map(|lesson| lesson.id);
+    Json::object([
+        ("completed", Json::strings(completed)),
+        ("lessons", Json::from(LESSONS.len() as i64)),
+        ("minutes_done", Json::from(minutes(true))),
+        ("minutes_total", Json::from(minutes(false))),
+    ])
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{} - roadmap</title>\n\
+         <style>{STYLE}</style>\n</head>\n<body>\n<nav><a href=\"/\">Lessons</a> <a href=\"/progress\">Progress</a></nav>\n\
+         {body}\n</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem}\
+nav a{margin-right:1rem}pre{background:#f4f4f4;padding:1rem;overflow-x:auto}\
+table{border-collapse:collapse}td,th{padding:.3rem .8rem;text-align:left}.meta{color:#666}";
+
+const INDEX_PAGE: &str = "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Lessons - roadmap</title>\n\
+<style>body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem}nav a{margin-right:1rem}\
+table{border-collapse:collapse}td,th{padding:.3rem .8rem;text-align:left}</style>\n</head>\n<body>\n\
+<nav><a href=\"/\">Lessons</a> <a href=\"/progress\">Progress</a></nav>\n<h1>Lessons</h1>\n\
+<table><thead><tr><th></th><th>Lesson</th><th>Minutes</th><th>Tags</th></tr></thead><tbody id=\"lessons\"></tbody></table>\n\
+<script>\nfetch('/api/lessons').then(r => r.json()).then(lessons => {\n\
+  const rows = document.getElementById('lessons');\n\
+  for (const l of lessons) {\n\
+    const tr = rows.insertRow();\n\
+    tr.insertCell().textContent = l.completed ? '\u{2713}' : '';\n\
+    const a = document.createElement('a');\n\
+    a.href = '/lessons/' + l.id; a.textContent = l.title;\n\
+    tr.insertCell().append(a, ' (' + l.id + ')');\n\
+    tr.insertCell().textContent = l.minutes;\n\
+    tr.insertCell().textContent = l.tags.join(', ');\n\
+  }\n\
+});\n</script>\n</body>\n</html>\n";
+
+const PROGRESS_PAGE: &str = "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Progress - roadmap</title>\n\
+<style>body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem}nav a{margin-right:1rem}</style>\n\
+</head>\n<body>\n<nav><a href=\"/\">Lessons</a> <a href=\"/progress\">Progress</a></nav>\n<h1>Progress</h1>\n\
+<p id=\"summary\"></p>\n<ul id=\"completed\"></ul>\n\
+<script>\nfetch('/api/progress').then(r => r.json()).then(p => {\n\
+  document.getElementById('summary').textContent =\n\
+    p.completed.length + ' of ' + p.lessons + ' lessons, ' + p.minutes_done + ' of ' + p.minutes_total + ' minutes';\n\
+  const list = document.getElementById('completed');\n\
+  for (const id of p.completed) {\n\
+    const a = document.createElement('a');\n\
+    a.href = '/lessons/' + id; a.textContent = id;\n\
+    list.appendChild(document.createElement('li')).appendChild(a);\n\
+  }\n\
+});\n</script>\n</body>\n</html>\n";
+
+const LESSON_SCRIPT: &str = "fetch('/api/lessons/' + ID + '/output')\n\
+  .then(r => r.ok ? r.json() : r.text().then(t => { throw t; }))\n\
+  .then(o => { document.getElementById('output').textContent = o.output; })\n\
+  .catch(e => { document.getElementById('output').textContent = String(e); });\n\
+const done = document.getElementById('done');\n\
+fetch('/api/lessons/' + ID).then(r => r.json()).then(l => { if (l.completed) done.textContent = 'Completed \u{2713}'; });\n\
+done.onclick = () => fetch('/api/progress/' + ID, { method: 'POST' })\n\
+  .then(r => { document.getElementById('status').textContent = r.ok ? 'Saved' : 'Could not save'; \
+if (r.ok) done.textContent = 'Completed \u{2713}'; });\n";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(
+            escape("a < b && \"c\" > d"),
+            "a &lt; b &amp;&amp; &quot;c&quot; &gt; d"
+        );
+    }
+}
@@ -0,0 +1,398 @@
+//! HTTP/1.1 messages: reading requests and writing responses.
+//!
+//! Covers what a local dashboard needs: origin-form targets, headers,
+//! `Content-Length` bodies and persistent connections. Chunked request
+//! bodies are refused with 400.
+
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+
+/// Longest request line or header line accepted, in bytes.
+const MAX_LINE: usize = 8 * 1024;
+const MAX_HEADERS: usize = 100;
+const MAX_BODY: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    Http10,
+    Http11,
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Version::Http10 => "HTTP/1.0",
+            Version::Http11 => "HTTP/1.1",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    /// Percent-decoded path, always starting with `/`.
+    pub path: String,
+    /// The raw text after `?`, if any.
+    pub query: Option<String>,
+    pub version: Version,
+    /// Names as sent; look them up with [`Request::header`].
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// The first header called `name`, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// HTTP/1.1 connections persist unless the client sends
+    /// `Connection: close`; HTTP/1.0 ones only with `keep-alive`.
+    pub fn keep_alive(&self) -> bool {
+        let has = |option: &str| {
+            self.header("connection").is_some_and(|value| {
+                value
+                    .split(',')
+                    .any(|v| v.trim().eq_ignore_ascii_case(option))
+            })
+        };
+        match self.version {
+            Version::Http11 => !has("close"),
+            Version::Http10 => has("keep-alive"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    /// The client closed the connection between requests.
+    Closed,
+    Io(io::Error),
+    /// Not a valid request; answer 400 with this reason and close.
+    Malformed(String),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Closed => f.write_str("connection closed"),
+            ReadError::Io(err) => write!(f, "{err}"),
+            ReadError::Malformed(reason) => write!(f, "bad request: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        ReadError::Io(err)
+    }
+}
+
+fn malformed(reason: impl Into<String>) -> ReadError {
+    ReadError::Malformed(reason.into())
+}
+
+/// Reads one request. Bytes after it stay in `reader` for the next call,
+/// so pipelined requests work.
+pub fn read_request(reader: &mut impl BufRead) -> Result<Request, ReadError> {
+    let line = match read_line(reader)? {
+        Some(line) => line,
+        None => return Err(ReadError::Closed),
+    };
+    let mut parts = line.split(' ');
+    let (Some(method), Some(target), Some(version), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(malformed("request line is not `METHOD TARGET VERSION`"));
+    };
+    if method.is_empty() || !method.bytes().all(|b| b.is_ascii_uppercase()) {
+        return Err(malformed(format!("invalid method `{method}`")));
+    }
+    let version = match version {
+        "HTTP/1.1" => Version::Http11,
+        "HTTP/1.0" => Version::Http10,
+        _ => return Err(malformed(format!("unsupported version `{version}`"))),
+    };
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, Some(query.to_string())),
+        None => (target, None),
+    };
+    if !path.starts_with('/') {
+        return Err(malformed(format!(
+            "target `{target}` is not an absolute path"
+        )));
+    }
+    let path = percent_decode(path).ok_or_else(|| malformed(format!("bad escape in `{path}`")))?;
+
+    let mut headers = Vec::new();
+    loop {
+        let line =
+            read_line(reader)?.ok_or_else(|| malformed("connection closed inside the headers"))?;
+        if line.is_empty() {
+            break;
+        }
+        if headers.len() == MAX_HEADERS {
+            return Err(malformed("too many headers"));
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| malformed(format!("header line without `:`: `{line}`")))?;
+        if name.is_empty() || name.bytes().any(|b| !is_token_byte(b)) {
+            return Err(malformed(format!("invalid header name `{name}`")));
+        }
+        headers.push((name.to_string(), value.trim().to_string()));
+    }
+
+    let mut request = Request {
+        method: method.to_string(),
+        path,
+        query,
+        version,
+        headers,
+        body: Vec::new(),
+    };
+    if version == Version::Http11 && request.header("host").is_none() {
+        return Err(malformed("HTTP/1.1 requests need a Host header"));
+    }
+    if request.header("transfer-encoding").is_some() {
+        return Err(malformed("chunked request bodies are not supported"));
+    }
+    if let Some(length) = request.header("content-length") {
+        let length: usize = length
+            .parse()
+            .map_err(|_| malformed(format!("invalid Content-Length `{length}`")))?;
+        if length > MAX_BODY {
+            return Err(malformed(format!(
+                "body of {length} bytes is over {MAX_BODY}"
+            )));
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        request.body = body;
+    }
+    Ok(request)
+}
+
+/// One line without its line ending, or `None` at end of input. Bare `\n`
+/// is accepted as well as `\r\n`.
+fn read_line(reader: &mut impl BufRead) -> Result<Option<String>, ReadError> {
+    let mut line = Vec::new();
+    reader
+        .by_ref()
+        .take(MAX_LINE as u64 + 2)
+        .read_until(b'\n', &mut line)?;
+    if line.is_empty() {
+        return Ok(None);
+    }
+    if line.pop() != Some(b'\n') {
+        return Err(if line.len() > MAX_LINE {
+            malformed(format!("line longer than {MAX_LINE} bytes"))
+        } else {
+            malformed("connection closed mid-line")
+        });
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    String::from_utf8(line)
+        .map(Some)
+        .map_err(|_| malformed("line is not UTF-8"))
+}
+
+/// `tchar` from RFC 9110.
+fn is_token_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+fn percent_decode(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    /// Besides `Content-Length` and `Connection`, which are added when
+    /// writing.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn new(status: u16, content_type: &str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".into(), content_type.into())],
+            body: body.into(),
+        }
+    }
+
+    pub fn html(body: impl Into<Vec<u8>>) -> Self {
+        Self::new(200, "text/html; charset=utf-8", body)
+    }
+
+    pub fn json(status: u16, body: &super::Json) -> Self {
+        Self::new(status, "application/json", body.to_string())
+    }
+
+    /// A plain-text error page such as `404 Not Found`.
+    pub fn error(status: u16, detail: &str) -> Self {
+        let mut body = format!("{status} {}\n", reason(status));
+        if !detail.is_empty() {
+            body.push_str(detail);
+            body.push('\n');
+        }
+        Self::new(status, "text/plain; charset=utf-8", body)
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Writes the response; without the body for `HEAD` requests.
+    pub fn write_to(&self, out: &mut impl Write, keep_alive: bool, head: bool) -> io::Result<()> {
+        let mut text = format!("HTTP/1.1 {} {}\r\n", self.status, reason(self.status));
+        for (name, value) in &self.headers {
+            text.push_str(&format!("{name}: {value}\r\n"));
+        }
+        text.push_str(&format!("Content-Length: {}\r\n", self.body.len()));
+        let connection = if keep_alive { "keep-alive" } else { "close" };
+        text.push_str(&format!("Connection: {connection}\r\n\r\n"));
+        out.write_all(text.as_bytes())?;
+        if !head {
+            out.write_all(&self.body)?;
+        }
+        out.flush()
+    }
+}
+
+pub fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        500 => "Internal Server Error",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(raw: &str) -> Result<Request, ReadError> {
+        read_request(&mut raw.as_bytes())
+    }
+
+    fn reason_of(raw: &str) -> String {
+        match read(raw) {
+            Err(ReadError::Malformed(reason)) => reason,
+            other => panic!("expected a malformed request, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_pipelined_requests() {
+        let raw = "GET /lessons/constructs%3A%3Atraits?x=1 HTTP/1.1\r\nHost: localhost\r\nConnection: Close\r\n\r\n\
+                   POST /api HTTP/1.0\nContent-Length: 5\nConnection: keep-alive\n\nhello";
+        let mut reader = raw.as_bytes();
+        let first = read_request(&mut reader).unwrap();
+        assert_eq!(first.path, "/lessons/constructs::traits");
+        assert_eq!(first.query.as_deref(), Some("x=1"));
+        assert_eq!(first.header("HOST"), Some("localhost"));
+        assert!(!first.keep_alive());
+
+        let second = read_request(&mut reader).unwrap();
+        assert_eq!(
+            (second.method.as_str(), second.version),
+            ("POST", Version::Http10)
+        );
+        assert_eq!(second.body, b"hello");
+        assert!(second.keep_alive());
+        assert!(matches!(read_request(&mut reader), Err(ReadError::Closed)));
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        let long = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE));
+        let cases = [
+            (
+                "GET /\r\n\r\n",
+                "request line is not `METHOD TARGET VERSION`",
+            ),
+            ("get / HTTP/1.1\r\n\r\n", "invalid method `get`"),
+            ("GET / HTTP/2\r\n\r\n", "unsupported version `HTTP/2`"),
+            (
+                "GET x HTTP/1.1\r\n\r\n",
+                "target `x` is not an absolute path",
+            ),
+            ("GET /%zz HTTP/1.1\r\n\r\n", "bad escape in `/%zz`"),
+            (
+                "GET / HTTP/1.1\r\nHost localhost\r\n\r\n",
+                "header line without `:`: `Host localhost`",
+            ),
+            (
+                "GET / HTTP/1.1\r\nBad Name: x\r\n\r\n",
+                "invalid header name `Bad Name`",
+            ),
+            (
+                "GET / HTTP/1.1\r\n\r\n",
+                "HTTP/1.1 requests need a Host header",
+            ),
+            (
+                "GET / HTTP/1.0\r\nContent-Length: -1\r\n\r\n",
+                "invalid Content-Length `-1`",
+            ),
+            (
+                "GET / HTTP/1.0\r\nTransfer-Encoding: chunked\r\n\r\n",
+                "chunked request bodies are not supported",
+            ),
+            ("GET / HTTP/1.0\r\nHost: x", "connection closed mid-line"),
+            (long.as_str(), "line longer than 8192 bytes"),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(reason_of(raw), expected, "{raw:?}");
+        }
+    }
+
+    #[test]
+    fn writes_length_and_connection() {
+        let mut out = Vec::new();
+        Response::error(404, "no lesson x")
+            .write_to(&mut out, true, false)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain; charset=utf-8\r\n\
+             Content-Length: 26\r\nConnection: keep-alive\r\n\r\n404 Not Found\nno lesson x\n"
+        );
+        let mut out = Vec::new();
+        Response::html("<p>hi</p>")
+            .write_to(&mut out, false, true)
+            .unwrap();
+        assert!(
+            String::from_utf8(out)
+                .unwrap()
+                .ends_with("Content-Length: 9\r\nConnection: close\r\n\r\n")
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! `roadmap serve`: a local dashboard over HTTP/1.1, std only.
+//!
+//! [`serve`] accepts connections on a `TcpListener` and hands each to a
+//! [`ThreadPool`] worker, which answers requests on it until the client
+//! closes, asks to close, sends something malformed or stays idle for
+//! [`IDLE_TIMEOUT`]. What to answer is up to the handler; [`Dashboard`]
+//! is the one the CLI uses.
+
+use std::io::{self, BufReader};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod dashboard;
+pub mod http;
+mod json;
+mod pool;
+
+pub use dashboard::{Dashboard, RunLesson};
+pub use http::{Request, Response};
+pub use json::Json;
+pub use pool::ThreadPool;
+
+/// How long a kept-alive connection may wait for its next request.
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serves connections from `listener` on `threads` workers, forever.
+/// Returns only if accepting fails.
+pub fn serve<H>(listener: TcpListener, threads: usize, handler: H) -> io::Result<()>
+where
+    H: Fn(&Request) -> Response + Send + Sync + 'static,
+{
+    let pool = ThreadPool::new(threads);
+    let handler = Arc::new(handler);
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            // The client gave up before we accepted; keep serving others
+            Err(err) if err.kind() == io::ErrorKind::ConnectionAborted => continue,
+            Err(err) => return Err(err),
+        };
+        let handler = Arc::clone(&handler);
+        pool.execute(move || {
+            let _ = handle_connection(stream, &*handler);
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream, handler: &impl Fn(&Request) -> Response) -> io::Result<()> {
+    stream.set_read_timeout(Some(IDLE_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    loop {
+        match http::read_request(&mut reader) {
+            Ok(request) => {
+                let keep_alive = request.keep_alive();
+                let response = handler(&request);
+                response.write_to(&mut writer, keep_alive, request.method == "HEAD")?;
+                if !keep_alive {
+                    return Ok(());
+                }
+            }
+            Err(http::ReadError::Malformed(reason)) => {
+                return Response::error(400, &reason).write_to(&mut writer, false, false);
+            }
+            // Closed, timed out or reset
+            Err(_) => return Ok(()),
+        }
+    }
+}
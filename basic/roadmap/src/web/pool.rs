@@ -0,0 +1,98 @@
+//! A fixed set of worker threads fed through a channel, as in chapter 20
+//! of the Rust book.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+pub struct ThreadPool {
+    workers: Vec<JoinHandle<()>>,
+    /// `None` once dropping has started, which closes the channel.
+    sender: Option<Sender<Job>>,
+}
+
+impl ThreadPool {
+    /// A pool of `size` threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "a thread pool needs at least one thread");
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..size)
+            .map(|id| {
+                let receiver = Arc::clone(&receiver);
+                thread::Builder::new()
+                    .name(format!("worker-{id}"))
+                    .spawn(move || work(&receiver))
+                    .expect("failed to spawn a worker thread")
+            })
+            .collect();
+        Self {
+            workers,
+            sender: Some(sender),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Queues `job` for the next idle worker.
+    pub fn execute(&self, job: impl FnOnce() + Send + 'static) {
+        if let Some(sender) = &self.sender {
+            // Workers only stop once the sender is gone, so this succeeds
+            let _ = sender.send(Box::new(job));
+        }
+    }
+}
+
+fn work(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        // The guard is a temporary, so the lock is released before the
+        // job runs and other workers can take the next one
+        let job = receiver
+            .lock()
+            .map_err(|_| ())
+            .and_then(|r| r.recv().map_err(|_| ()));
+        match job {
+            Ok(job) => job(),
+            Err(()) => break,
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    /// Lets queued jobs finish, then joins every worker.
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn runs_every_job_before_drop_returns() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::new(3);
+        assert_eq!(pool.size(), 3);
+        for _ in 0..20 {
+            let count = Arc::clone(&count);
+            pool.execute(move || {
+                count.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        drop(pool);
+        assert_eq!(count.load(Ordering::SeqCst), 20);
+    }
+}
@@ -0,0 +1,126 @@
+//! Checks the heap claims made by the ownership lessons by running them
+//! under the counting allocator.
+//!
+//! Lesson sections are measured through the `roadmap` binary rather than
+//! in-process, because the test harness captures stdout into a buffer that
+//! grows while a section prints, which would be charged to the section.
+
+use std::collections::HashMap;
+use std::process::Command;
+
+use roadmap::alloc_tracker::{self, AllocStats, CountingAllocator};
+use roadmap::lessons;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs `roadmap run <lesson> --allocs` and parses its summary table.
+fn measure_lesson(lesson: &str) -> HashMap<String, AllocStats> {
+    let output = Command::new(env!("CARGO_BIN_EXE_roadmap"))
+        .args(["run", lesson, "--allocs"])
+        .output()
+        .expect("roadmap runs");
+    assert!(
+        output.status.success(),
+        "roadmap run {lesson} --allocs failed"
+    );
+
+    let stdout = String::from_utf8(output.stdout).expect("utf-8 output");
+    let table = stdout
+        .split("=== Allocation summary ===")
+        .nth(1)
+        .expect("summary table is printed");
+    table
+        .lines()
+        .filter(|line| line.starts_with("demonstrate_"))
+        .map(|line| {
+            let cells: Vec<&str> = line.split_whitespace().collect();
+            let n = |i: usize| cells[i].parse::<usize>().expect("numeric cell");
+            let stats = AllocStats {
+                allocations: n(1),
+                deallocations: n(2),
+                reallocations: n(3),
+                bytes_allocated: n(4),
+                bytes_freed: n(5),
+                peak_bytes: n(6),
+            };
+            (cells[0].to_string(), stats)
+        })
+        .collect()
+}
+
+const OWNERSHIP: &str = "ownership_system::ownership_rules_and_memory_safety";
+
+#[test]
+fn allocator_is_installed() {
+    assert!(alloc_tracker::is_installed());
+}
+
+#[test]
+fn measure_counts_a_single_box() {
+    let (_, stats) = alloc_tracker::measure(|| drop(Box::new([0u8; 64])));
+    assert_eq!(stats.allocations, 1);
+    assert_eq!(stats.deallocations, 1);
+    assert_eq!(stats.bytes_allocated, 64);
+    assert_eq!(stats.peak_bytes, 64);
+    assert_eq!(stats.bytes_retained(), 0);
+}
+
+#[test]
+fn measure_counts_growth_as_reallocation() {
+    let (_, stats) = alloc_tracker::measure(|| {
+        let mut v: Vec<u8> = Vec::with_capacity(8);
+        v.extend_from_slice(&[0; 32]);
+    });
+    assert_eq!(stats.allocations, 1);
+    assert!(stats.reallocations >= 1);
+    assert_eq!(stats.bytes_retained(), 0);
+}
+
+#[test]
+fn move_does_not_copy_heap_data_but_clone_does() {
+    let stats = measure_lesson(OWNERSHIP);
+    let moved = stats["demonstrate_move_semantics"];
+    let cloned = stats["demonstrate_clone"];
+
+    // `String::from("hello")` once, then a move: one allocation
+    assert_eq!(moved.allocations, 1);
+    assert_eq!(moved.bytes_allocated, "hello".len());
+
+    // `String::from("hello")` then `.clone()`: the bytes are copied
+    assert_eq!(cloned.allocations, 2);
+    assert_eq!(cloned.bytes_allocated, 2 * "hello".len());
+    assert_eq!(cloned.peak_bytes, 2 * "hello".len());
+}
+
+#[test]
+fn copy_types_never_touch_the_heap() {
+    let stats = measure_lesson(OWNERSHIP);
+    assert_eq!(stats["demonstrate_copy_trait"], AllocStats::default());
+    assert_eq!(stats["demonstrate_variable_scope"], AllocStats::default());
+}
+
+#[test]
+fn boxing_a_large_array_allocates_it_on_the_heap() {
+    let stats = measure_lesson("ownership_system::box_stack_heap");
+    let heap = stats["demonstrate_heap_allocation"];
+    // Box::new(5) and Box::new([0; 1000]) of i32
+    assert_eq!(heap.allocations, 2);
+    assert_eq!(heap.peak_bytes, 4 + 4000);
+}
+
+#[test]
+fn every_measured_section_frees_what_it_allocates() {
+    for lesson in lessons::LESSONS.iter().filter(|l| !l.sections.is_empty()) {
+        let stats = measure_lesson(lesson.id);
+        assert_eq!(stats.len(), lesson.sections.len(), "{}", lesson.id);
+        for (section, s) in stats {
+            assert_eq!(
+                s.bytes_retained(),
+                0,
+                "{}::{section} keeps {s:?}",
+                lesson.id
+            );
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Exhaustive checks of `roadmap::calendar` against independent
+//! formulas and known historical dates.
+//!
+//! Every day from 1600 to 2400 is walked one at a time, so each
+//! arithmetic shortcut in `Date` is compared with plain counting over
+//! two full 400-year cycles.
+
+use roadmap::calendar::{self, Date, Month, Weekday};
+
+fn date(year: i32, month: u8, day: u8) -> Date {
+    Date::new(year, month, day).unwrap()
+}
+
+/// Zeller's congruence, written independently of `Date::weekday`.
+fn zeller(year: i32, month: u8, day: u8) -> Weekday {
+    let (y, m) = if month < 3 {
+        (year - 1, i32::from(month) + 12)
+    } else {
+        (year, i32::from(month))
+    };
+    let (k, j) = (y.rem_euclid(100), y.div_euclid(100));
+    // 0 = Saturday
+    let h = (i32::from(day) + 13 * (m + 1) / 5 + k + k / 4 + j.div_euclid(4) + 5 * j).rem_euclid(7);
+    Weekday::from_number(((h + 5) % 7 + 1) as u8).unwrap()
+}
+
+#[test]
+fn walking_every_day_agrees_with_counting() {
+    let mut day = date(1600, 1, 1);
+    let mut days = day.days_since_epoch();
+    let mut ordinal = 1;
+    while day.year() <= 2400 {
+        assert_eq!(Date::from_days_since_epoch(days), Some(day));
+        assert_eq!(day.days_since_epoch(), days);
+        assert_eq!(day.ordinal(), ordinal);
+        assert_eq!(
+            day.weekday(),
+            zeller(day.year(), day.month().number(), day.day()),
+            "{day}"
+        );
+        assert_eq!(day.to_string().parse(), Ok(day));
+        assert_eq!(day.to_iso_week_string().parse(), Ok(day));
+        assert_eq!(day.to_ordinal_string().parse(), Ok(day));
+
+        let next = day.add_days(1);
+        assert!(next > day);
+        if next.year() == day.year() {
+            ordinal += 1;
+        } else {
+            assert_eq!(ordinal, calendar::days_in_year(day.year()));
+            ordinal = 1;
+        }
+        if next.month() != day.month() {
+            assert_eq!(
+                day.day(),
+                calendar::days_in_month(day.year(), day.month().number()).unwrap()
+            );
+            assert_eq!(next.day(), 1);
+        }
+        day = next;
+        days += 1;
+    }
+}
+
+#[test]
+fn iso_weeks_advance_by_one_each_monday() {
+    let mut day = date(1600, 1, 3);
+    let mut previous = day.iso_week();
+    while day.year() <= 2400 {
+        day = day.add_days(1);
+        let week = day.iso_week();
+        if day.weekday() == Weekday::Monday {
+            let expected = if previous.1 == calendar::weeks_in_iso_year(previous.0) {
+                (previous.0 + 1, 1)
+            } else {
+                (previous.0, previous.1 + 1)
+            };
+            assert_eq!(week, expected, "{day}");
+        } else {
+            assert_eq!(week, previous, "{day}");
+        }
+        previous = week;
+    }
+}
+
+#[test]
+fn known_dates() {
+    // (date, weekday, ISO week date, days since 1970-01-01)
+    let table = [
+        ("0001-01-01", Weekday::Monday, "0001-W01-1", -719_162),
+        ("1582-10-15", Weekday::Friday, "1582-W41-5", -141_427),
+        ("1600-01-01", Weekday::Saturday, "1599-W52-6", -135_140),
+        ("1700-03-01", Weekday::Monday, "1700-W09-1", -98_556),
+        ("1776-07-04", Weekday::Thursday, "1776-W27-4", -70_672),
+        ("1800-01-01", Weekday::Wednesday, "1800-W01-3", -62_091),
+        ("1900-01-01", Weekday::Monday, "1900-W01-1", -25_567),
+        ("1969-07-20", Weekday::Sunday, "1969-W29-7", -165),
+        ("1970-01-01", Weekday::Thursday, "1970-W01-4", 0),
+        ("2000-01-01", Weekday::Saturday, "1999-W52-6", 10_957),
+        ("2000-02-29", Weekday::Tuesday, "2000-W09-2", 11_016),
+        ("2024-02-29", Weekday::Thursday, "2024-W09-4", 19_782),
+        ("2100-03-01", Weekday::Monday, "2100-W09-1", 47_541),
+        ("2400-02-29", Weekday::Tuesday, "2400-W09-2", 157_113),
+        ("9999-12-31", Weekday::Friday, "9999-W52-5", 2_932_896),
+    ];
+    for (text, weekday, week, days) in table {
+        let day: Date = text.parse().unwrap();
+        assert_eq!(day.weekday(), weekday, "{text}");
+        assert_eq!(day.to_iso_week_string(), week, "{text}");
+        assert_eq!(day.days_since_epoch(), days, "{text}");
+    }
+}
+
+#[test]
+fn iso_week_year_boundaries() {
+    let table = [
+        ("1600-12-31", "1600-W52-7"),
+        ("1999-01-01", "1998-W53-5"),
+        ("2004-12-31", "2004-W53-5"),
+        ("2005-01-01", "2004-W53-6"),
+        ("2008-12-29", "2009-W01-1"),
+        ("2010-01-03", "2009-W53-7"),
+        ("2021-01-03", "2020-W53-7"),
+        ("2026-12-31", "2026-W53-4"),
+        ("2027-01-01", "2026-W53-5"),
+    ];
+    for (text, week) in table {
+        let day: Date = text.parse().unwrap();
+        assert_eq!(day.to_iso_week_string(), week, "{text}");
+    }
+}
+
+#[test]
+fn leap_years() {
+    let leap: Vec<i32> = (1896..=1912)
+        .filter(|&y| calendar::is_leap_year(y))
+        .collect();
+    assert_eq!(leap, [1896, 1904, 1908, 1912]);
+    assert!(calendar::is_leap_year(2000));
+    assert!(calendar::is_leap_year(0));
+    assert!(calendar::is_leap_year(-4));
+    assert!(!calendar::is_leap_year(-100));
+    // 97 leap years in every 400
+    for start in [-400, 1600, 2000] {
+        assert_eq!(
+            (start..start + 400)
+                .filter(|&y| calendar::is_leap_year(y))
+                .count(),
+            97
+        );
+    }
+}
+
+#[test]
+fn the_whole_range_round_trips_through_day_numbers() {
+    let (min, max) = (Date::MIN.days_since_epoch(), Date::MAX.days_since_epoch());
+    assert_eq!(Date::from_days_since_epoch(min), Some(Date::MIN));
+    assert_eq!(Date::from_days_since_epoch(max), Some(Date::MAX));
+    assert_eq!(Date::from_days_since_epoch(min - 1), None);
+    assert_eq!(Date::from_days_since_epoch(max + 1), None);
+    // Every 997th day across all 20,000 years
+    for days in (min..=max).step_by(997) {
+        let day = Date::from_days_since_epoch(days).unwrap();
+        assert_eq!(day.days_since_epoch(), days);
+        assert_eq!(day.to_string().parse(), Ok(day));
+    }
+}
+
+#[test]
+fn month_steps_clamp_and_never_skip_a_month() {
+    let start = date(1999, 1, 31);
+    for months in 0..=24 * 12 {
+        let moved = start.add_months(months);
+        let expected_month = (months % 12) as u8 + 1;
+        assert_eq!(moved.month().number(), expected_month);
+        assert_eq!(moved.year(), 1999 + (months / 12) as i32);
+        assert_eq!(moved.day(), 31.min(moved.month().days_in(moved.year())));
+    }
+    assert_eq!(date(2024, 2, 29).add_months(12), date(2025, 2, 28));
+    assert_eq!(date(2024, 2, 29).add_months(48), date(2028, 2, 29));
+    assert_eq!(
+        Month::ALL
+            .iter()
+            .map(|m| u32::from(m.days_in(2023)))
+            .sum::<u32>(),
+        365
+    );
+}
@@ -0,0 +1,113 @@
+//! Recorded sessions replay to the same result every time.
+
+use std::cell::RefCell;
+use std::env;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+
+use roadmap::events::{Bus, EventKind, EventLog, Propagation, WebEvent};
+
+const TYPING: &str = include_str!("sessions/typing.log");
+
+fn events_bin() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_events"))
+}
+
+fn stdout(mut command: Command) -> String {
+    let output = command.output().expect("events runs");
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Replays `log` into a bus that traces every handler call.
+fn trace(log: &EventLog) -> Vec<String> {
+    let trace = Rc::new(RefCell::new(Vec::new()));
+    let mut bus = Bus::new();
+    let sink = Rc::clone(&trace);
+    bus.on_any(1, move |event| {
+        sink.borrow_mut().push(format!("any {:?}", event));
+        Propagation::Continue
+    });
+    for kind in EventKind::ALL {
+        let sink = Rc::clone(&trace);
+        bus.on(kind, 0, move |event| {
+            sink.borrow_mut().push(format!("{kind} {:?}", event));
+            Propagation::Continue
+        });
+    }
+    bus.replay(log);
+    trace.take()
+}
+
+#[test]
+fn library_replay_is_deterministic() {
+    let log: EventLog = TYPING.parse().unwrap();
+    assert_eq!(log.len(), 11);
+    let first = trace(&log);
+    assert_eq!(first.len(), 22);
+    assert_eq!(first, trace(&log));
+    assert_eq!(first[0], "any PageLoad");
+    assert_eq!(first[1], "page_load PageLoad");
+    assert_eq!(first[13], "click Click { x: 12, y: 3 }");
+}
+
+#[test]
+fn replaying_a_recorded_session() {
+    let expected = "\
+page loaded
+key 'h'
+key 'i'
+key 'x'
+backspace: removed Some('x')
+enter: \"hi\"
+click at column 12, row 3
+paste: 18 chars
+ignored control key '\\u{1b}'
+key '!'
+page unloaded, 1 line(s) entered
+  hi
+  from the clipboard! (not entered)
+";
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/sessions/typing.log");
+    for _ in 0..2 {
+        let mut command = events_bin();
+        command.args(["replay", path]);
+        assert_eq!(stdout(command), expected);
+    }
+}
+
+#[test]
+fn a_fresh_recording_replays_to_the_same_output() {
+    let path = env::temp_dir().join(format!("roadmap-events-{}.log", std::process::id()));
+    let mut child = events_bin()
+        .arg("record")
+        .arg(&path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("events runs");
+    // Keys, a bracketed paste and a mouse click, then Ctrl-D
+    let input = b"ok\x7f\x7fyes\r\x1b[200~two\nlines\x1b[201~\x1b[<0;7;9M\x04ignored";
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let recorded = String::from_utf8(output.stdout).unwrap();
+
+    let log: EventLog = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+    let events: Vec<&WebEvent> = log.events().collect();
+    assert_eq!(events.first(), Some(&&WebEvent::PageLoad));
+    assert_eq!(events.last(), Some(&&WebEvent::PageUnload));
+    assert!(events.contains(&&WebEvent::Paste("two\nlines".into())));
+    assert!(!events.contains(&&WebEvent::KeyPress('i')));
+
+    let mut command = events_bin();
+    command.arg("replay").arg(&path);
+    let replayed = stdout(command);
+    std::fs::remove_file(&path).unwrap();
+
+    // Recording additionally reports where the log went
+    let (echo, footer) = recorded.rsplit_once("wrote ").unwrap();
+    assert_eq!(echo, replayed);
+    assert!(footer.starts_with("12 events"), "{footer}");
+}
@@ -0,0 +1,78 @@
+//! Compares each lesson's English output with `tests/golden/<topic>/<lesson>.txt`.
+//!
+//! Lessons without a file are not checked, and a file that still holds the
+//! placeholder written by `roadmap new` is skipped. Run with
+//! `ROADMAP_BLESS=1` to record the current output into every golden file
+//! instead of comparing.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use roadmap::lessons::LESSONS;
+use roadmap::scaffold::GOLDEN_PLACEHOLDER;
+
+fn run_lesson(id: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_roadmap"))
+        .args(["--lang", "en", "run", id])
+        .output()
+        .expect("roadmap runs");
+    assert!(output.status.success(), "roadmap run {id} failed");
+    String::from_utf8(output.stdout).expect("utf-8 output")
+}
+
+#[test]
+fn lessons_print_their_golden_output() {
+    let bless = env::var_os("ROADMAP_BLESS").is_some();
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let mut mismatched = Vec::new();
+    for lesson in LESSONS {
+        let Some((topic, stem)) = lesson.id.split_once("::") else {
+            continue;
+        };
+        let path = golden.join(topic).join(format!("{stem}.txt"));
+        let Ok(expected) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let placeholder = expected.starts_with(GOLDEN_PLACEHOLDER);
+        if !bless && placeholder {
+            continue;
+        }
+        let actual = run_lesson(lesson.id);
+        if bless {
+            fs::write(&path, actual).expect("write golden output");
+        } else if actual != expected {
+            mismatched.push(lesson.id);
+        }
+    }
+    assert!(
+        mismatched.is_empty(),
+        "output differs from tests/golden for {mismatched:?}; \
+         rerun with ROADMAP_BLESS=1 if the change is intended"
+    );
+}
+
+#[test]
+fn golden_files_belong_to_registered_lessons() {
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let Ok(topics) = fs::read_dir(&golden) else {
+        return;
+    };
+    for topic in topics {
+        let topic = topic.expect("read tests/golden").path();
+        for file in fs::read_dir(&topic).expect("read topic directory") {
+            let path = file.expect("read golden file").path();
+            let id = format!(
+                "{}::{}",
+                topic.file_name().unwrap().to_string_lossy(),
+                path.file_stem().unwrap().to_string_lossy()
+            );
+            assert!(
+                LESSONS.iter().any(|lesson| lesson.id == id),
+                "{} is for unregistered lesson {id}",
+                path.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Fuzz-style agreement between `roadmap::net` and `std::net`.
+//!
+//! A fixed-seed generator produces random strings, random valid addresses
+//! and single-character mutations of them. For every input both parsers
+//! must agree on whether it is valid, on the parsed value and on the
+//! canonical text form.
+
+use roadmap::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// xorshift64*, deterministic so failures are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick(&mut self, chars: &[u8]) -> char {
+        chars[self.below(chars.len())] as char
+    }
+}
+
+const ALPHABET: &[u8] = b"0123456789abcdefABCDEFg:::...%/ ";
+
+/// Segments with plenty of zero runs, since those drive `::` placement.
+fn random_segments(rng: &mut Rng) -> [u16; 8] {
+    let mut segments = [0u16; 8];
+    for segment in &mut segments {
+        *segment = match rng.below(4) {
+            0 | 1 => 0,
+            2 => rng.below(16) as u16,
+            _ => rng.next() as u16,
+        };
+    }
+    if rng.below(8) == 0 {
+        // IPv4-mapped and IPv4-compatible forms
+        segments[..5].fill(0);
+        segments[5] = if rng.below(2) == 0 { 0xffff } else { 0 };
+    }
+    segments
+}
+
+fn random_valid(rng: &mut Rng) -> String {
+    match rng.below(4) {
+        0 => std::net::Ipv4Addr::from(rng.next() as u32).to_string(),
+        1 => {
+            // Non-canonical spelling: every group written out in full
+            let segments = random_segments(rng);
+            let groups: Vec<String> = segments.iter().map(|s| format!("{s:04X}")).collect();
+            groups.join(":")
+        }
+        _ => std::net::Ipv6Addr::from(random_segments(rng)).to_string(),
+    }
+}
+
+fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    let at = rng.below(chars.len() + 1);
+    match rng.below(3) {
+        0 if at < chars.len() => {
+            chars.remove(at);
+        }
+        1 if at < chars.len() => chars[at] = rng.pick(ALPHABET),
+        _ => chars.insert(at, rng.pick(ALPHABET)),
+    }
+    chars.into_iter().collect()
+}
+
+fn inputs() -> Vec<String> {
+    let mut rng = Rng(0x5eed_1234_abcd_0001);
+    let mut inputs = Vec::new();
+    for _ in 0..20_000 {
+        let len = rng.below(24);
+        inputs.push((0..len).map(|_| rng.pick(ALPHABET)).collect());
+    }
+    for _ in 0..20_000 {
+        let valid = random_valid(&mut rng);
+        let mut mutated = valid.clone();
+        for _ in 0..=rng.below(2) {
+            mutated = mutate(&mut rng, &mutated);
+        }
+        inputs.push(valid);
+        inputs.push(mutated);
+    }
+    inputs
+}
+
+fn check<Ours, Std>(input: &str)
+where
+    Ours: std::str::FromStr + std::fmt::Display + PartialEq + std::fmt::Debug + From<Std>,
+    Std: std::str::FromStr + std::fmt::Display,
+{
+    let ours = input.parse::<Ours>();
+    let theirs = input.parse::<Std>();
+    match (ours, theirs) {
+        (Ok(ours), Ok(theirs)) => {
+            assert_eq!(ours.to_string(), theirs.to_string(), "formatting {input:?}");
+            assert_eq!(ours, Ours::from(theirs), "value of {input:?}");
+        }
+        (Err(_), Err(_)) => {}
+        (ours, theirs) => panic!(
+            "{input:?}: ours {} but std {}",
+            if ours.is_ok() { "accepts" } else { "rejects" },
+            if theirs.is_ok() { "accepts" } else { "rejects" },
+        ),
+    }
+}
+
+/// `IpAddr` behind a local type so the conversion from std can be written here.
+#[derive(Debug, PartialEq)]
+struct Wrapper(IpAddr);
+
+impl From<std::net::IpAddr> for Wrapper {
+    fn from(addr: std::net::IpAddr) -> Self {
+        Wrapper(match addr {
+            std::net::IpAddr::V4(v4) => IpAddr::V4(v4.into()),
+            std::net::IpAddr::V6(v6) => IpAddr::V6(v6.into()),
+        })
+    }
+}
+
+impl std::str::FromStr for Wrapper {
+    type Err = roadmap::net::AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Wrapper)
+    }
+}
+
+impl std::fmt::Display for Wrapper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[test]
+fn ipv4_parsing_agrees_with_std() {
+    for input in inputs() {
+        check::<Ipv4Addr, std::net::Ipv4Addr>(&input);
+    }
+}
+
+#[test]
+fn ipv6_parsing_agrees_with_std() {
+    for input in inputs() {
+        check::<Ipv6Addr, std::net::Ipv6Addr>(&input);
+    }
+}
+
+#[test]
+fn ip_parsing_agrees_with_std() {
+    for input in inputs() {
+        check::<Wrapper, std::net::IpAddr>(&input);
+    }
+}
+
+#[test]
+fn canonical_form_round_trips() {
+    let mut rng = Rng(7);
+    for _ in 0..10_000 {
+        let addr = Ipv6Addr::from(random_segments(&mut rng));
+        let text = addr.to_string();
+        assert_eq!(text.parse::<Ipv6Addr>(), Ok(addr), "{text}");
+    }
+}
+
+#[test]
+fn known_canonical_forms() {
+    let cases = [
+        ("2001:0db8:0000:0000:0000:0000:0002:0001", "2001:db8::2:1"),
+        ("2001:db8:0:1:1:1:1:1", "2001:db8:0:1:1:1:1:1"),
+        ("2001:db8:0:0:1:0:0:1", "2001:db8::1:0:0:1"),
+        ("2001:0:0:1:0:0:0:1", "2001:0:0:1::1"),
+        ("0:0:0:0:0:0:0:0", "::"),
+        ("::1", "::1"),
+        ("1::", "1::"),
+        ("::FFFF:192.0.2.1", "::ffff:192.0.2.1"),
+        ("1:2:3:4:5:6:7::", "1:2:3:4:5:6:7:0"),
+    ];
+    for (input, canonical) in cases {
+        let addr: Ipv6Addr = input.parse().unwrap();
+        assert_eq!(addr.to_string(), canonical, "{input}");
+    }
+}
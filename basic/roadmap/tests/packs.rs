@@ -0,0 +1,88 @@
+//! Runs the `roadmap` binary against the example pack in `tests/packs`.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn roadmap(home: &Path, packs: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_roadmap"))
+        .args(args)
+        .env("ROADMAP_HOME", home)
+        .env("ROADMAP_PACKS", packs)
+        .output()
+        .expect("roadmap runs")
+}
+
+fn example_packs() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/packs")
+}
+
+fn fresh_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("roadmap-packs-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+#[test]
+fn pack_lessons_are_listed_and_run() {
+    let home = fresh_dir("run");
+    let list = roadmap(&home, &example_packs(), &["list"]);
+    let list = String::from_utf8(list.stdout).unwrap();
+    assert!(list.contains("constructs::traits\n"));
+    assert!(list.ends_with("acme::errors\nacme::serialization\n"));
+
+    let run = roadmap(&home, &example_packs(), &["run", "acme::errors"]);
+    assert!(
+        run.status.success(),
+        "{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+    let stdout = String::from_utf8(run.stdout).unwrap();
+    assert!(stdout.starts_with("=== Error types at ACME ===\n\n# Error types\n"));
+    assert!(stdout.contains(
+        "--- snippets/error_display.rs ---\nexpected 4 bytes, found 2\nTruncated { needed: 4, found: 2 }\n"
+    ));
+
+    // The output is cached under ROADMAP_HOME and reused
+    let cache = home.join("cache/snippets");
+    let cached: Vec<_> = fs::read_dir(&cache).unwrap().collect();
+    assert_eq!(cached.len(), 1);
+    let again = roadmap(&home, &example_packs(), &["run", "acme::errors"]);
+    assert_eq!(String::from_utf8(again.stdout).unwrap(), stdout);
+    let _ = fs::remove_dir_all(home);
+}
+
+#[test]
+fn invalid_packs_are_reported_by_file_and_field() {
+    let home = fresh_dir("invalid");
+    let packs = home.join("packs");
+    let pack = packs.join("broken");
+    fs::create_dir_all(&pack).unwrap();
+    fs::write(
+        pack.join("pack.toml"),
+        "name = \"broken\"\ntitle = \"Broken\"\n\n[[lesson]]\nid = \"one\"\ntitle = \"One\"\nminutes = \"ten\"\n",
+    )
+    .unwrap();
+
+    let check = roadmap(&home, &packs, &["packs"]);
+    assert!(!check.status.success());
+    assert_eq!(
+        String::from_utf8(check.stderr).unwrap(),
+        format!(
+            "{}: lesson[1].minutes: expected whole minutes from 1 to 600, found a string\n",
+            format_args!("{}:7", pack.join("pack.toml").display())
+        )
+    );
+
+    // Other commands skip the pack and carry on
+    let list = roadmap(&home, &packs, &["list"]);
+    assert!(list.status.success());
+    assert!(
+        String::from_utf8(list.stderr)
+            .unwrap()
+            .starts_with("skipping pack: ")
+    );
+    let _ = fs::remove_dir_all(home);
+}
@@ -0,0 +1,24 @@
+use std::fmt;
+
+#[derive(Debug)]
+enum WireError {
+    Truncated { needed: usize, found: usize },
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::Truncated { needed, found } => {
+                write!(f, "expected {needed} bytes, found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+fn main() {
+    let err = WireError::Truncated { needed: 4, found: 2 };
+    println!("{err}");
+    println!("{err:?}");
+}
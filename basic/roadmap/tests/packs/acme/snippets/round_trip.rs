@@ -0,0 +1,39 @@
+trait Encode {
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+trait Decode: Sized {
+    fn decode(bytes: &[u8]) -> Option<Self>;
+}
+
+#[derive(Debug, PartialEq)]
+struct Reading {
+    sensor: u16,
+    value: i32,
+}
+
+impl Encode for Reading {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.sensor.to_le_bytes());
+        out.extend_from_slice(&self.value.to_le_bytes());
+    }
+}
+
+impl Decode for Reading {
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; 6] = bytes.try_into().ok()?;
+        Some(Reading {
+            sensor: u16::from_le_bytes([bytes[0], bytes[1]]),
+            value: i32::from_le_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]),
+        })
+    }
+}
+
+fn main() {
+    let reading = Reading { sensor: 7, value: -2 };
+    let mut bytes = Vec::new();
+    reading.encode(&mut bytes);
+    println!("encoded: {bytes:?}");
+    println!("round trip: {}", Reading::decode(&bytes) == Some(reading));
+    println!("with a trailing byte: {:?}", Reading::decode(&[7, 0, 254, 255, 255, 255, 0]));
+}
@@ -0,0 +1,248 @@
+//! Talks raw HTTP/1.1 to the dashboard on an ephemeral port.
+//!
+//! Lessons are "run" by a stub that echoes the id, so these tests check
+//! the server and routes, not lesson output.
+
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use roadmap::web::{self, Dashboard};
+
+/// Starts a server in the background whose progress lives in a fresh file
+/// named after the test.
+fn start(name: &str) -> (SocketAddr, PathBuf) {
+    let progress = env::temp_dir().join(format!("roadmap-serve-{}-{name}", std::process::id()));
+    let _ = fs::remove_file(&progress);
+    let dashboard = Dashboard::new(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+        Some(progress.clone()),
+        Box::new(|lesson| match lesson.id {
+            "syntax_and_semantics::arrays" => Err("it broke".to_string()),
+            id => Ok(format!("ran {id}\n")),
+        }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind an ephemeral port");
+    let address = listener.local_addr().expect("local address");
+    thread::spawn(move || web::serve(listener, 2, move |request| dashboard.handle(request)));
+    (address, progress)
+}
+
+fn connect(address: SocketAddr) -> BufReader<TcpStream> {
+    let stream = TcpStream::connect(address).expect("connect");
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .expect("set timeout");
+    BufReader::new(stream)
+}
+
+struct Reply {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Reply {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Reads one response, using `Content-Length` to find its end.
+fn read_reply(conn: &mut BufReader<TcpStream>, head: bool) -> Reply {
+    let mut line = String::new();
+    conn.read_line(&mut line).expect("status line");
+    let status = line
+        .split(' ')
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(|| panic!("bad status line {line:?}"));
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        conn.read_line(&mut line).expect("header line");
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(": ").expect("name: value");
+        headers.push((name.to_string(), value.to_string()));
+    }
+    let mut reply = Reply {
+        status,
+        headers,
+        body: String::new(),
+    };
+    if !head {
+        let length: usize = reply
+            .header("Content-Length")
+            .and_then(|n| n.parse().ok())
+            .expect("Content-Length");
+        let mut body = vec![0; length];
+        conn.read_exact(&mut body).expect("body");
+        reply.body = String::from_utf8(body).expect("utf-8 body");
+    }
+    reply
+}
+
+fn send(conn: &mut BufReader<TcpStream>, raw: &str) {
+    conn.get_mut().write_all(raw.as_bytes()).expect("send");
+}
+
+/// One request on its own connection.
+fn request(address: SocketAddr, method: &str, path: &str) -> Reply {
+    let mut conn = connect(address);
+    send(
+        &mut conn,
+        &format!("{method} {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n"),
+    );
+    read_reply(&mut conn, method == "HEAD")
+}
+
+#[test]
+fn pages_and_lesson_api() {
+    let (address, _) = start("pages");
+
+    let index = request(address, "GET", "/");
+    assert_eq!(index.status, 200);
+    assert_eq!(
+        index.header("Content-Type"),
+        Some("text/html; charset=utf-8")
+    );
+    assert!(index.body.contains("/api/lessons"));
+
+    let lessons = request(address, "GET", "/api/lessons");
+    assert_eq!(lessons.status, 200);
+    assert_eq!(lessons.header("Content-Type"), Some("application/json"));
+    assert!(
+        lessons
+            .body
+            .starts_with("[{\"id\":\"constructs::structs\",\"title\":")
+    );
+    assert!(lessons.body.contains("\"completed\":false"));
+
+    let page = request(address, "GET", "/lessons/constructs::enums");
+    assert_eq!(page.status, 200);
+    // The source is shown escaped
+    assert!(page.body.contains("Option&lt;"));
+
+    let output = request(address, "GET", "/api/lessons/constructs::enums/output");
+    assert_eq!(
+        output.body,
+        "{\"id\":\"constructs::enums\",\"output\":\"ran constructs::enums\\n\"}"
+    );
+    let failed = request(
+        address,
+        "GET",
+        "/api/lessons/syntax_and_semantics::arrays/output",
+    );
+    assert_eq!(failed.status, 500);
+    assert!(failed.body.contains("it broke"));
+
+    let head = request(address, "HEAD", "/api/lessons");
+    assert_eq!(head.status, 200);
+    assert_eq!(
+        head.header("Content-Length"),
+        lessons.header("Content-Length")
+    );
+}
+
+#[test]
+fn keeps_connections_alive_until_asked_to_close() {
+    let (address, _) = start("keep-alive");
+    let mut conn = connect(address);
+
+    // Two requests sent back to back are answered in order
+    send(
+        &mut conn,
+        "GET /api/progress HTTP/1.1\r\nHost: localhost\r\n\r\n\
+         GET /api/lessons/constructs::traits HTTP/1.1\r\nHost: localhost\r\n\r\n",
+    );
+    let first = read_reply(&mut conn, false);
+    assert_eq!(first.header("Connection"), Some("keep-alive"));
+    assert!(first.body.starts_with("{\"completed\":[]"));
+    let second = read_reply(&mut conn, false);
+    assert!(second.body.starts_with("{\"id\":\"constructs::traits\""));
+
+    send(
+        &mut conn,
+        "GET /progress HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+    );
+    let last = read_reply(&mut conn, false);
+    assert_eq!(last.status, 200);
+    assert_eq!(last.header("Connection"), Some("close"));
+    let mut rest = Vec::new();
+    conn.read_to_end(&mut rest).expect("server closes");
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn unknown_paths_and_methods() {
+    let (address, _) = start("errors");
+
+    let missing = request(address, "GET", "/nowhere");
+    assert_eq!(missing.status, 404);
+    assert_eq!(missing.body, "404 Not Found\nnothing at /nowhere\n");
+    assert_eq!(
+        request(address, "GET", "/lessons/constructs::nope").status,
+        404
+    );
+    assert_eq!(
+        request(address, "GET", "/api/lessons/nope/output").status,
+        404
+    );
+
+    let wrong = request(address, "DELETE", "/api/lessons");
+    assert_eq!(wrong.status, 405);
+    assert_eq!(wrong.header("Allow"), Some("GET"));
+    let wrong = request(address, "GET", "/api/progress/constructs::enums");
+    assert_eq!(wrong.header("Allow"), Some("POST, DELETE"));
+}
+
+#[test]
+fn malformed_requests_get_400_and_a_closed_connection() {
+    let (address, _) = start("malformed");
+    for raw in [
+        "NONSENSE\r\n\r\n",
+        "GET / HTTP/1.1\r\n\r\n",
+        "GET / HTTP/1.1\r\nHost: localhost\r\nno colon here\r\n\r\n",
+        "GET / HTTP/9.9\r\nHost: localhost\r\n\r\n",
+    ] {
+        let mut conn = connect(address);
+        send(&mut conn, raw);
+        let reply = read_reply(&mut conn, false);
+        assert_eq!(reply.status, 400, "{raw:?}");
+        assert_eq!(reply.header("Connection"), Some("close"));
+        assert!(reply.body.starts_with("400 Bad Request\n"));
+    }
+}
+
+#[test]
+fn progress_is_saved() {
+    let (address, path) = start("progress");
+
+    let marked = request(address, "POST", "/api/progress/constructs::structs");
+    assert_eq!(marked.status, 200);
+    assert!(
+        marked
+            .body
+            .starts_with("{\"completed\":[\"constructs::structs\"],")
+    );
+    let saved = fs::read_to_string(&path).expect("progress file");
+    assert!(saved.lines().any(|line| line == "constructs::structs"));
+
+    let lesson = request(address, "GET", "/api/lessons/constructs::structs");
+    assert!(lesson.body.ends_with("\"completed\":true}"));
+
+    let unmarked = request(address, "DELETE", "/api/progress/constructs::structs");
+    assert!(unmarked.body.starts_with("{\"completed\":[],"));
+    assert_eq!(request(address, "POST", "/api/progress/nope").status, 404);
+    let _ = fs::remove_file(path);
+}